@@ -1,6 +1,8 @@
-use bitio_rs::byte_order::ByteOrder;
+use bitio_rs::byte_order::{Big, ByteOrder, Little};
 use bitio_rs::fast::reader::{FastBitReaderBig, FastBitReaderLittle};
-use bitio_rs::reader::{BitReader, BulkBitReader};
+use bitio_rs::fast::reader128::{FastBitReader128Big, FastBitReader128Little};
+use bitio_rs::fast::slice::{FastSliceReaderBig, FastSliceReaderLittle};
+use bitio_rs::reader::{BitReader, BitReaderBig, BitReaderLittle, BulkBitReader};
 use bitio_rs::traits::BitRead;
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
 use std::io::Cursor;
@@ -29,6 +31,132 @@ fn bench_fast_little_read_32(c: &mut Criterion) {
     });
 }
 
+fn bench_fast_big_read_5(c: &mut Criterion) {
+    let data = vec![0xFFu8; 4096];
+    c.bench_function("FastBitReaderBig read 5 bits", |b| {
+        b.iter(|| {
+            let mut reader = FastBitReaderBig::new(Cursor::new(&data));
+            for _ in 0..(data.len() * 8 / 5) {
+                black_box(reader.read_bits_fast(5).unwrap());
+            }
+        })
+    });
+}
+
+fn bench_fast_little_read_5(c: &mut Criterion) {
+    let data = vec![0xFFu8; 4096];
+    c.bench_function("FastBitReaderLittle read 5 bits", |b| {
+        b.iter(|| {
+            let mut reader = FastBitReaderLittle::new(Cursor::new(&data));
+            for _ in 0..(data.len() * 8 / 5) {
+                black_box(reader.read_bits_fast(5).unwrap());
+            }
+        })
+    });
+}
+
+const MIXED_WIDTHS: [usize; 8] = [3, 11, 7, 13, 1, 17, 5, 23];
+
+fn bench_fast_big_read_mixed(c: &mut Criterion) {
+    let data = vec![0xA5u8; 4096];
+    c.bench_function("FastBitReaderBig read mixed widths", |b| {
+        b.iter(|| {
+            let mut reader = FastBitReaderBig::new(Cursor::new(&data));
+            for _ in 0..(data.len() / 10) {
+                for &n in &MIXED_WIDTHS {
+                    black_box(reader.read_bits_fast(black_box(n)).unwrap());
+                }
+            }
+        })
+    });
+}
+
+fn bench_fast_little_read_mixed(c: &mut Criterion) {
+    let data = vec![0xA5u8; 4096];
+    c.bench_function("FastBitReaderLittle read mixed widths", |b| {
+        b.iter(|| {
+            let mut reader = FastBitReaderLittle::new(Cursor::new(&data));
+            for _ in 0..(data.len() / 10) {
+                for &n in &MIXED_WIDTHS {
+                    black_box(reader.read_bits_fast(black_box(n)).unwrap());
+                }
+            }
+        })
+    });
+}
+
+fn bench_fast128_read_5(c: &mut Criterion) {
+    let data = vec![0xFFu8; 4096];
+    c.bench_function("FastBitReader128Big read 5 bits", |b| {
+        b.iter(|| {
+            let mut reader = FastBitReader128Big::new(Cursor::new(&data));
+            for _ in 0..(data.len() * 8 / 5) {
+                black_box(reader.read_bits_fast(5).unwrap());
+            }
+        })
+    });
+    c.bench_function("FastBitReader128Little read 5 bits", |b| {
+        b.iter(|| {
+            let mut reader = FastBitReader128Little::new(Cursor::new(&data));
+            for _ in 0..(data.len() * 8 / 5) {
+                black_box(reader.read_bits_fast(5).unwrap());
+            }
+        })
+    });
+}
+
+fn bench_fast128_read_mixed(c: &mut Criterion) {
+    let data = vec![0xA5u8; 4096];
+    c.bench_function("FastBitReader128Big read mixed widths", |b| {
+        b.iter(|| {
+            let mut reader = FastBitReader128Big::new(Cursor::new(&data));
+            for _ in 0..(data.len() / 10) {
+                for &n in &MIXED_WIDTHS {
+                    black_box(reader.read_bits_fast(black_box(n)).unwrap());
+                }
+            }
+        })
+    });
+    c.bench_function("FastBitReader128Little read mixed widths", |b| {
+        b.iter(|| {
+            let mut reader = FastBitReader128Little::new(Cursor::new(&data));
+            for _ in 0..(data.len() / 10) {
+                for &n in &MIXED_WIDTHS {
+                    black_box(reader.read_bits_fast(black_box(n)).unwrap());
+                }
+            }
+        })
+    });
+}
+
+fn bench_slice_big_read_mixed(c: &mut Criterion) {
+    let data = vec![0xA5u8; 4096];
+    c.bench_function("FastSliceReaderBig read mixed widths", |b| {
+        b.iter(|| {
+            let mut reader = FastSliceReaderBig::new(&data);
+            for _ in 0..(data.len() / 10) {
+                for &n in &MIXED_WIDTHS {
+                    black_box(reader.read_bits_fast(black_box(n)).unwrap());
+                }
+            }
+        })
+    });
+}
+
+fn bench_slice_little_read_mixed(c: &mut Criterion) {
+    let data = vec![0xA5u8; 4096];
+    c.bench_function("FastSliceReaderLittle read mixed widths", |b| {
+        b.iter(|| {
+            let mut reader = FastSliceReaderLittle::new(&data);
+            for _ in 0..(data.len() / 10) {
+                for &n in &MIXED_WIDTHS {
+                    black_box(reader.read_bits_fast(black_box(n)).unwrap());
+                }
+            }
+        })
+    });
+}
+
 fn bench_standard_big_read_32(c: &mut Criterion) {
     let data = vec![0xFFu8; 4096];
     c.bench_function("StandardBitReader(BigEndian) read 32 bits", |b| {
@@ -54,6 +182,63 @@ fn bench_standard_little_read_32(c: &mut Criterion) {
     });
 }
 
+fn bench_standard_big_read_mixed(c: &mut Criterion) {
+    let data = vec![0xA5u8; 4096];
+    c.bench_function("StandardBitReader(BigEndian) read mixed widths", |b| {
+        b.iter(|| {
+            let mut reader = BitReader::with_byte_order(ByteOrder::BigEndian, Cursor::new(&data));
+            for _ in 0..(data.len() / 10) {
+                for &n in &MIXED_WIDTHS {
+                    black_box(reader.read_bits(black_box(n)).unwrap());
+                }
+            }
+        })
+    });
+}
+
+fn bench_standard_little_read_mixed(c: &mut Criterion) {
+    let data = vec![0xA5u8; 4096];
+    c.bench_function("StandardBitReader(LittleEndian) read mixed widths", |b| {
+        b.iter(|| {
+            let mut reader =
+                BitReader::with_byte_order(ByteOrder::LittleEndian, Cursor::new(&data));
+            for _ in 0..(data.len() / 10) {
+                for &n in &MIXED_WIDTHS {
+                    black_box(reader.read_bits(black_box(n)).unwrap());
+                }
+            }
+        })
+    });
+}
+
+fn bench_monomorphized_big_read_mixed(c: &mut Criterion) {
+    let data = vec![0xA5u8; 4096];
+    c.bench_function("BitReaderBig read mixed widths", |b| {
+        b.iter(|| {
+            let mut reader = BitReaderBig::with_endianness(Big, Cursor::new(&data));
+            for _ in 0..(data.len() / 10) {
+                for &n in &MIXED_WIDTHS {
+                    black_box(reader.read_bits(black_box(n)).unwrap());
+                }
+            }
+        })
+    });
+}
+
+fn bench_monomorphized_little_read_mixed(c: &mut Criterion) {
+    let data = vec![0xA5u8; 4096];
+    c.bench_function("BitReaderLittle read mixed widths", |b| {
+        b.iter(|| {
+            let mut reader = BitReaderLittle::with_endianness(Little, Cursor::new(&data));
+            for _ in 0..(data.len() / 10) {
+                for &n in &MIXED_WIDTHS {
+                    black_box(reader.read_bits(black_box(n)).unwrap());
+                }
+            }
+        })
+    });
+}
+
 fn bench_bulk_big_read_32(c: &mut Criterion) {
     let data = vec![0xFFu8; 4096];
     c.bench_function("BulkBitReader(BigEndian) read 32 bits", |b| {
@@ -84,8 +269,20 @@ criterion_group!(
     benches,
     bench_fast_big_read_32,
     bench_fast_little_read_32,
+    bench_fast_big_read_5,
+    bench_fast_little_read_5,
+    bench_fast_big_read_mixed,
+    bench_fast_little_read_mixed,
+    bench_fast128_read_5,
+    bench_fast128_read_mixed,
+    bench_slice_big_read_mixed,
+    bench_slice_little_read_mixed,
     bench_standard_big_read_32,
     bench_standard_little_read_32,
+    bench_standard_big_read_mixed,
+    bench_standard_little_read_mixed,
+    bench_monomorphized_big_read_mixed,
+    bench_monomorphized_little_read_mixed,
     bench_bulk_big_read_32,
     bench_bulk_little_read_32,
 );
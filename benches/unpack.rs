@@ -0,0 +1,35 @@
+use bitio_rs::byte_order::ByteOrder;
+use bitio_rs::fast::simd::unpack_u32;
+use bitio_rs::fast::slice::FastSliceReaderBig;
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+
+const COUNT: usize = 10_000;
+const WIDTH: usize = 13;
+
+fn bench_slice_loop_13(c: &mut Criterion) {
+    let data = vec![0xA5u8; COUNT * WIDTH / 8 + 1];
+    let mut out = vec![0u32; COUNT];
+    c.bench_function("FastSliceReaderBig loop 10000 x 13 bits", |b| {
+        b.iter(|| {
+            let mut reader = FastSliceReaderBig::new(&data);
+            for slot in out.iter_mut() {
+                *slot = reader.read_bits_fast(black_box(WIDTH)).unwrap() as u32;
+            }
+            black_box(&out);
+        })
+    });
+}
+
+fn bench_simd_unpack_13(c: &mut Criterion) {
+    let data = vec![0xA5u8; COUNT * WIDTH / 8 + 1];
+    let mut out = vec![0u32; COUNT];
+    c.bench_function("unpack_u32 10000 x 13 bits (BigEndian)", |b| {
+        b.iter(|| {
+            unpack_u32(&data, ByteOrder::BigEndian, black_box(WIDTH), &mut out).unwrap();
+            black_box(&out);
+        })
+    });
+}
+
+criterion_group!(benches, bench_slice_loop_13, bench_simd_unpack_13);
+criterion_main!(benches);
@@ -0,0 +1,97 @@
+use bitio_rs::byte_order::ByteOrder;
+use bitio_rs::fast::writer::{FastBitWriterBig, FastBitWriterLittle};
+use bitio_rs::traits::BitWrite;
+use bitio_rs::writer::BitWriter;
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+
+fn bench_fast_big_write_32(c: &mut Criterion) {
+    c.bench_function("FastBitWriterBig write 32 bits", |b| {
+        b.iter(|| {
+            let mut writer = FastBitWriterBig::new(Vec::with_capacity(4096));
+            for i in 0..1024u64 {
+                writer.write_bits_fast(black_box(i), 32).unwrap();
+            }
+            black_box(writer.into_inner().unwrap());
+        })
+    });
+}
+
+fn bench_fast_little_write_32(c: &mut Criterion) {
+    c.bench_function("FastBitWriterLittle write 32 bits", |b| {
+        b.iter(|| {
+            let mut writer = FastBitWriterLittle::new(Vec::with_capacity(4096));
+            for i in 0..1024u64 {
+                writer.write_bits_fast(black_box(i), 32).unwrap();
+            }
+            black_box(writer.into_inner().unwrap());
+        })
+    });
+}
+
+fn bench_standard_big_write_32(c: &mut Criterion) {
+    c.bench_function("StandardBitWriter(BigEndian) write 32 bits", |b| {
+        b.iter(|| {
+            let mut buffer = Vec::with_capacity(4096);
+            let mut writer = BitWriter::with_byte_order(ByteOrder::BigEndian, &mut buffer);
+            for i in 0..1024u64 {
+                writer.write_bits(black_box(i), 32).unwrap();
+            }
+            writer.finish().unwrap();
+            drop(writer);
+            black_box(buffer);
+        })
+    });
+}
+
+fn bench_standard_little_write_32(c: &mut Criterion) {
+    c.bench_function("StandardBitWriter(LittleEndian) write 32 bits", |b| {
+        b.iter(|| {
+            let mut buffer = Vec::with_capacity(4096);
+            let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, &mut buffer);
+            for i in 0..1024u64 {
+                writer.write_bits(black_box(i), 32).unwrap();
+            }
+            writer.finish().unwrap();
+            drop(writer);
+            black_box(buffer);
+        })
+    });
+}
+
+fn bench_standard_mixed_widths(c: &mut Criterion) {
+    // 小字段为主的典型编码器负载：每次调用都会写出刚凑满的字节
+    let widths: Vec<usize> = (0..4096).map(|i| 1 + (i * 7) % 17).collect();
+    for (name, order) in [
+        (
+            "StandardBitWriter(BigEndian) write 1-17 bit fields",
+            ByteOrder::BigEndian,
+        ),
+        (
+            "StandardBitWriter(LittleEndian) write 1-17 bit fields",
+            ByteOrder::LittleEndian,
+        ),
+    ] {
+        c.bench_function(name, |b| {
+            b.iter(|| {
+                let mut buffer = Vec::with_capacity(8192);
+                let mut writer = BitWriter::with_byte_order(order, &mut buffer);
+                for (i, &n) in widths.iter().enumerate() {
+                    writer.write_bits(black_box(i as u64), n).unwrap();
+                }
+                writer.finish().unwrap();
+                drop(writer);
+                black_box(buffer);
+            })
+        });
+    }
+}
+
+criterion_group!(
+    benches,
+    bench_fast_big_write_32,
+    bench_fast_little_write_32,
+    bench_standard_big_write_32,
+    bench_standard_little_write_32,
+    bench_standard_mixed_widths,
+);
+criterion_main!(benches);
@@ -0,0 +1,362 @@
+//! `#[derive(BitRead, BitWrite)]` for [bitio-rs](https://github.com/F2077/bitio-rs)
+//!
+//! Generates `BitDecode::from_reader` / `BitEncode::to_writer` impls that read or write the
+//! fields of a struct in declaration order. Use it through the `derive` feature of `bitio-rs`.
+//!
+//! ## Field attributes
+//! - `#[bits(5)]`: width of an integer or `bool` field (default: the natural width, 1 for `bool`)
+//! - `#[bits(endian = "little")]` / `#[bits(endian = "big")]`: byte order of a multi-byte field,
+//!   read and written one byte at a time regardless of the stream's byte order (the width must
+//!   be a multiple of 8); both can be combined as `#[bits(24, endian = "little")]`
+//! - `#[align(8)]`: skip (or write zero) bits before the field up to a multiple of 8 bits,
+//!   counted from the start of the struct
+//!
+//! Fields of any other type must implement `BitDecode` / `BitEncode` themselves; arrays
+//! `[T; N]` apply the field attributes to every element.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    Data, DeriveInput, Error, Fields, Index, LitInt, LitStr, Member, Result, Token, Type,
+    parse_macro_input,
+};
+
+#[proc_macro_derive(BitRead, attributes(bits, align))]
+pub fn derive_bit_read(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_read(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(BitWrite, attributes(bits, align))]
+pub fn derive_bit_write(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_write(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+// ------------------------------- 属性解析 ------------------------------- //
+
+enum BitsArg {
+    Width(LitInt),
+    Endian(LitStr),
+}
+
+impl Parse for BitsArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitInt) {
+            return Ok(BitsArg::Width(input.parse()?));
+        }
+        let key: syn::Ident = input.parse()?;
+        if key != "endian" {
+            return Err(Error::new(
+                key.span(),
+                "expected a width or `endian = \"...\"`",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        Ok(BitsArg::Endian(input.parse()?))
+    }
+}
+
+struct FieldSpec {
+    member: Member,
+    binding: syn::Ident,
+    ty: Type,
+    width: Option<(usize, Span)>,
+    little: Option<bool>,
+    align: Option<usize>,
+}
+
+fn parse_field(index: usize, field: &syn::Field) -> Result<FieldSpec> {
+    let mut spec = FieldSpec {
+        member: match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(index)),
+        },
+        binding: format_ident!("__field{}", index),
+        ty: field.ty.clone(),
+        width: None,
+        little: None,
+        align: None,
+    };
+    for attr in &field.attrs {
+        if attr.path().is_ident("bits") {
+            let args = attr.parse_args_with(Punctuated::<BitsArg, Token![,]>::parse_terminated)?;
+            for arg in args {
+                match arg {
+                    BitsArg::Width(lit) => spec.width = Some((lit.base10_parse()?, lit.span())),
+                    BitsArg::Endian(lit) => {
+                        spec.little = Some(match lit.value().as_str() {
+                            "little" => true,
+                            "big" => false,
+                            _ => {
+                                return Err(Error::new(
+                                    lit.span(),
+                                    "endian must be \"little\" or \"big\"",
+                                ));
+                            }
+                        })
+                    }
+                }
+            }
+        } else if attr.path().is_ident("align") {
+            let lit: LitInt = attr.parse_args()?;
+            let align: usize = lit.base10_parse()?;
+            if align == 0 {
+                return Err(Error::new(lit.span(), "alignment must be at least 1 bit"));
+            }
+            spec.align = Some(align);
+        }
+    }
+    Ok(spec)
+}
+
+fn parse_fields(input: &DeriveInput) -> Result<(Vec<FieldSpec>, bool)> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "BitRead/BitWrite can only be derived for structs",
+            ));
+        }
+    };
+    let named = matches!(fields, Fields::Named(_));
+    let specs = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| parse_field(i, f))
+        .collect::<Result<Vec<_>>>()?;
+    Ok((specs, named))
+}
+
+// ------------------------------- 类型分类 ------------------------------- //
+
+enum Kind {
+    Unsigned(usize),
+    Signed(usize),
+    Bool,
+    Nested,
+}
+
+fn kind_of(ty: &Type) -> Kind {
+    let Type::Path(path) = ty else {
+        return Kind::Nested;
+    };
+    match path
+        .path
+        .get_ident()
+        .map(|ident| ident.to_string())
+        .as_deref()
+    {
+        Some("u8") => Kind::Unsigned(8),
+        Some("u16") => Kind::Unsigned(16),
+        Some("u32") => Kind::Unsigned(32),
+        Some("u64") => Kind::Unsigned(64),
+        Some("i8") => Kind::Signed(8),
+        Some("i16") => Kind::Signed(16),
+        Some("i32") => Kind::Signed(32),
+        Some("i64") => Kind::Signed(64),
+        Some("bool") => Kind::Bool,
+        _ => Kind::Nested,
+    }
+}
+
+/// 校验并返回字段的实际宽度
+fn resolve_width(spec: &FieldSpec, ty: &Type, natural: usize) -> Result<usize> {
+    let (width, span) = spec.width.unwrap_or((natural, Span::call_site()));
+    if width == 0 || width > natural {
+        return Err(Error::new(
+            span,
+            format!(
+                "width must be between 1 and {} for `{}`",
+                natural,
+                quote!(#ty)
+            ),
+        ));
+    }
+    if spec.little.is_some() && width % 8 != 0 {
+        return Err(Error::new(
+            span,
+            "fields with an explicit endian must be a multiple of 8 bits wide",
+        ));
+    }
+    Ok(width)
+}
+
+fn little_tokens(little: Option<bool>) -> TokenStream2 {
+    match little {
+        None => quote!(::core::option::Option::None),
+        Some(l) => quote!(::core::option::Option::Some(#l)),
+    }
+}
+
+// ------------------------------- BitRead ------------------------------- //
+
+fn read_value(spec: &FieldSpec, ty: &Type) -> Result<TokenStream2> {
+    let little = little_tokens(spec.little);
+    if let Type::Array(array) = ty {
+        let elem = read_value(spec, &array.elem)?;
+        let len = &array.len;
+        return Ok(quote! {{
+            let mut __items = ::std::vec::Vec::with_capacity(#len);
+            for _ in 0..#len {
+                __items.push(#elem);
+            }
+            match __items.try_into() {
+                ::core::result::Result::Ok(array) => array,
+                ::core::result::Result::Err(_) => unreachable!(),
+            }
+        }});
+    }
+    Ok(match kind_of(ty) {
+        Kind::Unsigned(natural) => {
+            let width = resolve_width(spec, ty, natural)?;
+            quote!(::bitio_rs::derive_support::read_unsigned(&mut *__reader, #width, #little)? as #ty)
+        }
+        Kind::Signed(natural) => {
+            let width = resolve_width(spec, ty, natural)?;
+            quote!(::bitio_rs::derive_support::read_signed(&mut *__reader, #width, #little)? as #ty)
+        }
+        Kind::Bool => {
+            let width = resolve_width(spec, ty, if spec.width.is_some() { 64 } else { 1 })?;
+            quote!(::bitio_rs::derive_support::read_unsigned(&mut *__reader, #width, #little)? != 0)
+        }
+        Kind::Nested => {
+            if spec.width.is_some() || spec.little.is_some() {
+                return Err(Error::new_spanned(
+                    ty,
+                    "#[bits] only applies to integer and bool fields",
+                ));
+            }
+            quote!(<#ty as ::bitio_rs::traits::BitDecode>::from_reader(&mut *__reader)?)
+        }
+    })
+}
+
+fn expand_read(input: &DeriveInput) -> Result<TokenStream2> {
+    let (specs, named) = parse_fields(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let aligned = specs.iter().any(|s| s.align.is_some());
+
+    let mut reads = Vec::new();
+    for spec in &specs {
+        if let Some(align) = spec.align {
+            reads.push(quote!(__reader.align_read(#align)?;));
+        }
+        let binding = &spec.binding;
+        let value = read_value(spec, &spec.ty)?;
+        reads.push(quote!(let #binding = #value;));
+    }
+
+    let bindings = specs.iter().map(|s| &s.binding);
+    let construct = if named {
+        let members = specs.iter().map(|s| &s.member);
+        quote!(Self { #(#members: #bindings),* })
+    } else {
+        quote!(Self(#(#bindings),*))
+    };
+    let setup = if aligned {
+        quote! {
+            let mut __counting = ::bitio_rs::derive_support::Counting::new(reader);
+            let __reader = &mut __counting;
+        }
+    } else {
+        quote!(let __reader = reader;)
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::bitio_rs::traits::BitDecode for #name #ty_generics #where_clause {
+            fn from_reader<__R>(reader: &mut __R) -> ::std::io::Result<Self>
+            where
+                __R: ::bitio_rs::traits::BitRead<Output = u64> + ?Sized,
+            {
+                #setup
+                #(#reads)*
+                ::core::result::Result::Ok(#construct)
+            }
+        }
+    })
+}
+
+// ------------------------------- BitWrite ------------------------------- //
+
+fn write_value(spec: &FieldSpec, ty: &Type, value: TokenStream2) -> Result<TokenStream2> {
+    let little = little_tokens(spec.little);
+    if let Type::Array(array) = ty {
+        let elem = write_value(spec, &array.elem, quote!(__item))?;
+        return Ok(quote! {
+            for __item in (#value).iter() {
+                #elem
+            }
+        });
+    }
+    Ok(match kind_of(ty) {
+        Kind::Unsigned(natural) => {
+            let width = resolve_width(spec, ty, natural)?;
+            quote!(::bitio_rs::derive_support::write_unsigned(&mut *__writer, *(#value) as u64, #width, #little)?;)
+        }
+        Kind::Signed(natural) => {
+            let width = resolve_width(spec, ty, natural)?;
+            quote!(::bitio_rs::derive_support::write_signed(&mut *__writer, *(#value) as i64, #width, #little)?;)
+        }
+        Kind::Bool => {
+            let width = resolve_width(spec, ty, if spec.width.is_some() { 64 } else { 1 })?;
+            quote!(::bitio_rs::derive_support::write_unsigned(&mut *__writer, *(#value) as u64, #width, #little)?;)
+        }
+        Kind::Nested => {
+            if spec.width.is_some() || spec.little.is_some() {
+                return Err(Error::new_spanned(
+                    ty,
+                    "#[bits] only applies to integer and bool fields",
+                ));
+            }
+            quote!(<#ty as ::bitio_rs::traits::BitEncode>::to_writer(#value, &mut *__writer)?;)
+        }
+    })
+}
+
+fn expand_write(input: &DeriveInput) -> Result<TokenStream2> {
+    let (specs, _) = parse_fields(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let aligned = specs.iter().any(|s| s.align.is_some());
+
+    let mut writes = Vec::new();
+    for spec in &specs {
+        if let Some(align) = spec.align {
+            writes.push(quote!(__writer.align_write(#align)?;));
+        }
+        let member = &spec.member;
+        writes.push(write_value(spec, &spec.ty, quote!(&self.#member))?);
+    }
+
+    let setup = if aligned {
+        quote! {
+            let mut __counting = ::bitio_rs::derive_support::Counting::new(writer);
+            let __writer = &mut __counting;
+        }
+    } else {
+        quote!(let __writer = writer;)
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::bitio_rs::traits::BitEncode for #name #ty_generics #where_clause {
+            fn to_writer<__W>(&self, writer: &mut __W) -> ::std::io::Result<()>
+            where
+                __W: ::bitio_rs::traits::BitWrite + ?Sized,
+            {
+                #setup
+                #(#writes)*
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
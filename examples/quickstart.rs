@@ -15,7 +15,7 @@ fn main() -> Result<()> {
         let mut bit_writer = BitWriter::new(Cursor::new(&mut buffer1));
         bit_writer.write_bits(0b101, 3)?;
         bit_writer.write_bits(0b1010101111001101, 16)?; // 0xABCD
-        bit_writer.flush()?;
+        bit_writer.finish()?;
     }
     println!(
         "Demo1: {}",
@@ -28,9 +28,9 @@ fn main() -> Result<()> {
     let mut buffer2 = Vec::new();
     {
         let mut bit_writer = BitWriter::new(Cursor::new(&mut buffer2));
-        bit_writer.write(&[0b00010001, 0b00100010])?; // 0x11, 0x22
+        bit_writer.write_all(&[0b00010001, 0b00100010])?; // 0x11, 0x22
         bit_writer.write_bits(0b11011, 5)?;
-        bit_writer.write(&[0b00110011])?; // 0x33
+        bit_writer.write_all(&[0b00110011])?; // 0x33
     }
     println!(
         "\nDemo2: {}",
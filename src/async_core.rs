@@ -0,0 +1,205 @@
+//! Runtime-independent bit buffering shared by the tokio and futures-io async readers/writers
+//!
+//! The cores never touch I/O themselves: byte sources and sinks are passed in as async
+//! closures, so each runtime adapter only supplies its own `read_u8` / `write_all`.
+
+use crate::byte_order::ByteOrder;
+use crate::error::BitReadWriteError;
+use std::io::{ErrorKind, Result};
+
+pub(crate) struct ReadCore {
+    byte_order: ByteOrder,
+    bits_buffer: u64,
+    bits_in_buffer: usize,
+}
+
+impl ReadCore {
+    pub(crate) fn new(byte_order: ByteOrder) -> Self {
+        Self {
+            byte_order,
+            bits_buffer: 0,
+            bits_in_buffer: 0,
+        }
+    }
+
+    pub(crate) fn is_byte_aligned(&self) -> bool {
+        self.bits_in_buffer.is_multiple_of(8)
+    }
+
+    pub(crate) async fn read_bits(
+        &mut self,
+        n: usize,
+        mut next_byte: impl AsyncFnMut() -> Result<u8>,
+    ) -> Result<u64> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+
+        self.fill(n, &mut next_byte).await?;
+        if self.bits_in_buffer >= n {
+            return Ok(self.take(n));
+        }
+
+        // 比特缓冲区中的零头比特加上所需的整字节超过 64 位：分两段读取再拼接
+        let first_bits = self.bits_in_buffer;
+        let first = self.take(first_bits);
+        let second_bits = n - first_bits;
+        self.fill(second_bits, &mut next_byte).await?;
+        let second = self.take(second_bits);
+        Ok(match self.byte_order {
+            ByteOrder::BigEndian => (first << second_bits) | second,
+            ByteOrder::LittleEndian => first | (second << first_bits),
+        })
+    }
+
+    /// 逐字节填充比特缓冲区，直到至少有 n 比特或再放不下一个字节
+    async fn fill(
+        &mut self,
+        n: usize,
+        next_byte: &mut impl AsyncFnMut() -> Result<u8>,
+    ) -> Result<()> {
+        while self.bits_in_buffer < n && self.bits_in_buffer <= 56 {
+            let byte = match next_byte().await {
+                Ok(b) => b as u64,
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+                    return Err(BitReadWriteError::UnexpectedEof.into());
+                }
+                Err(e) => return Err(e),
+            };
+            match self.byte_order {
+                ByteOrder::BigEndian => self.bits_buffer |= byte << (56 - self.bits_in_buffer),
+                ByteOrder::LittleEndian => self.bits_buffer |= byte << self.bits_in_buffer,
+            }
+            self.bits_in_buffer += 8;
+        }
+        Ok(())
+    }
+
+    /// 从比特缓冲区取出 n 比特，调用方保证 1 <= n <= bits_in_buffer
+    fn take(&mut self, n: usize) -> u64 {
+        let value = match self.byte_order {
+            ByteOrder::BigEndian => self.bits_buffer >> (64 - n),
+            ByteOrder::LittleEndian => {
+                let mask = if n == 64 { u64::MAX } else { (1u64 << n) - 1 };
+                self.bits_buffer & mask
+            }
+        };
+        if n == 64 {
+            self.bits_buffer = 0;
+        } else {
+            match self.byte_order {
+                ByteOrder::BigEndian => self.bits_buffer <<= n,
+                ByteOrder::LittleEndian => self.bits_buffer >>= n,
+            }
+        }
+        self.bits_in_buffer -= n;
+        value
+    }
+}
+
+pub(crate) struct WriteCore {
+    byte_order: ByteOrder,
+    bits_buffer: u64,
+    bits_in_buffer: usize, // 始终小于 8：凑满的字节会立即写出
+    bits_written: u64,
+}
+
+impl WriteCore {
+    pub(crate) fn new(byte_order: ByteOrder) -> Self {
+        Self {
+            byte_order,
+            bits_buffer: 0,
+            bits_in_buffer: 0,
+            bits_written: 0,
+        }
+    }
+
+    pub(crate) fn bits_written(&self) -> u64 {
+        self.bits_written
+    }
+
+    pub(crate) fn is_byte_aligned(&self) -> bool {
+        self.bits_in_buffer == 0
+    }
+
+    pub(crate) async fn write_bits(
+        &mut self,
+        value: u64,
+        n: usize,
+        mut write_all: impl AsyncFnMut(&[u8]) -> Result<()>,
+    ) -> Result<()> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        let value = if n == 64 {
+            value
+        } else {
+            value & ((1u64 << n) - 1)
+        };
+
+        let free = 64 - self.bits_in_buffer;
+        if n <= free {
+            self.push(value, n);
+        } else {
+            // 零头比特加上 n 超过 64 位：先放入能放下的部分并写出，再放入剩余部分
+            let rest = n - free;
+            let (first, second) = match self.byte_order {
+                ByteOrder::BigEndian => (value >> rest, value & ((1u64 << rest) - 1)),
+                ByteOrder::LittleEndian => (value & ((1u64 << free) - 1), value >> free),
+            };
+            self.push(first, free);
+            self.write_aligned_bytes(&mut write_all).await?;
+            self.push(second, rest);
+        }
+        self.write_aligned_bytes(&mut write_all).await?;
+        self.bits_written += n as u64;
+        Ok(())
+    }
+
+    /// 将残余的比特补零成整字节后写出
+    pub(crate) async fn write_padding(
+        &mut self,
+        mut write_all: impl AsyncFnMut(&[u8]) -> Result<()>,
+    ) -> Result<()> {
+        if self.bits_in_buffer > 0 {
+            self.bits_in_buffer = 8;
+            self.write_aligned_bytes(&mut write_all).await?;
+        }
+        Ok(())
+    }
+
+    /// 将 k 比特放入比特缓冲区，调用方保证 k <= 64 - bits_in_buffer
+    fn push(&mut self, value: u64, k: usize) {
+        match self.byte_order {
+            ByteOrder::BigEndian => self.bits_buffer |= value << (64 - self.bits_in_buffer - k),
+            ByteOrder::LittleEndian => self.bits_buffer |= value << self.bits_in_buffer,
+        }
+        self.bits_in_buffer += k;
+    }
+
+    /// 将比特缓冲区中凑满的字节写出
+    async fn write_aligned_bytes(
+        &mut self,
+        write_all: &mut impl AsyncFnMut(&[u8]) -> Result<()>,
+    ) -> Result<()> {
+        let count = self.bits_in_buffer / 8;
+        if count == 0 {
+            return Ok(());
+        }
+        let bytes = match self.byte_order {
+            ByteOrder::BigEndian => self.bits_buffer.to_be_bytes(),
+            ByteOrder::LittleEndian => self.bits_buffer.to_le_bytes(),
+        };
+        write_all(&bytes[..count]).await?;
+        if count == 8 {
+            self.bits_buffer = 0;
+        } else {
+            match self.byte_order {
+                ByteOrder::BigEndian => self.bits_buffer <<= count * 8,
+                ByteOrder::LittleEndian => self.bits_buffer >>= count * 8,
+            }
+        }
+        self.bits_in_buffer -= count * 8;
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use crate::async_core::ReadCore;
+use crate::byte_order::ByteOrder;
+
+// ------------------------------- AsyncBitReader ------------------------------- //
+
+/// Bit reader over a [`tokio::io::AsyncRead`] source (requires the `tokio` feature)
+///
+/// Mirrors [`BitReader`](crate::reader::BitReader): same byte order semantics and errors, but
+/// `read_bits` is an `async fn` that only awaits the bytes it actually needs, so frames can be
+/// parsed as they arrive instead of being buffered whole.
+#[cfg(feature = "tokio")]
+pub struct AsyncBitReader<R: tokio::io::AsyncRead + Unpin> {
+    inner: tokio::io::BufReader<R>,
+    core: ReadCore,
+}
+
+#[cfg(feature = "tokio")]
+impl<R: tokio::io::AsyncRead + Unpin> AsyncBitReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_byte_order(ByteOrder::BigEndian, inner)
+    }
+
+    pub fn with_byte_order(byte_order: ByteOrder, inner: R) -> Self {
+        Self {
+            inner: tokio::io::BufReader::new(inner),
+            core: ReadCore::new(byte_order),
+        }
+    }
+
+    /// Returns the underlying reader, discarding any buffered bits and bytes
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Returns `true` if at byte boundary (no pending bits)
+    pub fn is_byte_aligned(&self) -> bool {
+        self.core.is_byte_aligned()
+    }
+
+    /// Reads exactly `n` bits from the stream (1-64 bits)
+    ///
+    /// # Errors
+    /// Returns error if `n` is not between 1-64 or not enough bits are available
+    pub async fn read_bits(&mut self, n: usize) -> std::io::Result<u64> {
+        use tokio::io::AsyncReadExt;
+
+        let inner = &mut self.inner;
+        self.core.read_bits(n, async || inner.read_u8().await).await
+    }
+}
+
+// ------------------------------- FuturesBitReader ------------------------------- //
+
+/// Bit reader over a [`futures_util::io::AsyncRead`] source, for async-std, smol and other
+/// futures-based runtimes (requires the `futures-io` feature)
+///
+/// Same API and semantics as [`AsyncBitReader`].
+#[cfg(feature = "futures-io")]
+pub struct FuturesBitReader<R: futures_util::io::AsyncRead + Unpin> {
+    inner: futures_util::io::BufReader<R>,
+    core: ReadCore,
+}
+
+#[cfg(feature = "futures-io")]
+impl<R: futures_util::io::AsyncRead + Unpin> FuturesBitReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_byte_order(ByteOrder::BigEndian, inner)
+    }
+
+    pub fn with_byte_order(byte_order: ByteOrder, inner: R) -> Self {
+        Self {
+            inner: futures_util::io::BufReader::new(inner),
+            core: ReadCore::new(byte_order),
+        }
+    }
+
+    /// Returns the underlying reader, discarding any buffered bits and bytes
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Returns `true` if at byte boundary (no pending bits)
+    pub fn is_byte_aligned(&self) -> bool {
+        self.core.is_byte_aligned()
+    }
+
+    /// Reads exactly `n` bits from the stream (1-64 bits)
+    ///
+    /// # Errors
+    /// Returns error if `n` is not between 1-64 or not enough bits are available
+    pub async fn read_bits(&mut self, n: usize) -> std::io::Result<u64> {
+        use futures_util::io::AsyncReadExt;
+
+        let inner = &mut self.inner;
+        self.core
+            .read_bits(n, async || {
+                let mut byte = [0u8; 1];
+                inner.read_exact(&mut byte).await?;
+                Ok(byte[0])
+            })
+            .await
+    }
+}
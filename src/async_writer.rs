@@ -0,0 +1,196 @@
+use crate::async_core::WriteCore;
+use crate::byte_order::ByteOrder;
+
+// ------------------------------- AsyncBitWriter ------------------------------- //
+
+/// Bit writer over a [`tokio::io::AsyncWrite`] sink (requires the `tokio` feature)
+///
+/// Mirrors [`BitWriter`](crate::writer::BitWriter): same byte order semantics, `flush()` only
+/// writes completed bytes and `finish()` pads a trailing partial byte with zeros. Unlike
+/// `BitWriter` there is no flush on drop (drop cannot await), so always end with
+/// `finish().await` or `into_inner().await`.
+#[cfg(feature = "tokio")]
+pub struct AsyncBitWriter<W: tokio::io::AsyncWrite + Unpin> {
+    inner: tokio::io::BufWriter<W>,
+    core: WriteCore,
+    pad_on_flush: bool, // flush 是否像 finish 一样补齐并写出残余的不足 1 字节
+}
+
+#[cfg(feature = "tokio")]
+impl<W: tokio::io::AsyncWrite + Unpin> AsyncBitWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self::with_byte_order(ByteOrder::BigEndian, inner)
+    }
+
+    pub fn with_byte_order(byte_order: ByteOrder, inner: W) -> Self {
+        Self {
+            inner: tokio::io::BufWriter::new(inner),
+            core: WriteCore::new(byte_order),
+            pad_on_flush: false,
+        }
+    }
+
+    /// Makes [`flush`](Self::flush) pad and emit a pending partial byte like
+    /// [`finish`](Self::finish), the behavior of earlier versions
+    ///
+    /// Off by default: `flush` only writes completed bytes, so flushing in the middle of a
+    /// byte does not insert padding into the stream.
+    pub fn pad_on_flush(mut self, pad: bool) -> Self {
+        self.pad_on_flush = pad;
+        self
+    }
+
+    /// Returns the number of bits passed to `write_bits` so far (flush padding not included)
+    pub fn bits_written(&self) -> u64 {
+        self.core.bits_written()
+    }
+
+    /// Returns `true` if at byte boundary (no pending bits)
+    pub fn is_byte_aligned(&self) -> bool {
+        self.core.is_byte_aligned()
+    }
+
+    /// Writes the low `n` bits of `value` (1-64 bits)
+    ///
+    /// # Errors
+    /// Returns error if `n` is not between 1-64 or the inner writer fails
+    pub async fn write_bits(&mut self, value: u64, n: usize) -> std::io::Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let inner = &mut self.inner;
+        self.core
+            .write_bits(value, n, async |bytes: &[u8]| inner.write_all(bytes).await)
+            .await
+    }
+
+    /// Flushes the completed bytes to the inner writer
+    ///
+    /// Bits of an unfinished byte stay buffered so the stream is not corrupted by padding;
+    /// use [`finish`](Self::finish) (or [`pad_on_flush`](Self::pad_on_flush)) to pad and emit
+    /// them.
+    pub async fn flush(&mut self) -> std::io::Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        if self.pad_on_flush {
+            return self.finish().await;
+        }
+        // 凑满的字节在 write_bits 中已经写出，这里只需刷新底层写入器
+        self.inner.flush().await
+    }
+
+    /// Pads pending bits with zeros to a byte boundary, writes them, and flushes the inner writer
+    ///
+    /// Call it at the end of a stream, or wherever the format wants padding to a byte
+    /// boundary; writing can continue afterwards from the next byte.
+    pub async fn finish(&mut self) -> std::io::Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let inner = &mut self.inner;
+        self.core
+            .write_padding(async |bytes: &[u8]| inner.write_all(bytes).await)
+            .await?;
+        self.inner.flush().await
+    }
+
+    /// Finishes pending bits (see `finish`) and returns the inner writer
+    pub async fn into_inner(mut self) -> std::io::Result<W> {
+        self.finish().await?;
+        Ok(self.inner.into_inner())
+    }
+}
+
+// ------------------------------- FuturesBitWriter ------------------------------- //
+
+/// Bit writer over a [`futures_util::io::AsyncWrite`] sink, for async-std, smol and other
+/// futures-based runtimes (requires the `futures-io` feature)
+///
+/// Same API and semantics as [`AsyncBitWriter`], including the lack of flush on drop.
+#[cfg(feature = "futures-io")]
+pub struct FuturesBitWriter<W: futures_util::io::AsyncWrite + Unpin> {
+    inner: futures_util::io::BufWriter<W>,
+    core: WriteCore,
+    pad_on_flush: bool, // flush 是否像 finish 一样补齐并写出残余的不足 1 字节
+}
+
+#[cfg(feature = "futures-io")]
+impl<W: futures_util::io::AsyncWrite + Unpin> FuturesBitWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self::with_byte_order(ByteOrder::BigEndian, inner)
+    }
+
+    pub fn with_byte_order(byte_order: ByteOrder, inner: W) -> Self {
+        Self {
+            inner: futures_util::io::BufWriter::new(inner),
+            core: WriteCore::new(byte_order),
+            pad_on_flush: false,
+        }
+    }
+
+    /// Makes [`flush`](Self::flush) pad and emit a pending partial byte like
+    /// [`finish`](Self::finish), the behavior of earlier versions
+    ///
+    /// Off by default: `flush` only writes completed bytes, so flushing in the middle of a
+    /// byte does not insert padding into the stream.
+    pub fn pad_on_flush(mut self, pad: bool) -> Self {
+        self.pad_on_flush = pad;
+        self
+    }
+
+    /// Returns the number of bits passed to `write_bits` so far (flush padding not included)
+    pub fn bits_written(&self) -> u64 {
+        self.core.bits_written()
+    }
+
+    /// Returns `true` if at byte boundary (no pending bits)
+    pub fn is_byte_aligned(&self) -> bool {
+        self.core.is_byte_aligned()
+    }
+
+    /// Writes the low `n` bits of `value` (1-64 bits)
+    ///
+    /// # Errors
+    /// Returns error if `n` is not between 1-64 or the inner writer fails
+    pub async fn write_bits(&mut self, value: u64, n: usize) -> std::io::Result<()> {
+        use futures_util::io::AsyncWriteExt;
+
+        let inner = &mut self.inner;
+        self.core
+            .write_bits(value, n, async |bytes: &[u8]| inner.write_all(bytes).await)
+            .await
+    }
+
+    /// Flushes the completed bytes to the inner writer
+    ///
+    /// Bits of an unfinished byte stay buffered so the stream is not corrupted by padding;
+    /// use [`finish`](Self::finish) (or [`pad_on_flush`](Self::pad_on_flush)) to pad and emit
+    /// them.
+    pub async fn flush(&mut self) -> std::io::Result<()> {
+        use futures_util::io::AsyncWriteExt;
+
+        if self.pad_on_flush {
+            return self.finish().await;
+        }
+        // 凑满的字节在 write_bits 中已经写出，这里只需刷新底层写入器
+        self.inner.flush().await
+    }
+
+    /// Pads pending bits with zeros to a byte boundary, writes them, and flushes the inner writer
+    ///
+    /// Call it at the end of a stream, or wherever the format wants padding to a byte
+    /// boundary; writing can continue afterwards from the next byte.
+    pub async fn finish(&mut self) -> std::io::Result<()> {
+        use futures_util::io::AsyncWriteExt;
+
+        let inner = &mut self.inner;
+        self.core
+            .write_padding(async |bytes: &[u8]| inner.write_all(bytes).await)
+            .await?;
+        self.inner.flush().await
+    }
+
+    /// Finishes pending bits (see `finish`) and returns the inner writer
+    pub async fn into_inner(mut self) -> std::io::Result<W> {
+        self.finish().await?;
+        Ok(self.inner.into_inner())
+    }
+}
@@ -0,0 +1,95 @@
+//! Packed binary-coded decimal: one decimal digit per 4-bit nibble, most significant first
+//!
+//! `1234` is stored as `0x1234`. Use [`BitReadExt::read_bcd`] / [`BitWriteExt::write_bcd`] for BCD
+//! fields at any bit position. Telecom numbers (GSM SMS PDU addresses, IMSIs) instead swap the
+//! two digits of every byte and pad with `0xF`; [`decode_semi_octets`] /
+//! [`encode_semi_octets`] handle that form.
+//!
+//! [`BitReadExt::read_bcd`]: crate::traits::BitReadExt::read_bcd
+//! [`BitWriteExt::write_bcd`]: crate::traits::BitWriteExt::write_bcd
+
+use crate::error::BitReadWriteError;
+use std::io::{Error, Result};
+
+/// Most digits a BCD field read into a `u64` may have
+pub const MAX_DIGITS: usize = 19;
+
+/// TBCD 半字节 0xA-0xE 对应的字符
+const TBCD_EXTRA: [char; 5] = ['*', '#', 'a', 'b', 'c'];
+
+/// Converts `value` to packed BCD, or `None` if it has more than 16 digits
+pub fn to_bcd(value: u64) -> Option<u64> {
+    if value >= 10u64.pow(16) {
+        return None;
+    }
+    let (mut value, mut bcd, mut shift) = (value, 0, 0);
+    while value != 0 {
+        bcd |= (value % 10) << shift;
+        value /= 10;
+        shift += 4;
+    }
+    Some(bcd)
+}
+
+/// Converts packed BCD back to its value, or `None` if a nibble is above 9
+pub fn from_bcd(bcd: u64) -> Option<u64> {
+    (0..16).rev().try_fold(0u64, |value, i| {
+        let digit = (bcd >> (4 * i)) & 0xF;
+        (digit <= 9).then_some(value * 10 + digit)
+    })
+}
+
+/// 校验位数在 1-MAX_DIGITS 之间
+pub(crate) fn check_digits(digits: usize) -> Result<()> {
+    if digits == 0 || digits > MAX_DIGITS {
+        return Err(BitReadWriteError::InvalidDigitCount(digits).into());
+    }
+    Ok(())
+}
+
+/// Decodes swapped-nibble BCD (semi-octets), as in GSM 03.40 addresses and 3GPP TBCD strings
+///
+/// The low nibble of each byte is the first digit. A `0xF` high nibble in the last byte is
+/// filler; nibbles `0xA`-`0xE` decode to `* # a b c`.
+///
+/// # Errors
+/// `InvalidData` for a `0xF` nibble anywhere but the filler position
+pub fn decode_semi_octets(data: &[u8]) -> Result<String> {
+    let mut digits = String::with_capacity(data.len() * 2);
+    for (i, &byte) in data.iter().enumerate() {
+        for (half, nibble) in [(0, byte & 0xF), (1, byte >> 4)] {
+            let c = match nibble {
+                0..=9 => (b'0' + nibble) as char,
+                0xF if half == 1 && i == data.len() - 1 => break,
+                0xF => {
+                    return Err(BitReadWriteError::MisplacedFiller.into());
+                }
+                _ => TBCD_EXTRA[(nibble - 0xA) as usize],
+            };
+            digits.push(c);
+        }
+    }
+    Ok(digits)
+}
+
+/// Encodes a digit string as swapped-nibble BCD, padding an odd length with a `0xF` nibble
+///
+/// # Errors
+/// `InvalidInput` for a character other than `0-9 * # a b c`
+pub fn encode_semi_octets(digits: &str) -> Result<Vec<u8>> {
+    let nibbles = digits
+        .chars()
+        .map(|c| match c {
+            '0'..='9' => Ok(c as u8 - b'0'),
+            _ => TBCD_EXTRA
+                .iter()
+                .position(|&x| x == c)
+                .map(|i| 0xA + i as u8)
+                .ok_or_else(|| Error::from(BitReadWriteError::InvalidSemiOctet(c))),
+        })
+        .collect::<Result<Vec<u8>>>()?;
+    Ok(nibbles
+        .chunks(2)
+        .map(|pair| pair[0] | pair.get(1).copied().unwrap_or(0xF) << 4)
+        .collect())
+}
@@ -0,0 +1,128 @@
+//! Interoperability with [binrw](https://docs.rs/binrw) (requires the `binrw` feature)
+//!
+//! binrw parses from `Read + Seek` streams and seeks back to restore the position when a
+//! parser fails or tries alternatives. [`BinrwReader`] exposes the byte-aligned part of a
+//! [`BitReader`] as such a stream: it keeps the bytes it has read so binrw can seek back over
+//! them, and when dropped it returns the bytes binrw did not end up consuming to the
+//! `BitReader`. The bit reader then continues right after the last byte binrw consumed.
+//!
+//! Positions of the adapter count bytes from where it was created. Seeking from the end is
+//! not supported, and the kept bytes grow with the size of the section read through it.
+
+use crate::error::BitReadWriteError;
+use crate::reader::BitReader;
+use binrw::{BinRead, Endian};
+use std::io::{ErrorKind, Read, Result, Seek, SeekFrom};
+
+/// `Read + Seek` view of a byte-aligned [`BitReader`] for binrw parsers
+pub struct BinrwReader<'a, R: Read> {
+    reader: &'a mut BitReader<R>,
+    history: Vec<u8>,
+    pos: usize,
+}
+
+impl<'a, R: Read> BinrwReader<'a, R> {
+    /// Wraps `reader` at its current position
+    ///
+    /// # Errors
+    /// - `UnalignedAccess` if the reader is not byte-aligned
+    pub fn new(reader: &'a mut BitReader<R>) -> Result<Self> {
+        if !reader.is_byte_aligned() {
+            return Err(BitReadWriteError::UnalignedAccess {
+                pending_bits: reader.pending_bits(),
+            }
+            .into());
+        }
+        Ok(Self {
+            reader,
+            history: Vec::new(),
+            pos: 0,
+        })
+    }
+
+    /// 从 BitReader 读取，直到已读字节达到 target 或遇到 EOF
+    fn fill_to(&mut self, target: usize) -> Result<()> {
+        let mut chunk = [0u8; 256];
+        while self.history.len() < target {
+            let want = (target - self.history.len()).min(chunk.len());
+            match self.reader.read(&mut chunk[..want]) {
+                Ok(0) => break,
+                Ok(count) => self.history.extend_from_slice(&chunk[..count]),
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for BinrwReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.pos > self.history.len() {
+            // 之前越过末尾的 seek：先补读到该位置
+            self.fill_to(self.pos)?;
+            if self.pos > self.history.len() {
+                return Ok(0);
+            }
+        }
+        if self.pos == self.history.len() {
+            let count = self.reader.read(buf)?;
+            self.history.extend_from_slice(&buf[..count]);
+            self.pos += count;
+            return Ok(count);
+        }
+        let count = buf.len().min(self.history.len() - self.pos);
+        buf[..count].copy_from_slice(&self.history[self.pos..self.pos + count]);
+        self.pos += count;
+        Ok(count)
+    }
+}
+
+impl<R: Read> Seek for BinrwReader<'_, R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(delta) => (self.pos as u64).checked_add_signed(delta),
+            SeekFrom::End(_) => {
+                return Err(BitReadWriteError::SeekFromEnd.into());
+            }
+        };
+        let target = target.ok_or(BitReadWriteError::InvalidSeek)?;
+        self.pos = usize::try_from(target).map_err(|_| BitReadWriteError::InvalidSeek)?;
+        Ok(target)
+    }
+}
+
+impl<R: Read> Drop for BinrwReader<'_, R> {
+    fn drop(&mut self) {
+        let unread = &self.history[self.pos.min(self.history.len())..];
+        self.reader.unread_bytes(unread);
+    }
+}
+
+/// Parses a `T` with binrw from the byte-aligned position of `reader`
+///
+/// On success the reader continues right after the bytes `T` consumed; on failure it is left
+/// where it was.
+///
+/// # Errors
+/// - `UnalignedAccess` if the reader is not byte-aligned
+/// - The I/O error if binrw failed reading the stream
+/// - `InvalidData` carrying the message of the [`binrw::Error`] for any other parse failure
+pub fn read_binrw<T, R>(reader: &mut BitReader<R>, endian: Endian, args: T::Args<'_>) -> Result<T>
+where
+    T: BinRead,
+    R: Read,
+{
+    let mut adapter = BinrwReader::new(reader)?;
+    match T::read_options(&mut adapter, endian, args) {
+        Ok(value) => Ok(value),
+        Err(e) => {
+            adapter.pos = 0;
+            Err(match e {
+                binrw::Error::Io(e) => e,
+                e => BitReadWriteError::Binrw(e).into(),
+            })
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Bit-plane decomposition of `n`-bit symbols, as used by wavelet / bit-plane coders
+//! (EBCOT, SPIHT) and progressive transmission
+//!
+//! Plane `k` holds bit `k` of every symbol, one bit per symbol in symbol order. Planes are
+//! always ordered most significant first, so a receiver that stops after the first few planes
+//! still gets every symbol at reduced precision: the missing low planes read as zeros.
+//!
+//! [`split_bit_planes`] / [`merge_bit_planes`] work on packed byte buffers (MSB-first within
+//! each byte, the last byte zero padded), [`write_bit_planes`] / [`read_bit_planes`] on any
+//! bit stream.
+
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite};
+use std::io::Result;
+
+/// 校验符号位宽并确认所有符号都放得下
+fn check_symbols(symbols: &[u64], bits: usize) -> Result<()> {
+    if bits == 0 || bits > 64 {
+        return Err(BitReadWriteError::InvalidBitCount(bits).into());
+    }
+    match symbols.iter().find(|&&s| bits < 64 && s >> bits != 0) {
+        Some(&value) => Err(BitReadWriteError::ValueOutOfRange { value, bits }.into()),
+        None => Ok(()),
+    }
+}
+
+fn check_planes(planes: usize, bits: usize) -> Result<()> {
+    if bits == 0 || bits > 64 {
+        return Err(BitReadWriteError::InvalidBitCount(bits).into());
+    }
+    if planes > bits {
+        return Err(BitReadWriteError::InvalidPlaneCount { planes, bits }.into());
+    }
+    Ok(())
+}
+
+/// Splits `bits`-bit symbols (1-64) into `bits` packed planes, most significant plane first
+///
+/// Every plane is `symbols.len().div_ceil(8)` bytes long.
+///
+/// # Errors
+/// - `InvalidBitCount` if `bits` is not between 1-64
+/// - `ValueOutOfRange` if a symbol does not fit in `bits` bits
+pub fn split_bit_planes(symbols: &[u64], bits: usize) -> Result<Vec<Vec<u8>>> {
+    check_symbols(symbols, bits)?;
+    Ok((0..bits)
+        .rev()
+        .map(|plane| {
+            symbols
+                .chunks(8)
+                .map(|chunk| {
+                    let byte = chunk
+                        .iter()
+                        .fold(0u8, |byte, &s| (byte << 1) | ((s >> plane) & 1) as u8);
+                    byte << (8 - chunk.len())
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Rebuilds `count` symbols of `bits` bits (1-64) from packed planes, most significant first
+///
+/// `planes` may hold fewer than `bits` planes; the missing low bits are zero.
+///
+/// # Errors
+/// - `InvalidBitCount` if `bits` is not between 1-64
+/// - `InvalidInput` if there are more planes than `bits`
+/// - `BufferTooSmall` if a plane is shorter than `count.div_ceil(8)` bytes
+pub fn merge_bit_planes<P: AsRef<[u8]>>(
+    planes: &[P],
+    count: usize,
+    bits: usize,
+) -> Result<Vec<u64>> {
+    check_planes(planes.len(), bits)?;
+    let bytes = count.div_ceil(8);
+    if let Some(plane) = planes.iter().find(|p| p.as_ref().len() < bytes) {
+        return Err(BitReadWriteError::BufferTooSmall {
+            bits: count,
+            capacity: plane.as_ref().len() * 8,
+        }
+        .into());
+    }
+    let mut symbols = vec![0u64; count];
+    for (k, plane) in planes.iter().enumerate() {
+        let shift = bits - 1 - k;
+        let plane = plane.as_ref();
+        for (i, symbol) in symbols.iter_mut().enumerate() {
+            let bit = (plane[i / 8] >> (7 - i % 8)) & 1;
+            *symbol |= (bit as u64) << shift;
+        }
+    }
+    Ok(symbols)
+}
+
+/// Writes the `bits` planes (1-64) of `symbols` to `writer`, most significant plane first,
+/// each plane `symbols.len()` bits long
+///
+/// # Errors
+/// - `InvalidBitCount` if `bits` is not between 1-64
+/// - `ValueOutOfRange` if a symbol does not fit in `bits` bits; nothing is written then
+/// - Any error of the writer
+pub fn write_bit_planes<W: BitWrite + ?Sized>(
+    writer: &mut W,
+    symbols: &[u64],
+    bits: usize,
+) -> Result<()> {
+    check_symbols(symbols, bits)?;
+    for plane in (0..bits).rev() {
+        // 每个平面按 64 位一组写出
+        for chunk in symbols.chunks(64) {
+            let word = chunk
+                .iter()
+                .fold(0u64, |word, &s| (word << 1) | ((s >> plane) & 1));
+            writer.write_bits(word, chunk.len())?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the `planes` most significant planes of `out.len()` symbols of `bits` bits (1-64)
+/// written by [`write_bit_planes`]; pass `planes == bits` to read them all
+///
+/// # Errors
+/// - `InvalidBitCount` if `bits` is not between 1-64
+/// - `InvalidInput` if `planes` exceeds `bits`
+/// - Any error of the reader; `out` then holds the planes read so far
+pub fn read_bit_planes<R>(reader: &mut R, out: &mut [u64], bits: usize, planes: usize) -> Result<()>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    check_planes(planes, bits)?;
+    out.fill(0);
+    for plane in (bits - planes..bits).rev() {
+        for chunk in out.chunks_mut(64) {
+            let len = chunk.len();
+            let word = reader.read_bits(len)?;
+            for (i, symbol) in chunk.iter_mut().enumerate() {
+                *symbol |= ((word >> (len - 1 - i)) & 1) << plane;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,882 @@
+//! Bit-packed serde format (requires the `serde` feature)
+//!
+//! Values are written field by field in declaration order with no tags or padding, so the wire
+//! format is exactly as compact as the [`Schema`] says:
+//!
+//! | Type                      | Encoding                                                        |
+//! |---------------------------|-----------------------------------------------------------------|
+//! | `u8`..`u64`, `i8`..`i64`  | schema width, or the natural width (two's complement if signed) |
+//! | `bool`                    | schema width, or 1 bit                                          |
+//! | `char`                    | schema width, or 32 bits                                        |
+//! | `f32` / `f64`             | 32 / 64 bits (IEEE 754)                                         |
+//! | `str`, bytes, seq, map    | `length_bits` length prefix, then the elements (bytes are 8 bits) |
+//! | `Option<T>`               | 1 presence bit, then `T` if present                             |
+//! | enum                      | `variant_bits` variant index, then the payload                  |
+//! | struct, tuple, unit       | fields in order / nothing                                       |
+//!
+//! A schema width set on a sequence field applies to each of its elements. Field widths are
+//! looked up as `"Struct.field"` (or `"Variant.field"` for struct enum variants) first, and
+//! then as `"field"`.
+
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite, sign_extend, signed_bits};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::ser::{self, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io;
+
+// ------------------------------- Schema ------------------------------- //
+
+/// Field bit widths and prefix sizes of a bit-packed format
+#[derive(Clone, Debug)]
+pub struct Schema {
+    fields: HashMap<String, usize>,
+    length_bits: usize,
+    variant_bits: usize,
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Self {
+            fields: HashMap::new(),
+            length_bits: 32,
+            variant_bits: 8,
+        }
+    }
+}
+
+impl Schema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the width of a field, named `"field"` or `"Struct.field"`
+    pub fn field(mut self, name: &str, bits: usize) -> Self {
+        self.fields.insert(name.to_string(), bits);
+        self
+    }
+
+    /// Sets the width of the length prefix of strings, bytes, sequences and maps (default 32)
+    pub fn length_bits(mut self, bits: usize) -> Self {
+        self.length_bits = bits;
+        self
+    }
+
+    /// Sets the width of enum variant indices (default 8)
+    pub fn variant_bits(mut self, bits: usize) -> Self {
+        self.variant_bits = bits;
+        self
+    }
+
+    fn width_of(&self, name: &str, field: &str) -> Option<usize> {
+        let qualified = (!name.is_empty())
+            .then(|| self.fields.get(&format!("{}.{}", name, field)))
+            .flatten();
+        qualified.or_else(|| self.fields.get(field)).copied()
+    }
+}
+
+// ------------------------------- Error ------------------------------- //
+
+/// Error of the bit-packed serializer and deserializer, convertible to and from `io::Error`
+#[derive(Debug)]
+pub struct Error(io::Error);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        message(msg)
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        message(msg)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error(e)
+    }
+}
+
+impl From<BitReadWriteError> for Error {
+    fn from(e: BitReadWriteError) -> Self {
+        Error(e.into())
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        e.0
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+fn message(msg: impl Display) -> Error {
+    BitReadWriteError::Serde(msg.to_string()).into()
+}
+
+/// Serializes `value` into `writer` according to `schema`
+pub fn to_writer<T, W>(value: &T, schema: &Schema, writer: &mut W) -> io::Result<()>
+where
+    T: Serialize + ?Sized,
+    W: BitWrite + ?Sized,
+{
+    value.serialize(&mut Serializer::new(writer, schema))?;
+    Ok(())
+}
+
+/// Deserializes a `T` from `reader` according to `schema`
+pub fn from_reader<T, R>(reader: &mut R, schema: &Schema) -> io::Result<T>
+where
+    T: DeserializeOwned,
+    R: BitRead<Output = u64> + ?Sized,
+{
+    Ok(T::deserialize(&mut Deserializer::new(reader, schema))?)
+}
+
+fn check_width(n: usize) -> Result<usize> {
+    if n == 0 || n > 64 {
+        return Err(BitReadWriteError::InvalidBitCount(n).into());
+    }
+    Ok(n)
+}
+
+// ------------------------------- Serializer ------------------------------- //
+
+pub struct Serializer<'a, W: BitWrite + ?Sized> {
+    writer: &'a mut W,
+    schema: &'a Schema,
+    width: Option<usize>, // 下一个基本类型值使用的宽度（来自 schema），用过即清空
+}
+
+impl<'a, W: BitWrite + ?Sized> Serializer<'a, W> {
+    pub fn new(writer: &'a mut W, schema: &'a Schema) -> Self {
+        Self {
+            writer,
+            schema,
+            width: None,
+        }
+    }
+
+    fn write_unsigned(&mut self, value: u64, natural: usize) -> Result<()> {
+        let n = check_width(self.width.take().unwrap_or(natural))?;
+        if n < 64 && value >> n != 0 {
+            return Err(BitReadWriteError::ValueOutOfRange { value, bits: n }.into());
+        }
+        Ok(self.writer.write_bits(value, n)?)
+    }
+
+    fn write_signed(&mut self, value: i64, natural: usize) -> Result<()> {
+        let n = check_width(self.width.take().unwrap_or(natural))?;
+        // 写入补码的低 n 位
+        Ok(self.writer.write_bits(signed_bits(value, n)?, n)?)
+    }
+
+    fn write_prefix(&mut self, value: u64, n: usize) -> Result<()> {
+        let n = check_width(n)?;
+        if n < 64 && value >> n != 0 {
+            return Err(BitReadWriteError::ValueOutOfRange { value, bits: n }.into());
+        }
+        Ok(self.writer.write_bits(value, n)?)
+    }
+
+    fn write_len(&mut self, len: Option<usize>) -> Result<()> {
+        let len = len.ok_or(BitReadWriteError::UnknownLength)?;
+        self.write_prefix(len as u64, self.schema.length_bits)
+    }
+
+    fn write_fixed(&mut self, value: u64, n: usize, ty: &'static str) -> Result<()> {
+        match self.width.take() {
+            Some(width) if width != n => {
+                Err(BitReadWriteError::FixedWidthMismatch { ty, bits: n, width }.into())
+            }
+            _ => Ok(self.writer.write_bits(value, n)?),
+        }
+    }
+}
+
+impl<'a, 'b, W: BitWrite + ?Sized> ser::Serializer for &'b mut Serializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'a, 'b, W>;
+    type SerializeTuple = Compound<'a, 'b, W>;
+    type SerializeTupleStruct = Compound<'a, 'b, W>;
+    type SerializeTupleVariant = Compound<'a, 'b, W>;
+    type SerializeMap = Compound<'a, 'b, W>;
+    type SerializeStruct = Compound<'a, 'b, W>;
+    type SerializeStructVariant = Compound<'a, 'b, W>;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.write_unsigned(v as u64, 1)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.write_signed(v as i64, 8)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.write_signed(v as i64, 16)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.write_signed(v as i64, 32)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.write_signed(v, 64)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        self.write_unsigned(v as u64, 8)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.write_unsigned(v as u64, 16)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        self.write_unsigned(v as u64, 32)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.write_unsigned(v, 64)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        self.write_fixed(v.to_bits() as u64, 32, "f32")
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        self.write_fixed(v.to_bits(), 64, "f64")
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.write_unsigned(v as u64, 32)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.width = None;
+        self.write_len(Some(v.len()))?;
+        for &b in v {
+            self.writer.write_bits(b as u64, 8)?;
+        }
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.width = None;
+        Ok(self.writer.write_bits(0, 1)?)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        // 存在位不占用字段宽度，宽度留给内部的值
+        self.writer.write_bits(1, 1)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.width = None;
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        self.width = None;
+        self.write_prefix(variant_index as u64, self.schema.variant_bits)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.write_prefix(variant_index as u64, self.schema.variant_bits)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        let width = self.width.take();
+        self.write_len(len)?;
+        Ok(Compound::new(self, "", width))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        let width = self.width.take();
+        Ok(Compound::new(self, "", width))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        let width = self.width.take();
+        Ok(Compound::new(self, "", width))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        let width = self.width.take();
+        self.write_prefix(variant_index as u64, self.schema.variant_bits)?;
+        Ok(Compound::new(self, "", width))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        self.width = None;
+        self.write_len(len)?;
+        Ok(Compound::new(self, "", None))
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        self.width = None;
+        Ok(Compound::new(self, name, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        self.width = None;
+        self.write_prefix(variant_index as u64, self.schema.variant_bits)?;
+        Ok(Compound::new(self, variant, None))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// State of a sequence, tuple, map or struct being serialized
+pub struct Compound<'a, 'b, W: BitWrite + ?Sized> {
+    ser: &'b mut Serializer<'a, W>,
+    name: &'static str,
+    width: Option<usize>, // 序列字段的 schema 宽度，作用于每个元素
+}
+
+impl<'a, 'b, W: BitWrite + ?Sized> Compound<'a, 'b, W> {
+    fn new(ser: &'b mut Serializer<'a, W>, name: &'static str, width: Option<usize>) -> Self {
+        Self { ser, name, width }
+    }
+
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.ser.width = self.width;
+        value.serialize(&mut *self.ser)?;
+        self.ser.width = None;
+        Ok(())
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.ser.width = self.ser.schema.width_of(self.name, key);
+        value.serialize(&mut *self.ser)?;
+        self.ser.width = None;
+        Ok(())
+    }
+}
+
+impl<W: BitWrite + ?Sized> ser::SerializeSeq for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: BitWrite + ?Sized> ser::SerializeTuple for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: BitWrite + ?Sized> ser::SerializeTupleStruct for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: BitWrite + ?Sized> ser::SerializeTupleVariant for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: BitWrite + ?Sized> ser::SerializeMap for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.element(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: BitWrite + ?Sized> ser::SerializeStruct for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: BitWrite + ?Sized> ser::SerializeStructVariant for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+// ------------------------------- Deserializer ------------------------------- //
+
+pub struct Deserializer<'a, R: BitRead<Output = u64> + ?Sized> {
+    reader: &'a mut R,
+    schema: &'a Schema,
+    width: Option<usize>, // 下一个基本类型值使用的宽度（来自 schema），用过即清空
+    variants: &'static [&'static str], // 正在解析的枚举的变体名，用于查找 "Variant.field"
+    variant: &'static str,
+}
+
+impl<'a, R: BitRead<Output = u64> + ?Sized> Deserializer<'a, R> {
+    pub fn new(reader: &'a mut R, schema: &'a Schema) -> Self {
+        Self {
+            reader,
+            schema,
+            width: None,
+            variants: &[],
+            variant: "",
+        }
+    }
+
+    fn read_unsigned(&mut self, natural: usize) -> Result<u64> {
+        let n = check_width(self.width.take().unwrap_or(natural))?;
+        Ok(self.reader.read_bits(n)?)
+    }
+
+    fn read_signed(&mut self, natural: usize) -> Result<i64> {
+        let n = check_width(self.width.take().unwrap_or(natural))?;
+        Ok(sign_extend(self.reader.read_bits(n)?, n))
+    }
+
+    fn read_prefix(&mut self, n: usize) -> Result<u64> {
+        Ok(self.reader.read_bits(check_width(n)?)?)
+    }
+
+    fn read_len(&mut self) -> Result<usize> {
+        let len = self.read_prefix(self.schema.length_bits)?;
+        usize::try_from(len).map_err(|_| BitReadWriteError::LengthPrefixTooLarge(len).into())
+    }
+
+    fn read_fixed(&mut self, n: usize, ty: &'static str) -> Result<u64> {
+        match self.width.take() {
+            Some(width) if width != n => {
+                Err(BitReadWriteError::FixedWidthMismatch { ty, bits: n, width }.into())
+            }
+            _ => Ok(self.reader.read_bits(n)?),
+        }
+    }
+
+    fn read_bytes(&mut self) -> Result<Vec<u8>> {
+        self.width = None;
+        let len = self.read_len()?;
+        let mut bytes = Vec::with_capacity(len.min(1 << 16));
+        for _ in 0..len {
+            bytes.push(self.reader.read_bits(8)? as u8);
+        }
+        Ok(bytes)
+    }
+}
+
+impl<'de, R: BitRead<Output = u64> + ?Sized> de::Deserializer<'de> for &mut Deserializer<'_, R> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(BitReadWriteError::NotSelfDescribing.into())
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_bool(self.read_unsigned(1)? != 0)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(self.read_signed(8)?)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(self.read_signed(16)?)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(self.read_signed(32)?)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(self.read_signed(64)?)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(self.read_unsigned(8)?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(self.read_unsigned(16)?)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(self.read_unsigned(32)?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(self.read_unsigned(64)?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f32(f32::from_bits(self.read_fixed(32, "f32")? as u32))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f64(f64::from_bits(self.read_fixed(64, "f64")?))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let value = self.read_unsigned(32)?;
+        let c = u32::try_from(value)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or(BitReadWriteError::InvalidChar(value))?;
+        visitor.visit_char(c)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bytes = self.read_bytes()?;
+        let s = String::from_utf8(bytes).map_err(message)?;
+        visitor.visit_string(s)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_byte_buf(self.read_bytes()?)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.reader.read_bits(1)? == 0 {
+            self.width = None;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.width = None;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let width = self.width.take();
+        let len = self.read_len()?;
+        visitor.visit_seq(Access::elements(self, len, width))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        let width = self.width.take();
+        visitor.visit_seq(Access::elements(self, len, width))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.width = None;
+        let len = self.read_len()?;
+        visitor.visit_map(Access::elements(self, len, None))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.width = None;
+        visitor.visit_seq(Access::fields(self, name, fields))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.variants = variants;
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_u32(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(BitReadWriteError::CannotSkip.into())
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// 序列、元组、映射和结构体的逐项访问
+struct Access<'a, 'b, R: BitRead<Output = u64> + ?Sized> {
+    de: &'b mut Deserializer<'a, R>,
+    index: usize,
+    remaining: usize,
+    width: Option<usize>,
+    name: &'static str,
+    fields: &'static [&'static str],
+}
+
+impl<'a, 'b, R: BitRead<Output = u64> + ?Sized> Access<'a, 'b, R> {
+    fn elements(de: &'b mut Deserializer<'a, R>, len: usize, width: Option<usize>) -> Self {
+        Self {
+            de,
+            index: 0,
+            remaining: len,
+            width,
+            name: "",
+            fields: &[],
+        }
+    }
+
+    fn fields(
+        de: &'b mut Deserializer<'a, R>,
+        name: &'static str,
+        fields: &'static [&'static str],
+    ) -> Self {
+        Self {
+            de,
+            index: 0,
+            remaining: fields.len(),
+            width: None,
+            name,
+            fields,
+        }
+    }
+
+    fn next<'de, T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value> {
+        self.de.width = match self.fields.get(self.index) {
+            Some(field) => self.de.schema.width_of(self.name, field),
+            None => self.width,
+        };
+        self.index += 1;
+        self.remaining -= 1;
+        let value = seed.deserialize(&mut *self.de)?;
+        self.de.width = None;
+        Ok(value)
+    }
+}
+
+impl<'de, R: BitRead<Output = u64> + ?Sized> de::SeqAccess<'de> for Access<'_, '_, R> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.next(seed).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de, R: BitRead<Output = u64> + ?Sized> de::MapAccess<'de> for Access<'_, '_, R> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        // 键和值共用一个计数：这里先取键，next_value_seed 不再递减
+        self.de.width = None;
+        let key = seed.deserialize(&mut *self.de)?;
+        Ok(Some(key))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        self.remaining -= 1;
+        self.de.width = None;
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de, R: BitRead<Output = u64> + ?Sized> de::EnumAccess<'de> for &mut Deserializer<'_, R> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let width = self.width.take();
+        let index = self.read_prefix(self.schema.variant_bits)?;
+        let index =
+            u32::try_from(index).map_err(|_| BitReadWriteError::VariantIndexTooLarge(index))?;
+        self.variant = self.variants.get(index as usize).copied().unwrap_or("");
+        let value = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(index))?;
+        // 变体的负载沿用字段宽度（如 newtype 变体）
+        self.width = width;
+        Ok((value, self))
+    }
+}
+
+impl<'de, R: BitRead<Output = u64> + ?Sized> de::VariantAccess<'de> for &mut Deserializer<'_, R> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        self.width = None;
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let variant = self.variant;
+        de::Deserializer::deserialize_struct(self, variant, fields, visitor)
+    }
+}
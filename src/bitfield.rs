@@ -0,0 +1,188 @@
+//! Declarative [`bitfield!`](crate::bitfield!) macro for simple fixed layouts, no proc macro needed
+//!
+//! ```
+//! use bitio_rs::bitfield;
+//!
+//! bitfield! {
+//!     #[derive(Debug, PartialEq)]
+//!     pub struct Header {
+//!         pub version: u8 => 3,
+//!         pub flag: bool => 1,
+//!         pub kind: u16 => 12,
+//!         pub offset: i8 => 5,
+//!     }
+//! }
+//! ```
+//!
+//! Fields are read and written back to back in declaration order, in the byte order of the
+//! reader/writer, so the layout above takes exactly 21 bits. The generated struct gets
+//! `read_from` / `write_to` methods (with the struct's visibility) and implements
+//! [`BitDecode`](crate::traits::BitDecode) / [`BitEncode`](crate::traits::BitEncode).
+
+use crate::error::BitReadWriteError;
+use crate::traits::{sign_extend, signed_bits};
+use std::io::Result;
+
+/// Conversion between a field value and its raw `width`-bit representation
+///
+/// Implemented for the primitive integers and `bool`; implement it for your own `Copy` types
+/// (e.g. C-like enums) to use them as [`bitfield!`](crate::bitfield!) fields.
+pub trait BitfieldValue: Sized {
+    /// Converts the `width` raw bits read from the stream into a value
+    fn from_bits(bits: u64, width: usize) -> Result<Self>;
+
+    /// Converts the value into `width` raw bits
+    ///
+    /// Returns `ValueOutOfRange` if the value does not fit in `width` bits
+    fn to_bits(self, width: usize) -> Result<u64>;
+}
+
+fn check_width(width: usize) -> Result<()> {
+    if width == 0 || width > 64 {
+        return Err(BitReadWriteError::InvalidBitCount(width).into());
+    }
+    Ok(())
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl BitfieldValue for $t {
+            fn from_bits(bits: u64, _width: usize) -> Result<Self> {
+                // 位宽大于类型宽度时，读到的值可能放不下
+                <$t>::try_from(bits).map_err(|_| {
+                    BitReadWriteError::ValueOutOfRange { value: bits, bits: <$t>::BITS as usize }
+                        .into()
+                })
+            }
+
+            fn to_bits(self, width: usize) -> Result<u64> {
+                check_width(width)?;
+                let value = self as u64;
+                if width < 64 && value >> width != 0 {
+                    return Err(BitReadWriteError::ValueOutOfRange { value, bits: width }.into());
+                }
+                Ok(value)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl BitfieldValue for $t {
+            fn from_bits(bits: u64, width: usize) -> Result<Self> {
+                check_width(width)?;
+                // 按 width 位补码做符号扩展
+                let value = sign_extend(bits, width);
+                <$t>::try_from(value).map_err(|_| {
+                    BitReadWriteError::ValueOutOfRange { value: bits, bits: <$t>::BITS as usize }
+                        .into()
+                })
+            }
+
+            fn to_bits(self, width: usize) -> Result<u64> {
+                check_width(width)?;
+                signed_bits(self as i64, width)
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64);
+impl_signed!(i8, i16, i32, i64);
+
+impl BitfieldValue for bool {
+    fn from_bits(bits: u64, _width: usize) -> Result<Self> {
+        Ok(bits != 0)
+    }
+
+    fn to_bits(self, width: usize) -> Result<u64> {
+        check_width(width)?;
+        Ok(self as u64)
+    }
+}
+
+/// Defines a struct together with sequential bit-level read/write functions
+///
+/// Each field is written as `name: Type => width`; see the [module docs](crate::bitfield) for
+/// an example. Field types must be `Copy` and implement
+/// [`BitfieldValue`](crate::bitfield::BitfieldValue).
+///
+/// # Errors
+/// The generated `read_from` fails with the reader's error (e.g. `UnexpectedEof`) or with
+/// `ValueOutOfRange` if a field is wider than its type and the value read does not fit.
+/// `write_to` fails with `ValueOutOfRange` if a field value needs more than its width, in which
+/// case the fields before it have already been written.
+#[macro_export]
+macro_rules! bitfield {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $field:ident : $ty:ty => $width:expr
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                $field_vis $field: $ty,
+            )*
+        }
+
+        impl $name {
+            /// Reads every field in declaration order
+            #[allow(dead_code)]
+            $vis fn read_from<R>(reader: &mut R) -> ::std::io::Result<Self>
+            where
+                R: $crate::traits::BitRead<Output = u64> + ?Sized,
+            {
+                // 结构体表达式中的字段按书写顺序求值
+                ::std::result::Result::Ok(Self {
+                    $(
+                        $field: $crate::bitfield::BitfieldValue::from_bits(
+                            $crate::traits::BitRead::read_bits(reader, $width)?,
+                            $width,
+                        )?,
+                    )*
+                })
+            }
+
+            /// Writes every field in declaration order
+            #[allow(dead_code)]
+            $vis fn write_to<W>(&self, writer: &mut W) -> ::std::io::Result<()>
+            where
+                W: $crate::traits::BitWrite + ?Sized,
+            {
+                $(
+                    $crate::traits::BitWrite::write_bits(
+                        writer,
+                        $crate::bitfield::BitfieldValue::to_bits(self.$field, $width)?,
+                        $width,
+                    )?;
+                )*
+                ::std::result::Result::Ok(())
+            }
+        }
+
+        impl $crate::traits::BitDecode for $name {
+            fn from_reader<R>(reader: &mut R) -> ::std::io::Result<Self>
+            where
+                R: $crate::traits::BitRead<Output = u64> + ?Sized,
+            {
+                Self::read_from(reader)
+            }
+        }
+
+        impl $crate::traits::BitEncode for $name {
+            fn to_writer<W>(&self, writer: &mut W) -> ::std::io::Result<()>
+            where
+                W: $crate::traits::BitWrite + ?Sized,
+            {
+                self.write_to(writer)
+            }
+        }
+    };
+}
@@ -0,0 +1,63 @@
+//! Conversions between bit streams and [`bitvec`](https://docs.rs/bitvec) collections
+//! (requires the `bitvec` feature)
+//!
+//! Index 0 of a `BitVec` / `BitSlice` is always the first bit in stream order, whatever the
+//! byte order of the reader/writer and the `BitOrder` of the collection. Bits are transferred
+//! one `read_bits(1)` / `write_bits(_, 1)` call at a time, since the generic traits do not
+//! expose the byte order needed to map wider reads onto bit indices.
+
+use crate::traits::{BitRead, BitWrite};
+use ::bitvec::order::BitOrder;
+use ::bitvec::slice::BitSlice;
+use ::bitvec::store::BitStore;
+use ::bitvec::vec::BitVec;
+use std::io::Result;
+
+/// Reads the next `n` bits into a new `BitVec`
+///
+/// # Errors
+/// Any error of the reader, e.g. `UnexpectedEof` when fewer than `n` bits are left
+pub fn read_bitvec<T, O, R>(reader: &mut R, n: usize) -> Result<BitVec<T, O>>
+where
+    T: BitStore,
+    O: BitOrder,
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let mut bits = BitVec::with_capacity(n);
+    for _ in 0..n {
+        bits.push(reader.read_bits(1)? != 0);
+    }
+    Ok(bits)
+}
+
+/// Fills `bits` with the next `bits.len()` bits
+///
+/// # Errors
+/// Any error of the reader; the bits read before the error are kept in `bits`
+pub fn read_into_bitslice<T, O, R>(reader: &mut R, bits: &mut BitSlice<T, O>) -> Result<()>
+where
+    T: BitStore,
+    O: BitOrder,
+    R: BitRead<Output = u64> + ?Sized,
+{
+    for i in 0..bits.len() {
+        bits.set(i, reader.read_bits(1)? != 0);
+    }
+    Ok(())
+}
+
+/// Writes every bit of `bits`, index 0 first
+///
+/// # Errors
+/// Any error of the writer
+pub fn write_bitslice<T, O, W>(writer: &mut W, bits: &BitSlice<T, O>) -> Result<()>
+where
+    T: BitStore,
+    O: BitOrder,
+    W: BitWrite + ?Sized,
+{
+    for bit in bits.iter().by_vals() {
+        writer.write_bits(bit as u64, 1)?;
+    }
+    Ok(())
+}
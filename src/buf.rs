@@ -0,0 +1,249 @@
+//! Bit readers and writers over [`bytes::Buf`] / [`bytes::BufMut`] (requires the `bytes` feature)
+//!
+//! Bytes are taken from and put into the buffer directly, so network code holding a `Bytes` or
+//! `BytesMut` needs no intermediate `Vec` or `std::io` adapter.
+
+use crate::byte_order::ByteOrder;
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite};
+use bytes::{Buf, BufMut};
+use std::io::Result;
+
+// ------------------------------- BufBitReader ------------------------------- //
+
+/// Bit reader over any [`Buf`]
+///
+/// Bytes are consumed from the buffer only as the bits are needed, and a read that would run
+/// past the end fails with `UnexpectedEof` without consuming anything.
+#[derive(Debug)]
+pub struct BufBitReader<B: Buf> {
+    byte_order: ByteOrder,
+    inner: B,
+
+    bits_buffer: u64,
+    bits_in_buffer: usize,
+}
+
+impl<B: Buf> BufBitReader<B> {
+    pub fn new(inner: B) -> Self {
+        Self::with_byte_order(ByteOrder::BigEndian, inner)
+    }
+
+    pub fn with_byte_order(byte_order: ByteOrder, inner: B) -> Self {
+        Self {
+            byte_order,
+            inner,
+            bits_buffer: 0,
+            bits_in_buffer: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Returns the inner buffer; bits already taken from it but not read yet are discarded
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+
+    /// Returns `true` if at byte boundary (no pending bits)
+    pub fn is_byte_aligned(&self) -> bool {
+        self.bits_in_buffer.is_multiple_of(8)
+    }
+
+    /// Returns the number of bits left to read
+    pub fn bits_remaining(&self) -> usize {
+        self.bits_in_buffer + self.inner.remaining() * 8
+    }
+
+    /// 一次性取出至少凑够 n 比特所需（且放得下）的整字节
+    fn fill(&mut self, n: usize) {
+        let needed = n.saturating_sub(self.bits_in_buffer).div_ceil(8);
+        let count = needed.min((64 - self.bits_in_buffer) / 8);
+        if count == 0 {
+            return;
+        }
+        match self.byte_order {
+            ByteOrder::BigEndian => {
+                let bytes = self.inner.get_uint(count);
+                self.bits_buffer |= bytes << (64 - self.bits_in_buffer - count * 8);
+            }
+            ByteOrder::LittleEndian => {
+                let bytes = self.inner.get_uint_le(count);
+                self.bits_buffer |= bytes << self.bits_in_buffer;
+            }
+        }
+        self.bits_in_buffer += count * 8;
+    }
+
+    /// 从比特缓冲区取出 n 比特，调用方保证 1 <= n <= bits_in_buffer
+    fn take(&mut self, n: usize) -> u64 {
+        let value = match self.byte_order {
+            ByteOrder::BigEndian => self.bits_buffer >> (64 - n),
+            ByteOrder::LittleEndian => {
+                let mask = if n == 64 { u64::MAX } else { (1u64 << n) - 1 };
+                self.bits_buffer & mask
+            }
+        };
+        if n == 64 {
+            self.bits_buffer = 0;
+        } else {
+            match self.byte_order {
+                ByteOrder::BigEndian => self.bits_buffer <<= n,
+                ByteOrder::LittleEndian => self.bits_buffer >>= n,
+            }
+        }
+        self.bits_in_buffer -= n;
+        value
+    }
+}
+
+impl<B: Buf> BitRead for BufBitReader<B> {
+    type Output = u64;
+
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        if n > self.bits_remaining() {
+            return Err(BitReadWriteError::UnexpectedEof.into());
+        }
+
+        self.fill(n);
+        if self.bits_in_buffer >= n {
+            return Ok(self.take(n));
+        }
+
+        // 比特缓冲区中的零头比特加上所需的整字节超过 64 位：分两段读取再拼接
+        let first_bits = self.bits_in_buffer;
+        let first = self.take(first_bits);
+        let second_bits = n - first_bits;
+        self.fill(second_bits);
+        let second = self.take(second_bits);
+        Ok(match self.byte_order {
+            ByteOrder::BigEndian => (first << second_bits) | second,
+            ByteOrder::LittleEndian => first | (second << first_bits),
+        })
+    }
+}
+
+// ------------------------------- BufBitWriter ------------------------------- //
+
+/// Bit writer into any [`BufMut`]
+///
+/// Complete bytes are put into the buffer as soon as they are formed; a trailing partial byte is
+/// padded with zeros by [`into_inner`](Self::into_inner). Nothing is written on drop.
+#[derive(Debug)]
+pub struct BufBitWriter<B: BufMut> {
+    byte_order: ByteOrder,
+    inner: B,
+
+    bits_buffer: u64,
+    bits_in_buffer: usize, // 始终小于 8：凑满的字节会立即写出
+}
+
+impl<B: BufMut> BufBitWriter<B> {
+    pub fn new(inner: B) -> Self {
+        Self::with_byte_order(ByteOrder::BigEndian, inner)
+    }
+
+    pub fn with_byte_order(byte_order: ByteOrder, inner: B) -> Self {
+        Self {
+            byte_order,
+            inner,
+            bits_buffer: 0,
+            bits_in_buffer: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Returns `true` if at byte boundary (no pending bits)
+    pub fn is_byte_aligned(&self) -> bool {
+        self.bits_in_buffer == 0
+    }
+
+    /// Pads any pending partial byte with zeros and returns the inner buffer
+    ///
+    /// # Errors
+    /// Returns `BufferTooSmall` if the buffer has no room for the padded byte
+    pub fn into_inner(mut self) -> Result<B> {
+        if self.bits_in_buffer > 0 {
+            self.bits_in_buffer = 8;
+            self.put_aligned_bytes()?;
+        }
+        Ok(self.inner)
+    }
+
+    /// 将 k 比特放入比特缓冲区，调用方保证 k <= 64 - bits_in_buffer
+    fn push(&mut self, value: u64, k: usize) {
+        match self.byte_order {
+            ByteOrder::BigEndian => self.bits_buffer |= value << (64 - self.bits_in_buffer - k),
+            ByteOrder::LittleEndian => self.bits_buffer |= value << self.bits_in_buffer,
+        }
+        self.bits_in_buffer += k;
+    }
+
+    /// 将比特缓冲区中凑满的字节写入 inner
+    fn put_aligned_bytes(&mut self) -> Result<()> {
+        let count = self.bits_in_buffer / 8;
+        if count == 0 {
+            return Ok(());
+        }
+        // BufMut::put_slice 在容量不足时会 panic，这里提前转为错误
+        if self.inner.remaining_mut() < count {
+            return Err(BitReadWriteError::BufferTooSmall {
+                bits: count * 8,
+                capacity: self.inner.remaining_mut() * 8,
+            }
+            .into());
+        }
+        let bytes = match self.byte_order {
+            ByteOrder::BigEndian => self.bits_buffer.to_be_bytes(),
+            ByteOrder::LittleEndian => self.bits_buffer.to_le_bytes(),
+        };
+        self.inner.put_slice(&bytes[..count]);
+        if count == 8 {
+            self.bits_buffer = 0;
+        } else {
+            match self.byte_order {
+                ByteOrder::BigEndian => self.bits_buffer <<= count * 8,
+                ByteOrder::LittleEndian => self.bits_buffer >>= count * 8,
+            }
+        }
+        self.bits_in_buffer -= count * 8;
+        Ok(())
+    }
+}
+
+impl<B: BufMut> BitWrite for BufBitWriter<B> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        let value = if n == 64 {
+            value
+        } else {
+            value & ((1u64 << n) - 1)
+        };
+
+        let free = 64 - self.bits_in_buffer;
+        if n <= free {
+            self.push(value, n);
+        } else {
+            // 零头比特加上 n 超过 64 位：先放入能放下的部分并写出，再放入剩余部分
+            let rest = n - free;
+            let (first, second) = match self.byte_order {
+                ByteOrder::BigEndian => (value >> rest, value & ((1u64 << rest) - 1)),
+                ByteOrder::LittleEndian => (value & ((1u64 << free) - 1), value >> free),
+            };
+            self.push(first, free);
+            self.put_aligned_bytes()?;
+            self.push(second, rest);
+        }
+        self.put_aligned_bytes()
+    }
+}
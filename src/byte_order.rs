@@ -1,5 +1,108 @@
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite};
+use std::fmt::Debug;
+use std::io::Result;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ByteOrder {
+    #[default]
     BigEndian,
     LittleEndian,
 }
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Compile-time byte order selector for generic readers and writers
+///
+/// Implemented by the zero-sized markers [`Big`] and [`Little`], and by [`ByteOrder`] itself
+/// for an order chosen at runtime. Since `byte_order()` returns a constant for each marker,
+/// matching on it is folded away after monomorphization, so generic code pays no runtime
+/// branching for endianness.
+pub trait Endianness: sealed::Sealed + Copy + Default + Debug {
+    fn byte_order(&self) -> ByteOrder;
+}
+
+/// Marker for BigEndian streams
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Big;
+
+/// Marker for LittleEndian streams
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Little;
+
+impl sealed::Sealed for Big {}
+impl sealed::Sealed for Little {}
+impl sealed::Sealed for ByteOrder {}
+
+impl Endianness for Big {
+    #[inline(always)]
+    fn byte_order(&self) -> ByteOrder {
+        ByteOrder::BigEndian
+    }
+}
+
+impl Endianness for Little {
+    #[inline(always)]
+    fn byte_order(&self) -> ByteOrder {
+        ByteOrder::LittleEndian
+    }
+}
+
+impl Endianness for ByteOrder {
+    #[inline(always)]
+    fn byte_order(&self) -> ByteOrder {
+        *self
+    }
+}
+
+/// 按 order 拼接读取 n 比特：每 8 比特一组按流自身的比特顺序读取，最后一组可能不足 8 比特，
+/// 各组再按 order 组装（大端序首组在高位，小端序首组在低位）
+pub(crate) fn read_bits_in_order<R>(reader: &mut R, order: ByteOrder, n: usize) -> Result<u64>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    if n == 0 || n > 64 {
+        return Err(BitReadWriteError::InvalidBitCount(n).into());
+    }
+    let mut value = 0u64;
+    let mut done = 0;
+    while done < n {
+        let k = (n - done).min(8);
+        let group = reader.read_bits(k)?;
+        value = match order {
+            ByteOrder::BigEndian => (value << k) | group,
+            ByteOrder::LittleEndian => value | (group << done),
+        };
+        done += k;
+    }
+    Ok(value)
+}
+
+/// read_bits_in_order 的逆操作
+pub(crate) fn write_bits_in_order<W>(
+    writer: &mut W,
+    order: ByteOrder,
+    value: u64,
+    n: usize,
+) -> Result<()>
+where
+    W: BitWrite + ?Sized,
+{
+    if n == 0 || n > 64 {
+        return Err(BitReadWriteError::InvalidBitCount(n).into());
+    }
+    let mut done = 0;
+    while done < n {
+        let k = (n - done).min(8);
+        let shift = match order {
+            ByteOrder::BigEndian => n - done - k,
+            ByteOrder::LittleEndian => done,
+        };
+        writer.write_bits((value >> shift) & ((1u64 << k) - 1), k)?;
+        done += k;
+    }
+    Ok(())
+}
@@ -0,0 +1,113 @@
+//! DBC-style CAN signal decoding and encoding (requires the `can` feature)
+//!
+//! A [`Signal`] describes where a value sits in a CAN (or CAN FD) frame payload, using the
+//! conventions of DBC files:
+//! - `ByteOrder::LittleEndian` is Intel (`@1`): `start_bit` is the least significant bit,
+//!   counted as `byte * 8 + bit` with bit 0 the LSB of each byte.
+//! - `ByteOrder::BigEndian` is Motorola (`@0`): `start_bit` is the most significant bit, in the
+//!   same numbering, and the signal continues towards the LSB of that byte and then into the
+//!   next byte.
+//!
+//! The physical value is `raw * scale + offset`.
+
+use crate::byte_order::ByteOrder;
+use crate::cursor::BitCursor;
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite, sign_extend};
+use std::io::Result;
+
+/// Placement and scaling of one signal in a frame payload
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Signal {
+    pub start_bit: usize,
+    /// Width in bits (1-64)
+    pub length: usize,
+    pub byte_order: ByteOrder,
+    /// Whether the raw value is two's complement
+    pub signed: bool,
+    pub scale: f64,
+    pub offset: f64,
+}
+
+impl Signal {
+    /// 信号首个比特在同字节序游标中的位置：小端序游标从每个字节的最低位开始计数，与 Intel
+    /// 编号一致；大端序游标从最高位开始计数，需把 Motorola 的起始位（最高位）换算过去
+    fn cursor_position(&self) -> u64 {
+        match self.byte_order {
+            ByteOrder::LittleEndian => self.start_bit as u64,
+            ByteOrder::BigEndian => (self.start_bit / 8 * 8 + 7 - self.start_bit % 8) as u64,
+        }
+    }
+
+    /// Extracts the raw bits of the signal, without sign extension or scaling
+    ///
+    /// # Errors
+    /// - `InvalidBitCount` if `length` is not between 1-64
+    /// - `UnexpectedEof` if the signal extends past the end of `frame`
+    pub fn extract_raw(&self, frame: &[u8]) -> Result<u64> {
+        let mut cursor = BitCursor::with_byte_order(self.byte_order, frame);
+        cursor.set_position(self.cursor_position());
+        cursor.read_bits(self.length)
+    }
+
+    /// Overwrites the signal's bits in `frame` with the low `length` bits of `raw`
+    ///
+    /// # Errors
+    /// - `InvalidBitCount` if `length` is not between 1-64
+    /// - `UnexpectedEof` if the signal extends past the end of `frame`
+    pub fn inject_raw(&self, frame: &mut [u8], raw: u64) -> Result<()> {
+        let mut cursor = BitCursor::with_byte_order(self.byte_order, frame);
+        cursor.set_position(self.cursor_position());
+        cursor.write_bits(raw, self.length)
+    }
+
+    /// Decodes the physical value of the signal
+    ///
+    /// # Errors
+    /// See [`extract_raw`](Self::extract_raw)
+    pub fn decode(&self, frame: &[u8]) -> Result<f64> {
+        let raw = self.extract_raw(frame)?;
+        let raw = if self.signed {
+            sign_extend(raw, self.length) as f64
+        } else {
+            raw as f64
+        };
+        Ok(raw * self.scale + self.offset)
+    }
+
+    /// Encodes the physical `value` into `frame`, rounding to the nearest raw step
+    ///
+    /// # Errors
+    /// - `ValueOutOfRange` if the raw value does not fit in the signal
+    /// - `InvalidInput` if `value` is NaN or `scale` is zero
+    /// - See [`inject_raw`](Self::inject_raw)
+    pub fn encode(&self, frame: &mut [u8], value: f64) -> Result<()> {
+        if self.length == 0 || self.length > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(self.length).into());
+        }
+        let raw = ((value - self.offset) / self.scale).round();
+        if !raw.is_finite() {
+            return Err(BitReadWriteError::UnscalableSignal.into());
+        }
+
+        let span = 2f64.powi(self.length as i32);
+        let (min, max) = if self.signed {
+            (-span / 2.0, span / 2.0)
+        } else {
+            (0.0, span)
+        };
+        if raw < min || raw >= max {
+            return Err(BitReadWriteError::ValueOutOfRange {
+                value: raw as i64 as u64,
+                bits: self.length,
+            }
+            .into());
+        }
+        let raw = if self.signed {
+            raw as i64 as u64
+        } else {
+            raw as u64
+        };
+        self.inject_raw(frame, raw)
+    }
+}
@@ -0,0 +1,194 @@
+//! Frame-of-reference (FOR) and patched frame-of-reference (PFOR) integer codec
+//!
+//! Values are split into blocks of [`BLOCK_SIZE`]. Each block stores its minimum (the frame of
+//! reference) and bit-packs `value - minimum` at the smallest width that fits. In
+//! [`Mode::PFor`] the width may be chosen narrower than the largest delta; the few deltas that
+//! do not fit ("exceptions") have their high bits patched in after the packed block.
+//!
+//! ## Stream layout
+//! ```text
+//! count: 32 bits
+//! per block:
+//!   reference:       32 bits
+//!   width:            6 bits  (0..=32, 0 means every value equals the reference)
+//!   exception count:  8 bits
+//!   exception width:  6 bits  (only when exception count > 0)
+//!   deltas:           n * width bits
+//!   exceptions:       exception count * (7-bit index + exception width bits)
+//! ```
+//! All fields use the byte order of the underlying reader/writer.
+
+use crate::error::BitReadWriteError;
+use crate::packing::{pack_u32s, required_width_u32, unpack_u32s};
+use crate::traits::{BitRead, BitWrite};
+use std::io::Result;
+
+/// Number of values per block (the last block may be shorter)
+pub const BLOCK_SIZE: usize = 128;
+
+const WIDTH_BITS: usize = 6;
+const EXCEPTION_COUNT_BITS: usize = 8;
+const INDEX_BITS: usize = 7;
+
+/// How the encoder chooses the bit width of each block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Plain frame-of-reference: the width fits the largest delta of the block
+    For,
+    /// Patched frame-of-reference: the width minimizing the encoded size, outliers stored as exceptions
+    PFor,
+}
+
+/// Encodes `values` into `writer`
+///
+/// # Errors
+/// - `ValueOutOfRange` if `values` holds more than `u32::MAX` elements
+/// - Any error of the writer
+pub fn encode_u32s<W: BitWrite + ?Sized>(values: &[u32], mode: Mode, writer: &mut W) -> Result<()> {
+    if values.len() > u32::MAX as usize {
+        return Err(BitReadWriteError::ValueOutOfRange {
+            value: values.len() as u64,
+            bits: 32,
+        }
+        .into());
+    }
+    writer.write_bits(values.len() as u64, 32)?;
+
+    let mut deltas = [0u32; BLOCK_SIZE];
+    for block in values.chunks(BLOCK_SIZE) {
+        let reference = block.iter().copied().min().unwrap_or(0);
+        let deltas = &mut deltas[..block.len()];
+        for (d, &v) in deltas.iter_mut().zip(block) {
+            *d = v - reference;
+        }
+        let max_width = match required_width_u32(deltas) {
+            1 if deltas.iter().all(|&d| d == 0) => 0,
+            w => w,
+        };
+        let width = match mode {
+            Mode::For => max_width,
+            Mode::PFor => choose_width(deltas, max_width),
+        };
+
+        writer.write_bits(reference as u64, 32)?;
+        writer.write_bits(width as u64, WIDTH_BITS)?;
+
+        // 超出宽度的差值记为例外：低 width 位照常打包，高位单独补写
+        let exceptions: Vec<(usize, u32)> = deltas
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &d)| {
+                let high = high_bits(d, width);
+                (high != 0).then_some((i, high))
+            })
+            .collect();
+        writer.write_bits(exceptions.len() as u64, EXCEPTION_COUNT_BITS)?;
+        let exception_width = if exceptions.is_empty() {
+            0
+        } else {
+            let highs: Vec<u32> = exceptions.iter().map(|&(_, h)| h).collect();
+            let w = required_width_u32(&highs);
+            writer.write_bits(w as u64, WIDTH_BITS)?;
+            w
+        };
+
+        if width > 0 {
+            for d in deltas.iter_mut() {
+                *d = low_bits(*d, width);
+            }
+            pack_u32s(deltas, width, writer)?;
+        }
+        for (index, high) in exceptions {
+            writer.write_bits(index as u64, INDEX_BITS)?;
+            writer.write_bits(high as u64, exception_width)?;
+        }
+    }
+    Ok(())
+}
+
+/// Decodes a stream written by [`encode_u32s`]
+///
+/// # Errors
+/// - `InvalidData` if a block header is corrupt
+/// - Any error of the reader, e.g. `UnexpectedEof` when the stream is truncated
+pub fn decode_u32s<R>(reader: &mut R) -> Result<Vec<u32>>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let count = reader.read_bits(32)? as usize;
+    let mut values = Vec::with_capacity(count.min(1 << 20));
+
+    let mut deltas = [0u32; BLOCK_SIZE];
+    while values.len() < count {
+        let n = (count - values.len()).min(BLOCK_SIZE);
+        let reference = reader.read_bits(32)? as u32;
+        let width = reader.read_bits(WIDTH_BITS)? as usize;
+        let exception_count = reader.read_bits(EXCEPTION_COUNT_BITS)? as usize;
+        if width > 32 || exception_count > n {
+            return Err(BitReadWriteError::InvalidBlockHeader {
+                width,
+                exceptions: exception_count,
+            }
+            .into());
+        }
+        let exception_width = if exception_count > 0 {
+            let w = reader.read_bits(WIDTH_BITS)? as usize;
+            if w == 0 || width + w > 32 {
+                return Err(BitReadWriteError::InvalidExceptionWidth(w).into());
+            }
+            w
+        } else {
+            0
+        };
+
+        let deltas = &mut deltas[..n];
+        if width > 0 {
+            unpack_u32s(reader, width, deltas)?;
+        } else {
+            deltas.fill(0);
+        }
+        for _ in 0..exception_count {
+            let index = reader.read_bits(INDEX_BITS)? as usize;
+            let high = reader.read_bits(exception_width)? as u32;
+            if index >= n {
+                return Err(BitReadWriteError::ExceptionIndexOutOfBlock { index, len: n }.into());
+            }
+            deltas[index] |= high << width;
+        }
+
+        for &d in deltas.iter() {
+            values.push(
+                reference
+                    .checked_add(d)
+                    .ok_or(BitReadWriteError::ReferenceOverflow)?,
+            );
+        }
+    }
+    Ok(values)
+}
+
+/// 选择使本块编码后总比特数最小的宽度；相同大小时取较大的宽度（例外更少）
+fn choose_width(deltas: &[u32], max_width: usize) -> usize {
+    let mut best = (deltas.len() * max_width, max_width);
+    for width in (0..max_width).rev() {
+        let (exceptions, highs) = deltas
+            .iter()
+            .map(|&d| high_bits(d, width))
+            .filter(|&h| h != 0)
+            .fold((0, 0), |(count, acc), h| (count + 1, acc | h));
+        let exception_width = required_width_u32(&[highs]);
+        let cost = deltas.len() * width + WIDTH_BITS + exceptions * (INDEX_BITS + exception_width);
+        if cost < best.0 {
+            best = (cost, width);
+        }
+    }
+    best.1
+}
+
+fn high_bits(delta: u32, width: usize) -> u32 {
+    (delta as u64 >> width) as u32
+}
+
+fn low_bits(delta: u32, width: usize) -> u32 {
+    (delta as u64 & ((1u64 << width) - 1)) as u32
+}
@@ -0,0 +1,5 @@
+//! Higher-level codecs built on the bit-level readers and writers
+
+pub mod frame_of_reference;
+pub mod rice;
+pub mod run_length;
@@ -0,0 +1,99 @@
+//! Unary and Rice codes, as used by FLAC, Monkey's Audio, lossless JPEG and similar codecs
+//!
+//! A unary value `q` is `q` zero bits followed by a one bit. A Rice code with parameter `k`
+//! stores `value >> k` in unary followed by the low `k` bits of `value`, which is compact for
+//! values of magnitude around `2^k`. Signed values are folded onto unsigned ones as
+//! `0, -1, 1, -2, 2, ...` (zigzag), the mapping FLAC uses.
+
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite};
+use std::io::Result;
+
+fn check_parameter(k: usize) -> Result<()> {
+    if k > 63 {
+        return Err(BitReadWriteError::InvalidBitCount(k).into());
+    }
+    Ok(())
+}
+
+/// Folds a signed value onto an unsigned one: `0, -1, 1, -2` to `0, 1, 2, 3`
+pub fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Inverse of [`zigzag_encode`]
+pub fn zigzag_decode(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+/// Reads a unary value: the number of zero bits before the next one bit
+///
+/// # Errors
+/// Any error of the reader, e.g. `UnexpectedEof` if no one bit follows
+pub fn read_unary<R>(reader: &mut R) -> Result<u64>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let mut count = 0;
+    while reader.read_bits(1)? == 0 {
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Writes `value` zero bits followed by a one bit
+pub fn write_unary<W: BitWrite + ?Sized>(writer: &mut W, value: u64) -> Result<()> {
+    let mut zeros = value;
+    while zeros >= 64 {
+        writer.write_bits(0, 64)?;
+        zeros -= 64;
+    }
+    if zeros > 0 {
+        writer.write_bits(0, zeros as usize)?;
+    }
+    writer.write_bits(1, 1)
+}
+
+/// Reads an unsigned Rice code with parameter `k` (0-63)
+///
+/// # Errors
+/// - `InvalidBitCount` if `k` exceeds 63
+/// - `InvalidData` if the decoded value does not fit in a `u64`
+/// - Any error of the reader
+pub fn read_rice<R>(reader: &mut R, k: usize) -> Result<u64>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    check_parameter(k)?;
+    let quotient = read_unary(reader)?;
+    if k > 0 && quotient >> (64 - k) != 0 {
+        return Err(BitReadWriteError::RiceOverflow.into());
+    }
+    let low = if k > 0 { reader.read_bits(k)? } else { 0 };
+    Ok((quotient << k) | low)
+}
+
+/// Writes `value` as a Rice code with parameter `k` (0-63)
+///
+/// The unary part has `value >> k` zero bits, so small `k` with large values gets long.
+pub fn write_rice<W: BitWrite + ?Sized>(writer: &mut W, value: u64, k: usize) -> Result<()> {
+    check_parameter(k)?;
+    write_unary(writer, value >> k)?;
+    if k > 0 {
+        writer.write_bits(value & ((1 << k) - 1), k)?;
+    }
+    Ok(())
+}
+
+/// Reads a zigzag-folded signed Rice code with parameter `k` (0-63)
+pub fn read_rice_signed<R>(reader: &mut R, k: usize) -> Result<i64>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    Ok(zigzag_decode(read_rice(reader, k)?))
+}
+
+/// Writes `value` as a zigzag-folded signed Rice code with parameter `k` (0-63)
+pub fn write_rice_signed<W: BitWrite + ?Sized>(writer: &mut W, value: i64, k: usize) -> Result<()> {
+    write_rice(writer, zigzag_encode(value), k)
+}
@@ -0,0 +1,96 @@
+//! Run-length encoding of bit sequences, e.g. for compressing masks
+//!
+//! The sequence is stored as the lengths of its alternating runs of identical bits, starting
+//! with a run of the first bit. Each length takes a configurable number of bits; a run longer
+//! than the field can hold is split by a zero-length run of the opposite bit.
+//!
+//! ## Stream layout
+//! ```text
+//! count:     32 bits  (number of bits in the sequence)
+//! width:      6 bits  (1..=32, width of each run length)
+//! first bit:  1 bit   (only when count > 0)
+//! runs:      width bits each, until they add up to count
+//! ```
+//! All fields use the byte order of the underlying reader/writer.
+
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite};
+use std::io::Result;
+
+const COUNT_BITS: usize = 32;
+const WIDTH_BITS: usize = 6;
+
+/// Encodes `bits` into `writer` with `width`-bit run lengths
+///
+/// Narrow widths suit sequences that flip often, wide ones long uniform stretches.
+///
+/// # Errors
+/// - `InvalidBitCount` if `width` is not between 1-32
+/// - `ValueOutOfRange` if `bits` holds more than `u32::MAX` elements
+/// - Any error of the writer
+pub fn encode_bits<W: BitWrite + ?Sized>(
+    bits: &[bool],
+    width: usize,
+    writer: &mut W,
+) -> Result<()> {
+    if width == 0 || width > 32 {
+        return Err(BitReadWriteError::InvalidBitCount(width).into());
+    }
+    if bits.len() > u32::MAX as usize {
+        return Err(BitReadWriteError::ValueOutOfRange {
+            value: bits.len() as u64,
+            bits: COUNT_BITS,
+        }
+        .into());
+    }
+    writer.write_bits(bits.len() as u64, COUNT_BITS)?;
+    writer.write_bits(width as u64, WIDTH_BITS)?;
+    let Some(&first) = bits.first() else {
+        return Ok(());
+    };
+    writer.write_bits(first as u64, 1)?;
+
+    let max_run = (1u64 << width) - 1;
+    for run in bits.chunk_by(|a, b| a == b) {
+        // 超出字段上限的游程拆成 max_run 加上一个对方比特的零长游程
+        let mut len = run.len() as u64;
+        while len > max_run {
+            writer.write_bits(max_run, width)?;
+            writer.write_bits(0, width)?;
+            len -= max_run;
+        }
+        writer.write_bits(len, width)?;
+    }
+    Ok(())
+}
+
+/// Decodes a stream written by [`encode_bits`]
+///
+/// # Errors
+/// - `InvalidData` if the header is corrupt or the runs overshoot the bit count
+/// - Any error of the reader, e.g. `UnexpectedEof` when the stream is truncated
+pub fn decode_bits<R>(reader: &mut R) -> Result<Vec<bool>>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let count = reader.read_bits(COUNT_BITS)? as usize;
+    let width = reader.read_bits(WIDTH_BITS)? as usize;
+    if width == 0 || width > 32 {
+        return Err(BitReadWriteError::InvalidRunWidth(width).into());
+    }
+    let mut bits = Vec::with_capacity(count.min(1 << 20));
+    if count == 0 {
+        return Ok(bits);
+    }
+
+    let mut bit = reader.read_bits(1)? != 0;
+    while bits.len() < count {
+        let len = reader.read_bits(width)? as usize;
+        if len > count - bits.len() {
+            return Err(BitReadWriteError::RunsExceedCount(count).into());
+        }
+        bits.resize(bits.len() + len, bit);
+        bit = !bit;
+    }
+    Ok(bits)
+}
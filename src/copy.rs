@@ -0,0 +1,28 @@
+//! Bit-level analogue of [`std::io::copy`]
+
+use crate::traits::{BitRead, BitWrite};
+use std::io::Result;
+
+/// Copies the next `n_bits` bits from `reader` to `writer`
+///
+/// Bits move in 64-bit chunks (plus one shorter tail), so long runs cost one call on each side
+/// per 64 bits instead of a manual loop. Reader and writer should use the same byte order; the
+/// bits then land in the output exactly as they were in the input, whatever the bit offset of
+/// either side.
+///
+/// # Errors
+/// Any error of the reader (e.g. `UnexpectedEof` if fewer than `n_bits` bits are left) or of the
+/// writer. The chunks copied before the error have already been written.
+pub fn copy_bits<R, W>(reader: &mut R, writer: &mut W, n_bits: u64) -> Result<()>
+where
+    R: BitRead<Output = u64> + ?Sized,
+    W: BitWrite + ?Sized,
+{
+    let mut remaining = n_bits;
+    while remaining > 0 {
+        let n = remaining.min(64) as usize;
+        writer.write_bits(reader.read_bits(n)?, n)?;
+        remaining -= n as u64;
+    }
+    Ok(())
+}
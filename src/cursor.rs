@@ -0,0 +1,282 @@
+use crate::byte_order::{self, ByteOrder};
+use crate::error::{self, BitReadWriteError};
+use crate::traits::{BitExpect, BitPeek, BitRead, BitWrite};
+use std::io::{Result, SeekFrom};
+
+/// In-memory bit cursor supporting reads, writes and bit-level seeking on the same buffer
+///
+/// This is the bit-level analogue of [`std::io::Cursor`]: the position is counted in **bits**
+/// and writes overwrite existing bits in place. `BitCursor<Vec<u8>>` grows the vector when
+/// writing past its end, while `BitCursor<&mut [u8]>` fails with `BufferTooSmall` instead.
+#[derive(Clone, Debug)]
+pub struct BitCursor<T> {
+    byte_order: ByteOrder,
+    inner: T,
+    pos: u64, // 当前位置，以比特为单位
+}
+
+impl<T> BitCursor<T> {
+    pub fn new(inner: T) -> Self {
+        Self::with_byte_order(ByteOrder::BigEndian, inner)
+    }
+
+    pub fn with_byte_order(byte_order: ByteOrder, inner: T) -> Self {
+        Self {
+            byte_order,
+            inner,
+            pos: 0,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
+    /// Returns the current position in bits
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Sets the current position in bits
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+
+    /// Returns `true` if the position is on a byte boundary
+    pub fn is_byte_aligned(&self) -> bool {
+        self.pos.is_multiple_of(8)
+    }
+}
+
+impl<T: AsRef<[u8]>> BitCursor<T> {
+    /// Returns the total length of the underlying buffer in bits
+    pub fn len_bits(&self) -> u64 {
+        self.inner.as_ref().len() as u64 * 8
+    }
+
+    /// Moves the position by a number of **bits**, like [`std::io::Seek::seek`] does for bytes
+    ///
+    /// Seeking past the end is allowed; subsequent reads fail and writes extend (or fail on
+    /// fixed-size buffers).
+    ///
+    /// # Returns
+    /// The new position in bits
+    ///
+    /// # Errors
+    /// Returns `InvalidInput` if the resulting position would be negative or overflow
+    pub fn seek_bits(&mut self, pos: SeekFrom) -> Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => {
+                self.pos = n;
+                return Ok(n);
+            }
+            SeekFrom::End(n) => (self.len_bits(), n),
+            SeekFrom::Current(n) => (self.pos, n),
+        };
+        match base.checked_add_signed(offset) {
+            Some(n) => {
+                self.pos = n;
+                Ok(n)
+            }
+            None => Err(BitReadWriteError::InvalidSeek.into()),
+        }
+    }
+
+    /// 把在当前位置请求 n 比特时产生的错误附上位置和剩余比特数
+    fn locate(&self, err: BitReadWriteError, n: usize) -> std::io::Error {
+        let available = match err {
+            BitReadWriteError::UnexpectedEof => Some(self.len_bits().saturating_sub(self.pos)),
+            _ => None,
+        };
+        error::locate(err.into(), self.pos, Some(n), available)
+    }
+
+    /// 写入 n 比特前检查结束位置不溢出且不超过 capacity 比特，否则报 BufferTooSmall
+    fn check_room(&self, n: usize, capacity: u64) -> Result<()> {
+        match self.pos.checked_add(n as u64) {
+            Some(end) if end <= capacity => Ok(()),
+            _ => {
+                let err = BitReadWriteError::BufferTooSmall {
+                    bits: n,
+                    capacity: capacity
+                        .saturating_sub(self.pos)
+                        .try_into()
+                        .unwrap_or(usize::MAX),
+                };
+                Err(self.locate(err, n))
+            }
+        }
+    }
+
+    fn get_bits(&self, n: usize) -> Result<u64> {
+        if n == 0 || n > 64 {
+            return Err(self.locate(BitReadWriteError::InvalidBitCount(n), n));
+        }
+        // set_position 之后 pos + n 可能溢出，此时同样是读过了末尾
+        if self
+            .pos
+            .checked_add(n as u64)
+            .is_none_or(|end| end > self.len_bits())
+        {
+            return Err(self.locate(BitReadWriteError::UnexpectedEof, n));
+        }
+
+        let data = self.inner.as_ref();
+        let mut pos = self.pos as usize;
+        let mut remaining = n;
+        let mut value = 0u64;
+        let mut shift = 0;
+        while remaining > 0 {
+            // 每轮最多处理当前字节内剩余的比特
+            let byte = data[pos / 8];
+            let offset = pos % 8;
+            let available = 8 - offset;
+            let take = available.min(remaining);
+            let mask = ((1u16 << take) - 1) as u8;
+            match self.byte_order {
+                ByteOrder::BigEndian => {
+                    // 大端序从字节最高位开始取，先取到的位放在结果的高位
+                    let bits = (byte >> (available - take)) & mask;
+                    value = (value << take) | bits as u64;
+                }
+                ByteOrder::LittleEndian => {
+                    // 小端序从字节最低位开始取，先取到的位放在结果的低位
+                    let bits = (byte >> offset) & mask;
+                    value |= (bits as u64) << shift;
+                    shift += take;
+                }
+            }
+            pos += take;
+            remaining -= take;
+        }
+        Ok(value)
+    }
+}
+
+impl<T: AsMut<[u8]>> BitCursor<T> {
+    /// 将 n 比特覆盖写入当前位置，调用方需确保缓冲区足够大
+    fn put_bits(&mut self, value: u64, n: usize) {
+        let byte_order = self.byte_order;
+        let data = self.inner.as_mut();
+        let mut pos = self.pos as usize;
+        let mut remaining = n;
+        let mut shift = 0;
+        while remaining > 0 {
+            let offset = pos % 8;
+            let available = 8 - offset;
+            let take = available.min(remaining);
+            let mask = ((1u16 << take) - 1) as u8;
+            let byte = &mut data[pos / 8];
+            match byte_order {
+                ByteOrder::BigEndian => {
+                    let bits = (value >> (remaining - take)) as u8 & mask;
+                    let at = available - take;
+                    *byte = (*byte & !(mask << at)) | (bits << at);
+                }
+                ByteOrder::LittleEndian => {
+                    let bits = (value >> shift) as u8 & mask;
+                    *byte = (*byte & !(mask << offset)) | (bits << offset);
+                    shift += take;
+                }
+            }
+            pos += take;
+            remaining -= take;
+        }
+        self.pos = pos as u64;
+    }
+}
+
+impl<T: AsRef<[u8]>> BitCursor<T> {
+    /// Reads `n` bits (1-64) assembled in `order` instead of the cursor's own byte order
+    ///
+    /// See [`BitReader::read_bits_with`](crate::reader::BitReader::read_bits_with).
+    pub fn read_bits_with(&mut self, order: ByteOrder, n: usize) -> Result<u64> {
+        if order == self.byte_order {
+            return self.read_bits(n);
+        }
+        byte_order::read_bits_in_order(self, order, n)
+    }
+}
+
+impl<T> BitCursor<T>
+where
+    Self: BitWrite,
+{
+    /// Writes the low `n` bits (1-64) of `value` in `order` instead of the cursor's own byte order
+    ///
+    /// See [`BitWriter::write_bits_with`](crate::writer::BitWriter::write_bits_with).
+    pub fn write_bits_with(&mut self, order: ByteOrder, value: u64, n: usize) -> Result<()> {
+        if order == self.byte_order {
+            return self.write_bits(value, n);
+        }
+        byte_order::write_bits_in_order(self, order, value, n)
+    }
+}
+
+impl<T: AsRef<[u8]>> BitRead for BitCursor<T> {
+    type Output = u64;
+
+    fn read_bits(&mut self, n: usize) -> Result<Self::Output> {
+        let value = self.get_bits(n)?;
+        self.pos += n as u64;
+        Ok(value)
+    }
+}
+
+impl<T: AsRef<[u8]>> BitExpect for BitCursor<T> {
+    fn bit_position(&self) -> u64 {
+        self.pos
+    }
+
+    /// At (or past) the end of the buffer
+    fn is_at_end(&mut self) -> Result<bool> {
+        Ok(self.pos >= self.len_bits())
+    }
+}
+
+impl<T: AsRef<[u8]>> BitPeek for BitCursor<T> {
+    type Output = u64;
+
+    fn peek_bits(&mut self, n: usize) -> Result<Self::Output> {
+        self.get_bits(n)
+    }
+}
+
+impl BitWrite for BitCursor<Vec<u8>> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        if n == 0 || n > 64 {
+            return Err(self.locate(BitReadWriteError::InvalidBitCount(n), n));
+        }
+        // 写入超过末尾时自动扩容（与 std::io::Cursor<Vec<u8>> 行为一致），结束位置不能超出可寻址范围
+        self.check_room(n, (isize::MAX as u64).saturating_mul(8))?;
+        let end = (self.pos + n as u64).div_ceil(8) as usize;
+        if end > self.inner.len() {
+            self.inner.resize(end, 0);
+        }
+        self.put_bits(value, n);
+        Ok(())
+    }
+}
+
+impl BitWrite for BitCursor<&mut [u8]> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        if n == 0 || n > 64 {
+            return Err(self.locate(BitReadWriteError::InvalidBitCount(n), n));
+        }
+        self.check_room(n, self.len_bits())?;
+        self.put_bits(value, n);
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+//! Interoperability with [deku](https://docs.rs/deku) (requires the `deku` feature)
+//!
+//! deku works on MSB-first bits: `from_bytes` takes `(&[u8], usize)`, a byte slice plus a bit
+//! offset into its first byte, and its `bits` feature exposes `BitSlice<u8, Msb0>`. That is
+//! the layout of a `BigEndian` stream, so use big-endian cursors with these adapters.
+//!
+//! - [`DekuBitReader`] reads a deku bit-slice through the [`BitRead`] / [`BitPeek`] traits.
+//! - [`parse_cursor`] runs a deku container parser on the bytes of a [`BitCursor`] and advances
+//!   it, so deku types can be decoded in the middle of a bitio-rs parse.
+
+use crate::byte_order::ByteOrder;
+use crate::cursor::BitCursor;
+use crate::error::BitReadWriteError;
+use crate::traits::{BitPeek, BitRead};
+use deku::DekuContainerRead;
+use deku::bitvec::{BitField, BitSlice, Msb0};
+use std::io::Result;
+
+/// [`BitRead`] over a deku `BitSlice<u8, Msb0>`, reading MSB-first like a `BigEndian` stream
+#[derive(Clone, Debug)]
+pub struct DekuBitReader<'a> {
+    bits: &'a BitSlice<u8, Msb0>,
+    pos: usize,
+}
+
+impl<'a> DekuBitReader<'a> {
+    pub fn new(bits: &'a BitSlice<u8, Msb0>) -> Self {
+        Self { bits, pos: 0 }
+    }
+
+    /// Number of bits consumed so far
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The bits that have not been read yet
+    pub fn remaining(&self) -> &'a BitSlice<u8, Msb0> {
+        &self.bits[self.pos..]
+    }
+
+    fn get_bits(&self, n: usize) -> Result<u64> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        if n > self.bits.len() - self.pos {
+            return Err(BitReadWriteError::UnexpectedEof.into());
+        }
+        Ok(self.bits[self.pos..self.pos + n].load_be::<u64>())
+    }
+}
+
+impl<'a> From<&'a BitSlice<u8, Msb0>> for DekuBitReader<'a> {
+    fn from(bits: &'a BitSlice<u8, Msb0>) -> Self {
+        Self::new(bits)
+    }
+}
+
+impl BitRead for DekuBitReader<'_> {
+    type Output = u64;
+
+    fn read_bits(&mut self, n: usize) -> Result<Self::Output> {
+        let value = self.get_bits(n)?;
+        self.pos += n;
+        Ok(value)
+    }
+}
+
+impl BitPeek for DekuBitReader<'_> {
+    type Output = u64;
+
+    fn peek_bits(&mut self, n: usize) -> Result<Self::Output> {
+        self.get_bits(n)
+    }
+}
+
+/// Runs the deku container parser of `T` on the unread bits of a big-endian `cursor` and
+/// advances it past the bits `T` consumed
+///
+/// # Errors
+/// - `InvalidInput` if the cursor is little-endian
+/// - `UnexpectedEof` if deku needs more bits than the cursor holds
+/// - `InvalidData` for any other deku error
+pub fn parse_cursor<T, D>(cursor: &mut BitCursor<D>) -> Result<T>
+where
+    T: for<'a> DekuContainerRead<'a>,
+    D: AsRef<[u8]>,
+{
+    if cursor.byte_order() == ByteOrder::LittleEndian {
+        return Err(BitReadWriteError::BigEndianRequired.into());
+    }
+    let pos = cursor.position();
+    let data = cursor.get_ref().as_ref();
+    let start = ((pos / 8) as usize).min(data.len());
+    match T::from_bytes((&data[start..], (pos % 8) as usize)) {
+        Ok(((rest, offset), value)) => {
+            let consumed = (data.len() - start - rest.len()) as u64;
+            cursor.set_position(start as u64 * 8 + consumed * 8 + offset as u64);
+            Ok(value)
+        }
+        Err(deku::DekuError::Incomplete(_)) => Err(BitReadWriteError::UnexpectedEof.into()),
+        Err(e) => Err(BitReadWriteError::Deku(e).into()),
+    }
+}
@@ -0,0 +1,135 @@
+//! Runtime helpers called by the code generated by `#[derive(BitRead, BitWrite)]`
+//!
+//! Not a public API: items here may change without notice.
+
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite, sign_extend, signed_bits};
+use std::io::Result;
+
+/// Counts the bits passing through, so `#[align(n)]` can pad relative to the start of a struct
+pub struct Counting<'a, T: ?Sized> {
+    inner: &'a mut T,
+    bits: u64,
+}
+
+impl<'a, T: ?Sized> Counting<'a, T> {
+    pub fn new(inner: &'a mut T) -> Self {
+        Self { inner, bits: 0 }
+    }
+
+    /// 距离下一个 align 比特边界还差的比特数
+    fn padding(&self, align: usize) -> usize {
+        let rem = (self.bits % align as u64) as usize;
+        if rem == 0 { 0 } else { align - rem }
+    }
+}
+
+impl<T: BitRead<Output = u64> + ?Sized> Counting<'_, T> {
+    pub fn align_read(&mut self, align: usize) -> Result<()> {
+        let mut padding = self.padding(align);
+        while padding > 0 {
+            let n = padding.min(64);
+            self.read_bits(n)?;
+            padding -= n;
+        }
+        Ok(())
+    }
+}
+
+impl<T: BitWrite + ?Sized> Counting<'_, T> {
+    pub fn align_write(&mut self, align: usize) -> Result<()> {
+        let mut padding = self.padding(align);
+        while padding > 0 {
+            let n = padding.min(64);
+            self.write_bits(0, n)?;
+            padding -= n;
+        }
+        Ok(())
+    }
+}
+
+impl<T: BitRead<Output = u64> + ?Sized> BitRead for Counting<'_, T> {
+    type Output = u64;
+
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        let value = self.inner.read_bits(n)?;
+        self.bits += n as u64;
+        Ok(value)
+    }
+}
+
+impl<T: BitWrite + ?Sized> BitWrite for Counting<'_, T> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        self.inner.write_bits(value, n)?;
+        self.bits += n as u64;
+        Ok(())
+    }
+}
+
+/// 读取 n 比特的无符号值；little 为 Some 时按字节读取并按指定的字节序拼接
+pub fn read_unsigned<R>(reader: &mut R, n: usize, little: Option<bool>) -> Result<u64>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    match little {
+        None => reader.read_bits(n),
+        Some(little) => {
+            let mut value = 0u64;
+            for i in 0..n / 8 {
+                let byte = reader.read_bits(8)?;
+                if little {
+                    value |= byte << (8 * i);
+                } else {
+                    value = (value << 8) | byte;
+                }
+            }
+            Ok(value)
+        }
+    }
+}
+
+/// 读取 n 比特的补码并做符号扩展
+pub fn read_signed<R>(reader: &mut R, n: usize, little: Option<bool>) -> Result<i64>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let value = read_unsigned(reader, n, little)?;
+    Ok(sign_extend(value, n))
+}
+
+/// 写入 n 比特的无符号值，放不下时返回 ValueOutOfRange
+pub fn write_unsigned<W>(writer: &mut W, value: u64, n: usize, little: Option<bool>) -> Result<()>
+where
+    W: BitWrite + ?Sized,
+{
+    if n < 64 && value >> n != 0 {
+        return Err(BitReadWriteError::ValueOutOfRange { value, bits: n }.into());
+    }
+    write_raw(writer, value, n, little)
+}
+
+/// 写入 n 比特的补码，超出 n 比特补码范围时返回 ValueOutOfRange
+pub fn write_signed<W>(writer: &mut W, value: i64, n: usize, little: Option<bool>) -> Result<()>
+where
+    W: BitWrite + ?Sized,
+{
+    let bits = signed_bits(value, n)?;
+    write_raw(writer, bits, n, little)
+}
+
+fn write_raw<W>(writer: &mut W, value: u64, n: usize, little: Option<bool>) -> Result<()>
+where
+    W: BitWrite + ?Sized,
+{
+    match little {
+        None => writer.write_bits(value, n),
+        Some(little) => {
+            let bytes = n / 8;
+            for i in 0..bytes {
+                let shift = if little { 8 * i } else { 8 * (bytes - 1 - i) };
+                writer.write_bits((value >> shift) & 0xFF, 8)?;
+            }
+            Ok(())
+        }
+    }
+}
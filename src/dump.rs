@@ -0,0 +1,117 @@
+//! Annotated hex and binary views of a byte buffer, for error messages and debug output
+//!
+//! Both views can mark a bit position (e.g. where a reader stopped) with a `^` on the line below
+//! the byte holding it; positions past the end of the data are drawn at the end:
+//!
+//! ```text
+//! 00000000  10110011 01011010 11000011
+//!                       ^ bit 11
+//! ```
+
+use crate::byte_order::ByteOrder;
+use crate::cursor::BitCursor;
+use std::fmt::Write;
+
+const HEX_PER_LINE: usize = 16;
+const BINARY_PER_LINE: usize = 8;
+
+/// Hex view, 16 bytes per line with an ASCII column; `marker` points at the byte holding that bit
+pub fn hex(data: &[u8], marker: Option<u64>) -> String {
+    let mut out = String::new();
+    let at = clamp_marker(data.len(), marker);
+    for (line, start) in line_starts(data.len(), HEX_PER_LINE, at) {
+        let bytes = &data[start.min(data.len())..(start + HEX_PER_LINE).min(data.len())];
+        let _ = write!(out, "{:08x} ", start);
+        for i in 0..HEX_PER_LINE {
+            match bytes.get(i) {
+                Some(b) => {
+                    let _ = write!(out, " {:02x}", b);
+                }
+                None => out.push_str("   "),
+            }
+        }
+        out.push_str("  |");
+        out.extend(bytes.iter().map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            }
+        }));
+        out.push_str("|\n");
+        if let (Some(at), Some(pos)) = (at, marker)
+            && (at / 8) as usize / HEX_PER_LINE == line
+        {
+            let column = 10 + ((at / 8) as usize % HEX_PER_LINE) * 3;
+            let _ = writeln!(out, "{:column$}^ bit {}", "", pos);
+        }
+    }
+    out
+}
+
+/// Binary view, 8 bytes per line; `marker` points at the exact bit, which for `LittleEndian`
+/// streams is counted from the low (rightmost) bit of each byte
+pub fn binary(data: &[u8], byte_order: ByteOrder, marker: Option<u64>) -> String {
+    render_binary(data, 0, byte_order, marker)
+}
+
+/// Binary view of the line holding the cursor position and the lines right before and after it,
+/// with the position marked
+pub fn cursor<T: AsRef<[u8]>>(cursor: &BitCursor<T>) -> String {
+    let data = cursor.get_ref().as_ref();
+    let pos = cursor.position();
+    let line = (pos / 8) as usize / BINARY_PER_LINE;
+    let start = line.saturating_sub(1) * BINARY_PER_LINE;
+    let end = ((line + 2) * BINARY_PER_LINE).min(data.len());
+    render_binary(
+        &data[start.min(end)..end],
+        start,
+        cursor.byte_order(),
+        Some(pos - start as u64 * 8),
+    )
+}
+
+/// 渲染二进制视图，base 为 data[0] 在原缓冲区中的偏移（只影响行首偏移与标记文字）
+fn render_binary(data: &[u8], base: usize, byte_order: ByteOrder, marker: Option<u64>) -> String {
+    let mut out = String::new();
+    let at = clamp_marker(data.len(), marker);
+    for (line, start) in line_starts(data.len(), BINARY_PER_LINE, at) {
+        let bytes = &data[start.min(data.len())..(start + BINARY_PER_LINE).min(data.len())];
+        let _ = write!(out, "{:08x} ", base + start);
+        for b in bytes {
+            let _ = write!(out, " {:08b}", b);
+        }
+        out.push('\n');
+        if let (Some(at), Some(pos)) = (at, marker)
+            && (at / 8) as usize / BINARY_PER_LINE == line
+        {
+            let bit = (at % 8) as usize;
+            let bit = match byte_order {
+                ByteOrder::BigEndian => bit,
+                ByteOrder::LittleEndian => 7 - bit,
+            };
+            let column = 10 + ((at / 8) as usize % BINARY_PER_LINE) * 9 + bit;
+            let _ = writeln!(out, "{:column$}^ bit {}", "", pos + base as u64 * 8);
+        }
+    }
+    out
+}
+
+/// 标记画在哪一比特：超出数据末尾的标记画在末尾，避免为远处的标记输出大量空行
+fn clamp_marker(len: usize, marker: Option<u64>) -> Option<u64> {
+    marker.map(|pos| pos.min(len as u64 * 8))
+}
+
+/// 每行的 (行号, 起始字节)；标记恰好位于数据末尾时补足到标记所在行
+fn line_starts(
+    len: usize,
+    per_line: usize,
+    marker: Option<u64>,
+) -> impl Iterator<Item = (usize, usize)> {
+    let lines = len.div_ceil(per_line);
+    let lines = match marker {
+        Some(pos) => lines.max((pos / 8) as usize / per_line + 1),
+        None => lines,
+    };
+    (0..lines).map(move |line| (line, line * per_line))
+}
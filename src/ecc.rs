@@ -0,0 +1,221 @@
+//! Hamming error-correcting codes as [`BitWrite`] / [`BitRead`] adapters
+//!
+//! [`HammingWriter`] splits the data bits written to it into 4-bit groups and writes each one
+//! to the inner writer as a codeword; [`HammingReader`] reads codewords, corrects single-bit
+//! errors and returns the data bits. Two codes are available:
+//! - [`HammingCode::Hamming74`]: 7-bit codewords, corrects any single-bit error
+//! - [`HammingCode::SecDed`]: Hamming(7,4) plus an overall parity bit (8-bit codewords), which
+//!   also detects (but cannot correct) double-bit errors
+//!
+//! Codewords hold the bits in the classic order `p1 p2 d1 p3 d2 d3 d4 [p0]`, first bit first.
+//! On the data side, the bits of each `write_bits` / `read_bits` value go in most significant
+//! bit first, whatever the byte order of the inner reader/writer.
+
+use crate::error::{BitReadWriteError, check_bit_count};
+use crate::traits::{BitRead, BitWrite};
+use std::io::Result;
+
+/// The error-correcting code used for each 4-bit data group
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HammingCode {
+    Hamming74,
+    SecDed,
+}
+
+impl HammingCode {
+    /// Returns the number of bits per codeword
+    pub fn code_bits(self) -> usize {
+        match self {
+            HammingCode::Hamming74 => 7,
+            HammingCode::SecDed => 8,
+        }
+    }
+
+    /// Encodes the low 4 bits of `nibble` into a codeword
+    pub fn encode(self, nibble: u8) -> u8 {
+        let d = |i: u8| (nibble >> (3 - i)) & 1; // d(0) 为最高位 d1
+        let p1 = d(0) ^ d(1) ^ d(3);
+        let p2 = d(0) ^ d(2) ^ d(3);
+        let p3 = d(1) ^ d(2) ^ d(3);
+        let code =
+            (p1 << 6) | (p2 << 5) | (d(0) << 4) | (p3 << 3) | (d(1) << 2) | (d(2) << 1) | d(3);
+        match self {
+            HammingCode::Hamming74 => code,
+            HammingCode::SecDed => (code << 1) | (code.count_ones() as u8 & 1),
+        }
+    }
+
+    /// Decodes a codeword into its 4 data bits, correcting a single-bit error
+    ///
+    /// # Returns
+    /// The data bits and whether a bit was corrected
+    ///
+    /// # Errors
+    /// Returns `InvalidData` if [`SecDed`](Self::SecDed) detects a double-bit error
+    pub fn decode(self, code: u8) -> Result<(u8, bool)> {
+        let (mut code, overall) = match self {
+            HammingCode::Hamming74 => (code & 0x7F, None),
+            HammingCode::SecDed => ((code >> 1) & 0x7F, Some(code.count_ones() & 1)),
+        };
+        // 校验子即出错位置
+        let c = |pos: u8| bit_at(code, pos);
+        let syndrome = (c(1) ^ c(3) ^ c(5) ^ c(7))
+            | (c(2) ^ c(3) ^ c(6) ^ c(7)) << 1
+            | (c(4) ^ c(5) ^ c(6) ^ c(7)) << 2;
+
+        let corrected = match (syndrome, overall) {
+            (0, None | Some(0)) => false,
+            // 只有总校验位出错，数据无误
+            (0, Some(_)) => true,
+            (_, None | Some(1)) => {
+                code ^= 1 << (7 - syndrome);
+                true
+            }
+            (_, Some(_)) => {
+                return Err(BitReadWriteError::Uncorrectable.into());
+            }
+        };
+        let c = |pos: u8| bit_at(code, pos);
+        Ok(((c(3) << 3) | (c(5) << 2) | (c(6) << 1) | c(7), corrected))
+    }
+}
+
+/// 取 7 位码字中位置 pos（1..=7，对应第 6..=0 位）的比特
+fn bit_at(code: u8, pos: u8) -> u8 {
+    (code >> (7 - pos)) & 1
+}
+
+// ------------------------------- HammingWriter ------------------------------- //
+
+/// Encodes every 4 data bits written to it into a codeword on the inner writer
+///
+/// A trailing group of fewer than 4 bits is padded with zeros by [`finish`](Self::finish) or
+/// [`into_inner`](Self::into_inner); nothing is written on drop.
+#[derive(Debug)]
+pub struct HammingWriter<W> {
+    inner: W,
+    code: HammingCode,
+    pending: u8,
+    pending_bits: usize, // 始终小于 4
+}
+
+impl<W: BitWrite> HammingWriter<W> {
+    pub fn new(inner: W, code: HammingCode) -> Self {
+        Self {
+            inner,
+            code,
+            pending: 0,
+            pending_bits: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Pads a pending partial group with zeros and writes its codeword
+    ///
+    /// # Errors
+    /// Any error of the inner writer
+    pub fn finish(&mut self) -> Result<()> {
+        if self.pending_bits > 0 {
+            let padding = 4 - self.pending_bits;
+            self.write_bits(0, padding)?;
+        }
+        Ok(())
+    }
+
+    /// Calls [`finish`](Self::finish) and returns the inner writer
+    pub fn into_inner(mut self) -> Result<W> {
+        self.finish()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: BitWrite> BitWrite for HammingWriter<W> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        check_bit_count(n, 64)?;
+        let mut remaining = n;
+        while remaining > 0 {
+            // 从高位开始取，凑满 4 比特就编码写出
+            let take = (4 - self.pending_bits).min(remaining);
+            let bits = (value >> (remaining - take)) as u8 & ((1u8 << take) - 1);
+            self.pending = (self.pending << take) | bits;
+            self.pending_bits += take;
+            remaining -= take;
+            if self.pending_bits == 4 {
+                let code = self.code.encode(self.pending);
+                self.inner.write_bits(code as u64, self.code.code_bits())?;
+                self.pending = 0;
+                self.pending_bits = 0;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------- HammingReader ------------------------------- //
+
+/// Decodes codewords from the inner reader, correcting single-bit errors
+///
+/// An uncorrectable codeword fails the read with `InvalidData`; data bits of the same read that
+/// came before it are lost.
+#[derive(Debug)]
+pub struct HammingReader<R> {
+    inner: R,
+    code: HammingCode,
+    pending: u8,
+    pending_bits: usize,
+    corrected: u64,
+}
+
+impl<R: BitRead<Output = u64>> HammingReader<R> {
+    pub fn new(inner: R, code: HammingCode) -> Self {
+        Self {
+            inner,
+            code,
+            pending: 0,
+            pending_bits: 0,
+            corrected: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the number of codewords corrected so far
+    pub fn corrected(&self) -> u64 {
+        self.corrected
+    }
+
+    /// Returns the inner reader; data bits of a partly read codeword are discarded
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BitRead<Output = u64>> BitRead for HammingReader<R> {
+    type Output = u64;
+
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        check_bit_count(n, 64)?;
+        let mut value = 0u64;
+        let mut remaining = n;
+        while remaining > 0 {
+            if self.pending_bits == 0 {
+                let code = self.inner.read_bits(self.code.code_bits())? as u8;
+                let (nibble, corrected) = self.code.decode(code)?;
+                self.corrected += corrected as u64;
+                self.pending = nibble;
+                self.pending_bits = 4;
+            }
+            let take = self.pending_bits.min(remaining);
+            let bits = (self.pending >> (self.pending_bits - take)) & ((1u8 << take) - 1);
+            value = (value << take) | bits as u64;
+            self.pending_bits -= take;
+            remaining -= take;
+        }
+        Ok(value)
+    }
+}
@@ -1,30 +1,547 @@
 use std::fmt::Formatter;
 
-#[derive(Debug)]
+/// Error carried inside the `io::Error`s returned by readers and writers
+///
+/// Every fallible method keeps the `std::io::Result` signature of `Read` / `Write`; the
+/// `io::Error` holds one of these variants as its payload. Convert it back with
+/// `BitReadWriteError::from(err)` (or `?` in a function returning [`Result`]) to match on
+/// what went wrong; errors of the underlying reader or writer come back as [`Io`](Self::Io).
+#[derive(Debug, thiserror::Error)]
 pub enum BitReadWriteError {
+    /// Error from the underlying reader or writer
+    #[error(transparent)]
+    Io(std::io::Error),
+    #[error("Bit count must be between 1-64, got {0}")]
     InvalidBitCount(usize),
+    #[error("Unexpected end of stream")]
     UnexpectedEof,
-    UnalignedAccess,
+    /// A byte operation was attempted in the middle of a byte
+    #[error("Attempted to consume bytes while bits are buffered")]
+    UnalignedAccess {
+        /// Bits of the partial byte held by the reader or writer
+        pending_bits: usize,
+    },
+    #[error("Buffer holds {capacity} bits, but {bits} bits are required")]
+    BufferTooSmall { bits: usize, capacity: usize },
+    #[error("Value {value} does not fit in {bits} bits")]
+    ValueOutOfRange { value: u64, bits: usize },
+    #[error("Read would exceed the limit of {limit} bits")]
+    LimitExceeded { limit: u64 },
+    #[error("Parity check failed")]
+    ParityMismatch,
+    /// The inner writer was already taken out of the `BitWriter`
+    #[error("Inner writer has already been taken")]
+    InnerTaken,
+    /// A field read by `expect_bits` / `expect_bytes` did not hold the expected constant
+    #[error("Expected {expected:#x} ({bits} bits) at bit offset {offset}, found {actual:#x}")]
+    UnexpectedValue {
+        /// Bit offset of the field from the start of the stream
+        offset: u64,
+        expected: u64,
+        actual: u64,
+        bits: usize,
+    },
+    /// `expect_byte_aligned` found the position in the middle of a byte
+    #[error("Expected a byte boundary at bit offset {offset}")]
+    NotByteAligned { offset: u64 },
+    /// `expect_eof` found bits left after the position
+    #[error("Expected end of stream at bit offset {offset}")]
+    TrailingData { offset: u64 },
+
+    // 调用方传入的参数不可用（ErrorKind::InvalidInput）
+    #[error("Initial bit offset must be 0-7, got {0}")]
+    InvalidInitialOffset(usize),
+    /// `import_state` was given a state taken with another byte order, or a malformed one
+    #[error("Reader state does not match this reader")]
+    StateMismatch,
+    #[error("Seek to a negative or overflowing position")]
+    InvalidSeek,
+    /// `end_section` was called without a matching `begin_section`
+    #[error("No open section")]
+    NoOpenSection,
+    /// `write_bits_at` targets bytes that were not written yet
+    #[error("Field ends at byte {end}, past the {written} bytes written so far")]
+    PatchPastEnd { end: u64, written: u64 },
+    #[error("Output holds {len} values for {fields} field widths")]
+    OutputTooShort { fields: usize, len: usize },
+    #[error("BCD fields hold 1-19 digits, got {0}")]
+    InvalidDigitCount(usize),
+    #[error("{0:?} cannot be encoded as a semi-octet")]
+    InvalidSemiOctet(char),
+    #[error("{0:?} is not a 6-bit character")]
+    InvalidSixBitChar(char),
+    #[error("String contains a null byte")]
+    InteriorNul,
+    #[error("NaN cannot be stored as fixed-point")]
+    NotANumber,
+    /// A CAN signal value that is not finite once offset and scale are applied
+    #[error("Value cannot be scaled to a raw signal value")]
+    UnscalableSignal,
+    #[error("{planes} bit-planes given for {bits}-bit symbols")]
+    InvalidPlaneCount { planes: usize, bits: usize },
+    /// LFSR taps that are zero or have bits at or above the degree
+    #[error("LFSR taps {taps:#x} must be non-zero and within the {degree}-bit register")]
+    InvalidTaps { taps: u64, degree: usize },
+    #[error("Sample count {samples} is not a multiple of {group}")]
+    SampleCountNotMultiple { samples: usize, group: usize },
+    #[error("Token tree index out of range")]
+    InvalidTokenTree,
+    #[error("{0} is not a leaf of the token tree")]
+    NotInTokenTree(u8),
+    /// The nom and deku adapters only read MSB-first cursors
+    #[error("Bit parser adapters require a big-endian cursor")]
+    BigEndianRequired,
+    #[error("Record boundaries must be increasing and within the data")]
+    InvalidRecordBoundaries,
+    #[error("Expected NAL unit type {expected}, found {found}")]
+    UnexpectedNalType { expected: u8, found: u8 },
+    #[error("Invalid field `{0}`: expected `name:type`")]
+    MissingFieldType(String),
+    #[error("Invalid field `{0}`: empty field name")]
+    EmptyFieldName(String),
+    #[error("Invalid field `{0}`: expected `[count]`")]
+    InvalidRepeatCount(String),
+    #[error("Invalid field `{0}`: `bytes` needs a `[count]`")]
+    MissingByteCount(String),
+    #[error("Invalid field `{0}`: byte order needs `uN` or `iN`")]
+    ByteOrderNeedsInteger(String),
+    #[error("Invalid field `{0}`: width must be between 1-64")]
+    InvalidFieldWidth(String),
+    #[error("Invalid field `{0}`: condition value must be a number")]
+    InvalidConditionValue(String),
+    #[error("Invalid field `{0}`: condition needs a field name")]
+    MissingConditionField(String),
+    /// A repeat count naming a field that was not read as an unsigned integer
+    #[error("Repeat count of `{field}` refers to `{count}`, which is not a count")]
+    NotACount { field: String, count: String },
+
+    // 调用方不支持的操作（ErrorKind::Unsupported）
+    #[error("Cannot seek from the end of the stream")]
+    SeekFromEnd,
+
+    // 流中的数据不合法（ErrorKind::InvalidData）
+    #[error("Invalid BCD digit")]
+    InvalidBcdDigit,
+    #[error("Exp-Golomb code longer than 64 bits")]
+    ExpGolombTooLong,
+    #[error("Rice code overflows 64 bits")]
+    RiceOverflow,
+    #[error("Uncorrectable double-bit error")]
+    Uncorrectable,
+    #[error("Filler nibble before the end of a semi-octet string")]
+    MisplacedFiller,
+    #[error("Invalid UTF-8: {0}")]
+    InvalidUtf8(#[source] std::string::FromUtf8Error),
+    #[error("No null terminator within {max_len} bytes")]
+    MissingNulTerminator { max_len: usize },
+    #[error("{0:?} is not an AIS payload character")]
+    InvalidAisChar(char),
+    /// A Manchester half-bit pair of `00` or `11`
+    #[error("Invalid Manchester symbol")]
+    InvalidManchesterSymbol,
+    #[error("HDLC frame is not a whole number of bytes")]
+    HdlcPartialByte,
+    #[error("HDLC frame aborted")]
+    HdlcAbort,
+    #[error("Unexpected HDLC flag")]
+    UnexpectedHdlcFlag,
+    #[error("Empty VP9 bool partition")]
+    EmptyBoolPartition,
+    #[error("VP9 bool marker bit is set")]
+    BoolMarkerSet,
+    #[error("Expected a restart marker, found 0xFF{0:02X}")]
+    NotRestartMarker(u8),
+    /// `decode_split` got a record length of zero or past the end of the data
+    #[error("Record length {len} is zero or past the {remaining} bytes left")]
+    InvalidRecordLength { len: usize, remaining: usize },
+    /// Message of a `Serialize` / `Deserialize` impl, which serde only hands over as text
+    #[error("{0}")]
+    Serde(String),
+    #[error("Sequence length must be known")]
+    UnknownLength,
+    #[error("{ty} values are always {bits} bits, schema asks for {width}")]
+    FixedWidthMismatch {
+        ty: &'static str,
+        bits: usize,
+        width: usize,
+    },
+    #[error("Length prefix {0} too large")]
+    LengthPrefixTooLarge(u64),
+    #[error("Invalid char {0:#x}")]
+    InvalidChar(u64),
+    #[error("Variant index {0} too large")]
+    VariantIndexTooLarge(u64),
+    #[error("The bit-packed format is not self-describing")]
+    NotSelfDescribing,
+    #[error("The bit-packed format cannot skip unknown values")]
+    CannotSkip,
+    #[cfg(feature = "nom")]
+    #[error("nom parser failed: {0:?}")]
+    Nom(nom::error::ErrorKind),
+    #[cfg(feature = "binrw")]
+    #[error("binrw parser failed: {0}")]
+    Binrw(#[source] binrw::Error),
+    #[cfg(feature = "deku")]
+    #[error("deku parser failed: {0}")]
+    Deku(#[source] deku::DekuError),
+    /// A frame-of-reference block header with a delta width over 32 bits or more exceptions
+    /// than values
+    #[error("Invalid frame-of-reference block header (width {width}, {exceptions} exceptions)")]
+    InvalidBlockHeader { width: usize, exceptions: usize },
+    /// A frame-of-reference exception width that is zero or overflows 32 bits with the delta
+    /// width
+    #[error("Invalid frame-of-reference exception width {0}")]
+    InvalidExceptionWidth(usize),
+    #[error("Frame-of-reference exception index {index} is outside the block of {len} values")]
+    ExceptionIndexOutOfBlock { index: usize, len: usize },
+    /// A frame-of-reference value does not fit in `u32` once the reference is added
+    #[error("Frame-of-reference value overflows u32")]
+    ReferenceOverflow,
+    #[error("Invalid run length width {0}")]
+    InvalidRunWidth(usize),
+    #[error("Runs exceed the bit count of {0}")]
+    RunsExceedCount(usize),
+    /// A FLAC frame or sample number with an invalid first or continuation byte
+    #[error("Invalid byte {0:#04x} in a FLAC coded number")]
+    InvalidCodedNumber(u8),
+    #[error("FLAC frame sync code not found")]
+    FlacSyncNotFound,
+    #[error("Reserved bit of the FLAC frame header is set")]
+    FlacReservedBit,
+    #[error("Reserved FLAC channel assignment {0}")]
+    ReservedChannelAssignment(u8),
+    #[error("Reserved FLAC sample size code {0}")]
+    ReservedSampleSize(u8),
+    #[error("Reserved FLAC block size code 0")]
+    ReservedBlockSize,
+    #[error("Invalid FLAC sample rate code {0}")]
+    InvalidSampleRate(u8),
+    /// A fixed-block-size FLAC frame number over 31 bits
+    #[error("FLAC frame number {0} exceeds 31 bits")]
+    FlacFrameNumberTooLarge(u64),
+    #[error("FLAC frame header CRC-8 is {actual:#04x}, expected {expected:#04x}")]
+    FlacCrcMismatch { expected: u8, actual: u8 },
+    #[error("Reserved FLAC residual coding method {0}")]
+    ReservedResidualCoding(u8),
+    /// A FLAC partition order that does not divide the block size or leaves the first
+    /// partition shorter than the predictor order
+    #[error("FLAC partition order {0} does not fit the block size")]
+    InvalidPartitionOrder(u8),
+    #[error("Transport stream sync byte not found, got {0:#04x}")]
+    TsSyncNotFound(u8),
+    /// The flagged fields of an adaptation field need more bytes than its length
+    #[error("Adaptation field is longer than its length of {0} bytes")]
+    AdaptationFieldOverrun(usize),
+    #[error("Adaptation field of {0} bytes exceeds the packet")]
+    AdaptationFieldTooLong(usize),
+    #[error("ADTS syncword not found")]
+    AdtsSyncNotFound,
+    #[error("ADTS layer must be 0, got {0}")]
+    InvalidAdtsLayer(u8),
+    #[error("ADTS frame_length {frame_length} is shorter than the {header_length}-byte header")]
+    AdtsFrameTooShort {
+        frame_length: u16,
+        header_length: usize,
+    },
+    /// An H.264 `ue(v)` syntax element over `u32::MAX`
+    #[error("ue(v) value {0} out of range")]
+    UeOutOfRange(u64),
+    /// An H.264 `se(v)` syntax element outside `i32`
+    #[error("se(v) value {0} out of range")]
+    SeOutOfRange(i64),
+    #[error("delta_scale {0} out of range")]
+    DeltaScaleOutOfRange(i64),
+    #[error("Empty NAL unit")]
+    EmptyNalUnit,
+    #[error("forbidden_zero_bit of the NAL unit header is set")]
+    NalForbiddenBit,
+    #[error("chroma_format_idc {0} out of range")]
+    ChromaFormatOutOfRange(u32),
+    #[error("num_ref_frames_in_pic_order_cnt_cycle {0} out of range")]
+    RefFrameCycleOutOfRange(u64),
+    #[error("pic_order_cnt_type {0} out of range")]
+    PicOrderCntTypeOutOfRange(u64),
+    #[error("RBSP stop bit not found")]
+    MissingStopBit,
+    #[error("num_slice_groups_minus1 {0} out of range")]
+    SliceGroupCountOutOfRange(u32),
+    #[error("slice_group_map_type {0} out of range")]
+    SliceGroupMapTypeOutOfRange(u32),
+    /// An AV1 `leb128()` value over `MAX_LEB128`
+    #[error("leb128 value {0} exceeds 32 bits")]
+    Leb128Overflow(u64),
+    #[error("obu_forbidden_bit is set")]
+    ObuForbiddenBit,
+    #[error("OBU has no size field")]
+    MissingObuSize,
+
+    /// `error` raised by a reader or cursor, with the position of the failing operation
+    ///
+    /// Reported by `BitReader`, `PeekableBitReader`, `CompactBitReader` and `BitCursor`. The
+    /// readers in [`fast`](crate::fast) do not track their position and return bare errors.
+    #[error("{error}{}", Location(*.offset, *.requested, *.available))]
+    Positioned {
+        /// Bit offset from the start of the stream where the operation started
+        offset: u64,
+        /// Bits the operation asked for
+        requested: Option<usize>,
+        /// Bits that were left to read (before the end of the stream or of `max_bits`)
+        available: Option<u64>,
+        error: Box<BitReadWriteError>,
+    },
 }
 
-impl std::fmt::Display for BitReadWriteError {
+/// The crate's error type, also reachable as `bitio_rs::Error`
+pub type Error = BitReadWriteError;
+
+/// `Result` with [`Error`] as the error type, for code built on top of the crate's readers
+///
+/// `?` converts the `io::Error`s returned by readers and writers back into the variant they
+/// carry.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// 错误信息中的位置后缀：" at bit offset 5 (24 bits requested, 19 available)"
+struct Location(u64, Option<usize>, Option<u64>);
+
+impl std::fmt::Display for Location {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        match self {
-            BitReadWriteError::InvalidBitCount(n) => {
-                write!(f, "Bit count must be between 1-64, got {}", n)
-            }
-            BitReadWriteError::UnexpectedEof => write!(f, "Unexpected end of stream"),
-            BitReadWriteError::UnalignedAccess => {
-                write!(f, "Attempted to consume bytes while bits are buffered")
-            }
+        let Location(offset, requested, available) = *self;
+        write!(f, " at bit offset {}", offset)?;
+        match (requested, available) {
+            (Some(requested), Some(available)) => write!(
+                f,
+                " ({} bits requested, {} available)",
+                requested, available
+            ),
+            (Some(requested), None) => write!(f, " ({} bits requested)", requested),
+            _ => Ok(()),
         }
     }
 }
 
-impl std::error::Error for BitReadWriteError {}
+impl BitReadWriteError {
+    /// Returns the [`std::io::ErrorKind`] used when converting into an `io::Error`
+    pub fn kind(&self) -> std::io::ErrorKind {
+        use std::io::ErrorKind;
+        match self {
+            BitReadWriteError::Io(e) => e.kind(),
+            BitReadWriteError::Positioned { error, .. } => error.kind(),
+            BitReadWriteError::UnexpectedEof => ErrorKind::UnexpectedEof,
+            BitReadWriteError::UnalignedAccess { .. }
+            | BitReadWriteError::BufferTooSmall { .. }
+            | BitReadWriteError::ValueOutOfRange { .. }
+            | BitReadWriteError::LimitExceeded { .. }
+            | BitReadWriteError::InnerTaken => ErrorKind::Other,
+            BitReadWriteError::SeekFromEnd => ErrorKind::Unsupported,
+            BitReadWriteError::InvalidBitCount(_)
+            | BitReadWriteError::InvalidInitialOffset(_)
+            | BitReadWriteError::StateMismatch
+            | BitReadWriteError::InvalidSeek
+            | BitReadWriteError::NoOpenSection
+            | BitReadWriteError::PatchPastEnd { .. }
+            | BitReadWriteError::OutputTooShort { .. }
+            | BitReadWriteError::InvalidDigitCount(_)
+            | BitReadWriteError::InvalidSemiOctet(_)
+            | BitReadWriteError::InvalidSixBitChar(_)
+            | BitReadWriteError::InteriorNul
+            | BitReadWriteError::NotANumber
+            | BitReadWriteError::UnscalableSignal
+            | BitReadWriteError::InvalidPlaneCount { .. }
+            | BitReadWriteError::InvalidTaps { .. }
+            | BitReadWriteError::SampleCountNotMultiple { .. }
+            | BitReadWriteError::InvalidTokenTree
+            | BitReadWriteError::NotInTokenTree(_)
+            | BitReadWriteError::BigEndianRequired
+            | BitReadWriteError::InvalidRecordBoundaries
+            | BitReadWriteError::UnexpectedNalType { .. }
+            | BitReadWriteError::MissingFieldType(_)
+            | BitReadWriteError::EmptyFieldName(_)
+            | BitReadWriteError::InvalidRepeatCount(_)
+            | BitReadWriteError::MissingByteCount(_)
+            | BitReadWriteError::ByteOrderNeedsInteger(_)
+            | BitReadWriteError::InvalidFieldWidth(_)
+            | BitReadWriteError::InvalidConditionValue(_)
+            | BitReadWriteError::MissingConditionField(_)
+            | BitReadWriteError::NotACount { .. } => ErrorKind::InvalidInput,
+            BitReadWriteError::ParityMismatch
+            | BitReadWriteError::UnexpectedValue { .. }
+            | BitReadWriteError::NotByteAligned { .. }
+            | BitReadWriteError::TrailingData { .. }
+            | BitReadWriteError::InvalidBcdDigit
+            | BitReadWriteError::ExpGolombTooLong
+            | BitReadWriteError::RiceOverflow
+            | BitReadWriteError::Uncorrectable
+            | BitReadWriteError::MisplacedFiller
+            | BitReadWriteError::InvalidUtf8(_)
+            | BitReadWriteError::MissingNulTerminator { .. }
+            | BitReadWriteError::InvalidAisChar(_)
+            | BitReadWriteError::InvalidManchesterSymbol
+            | BitReadWriteError::HdlcPartialByte
+            | BitReadWriteError::HdlcAbort
+            | BitReadWriteError::UnexpectedHdlcFlag
+            | BitReadWriteError::EmptyBoolPartition
+            | BitReadWriteError::BoolMarkerSet
+            | BitReadWriteError::NotRestartMarker(_)
+            | BitReadWriteError::InvalidRecordLength { .. }
+            | BitReadWriteError::Serde(_)
+            | BitReadWriteError::UnknownLength
+            | BitReadWriteError::FixedWidthMismatch { .. }
+            | BitReadWriteError::LengthPrefixTooLarge(_)
+            | BitReadWriteError::InvalidChar(_)
+            | BitReadWriteError::VariantIndexTooLarge(_)
+            | BitReadWriteError::NotSelfDescribing
+            | BitReadWriteError::CannotSkip
+            | BitReadWriteError::InvalidBlockHeader { .. }
+            | BitReadWriteError::InvalidExceptionWidth(_)
+            | BitReadWriteError::ExceptionIndexOutOfBlock { .. }
+            | BitReadWriteError::ReferenceOverflow
+            | BitReadWriteError::InvalidRunWidth(_)
+            | BitReadWriteError::RunsExceedCount(_)
+            | BitReadWriteError::InvalidCodedNumber(_)
+            | BitReadWriteError::FlacSyncNotFound
+            | BitReadWriteError::FlacReservedBit
+            | BitReadWriteError::ReservedChannelAssignment(_)
+            | BitReadWriteError::ReservedSampleSize(_)
+            | BitReadWriteError::ReservedBlockSize
+            | BitReadWriteError::InvalidSampleRate(_)
+            | BitReadWriteError::FlacFrameNumberTooLarge(_)
+            | BitReadWriteError::FlacCrcMismatch { .. }
+            | BitReadWriteError::ReservedResidualCoding(_)
+            | BitReadWriteError::InvalidPartitionOrder(_)
+            | BitReadWriteError::TsSyncNotFound(_)
+            | BitReadWriteError::AdaptationFieldOverrun(_)
+            | BitReadWriteError::AdaptationFieldTooLong(_)
+            | BitReadWriteError::AdtsSyncNotFound
+            | BitReadWriteError::InvalidAdtsLayer(_)
+            | BitReadWriteError::AdtsFrameTooShort { .. }
+            | BitReadWriteError::UeOutOfRange(_)
+            | BitReadWriteError::SeOutOfRange(_)
+            | BitReadWriteError::DeltaScaleOutOfRange(_)
+            | BitReadWriteError::EmptyNalUnit
+            | BitReadWriteError::NalForbiddenBit
+            | BitReadWriteError::ChromaFormatOutOfRange(_)
+            | BitReadWriteError::RefFrameCycleOutOfRange(_)
+            | BitReadWriteError::PicOrderCntTypeOutOfRange(_)
+            | BitReadWriteError::MissingStopBit
+            | BitReadWriteError::SliceGroupCountOutOfRange(_)
+            | BitReadWriteError::SliceGroupMapTypeOutOfRange(_)
+            | BitReadWriteError::Leb128Overflow(_)
+            | BitReadWriteError::ObuForbiddenBit
+            | BitReadWriteError::MissingObuSize => ErrorKind::InvalidData,
+            #[cfg(feature = "nom")]
+            BitReadWriteError::Nom(_) => ErrorKind::InvalidData,
+            #[cfg(feature = "binrw")]
+            BitReadWriteError::Binrw(_) => ErrorKind::InvalidData,
+            #[cfg(feature = "deku")]
+            BitReadWriteError::Deku(_) => ErrorKind::InvalidData,
+        }
+    }
 
+    /// Returns the underlying error, without the [`Positioned`](Self::Positioned) wrapper
+    pub fn root(&self) -> &BitReadWriteError {
+        match self {
+            BitReadWriteError::Positioned { error, .. } => error.root(),
+            _ => self,
+        }
+    }
+
+    /// Returns the bit offset the error occurred at, if it carries one
+    pub fn offset(&self) -> Option<u64> {
+        match self {
+            BitReadWriteError::Positioned { offset, .. }
+            | BitReadWriteError::UnexpectedValue { offset, .. }
+            | BitReadWriteError::NotByteAligned { offset }
+            | BitReadWriteError::TrailingData { offset } => Some(*offset),
+            _ => None,
+        }
+    }
+}
+
+/// Wraps the error as the `io::Error` payload, so it converts back into the same variant;
+/// [`Io`](BitReadWriteError::Io) is unwrapped instead
 impl From<BitReadWriteError> for std::io::Error {
     fn from(e: BitReadWriteError) -> Self {
-        std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
+        match e {
+            BitReadWriteError::Io(err) => err,
+            e => std::io::Error::new(e.kind(), e),
+        }
+    }
+}
+
+/// Recovers the error carried by the `io::Error`; any other error becomes
+/// [`Io`](BitReadWriteError::Io)
+impl From<std::io::Error> for BitReadWriteError {
+    fn from(err: std::io::Error) -> Self {
+        match err.downcast::<BitReadWriteError>() {
+            Ok(e) => e,
+            Err(err) => BitReadWriteError::Io(err),
+        }
+    }
+}
+
+/// 检查位数 n 在 1..=max 之内（max 通常是 64），否则报 InvalidBitCount
+pub(crate) fn check_bit_count(n: usize, max: usize) -> std::io::Result<()> {
+    if n == 0 || n > max {
+        return Err(BitReadWriteError::InvalidBitCount(n).into());
+    }
+    Ok(())
+}
+
+/// 取出 io::Error 中携带的 BitReadWriteError（去掉位置包装）
+pub(crate) fn typed_root(err: &std::io::Error) -> Option<&BitReadWriteError> {
+    err.get_ref()
+        .and_then(|e| e.downcast_ref::<BitReadWriteError>())
+        .map(BitReadWriteError::root)
+}
+
+/// 给读写器产生的 BitReadWriteError 附上位置；底层 io 错误和已带偏移的错误原样返回
+pub(crate) fn locate(
+    err: std::io::Error,
+    offset: u64,
+    requested: Option<usize>,
+    available: Option<u64>,
+) -> std::io::Error {
+    match err.downcast::<BitReadWriteError>() {
+        Ok(e) if e.offset().is_none() => BitReadWriteError::Positioned {
+            offset,
+            requested,
+            available,
+            error: Box::new(e),
+        }
+        .into(),
+        Ok(e) => e.into(),
+        Err(err) => err,
+    }
+}
+
+/// 读到的 n 比特与期望的常量比较，不一致时给出带偏移的错误
+pub(crate) fn check_expected(
+    offset: u64,
+    expected: u64,
+    actual: u64,
+    bits: usize,
+) -> std::io::Result<()> {
+    if actual != expected {
+        return Err(BitReadWriteError::UnexpectedValue {
+            offset,
+            expected,
+            actual,
+            bits,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// expect_bits 的期望值必须能放进 n 比特，否则永远不会相等
+pub(crate) fn check_expected_fits(expected: u64, bits: usize) -> std::io::Result<()> {
+    check_bit_count(bits, 64)?;
+    if bits < 64 && expected >> bits != 0 {
+        return Err(BitReadWriteError::ValueOutOfRange {
+            value: expected,
+            bits,
+        }
+        .into());
     }
+    Ok(())
 }
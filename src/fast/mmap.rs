@@ -0,0 +1,178 @@
+use crate::byte_order::{Big, Endianness, Little};
+use crate::fast::reader::FastBitReader;
+use crate::fast::slice::FastSliceReader;
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::Result;
+use std::path::Path;
+
+/// Memory-mapped fast reader for BigEndian files
+pub type FastMmapReaderBig = FastMmapReader<Big>;
+
+/// Memory-mapped fast reader for LittleEndian files
+pub type FastMmapReaderLittle = FastMmapReader<Little>;
+
+/// Fast bit reader over a memory-mapped file
+///
+/// Reads go through the zero-copy [`FastSliceReader`] path on the mapping, so the file is
+/// paged in by the OS on demand instead of being copied through a `BufReader`. Usually created
+/// with [`FastBitReader::from_path`].
+///
+/// ## Safety contract
+/// The mapping reflects the file as it is on disk. The file must not be truncated or modified
+/// by this or another process while the reader exists; doing so is undefined behavior, as with
+/// any memory map.
+///
+/// ⚠️ **Use at your own risk**
+pub struct FastMmapReader<E: Endianness = Big> {
+    // reader 借用 map 中的数据，声明在前以先于 map 释放
+    reader: FastSliceReader<'static, E>,
+    map: Mmap,
+}
+
+impl<E: Endianness> FastMmapReader<E> {
+    /// Maps the file at `path` into memory
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: 见类型文档中的安全约定，映射期间文件不得被修改
+        let map = unsafe { Mmap::map(&file)? };
+        // SAFETY: 映射的地址在 map 被释放前保持不变（移动 Mmap 不会移动映射的内存），
+        // 而 reader 只在 map 存活期间通过 &self / &mut self 使用
+        let data: &'static [u8] = unsafe { std::slice::from_raw_parts(map.as_ptr(), map.len()) };
+        Ok(Self {
+            reader: FastSliceReader::new(data),
+            map,
+        })
+    }
+
+    /// Returns the mapped bytes
+    #[inline]
+    pub fn get_ref(&self) -> &[u8] {
+        &self.map
+    }
+
+    /// Returns the slice reader doing the work, e.g. to clone it for a second pass
+    #[inline]
+    pub fn as_slice_reader(&self) -> &FastSliceReader<'_, E> {
+        &self.reader
+    }
+
+    /// Returns the current position in bits
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.reader.position()
+    }
+
+    /// Returns the number of unread bits
+    #[inline]
+    pub fn bits_remaining(&self) -> usize {
+        self.reader.bits_remaining()
+    }
+
+    /// Reads 1..=64 bits with maximal performance
+    #[inline(always)]
+    pub fn read_bits_fast(&mut self, n: usize) -> Result<u64> {
+        self.reader.read_bits_fast(n)
+    }
+
+    /// Reads `N` bits (1..=64, checked at compile time), see [`FastSliceReader::read_bits_const`]
+    #[inline(always)]
+    pub fn read_bits_const<const N: usize>(&mut self) -> Result<u64> {
+        self.reader.read_bits_const::<N>()
+    }
+
+    /// Peeks at the next 1..=64 bits without consuming them
+    #[inline(always)]
+    pub fn peek_bits_fast(&self, n: usize) -> Result<u64> {
+        self.reader.peek_bits_fast(n)
+    }
+
+    /// Skips `n` bits, which may be any amount up to `bits_remaining()`
+    #[inline]
+    pub fn skip_bits(&mut self, n: usize) -> Result<()> {
+        self.reader.skip_bits(n)
+    }
+
+    /// Reads 1..=64 bits without any checks
+    ///
+    /// # Safety
+    /// The caller must guarantee `1 <= n <= 64` and `n <= self.bits_remaining()`
+    #[inline(always)]
+    pub unsafe fn read_bits_unchecked(&mut self, n: usize) -> u64 {
+        unsafe { self.reader.read_bits_unchecked(n) }
+    }
+}
+
+impl<E: Endianness> FastBitReader<File, E> {
+    /// Maps the file at `path` into memory and returns a [`FastMmapReader`] over it
+    ///
+    /// Multi-GB bitstreams are then read straight from the page cache without going through
+    /// the block buffer of `FastBitReader`. See the safety contract of [`FastMmapReader`].
+    pub fn from_path(path: impl AsRef<Path>) -> Result<FastMmapReader<E>> {
+        FastMmapReader::open(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast::reader::{FastBitReaderBig, FastBitReaderLittle};
+    use std::path::PathBuf;
+
+    /// 写入一个临时文件，测试结束时删除
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, data: &[u8]) -> Self {
+            let path = std::env::temp_dir().join(format!("bitio-{}-{}", std::process::id(), name));
+            std::fs::write(&path, data).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_from_path() {
+        let data: Vec<u8> = (0..100u8).map(|i| i.wrapping_mul(73)).collect();
+        let file = TempFile::new("mmap-big", &data);
+
+        let mut reader = FastBitReaderBig::from_path(&file.0).unwrap();
+        let mut expected = FastSliceReader::<Big>::new(&data);
+        assert_eq!(reader.get_ref(), &data[..]);
+        for n in [3, 64, 17, 1, 57, 58, 64, 33] {
+            assert_eq!(
+                reader.peek_bits_fast(n).unwrap(),
+                expected.peek_bits_fast(n).unwrap()
+            );
+            assert_eq!(
+                reader.read_bits_fast(n).unwrap(),
+                expected.read_bits_fast(n).unwrap()
+            );
+        }
+        reader.skip_bits(reader.bits_remaining() - 8).unwrap();
+        assert_eq!(reader.read_bits_fast(8).unwrap(), data[99] as u64);
+        assert!(reader.read_bits_fast(1).is_err());
+
+        let mut reader = FastBitReaderLittle::from_path(&file.0).unwrap();
+        assert_eq!(
+            reader.read_bits_fast(16).unwrap(),
+            u16::from_le_bytes([data[0], data[1]]) as u64
+        );
+        assert_eq!(reader.position(), 16);
+    }
+
+    #[test]
+    fn test_empty_and_missing_files() {
+        let file = TempFile::new("mmap-empty", &[]);
+        let mut reader = FastMmapReaderBig::open(&file.0).unwrap();
+        assert_eq!(reader.bits_remaining(), 0);
+        assert!(reader.read_bits_fast(1).is_err());
+
+        assert!(FastMmapReaderBig::open(file.0.with_extension("missing")).is_err());
+    }
+}
@@ -1 +1,8 @@
+#[cfg(feature = "mmap")]
+pub mod mmap;
 pub mod reader;
+pub mod reader128;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod slice;
+pub mod writer;
@@ -0,0 +1,275 @@
+//! SIMD bulk unpacking of fixed-width fields (requires the `simd` feature)
+//!
+//! On x86_64 CPUs with AVX2 the fields are fetched 8 at a time with gather loads and aligned
+//! with per-lane variable shifts; other targets, wider fields and the last few fields of the
+//! slice use the [`FastSliceReader`] scalar path. The output is identical either way.
+
+use crate::byte_order::{Big, ByteOrder, Endianness, Little};
+use crate::error::BitReadWriteError;
+use crate::fast::slice::FastSliceReader;
+use std::io::Result;
+
+/// Unpacks `out.len()` consecutive `width`-bit fields (1..=32) from the start of `data`
+///
+/// Equivalent to calling `read_bits(width)` once per element, but far faster.
+///
+/// # Errors
+/// - `InvalidBitCount` if `width` is not in 1..=32
+/// - `BufferTooSmall` if `data` holds fewer than `out.len() * width` bits
+pub fn unpack_u32(data: &[u8], byte_order: ByteOrder, width: usize, out: &mut [u32]) -> Result<()> {
+    if width == 0 || width > 32 {
+        return Err(BitReadWriteError::InvalidBitCount(width).into());
+    }
+    check_capacity(data, width, out.len())?;
+
+    #[allow(unused_mut)]
+    let mut done = 0;
+    #[cfg(target_arch = "x86_64")]
+    if width <= 25 && std::is_x86_feature_detected!("avx2") {
+        // SAFETY: 已在运行时确认 CPU 支持 AVX2
+        done = unsafe { avx2::unpack_u32(data, byte_order, width, out) };
+    }
+
+    match byte_order {
+        ByteOrder::BigEndian => unpack_scalar::<Big, _>(data, width, done, out, |v| v as u32),
+        ByteOrder::LittleEndian => unpack_scalar::<Little, _>(data, width, done, out, |v| v as u32),
+    }
+    Ok(())
+}
+
+/// Unpacks `out.len()` consecutive `width`-bit fields (1..=64) from the start of `data`
+///
+/// Equivalent to calling `read_bits(width)` once per element, but far faster.
+///
+/// # Errors
+/// - `InvalidBitCount` if `width` is not in 1..=64
+/// - `BufferTooSmall` if `data` holds fewer than `out.len() * width` bits
+pub fn unpack_u64(data: &[u8], byte_order: ByteOrder, width: usize, out: &mut [u64]) -> Result<()> {
+    if width == 0 || width > 64 {
+        return Err(BitReadWriteError::InvalidBitCount(width).into());
+    }
+    check_capacity(data, width, out.len())?;
+
+    #[allow(unused_mut)]
+    let mut done = 0;
+    #[cfg(target_arch = "x86_64")]
+    if width <= 57 && std::is_x86_feature_detected!("avx2") {
+        // SAFETY: 已在运行时确认 CPU 支持 AVX2
+        done = unsafe { avx2::unpack_u64(data, byte_order, width, out) };
+    }
+
+    match byte_order {
+        ByteOrder::BigEndian => unpack_scalar::<Big, _>(data, width, done, out, |v| v),
+        ByteOrder::LittleEndian => unpack_scalar::<Little, _>(data, width, done, out, |v| v),
+    }
+    Ok(())
+}
+
+fn check_capacity(data: &[u8], width: usize, count: usize) -> Result<()> {
+    let bits = count.saturating_mul(width);
+    let capacity = data.len().saturating_mul(8);
+    if bits > capacity {
+        return Err(BitReadWriteError::BufferTooSmall { bits, capacity }.into());
+    }
+    Ok(())
+}
+
+/// 用标量路径解出 out[done..]，调用方已检查数据长度
+fn unpack_scalar<E: Endianness, T>(
+    data: &[u8],
+    width: usize,
+    done: usize,
+    out: &mut [T],
+    convert: impl Fn(u64) -> T,
+) {
+    let mut reader = FastSliceReader::<E>::new(data);
+    // check_capacity 保证 out.len() * width 比特都在 data 范围内
+    reader.skip_bits(done * width).unwrap();
+    for slot in &mut out[done..] {
+        // SAFETY: 同上，剩余比特足够读出每个字段
+        *slot = convert(unsafe { reader.read_bits_unchecked(width) });
+    }
+}
+
+/// 返回可以安全地按 8 个一组处理的元素个数：每组最后一次加载的末尾不能越过 data
+///
+/// 每组 8 个字段共 8 * width 比特，必为整字节，所以每组的起始比特偏移都是 0
+#[cfg(target_arch = "x86_64")]
+fn simd_count(data: &[u8], width: usize, count: usize, load: usize) -> usize {
+    let reach = (7 * width) / 8 + load; // 一组内相对组首字节需要读取的字节数
+    if data.len() < reach || data.len() > i32::MAX as usize {
+        return 0;
+    }
+    let groups = ((data.len() - reach) / width + 1).min(count / 8);
+    groups * 8
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use super::simd_count;
+    use crate::byte_order::ByteOrder;
+    use std::arch::x86_64::*;
+
+    /// 用 AVX2 解出前若干个元素，返回已处理的元素个数（8 的倍数），要求 width <= 25
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn unpack_u32(
+        data: &[u8],
+        byte_order: ByteOrder,
+        width: usize,
+        out: &mut [u32],
+    ) -> usize {
+        let count = simd_count(data, width, out.len(), 4);
+        let w = width as i32;
+        let bits = _mm256_setr_epi32(0, w, 2 * w, 3 * w, 4 * w, 5 * w, 6 * w, 7 * w);
+        let offsets = _mm256_srli_epi32(bits, 3);
+        let shifts = _mm256_and_si256(bits, _mm256_set1_epi32(7));
+        let mask = _mm256_set1_epi32(((1u64 << width) - 1) as i32);
+        let right = _mm_cvtsi32_si128(32 - width as i32);
+        let bswap = _mm256_setr_epi8(
+            3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12, 3, 2, 1, 0, 7, 6, 5, 4, 11, 10,
+            9, 8, 15, 14, 13, 12,
+        );
+
+        let mut i = 0;
+        while i < count {
+            // SAFETY: simd_count 保证本组所有 4 字节加载都在 data 范围内
+            let base = unsafe { data.as_ptr().add(i * width / 8) };
+            let words = unsafe { _mm256_i32gather_epi32::<1>(base.cast(), offsets) };
+            let values = match byte_order {
+                // 大端序：先把每个 lane 转成大端数值，左移去掉字节内偏移，再右移到低位
+                ByteOrder::BigEndian => _mm256_srl_epi32(
+                    _mm256_sllv_epi32(_mm256_shuffle_epi8(words, bswap), shifts),
+                    right,
+                ),
+                ByteOrder::LittleEndian => _mm256_and_si256(_mm256_srlv_epi32(words, shifts), mask),
+            };
+            unsafe { _mm256_storeu_si256(out.as_mut_ptr().add(i).cast(), values) };
+            i += 8;
+        }
+        count
+    }
+
+    /// 用 AVX2 解出前若干个元素，返回已处理的元素个数（8 的倍数），要求 width <= 57
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn unpack_u64(
+        data: &[u8],
+        byte_order: ByteOrder,
+        width: usize,
+        out: &mut [u64],
+    ) -> usize {
+        let count = simd_count(data, width, out.len(), 8);
+        let w = width as i64;
+        let lo_bits = _mm256_setr_epi64x(0, w, 2 * w, 3 * w);
+        let hi_bits = _mm256_setr_epi64x(4 * w, 5 * w, 6 * w, 7 * w);
+        let seven = _mm256_set1_epi64x(7);
+        let lo_offsets = _mm256_srli_epi64(lo_bits, 3);
+        let hi_offsets = _mm256_srli_epi64(hi_bits, 3);
+        let lo_shifts = _mm256_and_si256(lo_bits, seven);
+        let hi_shifts = _mm256_and_si256(hi_bits, seven);
+        let mask = _mm256_set1_epi64x(((1u64 << width) - 1) as i64);
+        let right = _mm_cvtsi32_si128(64 - width as i32);
+        let bswap = _mm256_setr_epi8(
+            7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 15, 14,
+            13, 12, 11, 10, 9, 8,
+        );
+        let extract = |words: __m256i, shifts: __m256i| match byte_order {
+            ByteOrder::BigEndian => _mm256_srl_epi64(
+                _mm256_sllv_epi64(_mm256_shuffle_epi8(words, bswap), shifts),
+                right,
+            ),
+            ByteOrder::LittleEndian => _mm256_and_si256(_mm256_srlv_epi64(words, shifts), mask),
+        };
+
+        let mut i = 0;
+        while i < count {
+            // SAFETY: simd_count 保证本组所有 8 字节加载都在 data 范围内
+            let base = unsafe { data.as_ptr().add(i * width / 8) };
+            let lo = unsafe { _mm256_i64gather_epi64::<1>(base.cast(), lo_offsets) };
+            let hi = unsafe { _mm256_i64gather_epi64::<1>(base.cast(), hi_offsets) };
+            unsafe {
+                let dst = out.as_mut_ptr().add(i);
+                _mm256_storeu_si256(dst.cast(), extract(lo, lo_shifts));
+                _mm256_storeu_si256(dst.add(4).cast(), extract(hi, hi_shifts));
+            }
+            i += 8;
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cursor::BitCursor;
+    use crate::traits::{BitRead, BitWrite};
+
+    fn pack(byte_order: ByteOrder, width: usize, values: &[u64]) -> Vec<u8> {
+        let mut cursor = BitCursor::with_byte_order(byte_order, Vec::new());
+        for &v in values {
+            cursor.write_bits(v, width).unwrap();
+        }
+        cursor.into_inner()
+    }
+
+    fn sample(width: usize, count: usize) -> Vec<u64> {
+        let mask = if width == 64 {
+            u64::MAX
+        } else {
+            (1u64 << width) - 1
+        };
+        (0..count as u64)
+            .map(|i| i.wrapping_mul(0x9E3779B97F4A7C15).rotate_left(17) & mask)
+            .collect()
+    }
+
+    #[test]
+    fn test_unpack_u32_all_widths() {
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            for width in 1..=32 {
+                // 元素个数不是 8 的倍数，覆盖 SIMD 与标量尾部两条路径
+                let values = sample(width, 203);
+                let data = pack(byte_order, width, &values);
+                let mut out = vec![0u32; values.len()];
+                unpack_u32(&data, byte_order, width, &mut out).unwrap();
+                let expected: Vec<u32> = values.iter().map(|&v| v as u32).collect();
+                assert_eq!(out, expected, "{:?} width {}", byte_order, width);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unpack_u64_all_widths() {
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            for width in 1..=64 {
+                let values = sample(width, 203);
+                let data = pack(byte_order, width, &values);
+                let mut out = vec![0u64; values.len()];
+                unpack_u64(&data, byte_order, width, &mut out).unwrap();
+                assert_eq!(out, values, "{:?} width {}", byte_order, width);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unpack_matches_read_bits() {
+        let data: Vec<u8> = (0..17_000u32).map(|i| (i * 131 + 7) as u8).collect();
+        let mut out = vec![0u32; 10_000];
+        unpack_u32(&data, ByteOrder::BigEndian, 13, &mut out).unwrap();
+        let mut cursor = BitCursor::new(&data);
+        for &v in &out {
+            assert_eq!(v as u64, cursor.read_bits(13).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_unpack_errors() {
+        let data = [0u8; 4];
+        let mut out = [0u32; 4];
+        assert!(unpack_u32(&data, ByteOrder::BigEndian, 0, &mut out).is_err());
+        assert!(unpack_u32(&data, ByteOrder::BigEndian, 33, &mut out).is_err());
+        assert!(unpack_u32(&data, ByteOrder::BigEndian, 9, &mut out).is_err());
+        assert!(unpack_u32(&data, ByteOrder::BigEndian, 8, &mut out).is_ok());
+        let mut out = [0u64; 1];
+        assert!(unpack_u64(&data, ByteOrder::LittleEndian, 65, &mut out).is_err());
+    }
+}
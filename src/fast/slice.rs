@@ -0,0 +1,546 @@
+use crate::byte_order::{Big, ByteOrder, Endianness, Little};
+use crate::error::BitReadWriteError;
+use std::io::Result;
+
+/// Slice-specialized fast reader for BigEndian streams
+pub type FastSliceReaderBig<'a> = FastSliceReader<'a, Big>;
+
+/// Slice-specialized fast reader for LittleEndian streams
+pub type FastSliceReaderLittle<'a> = FastSliceReader<'a, Little>;
+
+/// Fast bit reader over an in-memory `&[u8]`
+///
+/// Every read is a single unaligned 64-bit load at the current byte followed by two shifts, with
+/// no accumulator state and no refill logic. Only the last 7 bytes of the slice take a slower
+/// copying path.
+///
+/// ## Safety contract
+/// - [`new`](Self::new) accepts any slice, and the `*_fast` methods check the bit count and the
+///   remaining length on every call, so they are always safe.
+/// - [`read_bits_unchecked`](Self::read_bits_unchecked) and
+///   [`peek_bits_unchecked`](Self::peek_bits_unchecked) skip those checks. The caller must
+///   guarantee `1 <= n <= 64` and `n <= self.bits_remaining()`, typically by checking
+///   `bits_remaining()` once before a batch of reads.
+///
+/// ⚠️ **Use at your own risk**
+#[derive(Clone, Debug)]
+pub struct FastSliceReader<'a, E: Endianness = Big> {
+    data: &'a [u8],
+    pos: usize, // 当前位置，以比特为单位
+    order: E,
+}
+
+impl<'a, E: Endianness> FastSliceReader<'a, E> {
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            order: E::default(),
+        }
+    }
+
+    /// Returns the underlying slice
+    #[inline]
+    pub fn get_ref(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the current position in bits
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of unread bits
+    #[inline]
+    pub fn bits_remaining(&self) -> usize {
+        self.data.len() * 8 - self.pos
+    }
+
+    /// 从第 byte 个字节开始加载 8 字节（按字节序解释），末尾不足 8 字节时以 0 补齐
+    ///
+    /// 调用方保证 byte < len
+    #[inline(always)]
+    unsafe fn load(&self, byte: usize) -> u64 {
+        let bytes = if byte + 8 <= self.data.len() {
+            // SAFETY: [byte, byte + 8) 在切片范围内
+            unsafe {
+                self.data
+                    .as_ptr()
+                    .add(byte)
+                    .cast::<[u8; 8]>()
+                    .read_unaligned()
+            }
+        } else {
+            unsafe { self.load_tail(byte) }
+        };
+        match self.order.byte_order() {
+            ByteOrder::BigEndian => u64::from_be_bytes(bytes),
+            ByteOrder::LittleEndian => u64::from_le_bytes(bytes),
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    unsafe fn load_tail(&self, byte: usize) -> [u8; 8] {
+        let mut bytes = [0u8; 8];
+        // SAFETY: 调用方保证 byte < len
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                self.data.as_ptr().add(byte),
+                bytes.as_mut_ptr(),
+                self.data.len() - byte,
+            )
+        };
+        bytes
+    }
+
+    /// 读取不超过 57 比特（字节内偏移最多 7 位，一次加载即可覆盖）
+    #[inline(always)]
+    unsafe fn extract(&self, pos: usize, n: usize) -> u64 {
+        let word = unsafe { self.load(pos >> 3) };
+        let offset = pos & 7;
+        match self.order.byte_order() {
+            ByteOrder::BigEndian => (word << offset) >> (64 - n),
+            ByteOrder::LittleEndian => (word >> offset) & ((1u64 << n) - 1),
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn get_bits(&self, n: usize) -> u64 {
+        if n <= 57 {
+            return unsafe { self.extract(self.pos, n) };
+        }
+        // 超过 57 比特时可能跨越 9 个字节：分成两次加载再拼接
+        let first = unsafe { self.extract(self.pos, 32) };
+        let second = unsafe { self.extract(self.pos + 32, n - 32) };
+        match self.order.byte_order() {
+            ByteOrder::BigEndian => (first << (n - 32)) | second,
+            ByteOrder::LittleEndian => first | (second << 32),
+        }
+    }
+
+    #[inline(always)]
+    fn check(&self, n: usize) -> Result<()> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        if n > self.bits_remaining() {
+            return Err(BitReadWriteError::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+
+    /// Reads 1..=64 bits with maximal performance
+    #[inline(always)]
+    pub fn read_bits_fast(&mut self, n: usize) -> Result<u64> {
+        self.check(n)?;
+        // SAFETY: 上面已检查 n 的范围和剩余长度
+        Ok(unsafe { self.read_bits_unchecked(n) })
+    }
+
+    /// Reads `N` bits (1..=64, checked at compile time) with the width known to the compiler
+    ///
+    /// Shifts and masks are constant-folded and the split path for widths over 57 bits is
+    /// dropped when `N <= 57`, which helps hot fixed-width decodes such as 12-bit samples.
+    #[inline(always)]
+    pub fn read_bits_const<const N: usize>(&mut self) -> Result<u64> {
+        const { assert!(N >= 1 && N <= 64, "bit count must be between 1-64") };
+        if N > self.bits_remaining() {
+            return Err(BitReadWriteError::UnexpectedEof.into());
+        }
+        // SAFETY: N 在编译期已检查，上面已检查剩余长度
+        Ok(unsafe { self.read_bits_unchecked(N) })
+    }
+
+    /// Peeks at the next 1..=64 bits without consuming them
+    #[inline(always)]
+    pub fn peek_bits_fast(&self, n: usize) -> Result<u64> {
+        self.check(n)?;
+        // SAFETY: 上面已检查 n 的范围和剩余长度
+        Ok(unsafe { self.peek_bits_unchecked(n) })
+    }
+
+    /// Skips `n` bits, which may be any amount up to `bits_remaining()`
+    #[inline]
+    pub fn skip_bits(&mut self, n: usize) -> Result<()> {
+        if n > self.bits_remaining() {
+            return Err(BitReadWriteError::UnexpectedEof.into());
+        }
+        self.pos += n;
+        Ok(())
+    }
+
+    /// Reads 1..=64 bits without any checks
+    ///
+    /// # Safety
+    /// The caller must guarantee `1 <= n <= 64` and `n <= self.bits_remaining()`
+    #[inline(always)]
+    pub unsafe fn read_bits_unchecked(&mut self, n: usize) -> u64 {
+        let value = unsafe { self.get_bits(n) };
+        self.pos += n;
+        value
+    }
+
+    /// Peeks at the next 1..=64 bits without any checks
+    ///
+    /// # Safety
+    /// The caller must guarantee `1 <= n <= 64` and `n <= self.bits_remaining()`
+    #[inline(always)]
+    pub unsafe fn peek_bits_unchecked(&self, n: usize) -> u64 {
+        unsafe { self.get_bits(n) }
+    }
+}
+
+/// Slice-specialized fast writer for BigEndian streams
+pub type FastSliceWriterBig<'a> = FastSliceWriter<'a, Big>;
+
+/// Slice-specialized fast writer for LittleEndian streams
+pub type FastSliceWriterLittle<'a> = FastSliceWriter<'a, Little>;
+
+/// Fast bit writer into a preallocated `&mut [u8]`
+///
+/// Bits are packed into a 64-bit accumulator and stored into the slice 8 bytes at a time,
+/// with no `Write` calls. [`finish`](Self::finish) stores the pending bits, zero padded to a
+/// byte boundary, and returns the number of bits written; until then the slice does not hold
+/// the last up to 63 bits.
+///
+/// ## Safety contract
+/// - The `*_fast` methods check the bit count and the remaining capacity on every call, so they
+///   are always safe; a failed write leaves the writer unchanged.
+/// - [`write_bits_unchecked`](Self::write_bits_unchecked) skips those checks. The caller must
+///   guarantee `1 <= n <= 64` and `n <= self.bits_remaining()`, typically by sizing the slice
+///   for the worst case up front.
+///
+/// ⚠️ **Use at your own risk**
+#[derive(Debug)]
+pub struct FastSliceWriter<'a, E: Endianness = Big> {
+    data: &'a mut [u8],
+    stored: usize, // 已存入切片的字节数，始终是 8 的倍数
+    buffer: u64,
+    bits_used: usize,
+    order: E,
+}
+
+impl<'a, E: Endianness> FastSliceWriter<'a, E> {
+    #[inline]
+    pub fn new(data: &'a mut [u8]) -> Self {
+        Self {
+            data,
+            stored: 0,
+            buffer: 0,
+            bits_used: 0,
+            order: E::default(),
+        }
+    }
+
+    /// Returns the number of bits written so far
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.stored * 8 + self.bits_used
+    }
+
+    /// Returns the number of bits that still fit in the slice
+    #[inline]
+    pub fn bits_remaining(&self) -> usize {
+        self.data.len() * 8 - self.position()
+    }
+
+    #[inline(always)]
+    fn buffer_bytes(&self) -> [u8; 8] {
+        match self.order.byte_order() {
+            ByteOrder::BigEndian => self.buffer.to_be_bytes(),
+            ByteOrder::LittleEndian => self.buffer.to_le_bytes(),
+        }
+    }
+
+    /// Writes the low 1..=64 bits of `value` with maximal performance
+    ///
+    /// # Errors
+    /// - `InvalidBitCount` if `n` is not between 1-64
+    /// - `BufferTooSmall` if fewer than `n` bits are left in the slice
+    #[inline(always)]
+    pub fn write_bits_fast(&mut self, value: u64, n: usize) -> Result<()> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        if n > self.bits_remaining() {
+            return Err(BitReadWriteError::BufferTooSmall {
+                bits: self.position() + n,
+                capacity: self.data.len() * 8,
+            }
+            .into());
+        }
+        // SAFETY: 上面已检查 n 的范围和剩余容量
+        unsafe { self.write_bits_unchecked(value, n) };
+        Ok(())
+    }
+
+    /// Writes the low 1..=64 bits of `value` without any checks
+    ///
+    /// # Safety
+    /// The caller must guarantee `1 <= n <= 64` and `n <= self.bits_remaining()`
+    #[inline(always)]
+    pub unsafe fn write_bits_unchecked(&mut self, value: u64, n: usize) {
+        let value = if n == 64 {
+            value
+        } else {
+            value & ((1u64 << n) - 1)
+        };
+        let free = 64 - self.bits_used;
+        if n < free {
+            match self.order.byte_order() {
+                ByteOrder::BigEndian => self.buffer |= value << (free - n),
+                ByteOrder::LittleEndian => self.buffer |= value << self.bits_used,
+            }
+            self.bits_used += n;
+            return;
+        }
+
+        // 累加器放满：整体存入切片，剩余位留在累加器中
+        let rest = n - free;
+        match self.order.byte_order() {
+            ByteOrder::BigEndian => self.buffer |= value >> rest,
+            ByteOrder::LittleEndian => self.buffer |= value << self.bits_used,
+        }
+        let bytes = self.buffer_bytes();
+        // SAFETY: 调用方保证容量足够，所以 [stored, stored + 8) 在切片范围内
+        unsafe {
+            self.data
+                .as_mut_ptr()
+                .add(self.stored)
+                .cast::<[u8; 8]>()
+                .write_unaligned(bytes)
+        };
+        self.stored += 8;
+        self.buffer = if rest == 0 {
+            0
+        } else {
+            match self.order.byte_order() {
+                ByteOrder::BigEndian => value << (64 - rest),
+                ByteOrder::LittleEndian => value >> free,
+            }
+        };
+        self.bits_used = rest;
+    }
+
+    /// Stores the pending bits, zero padded to a byte boundary, and returns the number of bits
+    /// written (without the padding)
+    ///
+    /// Bytes of the slice after the last written byte are left untouched.
+    #[inline]
+    pub fn finish(self) -> usize {
+        let bytes = self.bits_used.div_ceil(8);
+        let pending = self.buffer_bytes();
+        self.data[self.stored..self.stored + bytes].copy_from_slice(&pending[..bytes]);
+        self.stored * 8 + self.bits_used
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ================ Big Endian 测试 ================
+    #[test]
+    fn test_big_endian_basic() {
+        let data = [0b1010_1100, 0b1111_0000];
+        let mut reader = FastSliceReaderBig::new(&data);
+        assert_eq!(reader.read_bits_fast(4).unwrap(), 0b1010);
+        assert_eq!(reader.peek_bits_fast(4).unwrap(), 0b1100);
+        assert_eq!(reader.read_bits_fast(4).unwrap(), 0b1100);
+        assert_eq!(reader.read_bits_fast(8).unwrap(), 0b1111_0000);
+        assert!(reader.read_bits_fast(1).is_err());
+    }
+
+    #[test]
+    fn test_big_endian_unaligned_64_bit() {
+        let data = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0xF0];
+        let mut reader = FastSliceReaderBig::new(&data);
+        reader.skip_bits(3).unwrap();
+        assert_eq!(reader.peek_bits_fast(64).unwrap(), 0x091A2B3C4D5E6F7F);
+        assert_eq!(reader.read_bits_fast(64).unwrap(), 0x091A2B3C4D5E6F7F);
+        assert_eq!(reader.bits_remaining(), 5);
+        assert_eq!(reader.read_bits_fast(5).unwrap(), 0b10000);
+    }
+
+    // ================ Little Endian 测试 ================
+    #[test]
+    fn test_little_endian_basic() {
+        let data = [0b1010_1100, 0b1111_0000];
+        let mut reader = FastSliceReaderLittle::new(&data);
+        assert_eq!(reader.read_bits_fast(4).unwrap(), 0b1100);
+        assert_eq!(reader.read_bits_fast(4).unwrap(), 0b1010);
+        assert_eq!(reader.read_bits_fast(8).unwrap(), 0b1111_0000);
+        assert!(reader.read_bits_fast(1).is_err());
+    }
+
+    #[test]
+    fn test_little_endian_unaligned_64_bit() {
+        let data = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        let mut reader = FastSliceReaderLittle::new(&data);
+        reader.skip_bits(4).unwrap();
+        assert_eq!(reader.read_bits_fast(64).unwrap(), 0x8070605040302010);
+        assert_eq!(reader.read_bits_fast(4).unwrap(), 0);
+    }
+
+    // ================ 通用边界测试 ================
+    #[test]
+    fn test_matches_stream_reader() {
+        use crate::fast::reader::{FastBitReaderBig, FastBitReaderLittle};
+
+        let data: Vec<u8> = (0..200u32).map(|i| (i * 37 + 11) as u8).collect();
+        let widths = [3usize, 64, 11, 57, 1, 60, 23, 8, 33];
+
+        let mut slice = FastSliceReaderBig::new(&data);
+        let mut stream = FastBitReaderBig::new(&data[..]);
+        let mut i = 0;
+        while slice.bits_remaining() >= widths[i % widths.len()] {
+            let n = widths[i % widths.len()];
+            assert_eq!(
+                slice.read_bits_fast(n).unwrap(),
+                stream.read_bits_fast(n).unwrap()
+            );
+            i += 1;
+        }
+
+        let mut slice = FastSliceReaderLittle::new(&data);
+        let mut stream = FastBitReaderLittle::new(&data[..]);
+        let mut i = 0;
+        while slice.bits_remaining() >= widths[i % widths.len()] {
+            let n = widths[i % widths.len()];
+            assert_eq!(
+                slice.read_bits_fast(n).unwrap(),
+                stream.read_bits_fast(n).unwrap()
+            );
+            i += 1;
+        }
+    }
+
+    #[test]
+    fn test_invalid_bit_count() {
+        let data = [0u8; 16];
+        let mut reader = FastSliceReaderBig::new(&data);
+        assert!(reader.read_bits_fast(0).is_err());
+        assert!(reader.read_bits_fast(65).is_err());
+        assert!(reader.skip_bits(129).is_err());
+        assert_eq!(reader.position(), 0);
+    }
+
+    #[test]
+    fn test_read_bits_const() {
+        let data: Vec<u8> = (0..32u8).map(|i| i.wrapping_mul(91)).collect();
+        let mut big = FastSliceReaderBig::new(&data);
+        let mut little = FastSliceReaderLittle::new(&data);
+        let mut expected_big = FastSliceReaderBig::new(&data);
+        let mut expected_little = FastSliceReaderLittle::new(&data);
+        for _ in 0..10 {
+            assert_eq!(
+                big.read_bits_const::<12>().unwrap(),
+                expected_big.read_bits_fast(12).unwrap()
+            );
+            assert_eq!(
+                little.read_bits_const::<12>().unwrap(),
+                expected_little.read_bits_fast(12).unwrap()
+            );
+        }
+        assert_eq!(
+            big.read_bits_const::<64>().unwrap(),
+            expected_big.read_bits_fast(64).unwrap()
+        );
+        assert_eq!(
+            big.read_bits_const::<7>().unwrap(),
+            expected_big.read_bits_fast(7).unwrap()
+        );
+        assert_eq!(
+            big.read_bits_const::<57>().unwrap(),
+            expected_big.read_bits_fast(57).unwrap()
+        );
+        assert!(big.read_bits_const::<9>().is_err());
+        assert_eq!(
+            big.read_bits_const::<8>().unwrap(),
+            expected_big.read_bits_fast(8).unwrap()
+        );
+    }
+
+    // ================ FastSliceWriter 测试 ================
+    #[test]
+    fn test_slice_writer_big_endian() {
+        let mut buf = [0xEEu8; 12];
+        let mut writer = FastSliceWriterBig::new(&mut buf);
+        writer.write_bits_fast(0b1, 1).unwrap();
+        writer.write_bits_fast(0x0123456789ABCDEF, 64).unwrap();
+        writer.write_bits_fast(0x7F, 7).unwrap();
+        assert_eq!(writer.position(), 72);
+        assert_eq!(writer.finish(), 72);
+        assert_eq!(
+            buf,
+            [
+                0x80, 0x91, 0xA2, 0xB3, 0xC4, 0xD5, 0xE6, 0xF7, 0xFF, 0xEE, 0xEE, 0xEE
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slice_writer_little_endian() {
+        let mut buf = [0u8; 9];
+        let mut writer = FastSliceWriterLittle::new(&mut buf);
+        writer.write_bits_fast(0x0F, 4).unwrap();
+        writer.write_bits_fast(0x0123456789ABCDEF, 64).unwrap();
+        assert_eq!(writer.finish(), 68);
+        assert_eq!(buf, [0xFF, 0xDE, 0xBC, 0x9A, 0x78, 0x56, 0x34, 0x12, 0x00]);
+    }
+
+    #[test]
+    fn test_slice_writer_matches_stream_writer() {
+        use crate::fast::writer::{FastBitWriterBig, FastBitWriterLittle};
+
+        let widths = [3usize, 13, 64, 1, 27, 40, 7, 64, 5, 57, 11];
+        let values = |i: usize| 0x9E3779B97F4A7C15u64.rotate_left(i as u32 * 7);
+
+        let mut buf = [0u8; 64];
+        let mut slice = FastSliceWriterBig::new(&mut buf);
+        let mut stream = FastBitWriterBig::new(Vec::new());
+        for (i, &w) in widths.iter().enumerate() {
+            slice.write_bits_fast(values(i), w).unwrap();
+            stream.write_bits_fast(values(i), w).unwrap();
+        }
+        let bits = slice.finish();
+        let expected = stream.into_inner().unwrap();
+        assert_eq!(bits, widths.iter().sum::<usize>());
+        assert_eq!(buf[..expected.len()], expected[..]);
+
+        let mut buf = [0u8; 64];
+        let mut slice = FastSliceWriterLittle::new(&mut buf);
+        let mut stream = FastBitWriterLittle::new(Vec::new());
+        for (i, &w) in widths.iter().enumerate() {
+            slice.write_bits_fast(values(i), w).unwrap();
+            stream.write_bits_fast(values(i), w).unwrap();
+        }
+        slice.finish();
+        let expected = stream.into_inner().unwrap();
+        assert_eq!(buf[..expected.len()], expected[..]);
+    }
+
+    #[test]
+    fn test_slice_writer_capacity() {
+        let mut buf = [0u8; 2];
+        let mut writer = FastSliceWriterBig::new(&mut buf);
+        writer.write_bits_fast(0x3FF, 10).unwrap();
+        assert!(writer.write_bits_fast(0, 7).is_err());
+        assert!(writer.write_bits_fast(0, 0).is_err());
+        assert_eq!(writer.bits_remaining(), 6);
+        unsafe { writer.write_bits_unchecked(0b10_1010, 6) };
+        assert_eq!(writer.bits_remaining(), 0);
+        assert_eq!(writer.finish(), 16);
+        assert_eq!(buf, [0xFF, 0xEA]);
+
+        // 恰好填满 8 字节时直接存入
+        let mut buf = [0u8; 8];
+        let mut writer = FastSliceWriterLittle::new(&mut buf);
+        writer.write_bits_fast(u64::MAX, 64).unwrap();
+        assert_eq!(writer.finish(), 64);
+        assert_eq!(buf, [0xFF; 8]);
+    }
+}
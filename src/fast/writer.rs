@@ -0,0 +1,232 @@
+use crate::byte_order::{Big, ByteOrder, Endianness, Little};
+use crate::error::BitReadWriteError;
+use std::io::{Result, Write};
+
+/// Ultra-fast bit writer for BigEndian streams
+///
+/// ## Critical Performance Notice
+/// - This implementation does **NOT** implement the standard `BitWrite` trait to avoid abstraction overhead and enable aggressive optimizations.
+/// - API **intentionally** differs from standard implementations to ensure users are clearly aware they are using an incompatible version.
+/// - Bits are emitted to the raw writer 8 bytes at a time; pending bits are **NOT** flushed on drop,
+///   call `flush_fast()` or `into_inner()` when done.
+///
+/// ⚠️ **Use at your own risk**
+pub type FastBitWriterBig<W> = FastBitWriter<W, Big>;
+
+/// Ultra-fast bit writer for LittleEndian streams
+///
+/// Same performance characteristics and safety considerations as [`FastBitWriterBig`].
+///
+/// ⚠️ **Use at your own risk**
+pub type FastBitWriterLittle<W> = FastBitWriter<W, Little>;
+
+/// Ultra-fast bit writer generic over a compile-time [`Endianness`] marker
+///
+/// Usually used through the [`FastBitWriterBig`] / [`FastBitWriterLittle`] aliases.
+///
+/// ⚠️ **Use at your own risk**
+pub struct FastBitWriter<W: Write, E: Endianness> {
+    raw: W,
+    order: E,
+    buffer: u64,
+    bits_used: usize,
+}
+
+impl<W: Write, E: Endianness> FastBitWriter<W, E> {
+    #[inline]
+    pub fn new(raw: W) -> Self {
+        Self {
+            raw,
+            order: E::default(),
+            buffer: 0,
+            bits_used: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn buffer_bytes(&self) -> [u8; 8] {
+        match self.order.byte_order() {
+            ByteOrder::BigEndian => self.buffer.to_be_bytes(),
+            ByteOrder::LittleEndian => self.buffer.to_le_bytes(),
+        }
+    }
+
+    /// Writes the low 1..=64 bits of `value` with maximal performance
+    #[inline(always)]
+    pub fn write_bits_fast(&mut self, value: u64, n: usize) -> Result<()> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+
+        let value = if n == 64 {
+            value
+        } else {
+            value & ((1u64 << n) - 1)
+        };
+        let free = 64 - self.bits_used; // bits_used 永远小于 64，所以 free 至少为 1
+
+        if n < free {
+            // 累加器放得下：大端序从左往右码放，小端序从右往左码放
+            match self.order.byte_order() {
+                ByteOrder::BigEndian => self.buffer |= value << (free - n),
+                ByteOrder::LittleEndian => self.buffer |= value << self.bits_used,
+            }
+            self.bits_used += n;
+            return Ok(());
+        }
+
+        // 累加器放不下（或刚好放满）：先补满累加器并整体写出 8 字节，剩余位留在累加器中
+        // rest > 0 时 bits_used 必然大于 0，即 free < 64，移位不会溢出
+        let rest = n - free;
+        match self.order.byte_order() {
+            ByteOrder::BigEndian => self.buffer |= value >> rest,
+            ByteOrder::LittleEndian => self.buffer |= value << self.bits_used,
+        }
+        self.raw.write_all(&self.buffer_bytes())?;
+        self.buffer = if rest == 0 {
+            0
+        } else {
+            match self.order.byte_order() {
+                ByteOrder::BigEndian => value << (64 - rest),
+                ByteOrder::LittleEndian => value >> free,
+            }
+        };
+        self.bits_used = rest;
+        Ok(())
+    }
+
+    /// Pads pending bits with zeros to a byte boundary, writes them, and flushes the raw writer
+    #[inline]
+    pub fn flush_fast(&mut self) -> Result<()> {
+        let bytes = self.bits_used.div_ceil(8);
+        if bytes > 0 {
+            self.raw.write_all(&self.buffer_bytes()[..bytes])?;
+            self.buffer = 0;
+            self.bits_used = 0;
+        }
+        self.raw.flush()
+    }
+
+    /// Flushes pending bits (see `flush_fast`) and returns the raw writer
+    pub fn into_inner(mut self) -> Result<W> {
+        self.flush_fast()?;
+        Ok(self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::byte_order::ByteOrder;
+    use crate::cursor::BitCursor;
+    use crate::traits::BitRead;
+
+    // ================ Big Endian 测试 ================
+    #[test]
+    fn test_big_endian_basic() {
+        let mut writer = FastBitWriterBig::new(Vec::new());
+        writer.write_bits_fast(0b1010, 4).unwrap();
+        writer.write_bits_fast(0b1100, 4).unwrap();
+        writer.write_bits_fast(0b101, 3).unwrap();
+        assert_eq!(writer.into_inner().unwrap(), vec![0xAC, 0xA0]);
+    }
+
+    #[test]
+    fn test_big_endian_cross_accumulator() {
+        let mut writer = FastBitWriterBig::new(Vec::new());
+        writer.write_bits_fast(0b1, 1).unwrap();
+        writer.write_bits_fast(0x0123456789ABCDEF, 64).unwrap();
+        writer.write_bits_fast(0x7F, 7).unwrap();
+        assert_eq!(
+            writer.into_inner().unwrap(),
+            vec![0x80, 0x91, 0xA2, 0xB3, 0xC4, 0xD5, 0xE6, 0xF7, 0xFF]
+        );
+    }
+
+    #[test]
+    fn test_big_endian_exact_fill() {
+        let mut writer = FastBitWriterBig::new(Vec::new());
+        writer.write_bits_fast(0x0123456789ABCDEF, 64).unwrap();
+        writer.write_bits_fast(0x12345678, 32).unwrap();
+        writer.write_bits_fast(0x9ABCDEF0, 32).unwrap();
+        assert_eq!(
+            writer.into_inner().unwrap(),
+            vec![
+                0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC,
+                0xDE, 0xF0
+            ]
+        );
+    }
+
+    #[test]
+    fn test_big_endian_round_trip() {
+        let widths = [3usize, 13, 64, 1, 27, 40, 7, 64, 5];
+        let mut writer = FastBitWriterBig::new(Vec::new());
+        for (i, &w) in widths.iter().enumerate() {
+            writer
+                .write_bits_fast(0x9E3779B97F4A7C15u64.rotate_left(i as u32), w)
+                .unwrap();
+        }
+        let data = writer.into_inner().unwrap();
+        let mut reader = BitCursor::with_byte_order(ByteOrder::BigEndian, data);
+        for (i, &w) in widths.iter().enumerate() {
+            let mask = if w == 64 { u64::MAX } else { (1u64 << w) - 1 };
+            assert_eq!(
+                reader.read_bits(w).unwrap(),
+                0x9E3779B97F4A7C15u64.rotate_left(i as u32) & mask
+            );
+        }
+    }
+
+    // ================ Little Endian 测试 ================
+    #[test]
+    fn test_little_endian_basic() {
+        let mut writer = FastBitWriterLittle::new(Vec::new());
+        writer.write_bits_fast(0b1010, 4).unwrap();
+        writer.write_bits_fast(0b1100, 4).unwrap();
+        writer.write_bits_fast(0b101, 3).unwrap();
+        assert_eq!(writer.into_inner().unwrap(), vec![0xCA, 0x05]);
+    }
+
+    #[test]
+    fn test_little_endian_cross_accumulator() {
+        let mut writer = FastBitWriterLittle::new(Vec::new());
+        writer.write_bits_fast(0x0F, 4).unwrap();
+        writer.write_bits_fast(0x0123456789ABCDEF, 64).unwrap();
+        assert_eq!(
+            writer.into_inner().unwrap(),
+            vec![0xFF, 0xDE, 0xBC, 0x9A, 0x78, 0x56, 0x34, 0x12, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_little_endian_round_trip() {
+        let widths = [3usize, 13, 64, 1, 27, 40, 7, 64, 5];
+        let mut writer = FastBitWriterLittle::new(Vec::new());
+        for (i, &w) in widths.iter().enumerate() {
+            writer
+                .write_bits_fast(0x9E3779B97F4A7C15u64.rotate_left(i as u32), w)
+                .unwrap();
+        }
+        let data = writer.into_inner().unwrap();
+        let mut reader = BitCursor::with_byte_order(ByteOrder::LittleEndian, data);
+        for (i, &w) in widths.iter().enumerate() {
+            let mask = if w == 64 { u64::MAX } else { (1u64 << w) - 1 };
+            assert_eq!(
+                reader.read_bits(w).unwrap(),
+                0x9E3779B97F4A7C15u64.rotate_left(i as u32) & mask
+            );
+        }
+    }
+
+    // ================ 通用边界测试 ================
+    #[test]
+    fn test_invalid_bit_count() {
+        let mut big = FastBitWriterBig::new(Vec::new());
+        assert!(big.write_bits_fast(0, 0).is_err());
+        assert!(big.write_bits_fast(0, 65).is_err());
+        let mut little = FastBitWriterLittle::new(Vec::new());
+        assert!(little.write_bits_fast(0, 0).is_err());
+        assert!(little.write_bits_fast(0, 65).is_err());
+    }
+}
@@ -0,0 +1,111 @@
+//! Fixed-point Qm.n fields on top of [`BitRead`] / [`BitWrite`]
+//!
+//! A Qm.n field is `m + n` bits wide (1..=64) and stores `value * 2^n` as an integer: `m`
+//! integer bits and `n` fraction bits. For the signed variants the field is two's complement
+//! and `m` includes the sign bit, so Q1.15 is the usual 16-bit audio sample in `[-1, 1)`.
+//!
+//! Values are converted through `f64`, which is exact up to 53 significant bits.
+
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite, sign_extend};
+use std::io::Result;
+
+fn check_width(int_bits: usize, frac_bits: usize) -> Result<usize> {
+    let width = int_bits.saturating_add(frac_bits);
+    if width == 0 || width > 64 {
+        return Err(BitReadWriteError::InvalidBitCount(width).into());
+    }
+    Ok(width)
+}
+
+/// `2^n` 作为 f64，n 最大为 64，结果总是精确的
+fn scale(frac_bits: usize) -> f64 {
+    2f64.powi(frac_bits as i32)
+}
+
+/// 按 2^n 缩放并四舍五入成整数；NaN 无法编码
+fn to_raw(value: f64, frac_bits: usize) -> Result<f64> {
+    if value.is_nan() {
+        return Err(BitReadWriteError::NotANumber.into());
+    }
+    Ok((value * scale(frac_bits)).round())
+}
+
+/// Reads an unsigned UQm.n field (`int_bits + frac_bits` bits) and returns its value
+///
+/// # Errors
+/// - `InvalidBitCount` if the total width is not in 1..=64
+/// - Any error of the reader, e.g. `UnexpectedEof` when the stream is too short
+pub fn read_uq<R>(reader: &mut R, int_bits: usize, frac_bits: usize) -> Result<f64>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let width = check_width(int_bits, frac_bits)?;
+    Ok(reader.read_bits(width)? as f64 / scale(frac_bits))
+}
+
+/// Reads a signed two's-complement Qm.n field (`int_bits + frac_bits` bits, sign included in
+/// `int_bits`) and returns its value
+///
+/// # Errors
+/// - `InvalidBitCount` if the total width is not in 1..=64
+/// - Any error of the reader, e.g. `UnexpectedEof` when the stream is too short
+pub fn read_q<R>(reader: &mut R, int_bits: usize, frac_bits: usize) -> Result<f64>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let width = check_width(int_bits, frac_bits)?;
+    let raw = reader.read_bits(width)?;
+    Ok(sign_extend(raw, width) as f64 / scale(frac_bits))
+}
+
+/// Writes `value` as an unsigned UQm.n field, rounding to the nearest step of `2^-frac_bits`
+///
+/// # Errors
+/// - `InvalidBitCount` if the total width is not in 1..=64
+/// - `ValueOutOfRange` if the rounded value is negative or needs more than `int_bits`
+/// - `InvalidInput` if `value` is NaN
+pub fn write_uq<W>(writer: &mut W, value: f64, int_bits: usize, frac_bits: usize) -> Result<()>
+where
+    W: BitWrite + ?Sized,
+{
+    let width = check_width(int_bits, frac_bits)?;
+    let raw = to_raw(value, frac_bits)?;
+    if raw < 0.0 || raw >= scale(width) {
+        return Err(BitReadWriteError::ValueOutOfRange {
+            value: raw as i64 as u64,
+            bits: width,
+        }
+        .into());
+    }
+    writer.write_bits(raw as u64, width)
+}
+
+/// Writes `value` as a signed two's-complement Qm.n field, rounding to the nearest step of
+/// `2^-frac_bits`
+///
+/// # Errors
+/// - `InvalidBitCount` if the total width is not in 1..=64
+/// - `ValueOutOfRange` if the rounded value is outside `[-2^(m-1), 2^(m-1))`
+/// - `InvalidInput` if `value` is NaN
+pub fn write_q<W>(writer: &mut W, value: f64, int_bits: usize, frac_bits: usize) -> Result<()>
+where
+    W: BitWrite + ?Sized,
+{
+    let width = check_width(int_bits, frac_bits)?;
+    let raw = to_raw(value, frac_bits)?;
+    let limit = scale(width - 1);
+    if raw < -limit || raw >= limit {
+        return Err(BitReadWriteError::ValueOutOfRange {
+            value: raw as i64 as u64,
+            bits: width,
+        }
+        .into());
+    }
+    let mask = if width == 64 {
+        u64::MAX
+    } else {
+        (1u64 << width) - 1
+    };
+    writer.write_bits(raw as i64 as u64 & mask, width)
+}
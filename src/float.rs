@@ -0,0 +1,74 @@
+//! IEEE 754 half-precision conversions for
+//! [`BitReadExt::read_f16`](crate::traits::BitReadExt::read_f16) and
+//! [`BitWriteExt::write_f16`](crate::traits::BitWriteExt::write_f16)
+//!
+//! Half-precision values are passed around as their raw `u16` bits, so no `f16` type or extra
+//! dependency is needed.
+
+/// Converts half-precision bits to `f32`
+///
+/// Every half-precision value, including subnormals, infinities and NaN payloads, is exactly
+/// representable as `f32`, so the conversion is lossless.
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits & 0x8000) as u32) << 16;
+    let exp = ((bits >> 10) & 0x1F) as u32;
+    let mant = (bits & 0x3FF) as u32;
+    match exp {
+        0 => {
+            // 零或非规格化数：mant * 2^-24，在 f32 中可以精确表示
+            let magnitude = mant as f32 * f32::from_bits(0x3380_0000); // 2^-24
+            f32::from_bits(sign | magnitude.to_bits())
+        }
+        0x1F => f32::from_bits(sign | 0x7F80_0000 | (mant << 13)),
+        _ => f32::from_bits(sign | ((exp + 127 - 15) << 23) | (mant << 13)),
+    }
+}
+
+/// Converts `value` to half-precision bits, rounding to nearest with ties to even
+///
+/// Values too large for half precision become infinity, values too small become (signed)
+/// zero, and NaN stays NaN.
+pub fn f32_to_f16(value: f32) -> u16 {
+    let x = value.to_bits();
+    let sign = ((x >> 16) & 0x8000) as u16;
+    let exp = ((x >> 23) & 0xFF) as i32;
+    let mant = x & 0x7F_FFFF;
+
+    if exp == 0xFF {
+        // 无穷或 NaN：保留 NaN 的高位载荷，并保证结果仍是 NaN
+        let nan = if mant != 0 {
+            0x200 | (mant >> 13) as u16
+        } else {
+            0
+        };
+        return sign | 0x7C00 | nan;
+    }
+
+    let e = exp - 127 + 15;
+    if e >= 0x1F {
+        return sign | 0x7C00; // 上溢为无穷
+    }
+    if e <= 0 {
+        if e < -10 {
+            return sign; // 小于最小非规格化数的一半，下溢为零
+        }
+        // 非规格化数：补上隐含的 1 后右移
+        let m = mant | 0x80_0000;
+        let shift = (14 - e) as u32;
+        return sign | round_shift(m, shift) as u16;
+    }
+    // 舍入进位可能进入指数位，此时结果恰好是下一个指数（或无穷），仍然正确
+    sign | round_shift(((e as u32) << 23) | mant, 13) as u16
+}
+
+/// 右移 shift 位并按就近舍入、平局取偶
+fn round_shift(value: u32, shift: u32) -> u32 {
+    let truncated = value >> shift;
+    let rem = value & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    if rem > half || (rem == half && truncated & 1 == 1) {
+        truncated + 1
+    } else {
+        truncated
+    }
+}
@@ -0,0 +1,196 @@
+//! ADTS (Audio Data Transport Stream) headers of AAC frames, ISO/IEC 13818-7 and 14496-3
+//!
+//! ## Header layout
+//! ```text
+//! fixed header (28 bits):
+//!   syncword                 12  0xFFF
+//!   id                        1  0 = MPEG-4, 1 = MPEG-2
+//!   layer                     2  always 0
+//!   protection_absent         1  1 = no CRC
+//!   profile                   2  audio object type - 1
+//!   sampling_frequency_index  4
+//!   private_bit               1
+//!   channel_configuration     3
+//!   original_copy             1
+//!   home                      1
+//! variable header (28 bits):
+//!   copyright_id_bit          1
+//!   copyright_id_start        1
+//!   frame_length             13  including the header
+//!   buffer_fullness          11  0x7FF = variable bit rate
+//!   raw_data_blocks           2  number of raw data blocks - 1
+//! crc_check                  16  only when protection_absent is 0
+//! ```
+
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite};
+use std::io::Result;
+
+/// Syncword that starts every ADTS header
+pub const SYNCWORD: u64 = 0xFFF;
+
+/// Sampling frequencies in Hz by `sampling_frequency_index`
+pub const SAMPLING_FREQUENCIES: [u32; 13] = [
+    96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+];
+
+/// MPEG version signalled by the `id` bit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MpegVersion {
+    Mpeg4,
+    Mpeg2,
+}
+
+/// A decoded ADTS header
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AdtsHeader {
+    pub version: MpegVersion,
+    /// Audio object type minus 1: 0 Main, 1 LC, 2 SSR, 3 LTP
+    pub profile: u8,
+    pub sampling_frequency_index: u8,
+    pub private_bit: bool,
+    /// 0 = defined in the stream, 1-7 = channel layouts of ISO/IEC 14496-3
+    pub channel_configuration: u8,
+    pub original_copy: bool,
+    pub home: bool,
+    pub copyright_id_bit: bool,
+    pub copyright_id_start: bool,
+    /// Length of the whole frame in bytes, header included (13 bits)
+    pub frame_length: u16,
+    /// Buffer fullness (11 bits), `0x7FF` for variable bit rate streams
+    pub buffer_fullness: u16,
+    /// Number of raw data blocks in the frame minus 1 (2 bits)
+    pub raw_data_blocks: u8,
+    /// CRC of the frame, present when the header is protected
+    pub crc: Option<u16>,
+}
+
+impl AdtsHeader {
+    /// Reads a header, starting at its syncword
+    ///
+    /// # Errors
+    /// - `InvalidData` if the syncword or the layer is wrong
+    /// - Any error of the reader, e.g. `UnexpectedEof` when the stream is truncated
+    pub fn read<R>(reader: &mut R) -> Result<Self>
+    where
+        R: BitRead<Output = u64> + ?Sized,
+    {
+        if reader.read_bits(12)? != SYNCWORD {
+            return Err(BitReadWriteError::AdtsSyncNotFound.into());
+        }
+        let version = match reader.read_bits(1)? {
+            0 => MpegVersion::Mpeg4,
+            _ => MpegVersion::Mpeg2,
+        };
+        let layer = reader.read_bits(2)? as u8;
+        if layer != 0 {
+            return Err(BitReadWriteError::InvalidAdtsLayer(layer).into());
+        }
+        let protection_absent = reader.read_bits(1)? == 1;
+        let profile = reader.read_bits(2)? as u8;
+        let sampling_frequency_index = reader.read_bits(4)? as u8;
+        let private_bit = reader.read_bits(1)? == 1;
+        let channel_configuration = reader.read_bits(3)? as u8;
+        let original_copy = reader.read_bits(1)? == 1;
+        let home = reader.read_bits(1)? == 1;
+
+        let copyright_id_bit = reader.read_bits(1)? == 1;
+        let copyright_id_start = reader.read_bits(1)? == 1;
+        let frame_length = reader.read_bits(13)? as u16;
+        let buffer_fullness = reader.read_bits(11)? as u16;
+        let raw_data_blocks = reader.read_bits(2)? as u8;
+        let crc = if protection_absent {
+            None
+        } else {
+            Some(reader.read_bits(16)? as u16)
+        };
+        Ok(Self {
+            version,
+            profile,
+            sampling_frequency_index,
+            private_bit,
+            channel_configuration,
+            original_copy,
+            home,
+            copyright_id_bit,
+            copyright_id_start,
+            frame_length,
+            buffer_fullness,
+            raw_data_blocks,
+            crc,
+        })
+    }
+
+    /// Writes the header; fields wider than their slot are masked by the writer
+    ///
+    /// # Errors
+    /// Any error of the writer
+    pub fn write<W: BitWrite + ?Sized>(&self, writer: &mut W) -> Result<()> {
+        writer.write_bits(SYNCWORD, 12)?;
+        writer.write_bits((self.version == MpegVersion::Mpeg2) as u64, 1)?;
+        writer.write_bits(0, 2)?;
+        writer.write_bits(self.crc.is_none() as u64, 1)?;
+        writer.write_bits(self.profile as u64, 2)?;
+        writer.write_bits(self.sampling_frequency_index as u64, 4)?;
+        writer.write_bits(self.private_bit as u64, 1)?;
+        writer.write_bits(self.channel_configuration as u64, 3)?;
+        writer.write_bits(self.original_copy as u64, 1)?;
+        writer.write_bits(self.home as u64, 1)?;
+
+        writer.write_bits(self.copyright_id_bit as u64, 1)?;
+        writer.write_bits(self.copyright_id_start as u64, 1)?;
+        writer.write_bits(self.frame_length as u64, 13)?;
+        writer.write_bits(self.buffer_fullness as u64, 11)?;
+        writer.write_bits(self.raw_data_blocks as u64, 2)?;
+        if let Some(crc) = self.crc {
+            writer.write_bits(crc as u64, 16)?;
+        }
+        Ok(())
+    }
+
+    /// Length of the header in bytes: 7, or 9 with a CRC
+    pub fn header_length(&self) -> usize {
+        if self.crc.is_some() { 9 } else { 7 }
+    }
+
+    /// Length of the frame payload in bytes, `frame_length` minus the header
+    pub fn payload_length(&self) -> usize {
+        (self.frame_length as usize).saturating_sub(self.header_length())
+    }
+
+    /// Sampling frequency in Hz, or `None` for reserved and escape indices
+    pub fn sampling_frequency(&self) -> Option<u32> {
+        SAMPLING_FREQUENCIES
+            .get(self.sampling_frequency_index as usize)
+            .copied()
+    }
+
+    /// MPEG-4 audio object type (`profile + 1`), 2 for AAC LC
+    pub fn audio_object_type(&self) -> u8 {
+        self.profile + 1
+    }
+}
+
+/// Reads one ADTS frame: its header and payload bytes
+///
+/// # Errors
+/// - `InvalidData` if the header is invalid or `frame_length` is shorter than the header
+/// - Any error of the reader, e.g. `UnexpectedEof` when the frame is truncated
+pub fn read_frame<R>(reader: &mut R) -> Result<(AdtsHeader, Vec<u8>)>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let header = AdtsHeader::read(reader)?;
+    if (header.frame_length as usize) < header.header_length() {
+        return Err(BitReadWriteError::AdtsFrameTooShort {
+            frame_length: header.frame_length,
+            header_length: header.header_length(),
+        }
+        .into());
+    }
+    let mut payload = Vec::with_capacity(header.payload_length());
+    for _ in 0..header.payload_length() {
+        payload.push(reader.read_bits(8)? as u8);
+    }
+    Ok((header, payload))
+}
@@ -0,0 +1,258 @@
+//! AV1 OBU (open bitstream unit) headers and the `uvlc()` / `leb128()` primitives of the AV1
+//! bitstream specification, sections 4.10 and 5.3
+//!
+//! ## OBU header layout
+//! ```text
+//! obu_forbidden_bit           1  must be 0
+//! obu_type                    4
+//! obu_extension_flag          1
+//! obu_has_size_field          1
+//! obu_reserved_1bit           1
+//! extension (when flagged):
+//!   temporal_id               3
+//!   spatial_id                2
+//!   reserved                  3
+//! obu_size               leb128  when obu_has_size_field is 1
+//! ```
+
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite, BitWriteExt};
+use std::io::Result;
+
+/// Largest value `leb128()` may carry in a conforming stream, `(1 << 32) - 1`
+pub const MAX_LEB128: u64 = u32::MAX as u64;
+
+/// Reads a `leb128()` value: up to 8 bytes, 7 bits each, least significant group first
+///
+/// # Errors
+/// - `InvalidData` if the value exceeds [`MAX_LEB128`]
+/// - Any error of the reader
+pub fn read_leb128<R>(reader: &mut R) -> Result<u64>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let mut value = 0u64;
+    for i in 0..8 {
+        let byte = reader.read_bits(8)?;
+        value |= (byte & 0x7F) << (i * 7);
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    if value > MAX_LEB128 {
+        return Err(BitReadWriteError::Leb128Overflow(value).into());
+    }
+    Ok(value)
+}
+
+/// Writes `value` as a `leb128()` in the fewest bytes
+///
+/// # Errors
+/// - `ValueOutOfRange` if `value` exceeds [`MAX_LEB128`]
+/// - Any error of the writer
+pub fn write_leb128<W: BitWrite + ?Sized>(writer: &mut W, value: u64) -> Result<()> {
+    if value > MAX_LEB128 {
+        return Err(BitReadWriteError::ValueOutOfRange { value, bits: 32 }.into());
+    }
+    let mut rest = value;
+    loop {
+        let byte = rest & 0x7F;
+        rest >>= 7;
+        if rest == 0 {
+            return writer.write_bits(byte, 8);
+        }
+        writer.write_bits(byte | 0x80, 8)?;
+    }
+}
+
+/// Number of bytes [`write_leb128`] uses for `value`
+pub fn leb128_len(value: u64) -> usize {
+    (64 - value.leading_zeros() as usize).div_ceil(7).max(1)
+}
+
+/// Reads a `uvlc()` value, AV1's variant of the unsigned Exp-Golomb code
+///
+/// Unlike [`read_ue`](crate::traits::BitReadExt::read_ue), a run of 32 or more leading zeros
+/// ends the code and yields `u32::MAX` without reading any value bits.
+pub fn read_uvlc<R>(reader: &mut R) -> Result<u32>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let mut zeros = 0;
+    while reader.read_bits(1)? == 0 {
+        zeros += 1;
+    }
+    if zeros >= 32 {
+        return Ok(u32::MAX);
+    }
+    if zeros == 0 {
+        return Ok(0);
+    }
+    Ok(((1u64 << zeros) - 1 + reader.read_bits(zeros)?) as u32)
+}
+
+/// Writes `value` as a `uvlc()`; `u32::MAX` becomes 32 zeros and a one bit
+pub fn write_uvlc<W: BitWrite + ?Sized>(writer: &mut W, value: u32) -> Result<()> {
+    if value == u32::MAX {
+        writer.write_bits(0, 32)?;
+        return writer.write_bits(1, 1);
+    }
+    writer.write_ue(value as u64)
+}
+
+/// `obu_type` values of the specification
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObuType {
+    SequenceHeader,
+    TemporalDelimiter,
+    FrameHeader,
+    TileGroup,
+    Metadata,
+    Frame,
+    RedundantFrameHeader,
+    TileList,
+    Padding,
+    /// Types 0 and 9-14, reserved
+    Reserved(u8),
+}
+
+impl ObuType {
+    /// Maps the 4-bit `obu_type` field; only the low 4 bits are used
+    pub fn from_bits(bits: u8) -> Self {
+        match bits & 0x0F {
+            1 => ObuType::SequenceHeader,
+            2 => ObuType::TemporalDelimiter,
+            3 => ObuType::FrameHeader,
+            4 => ObuType::TileGroup,
+            5 => ObuType::Metadata,
+            6 => ObuType::Frame,
+            7 => ObuType::RedundantFrameHeader,
+            8 => ObuType::TileList,
+            15 => ObuType::Padding,
+            other => ObuType::Reserved(other),
+        }
+    }
+
+    /// The 4-bit `obu_type` field
+    pub fn bits(self) -> u8 {
+        match self {
+            ObuType::SequenceHeader => 1,
+            ObuType::TemporalDelimiter => 2,
+            ObuType::FrameHeader => 3,
+            ObuType::TileGroup => 4,
+            ObuType::Metadata => 5,
+            ObuType::Frame => 6,
+            ObuType::RedundantFrameHeader => 7,
+            ObuType::TileList => 8,
+            ObuType::Padding => 15,
+            ObuType::Reserved(bits) => bits & 0x0F,
+        }
+    }
+}
+
+/// Scalability layer of an OBU, from its extension header
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ObuExtension {
+    /// Temporal layer (3 bits)
+    pub temporal_id: u8,
+    /// Spatial layer (2 bits)
+    pub spatial_id: u8,
+}
+
+/// A decoded OBU header
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ObuHeader {
+    pub obu_type: ObuType,
+    pub extension: Option<ObuExtension>,
+    /// Size of the payload in bytes, `None` when the OBU has no size field (its size then
+    /// comes from the container)
+    pub obu_size: Option<u64>,
+}
+
+impl ObuHeader {
+    /// Reads an OBU header, including `obu_size` when present
+    ///
+    /// Reserved bits are ignored, as the specification requires of decoders.
+    ///
+    /// # Errors
+    /// - `InvalidData` if the forbidden bit is set or `obu_size` is out of range
+    /// - Any error of the reader, e.g. `UnexpectedEof` when the stream is truncated
+    pub fn read<R>(reader: &mut R) -> Result<Self>
+    where
+        R: BitRead<Output = u64> + ?Sized,
+    {
+        if reader.read_bits(1)? != 0 {
+            return Err(BitReadWriteError::ObuForbiddenBit.into());
+        }
+        let obu_type = ObuType::from_bits(reader.read_bits(4)? as u8);
+        let has_extension = reader.read_bits(1)? == 1;
+        let has_size = reader.read_bits(1)? == 1;
+        reader.read_bits(1)?;
+        let extension = if has_extension {
+            let temporal_id = reader.read_bits(3)? as u8;
+            let spatial_id = reader.read_bits(2)? as u8;
+            reader.read_bits(3)?;
+            Some(ObuExtension {
+                temporal_id,
+                spatial_id,
+            })
+        } else {
+            None
+        };
+        let obu_size = if has_size {
+            Some(read_leb128(reader)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            obu_type,
+            extension,
+            obu_size,
+        })
+    }
+
+    /// Writes the header with zero reserved bits and a minimal `leb128` size
+    ///
+    /// # Errors
+    /// - `ValueOutOfRange` if `obu_size` exceeds [`MAX_LEB128`]
+    /// - Any error of the writer
+    pub fn write<W: BitWrite + ?Sized>(&self, writer: &mut W) -> Result<()> {
+        writer.write_bits(0, 1)?;
+        writer.write_bits(self.obu_type.bits() as u64, 4)?;
+        writer.write_bits(self.extension.is_some() as u64, 1)?;
+        writer.write_bits(self.obu_size.is_some() as u64, 1)?;
+        writer.write_bits(0, 1)?;
+        if let Some(ext) = self.extension {
+            writer.write_bits(ext.temporal_id as u64, 3)?;
+            writer.write_bits(ext.spatial_id as u64, 2)?;
+            writer.write_bits(0, 3)?;
+        }
+        if let Some(size) = self.obu_size {
+            write_leb128(writer, size)?;
+        }
+        Ok(())
+    }
+
+    /// Length of the header in bytes as written by [`ObuHeader::write`]
+    pub fn header_length(&self) -> usize {
+        1 + self.extension.is_some() as usize + self.obu_size.map_or(0, leb128_len)
+    }
+}
+
+/// Reads one OBU with a size field: its header and payload bytes
+///
+/// # Errors
+/// - `InvalidData` if the header is invalid or has no `obu_size`
+/// - Any error of the reader, e.g. `UnexpectedEof` when the OBU is truncated
+pub fn read_obu<R>(reader: &mut R) -> Result<(ObuHeader, Vec<u8>)>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let header = ObuHeader::read(reader)?;
+    let size = header.obu_size.ok_or(BitReadWriteError::MissingObuSize)?;
+    let mut payload = Vec::new();
+    for _ in 0..size {
+        payload.push(reader.read_bits(8)? as u8);
+    }
+    Ok((header, payload))
+}
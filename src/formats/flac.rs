@@ -0,0 +1,271 @@
+//! FLAC frame headers and residuals, RFC 9639 sections 9.1 and 9.2.7
+//!
+//! ## Frame header layout
+//! ```text
+//! sync code                  14  0b11_1111_1111_1110
+//! reserved                    1  0
+//! blocking strategy           1  0 = fixed (frame number), 1 = variable (sample number)
+//! block size                  4  coded, 6/7 = 8/16-bit value at the end
+//! sample rate                 4  coded, 12-14 = 8/16-bit value at the end
+//! channel assignment          4  0-7 independent, 8-10 stereo decorrelation
+//! sample size                 3  coded, 0 = from STREAMINFO
+//! reserved                    1  0
+//! coded number         8-56 bits  frame or sample number, UTF-8-like
+//! uncommon block size    0/8/16
+//! uncommon sample rate   0/8/16
+//! CRC-8                       8  polynomial 0x07 over the header bytes
+//! ```
+//!
+//! Residuals are Rice coded (see [`codec::rice`](crate::codec::rice)), split into `2^order`
+//! partitions that each carry their own Rice parameter or an escape to fixed-width samples.
+
+use crate::codec::rice::read_rice_signed;
+use crate::cursor::BitCursor;
+use crate::error::BitReadWriteError;
+use crate::tee::TeeBitReader;
+use crate::traits::{BitRead, sign_extend};
+use std::io::Result;
+
+/// The 14-bit frame sync code
+pub const SYNC_CODE: u64 = 0x3FFE;
+
+/// CRC-8 with polynomial `x^8 + x^2 + x + 1` (0x07) and initial value 0, the frame header CRC
+pub fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |crc, &byte| {
+        (0..8).fold(crc ^ byte, |crc, _| {
+            if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// CRC-16 with polynomial `x^16 + x^15 + x^2 + 1` (0x8005) and initial value 0, the frame
+/// footer CRC over the whole frame
+pub fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, &byte| {
+        (0..8).fold(crc ^ ((byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// Reads a frame or sample number coded like UTF-8, extended to 7 bytes (36 bits)
+///
+/// # Errors
+/// - `InvalidData` for an invalid first byte or continuation byte
+/// - Any error of the reader
+pub fn read_coded_number<R>(reader: &mut R) -> Result<u64>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let first = reader.read_bits(8)? as u8;
+    let ones = first.leading_ones();
+    if ones == 0 {
+        return Ok(first as u64);
+    }
+    if ones == 1 || ones == 8 {
+        return Err(BitReadWriteError::InvalidCodedNumber(first).into());
+    }
+    let mut value = (first & (0x7F >> ones)) as u64;
+    for _ in 1..ones {
+        let byte = reader.read_bits(8)?;
+        if byte & 0xC0 != 0x80 {
+            return Err(BitReadWriteError::InvalidCodedNumber(byte as u8).into());
+        }
+        value = (value << 6) | (byte & 0x3F);
+    }
+    Ok(value)
+}
+
+/// How the channels of a frame are coded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelAssignment {
+    /// 1-8 independently coded channels
+    Independent(u8),
+    /// Left and side (left minus right)
+    LeftSide,
+    /// Side and right
+    RightSide,
+    /// Mid (average) and side
+    MidSide,
+}
+
+impl ChannelAssignment {
+    /// Number of audio channels
+    pub fn channels(&self) -> u8 {
+        match *self {
+            ChannelAssignment::Independent(count) => count,
+            _ => 2,
+        }
+    }
+}
+
+/// A decoded frame header
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameHeader {
+    /// Variable block size: `number` counts samples instead of frames
+    pub variable_block_size: bool,
+    /// Samples per channel in the frame
+    pub block_size: u32,
+    /// Sample rate in Hz, `None` when it is taken from STREAMINFO
+    pub sample_rate: Option<u32>,
+    pub channel_assignment: ChannelAssignment,
+    /// Bits per sample, `None` when it is taken from STREAMINFO
+    pub bits_per_sample: Option<u32>,
+    /// Frame number, or the number of the first sample with a variable block size
+    pub number: u64,
+    pub crc8: u8,
+}
+
+impl FrameHeader {
+    /// Reads a frame header, starting at its sync code, and checks its CRC-8
+    ///
+    /// # Errors
+    /// - `InvalidData` for a wrong sync code, a reserved bit or code, or a CRC mismatch
+    /// - Any error of the reader, e.g. `UnexpectedEof` when the stream is truncated
+    pub fn read<R>(reader: &mut R) -> Result<Self>
+    where
+        R: BitRead<Output = u64> + ?Sized,
+    {
+        // 记录读取的字节，最后对它们计算 CRC-8
+        let mut tee = TeeBitReader::new(&mut *reader, BitCursor::new(Vec::with_capacity(16)));
+        if tee.read_bits(14)? != SYNC_CODE {
+            return Err(BitReadWriteError::FlacSyncNotFound.into());
+        }
+        if tee.read_bits(1)? != 0 {
+            return Err(BitReadWriteError::FlacReservedBit.into());
+        }
+        let variable_block_size = tee.read_bits(1)? == 1;
+        let block_size_code = tee.read_bits(4)?;
+        let sample_rate_code = tee.read_bits(4)?;
+        let channel_assignment = match tee.read_bits(4)? {
+            n @ 0..=7 => ChannelAssignment::Independent(n as u8 + 1),
+            8 => ChannelAssignment::LeftSide,
+            9 => ChannelAssignment::RightSide,
+            10 => ChannelAssignment::MidSide,
+            n => return Err(BitReadWriteError::ReservedChannelAssignment(n as u8).into()),
+        };
+        let bits_per_sample = match tee.read_bits(3)? {
+            0 => None,
+            1 => Some(8),
+            2 => Some(12),
+            4 => Some(16),
+            5 => Some(20),
+            6 => Some(24),
+            7 => Some(32),
+            n => return Err(BitReadWriteError::ReservedSampleSize(n as u8).into()),
+        };
+        if tee.read_bits(1)? != 0 {
+            return Err(BitReadWriteError::FlacReservedBit.into());
+        }
+        let number = read_coded_number(&mut tee)?;
+        if !variable_block_size && number >> 31 != 0 {
+            return Err(BitReadWriteError::FlacFrameNumberTooLarge(number).into());
+        }
+
+        let block_size = match block_size_code {
+            0 => return Err(BitReadWriteError::ReservedBlockSize.into()),
+            1 => 192,
+            2..=5 => 576 << (block_size_code - 2),
+            6 => tee.read_bits(8)? as u32 + 1,
+            7 => tee.read_bits(16)? as u32 + 1,
+            _ => 256 << (block_size_code - 8),
+        };
+        let sample_rate = match sample_rate_code {
+            0 => None,
+            1 => Some(88_200),
+            2 => Some(176_400),
+            3 => Some(192_000),
+            4 => Some(8_000),
+            5 => Some(16_000),
+            6 => Some(22_050),
+            7 => Some(24_000),
+            8 => Some(32_000),
+            9 => Some(44_100),
+            10 => Some(48_000),
+            11 => Some(96_000),
+            12 => Some(tee.read_bits(8)? as u32 * 1000),
+            13 => Some(tee.read_bits(16)? as u32),
+            14 => Some(tee.read_bits(16)? as u32 * 10),
+            _ => return Err(BitReadWriteError::InvalidSampleRate(sample_rate_code as u8).into()),
+        };
+
+        let (_, recorded) = tee.into_inner();
+        let crc = reader.read_bits(8)? as u8;
+        let expected = crc8(recorded.get_ref());
+        if expected != crc {
+            return Err(BitReadWriteError::FlacCrcMismatch {
+                expected,
+                actual: crc,
+            }
+            .into());
+        }
+        Ok(Self {
+            variable_block_size,
+            block_size,
+            sample_rate,
+            channel_assignment,
+            bits_per_sample,
+            number,
+            crc8: crc,
+        })
+    }
+}
+
+/// Reads the residual of a subframe with `block_size` samples and a predictor of
+/// `predictor_order`, which precedes the residual with that many warm-up samples
+///
+/// # Errors
+/// - `InvalidData` for a reserved coding method or a partition order that does not fit the
+///   block size and predictor order
+/// - Any error of the reader
+pub fn read_residual<R>(reader: &mut R, block_size: u32, predictor_order: u32) -> Result<Vec<i64>>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let parameter_bits = match reader.read_bits(2)? {
+        0 => 4,
+        1 => 5,
+        method => return Err(BitReadWriteError::ReservedResidualCoding(method as u8).into()),
+    };
+    let escape = (1 << parameter_bits) - 1;
+    let order = reader.read_bits(4)? as u32;
+    let partition_size = block_size >> order;
+    if partition_size << order != block_size || partition_size < predictor_order {
+        return Err(BitReadWriteError::InvalidPartitionOrder(order as u8).into());
+    }
+
+    let mut residual = Vec::with_capacity((block_size - predictor_order) as usize);
+    for partition in 0..1u32 << order {
+        let count = if partition == 0 {
+            partition_size - predictor_order
+        } else {
+            partition_size
+        };
+        let parameter = reader.read_bits(parameter_bits)?;
+        if parameter == escape {
+            // 转义分区：5 位的样本位宽，之后是定长补码样本
+            let bits = reader.read_bits(5)? as usize;
+            for _ in 0..count {
+                let value = if bits == 0 {
+                    0
+                } else {
+                    sign_extend(reader.read_bits(bits)?, bits)
+                };
+                residual.push(value);
+            }
+        } else {
+            for _ in 0..count {
+                residual.push(read_rice_signed(reader, parameter as usize)?);
+            }
+        }
+    }
+    Ok(residual)
+}
@@ -0,0 +1,651 @@
+//! H.264 / AVC sequence and picture parameter sets, ITU-T H.264 sections 7.3.2.1 and 7.3.2.2
+//!
+//! Parameter sets arrive as NAL units: a one-byte header (`forbidden_zero_bit`, `nal_ref_idc`,
+//! `nal_unit_type`) followed by the payload with emulation prevention bytes inserted (`00 00 03`
+//! wherever the payload would otherwise contain `00 00 00` to `00 00 03`). [`rbsp_unescape`]
+//! removes them; [`Sps::from_nal`] and [`Pps::from_nal`] do the whole job for a NAL unit
+//! without start code.
+//!
+//! Most fields are Exp-Golomb coded and read with [`BitReadExt::read_ue`] /
+//! [`BitReadExt::read_se`]. Values the parser does not keep (scaling lists, slice group maps,
+//! HRD parameters) are skipped, and VUI parsing stops after the timing information.
+
+use crate::cursor::BitCursor;
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitReadExt};
+use std::io::Result;
+
+/// `nal_unit_type` of a sequence parameter set
+pub const NAL_TYPE_SPS: u8 = 7;
+/// `nal_unit_type` of a picture parameter set
+pub const NAL_TYPE_PPS: u8 = 8;
+
+/// Sample aspect ratios by `aspect_ratio_idc` 1-16 (Table E-1)
+pub const SAMPLE_ASPECT_RATIOS: [(u16, u16); 16] = [
+    (1, 1),
+    (12, 11),
+    (10, 11),
+    (16, 11),
+    (40, 33),
+    (24, 11),
+    (20, 11),
+    (32, 11),
+    (80, 33),
+    (18, 11),
+    (15, 11),
+    (64, 33),
+    (160, 99),
+    (4, 3),
+    (3, 2),
+    (2, 1),
+];
+
+/// `aspect_ratio_idc` signalling an explicit `sar_width` / `sar_height`
+pub const EXTENDED_SAR: u8 = 255;
+
+/// Profiles whose SPS carries chroma format, bit depths and scaling matrices
+const HIGH_PROFILES: [u8; 13] = [100, 110, 122, 244, 44, 83, 86, 118, 128, 138, 139, 134, 135];
+
+/// 读取 ue(v) 并检查它能放进 u32
+fn read_ue32<R>(reader: &mut R) -> Result<u32>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let value = reader.read_ue()?;
+    u32::try_from(value).map_err(|_| BitReadWriteError::UeOutOfRange(value).into())
+}
+
+/// 读取 se(v) 并检查它能放进 i32
+fn read_se32<R>(reader: &mut R) -> Result<i32>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let value = reader.read_se()?;
+    i32::try_from(value).map_err(|_| BitReadWriteError::SeOutOfRange(value).into())
+}
+
+fn read_flag<R>(reader: &mut R) -> Result<bool>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    Ok(reader.read_bits(1)? == 1)
+}
+
+/// 跳过 scaling_list()：只消费 delta_scale，不保存矩阵
+fn skip_scaling_list<R>(reader: &mut R, size: usize) -> Result<()>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let mut next_scale = 8i64;
+    for _ in 0..size {
+        if next_scale != 0 {
+            let delta = reader.read_se()?;
+            if !(-128..=127).contains(&delta) {
+                return Err(BitReadWriteError::DeltaScaleOutOfRange(delta).into());
+            }
+            next_scale = (next_scale + delta).rem_euclid(256);
+        }
+        if next_scale == 0 {
+            // useDefaultScalingMatrixFlag 或后续全部沿用 lastScale，不再有语法元素
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// 跳过 count 个 scaling_list_present_flag 及其对应的列表
+fn skip_scaling_matrix<R>(reader: &mut R, count: usize) -> Result<()>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    for i in 0..count {
+        if read_flag(reader)? {
+            skip_scaling_list(reader, if i < 6 { 16 } else { 64 })?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes the emulation prevention bytes of a NAL unit payload, turning it into the RBSP
+///
+/// Every `03` following two zero bytes is dropped, and the zero count restarts after it.
+pub fn rbsp_unescape(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut zeros = 0;
+    for &byte in data {
+        if zeros >= 2 && byte == 0x03 {
+            zeros = 0;
+            continue;
+        }
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+        out.push(byte);
+    }
+    out
+}
+
+/// Inserts emulation prevention bytes into an RBSP, the inverse of [`rbsp_unescape`]
+pub fn rbsp_escape(rbsp: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(rbsp.len() + rbsp.len() / 64);
+    let mut zeros = 0;
+    for &byte in rbsp {
+        if zeros >= 2 && byte <= 0x03 {
+            out.push(0x03);
+            zeros = 0;
+        }
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+        out.push(byte);
+    }
+    out
+}
+
+/// 检查 NAL 头并返回去掉转义字节的 RBSP
+fn nal_payload(nal: &[u8], nal_type: u8) -> Result<Vec<u8>> {
+    match nal.first() {
+        None => Err(BitReadWriteError::EmptyNalUnit.into()),
+        Some(&header) if header & 0x80 != 0 => Err(BitReadWriteError::NalForbiddenBit.into()),
+        Some(&header) if header & 0x1F != nal_type => Err(BitReadWriteError::UnexpectedNalType {
+            expected: nal_type,
+            found: header & 0x1F,
+        }
+        .into()),
+        Some(_) => Ok(rbsp_unescape(&nal[1..])),
+    }
+}
+
+/// `pic_order_cnt_type` of an SPS with the fields that depend on it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PicOrderCount {
+    Type0 {
+        log2_max_pic_order_cnt_lsb_minus4: u32,
+    },
+    Type1 {
+        delta_pic_order_always_zero: bool,
+        offset_for_non_ref_pic: i32,
+        offset_for_top_to_bottom_field: i32,
+        offset_for_ref_frame: Vec<i32>,
+    },
+    Type2,
+}
+
+/// Frame cropping offsets, in crop units of the chroma format (see [`Sps::width`])
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameCropping {
+    pub left: u32,
+    pub right: u32,
+    pub top: u32,
+    pub bottom: u32,
+}
+
+/// The leading part of the VUI parameters, up to and including the timing information
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Vui {
+    /// `aspect_ratio_idc`, when the aspect ratio is signalled
+    pub aspect_ratio_idc: Option<u8>,
+    /// `sar_width` / `sar_height`, present when `aspect_ratio_idc` is [`EXTENDED_SAR`]
+    pub sar: Option<(u16, u16)>,
+    /// `overscan_appropriate_flag`, when overscan information is present
+    pub overscan_appropriate: Option<bool>,
+    pub video_signal_type: Option<VideoSignalType>,
+    /// `chroma_sample_loc_type_top_field` and `_bottom_field`
+    pub chroma_sample_loc: Option<(u32, u32)>,
+    pub timing: Option<Timing>,
+}
+
+/// Video signal type of the VUI
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VideoSignalType {
+    /// 0 component, 1 PAL, 2 NTSC, 3 SECAM, 4 MAC, 5 unspecified
+    pub video_format: u8,
+    pub video_full_range: bool,
+    /// `colour_primaries`, `transfer_characteristics` and `matrix_coefficients`, if present
+    pub colour_description: Option<(u8, u8, u8)>,
+}
+
+/// Timing information of the VUI
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timing {
+    pub num_units_in_tick: u32,
+    pub time_scale: u32,
+    pub fixed_frame_rate: bool,
+}
+
+impl Vui {
+    /// Sample aspect ratio from the table or the explicit `sar`, `None` if unspecified
+    pub fn sample_aspect_ratio(&self) -> Option<(u16, u16)> {
+        match self.aspect_ratio_idc? {
+            EXTENDED_SAR => self.sar,
+            idc @ 1..=16 => Some(SAMPLE_ASPECT_RATIOS[idc as usize - 1]),
+            _ => None,
+        }
+    }
+
+    /// Frame rate `time_scale / (2 * num_units_in_tick)`, `None` without timing information
+    pub fn frame_rate(&self) -> Option<f64> {
+        let timing = self.timing?;
+        if timing.num_units_in_tick == 0 {
+            return None;
+        }
+        Some(timing.time_scale as f64 / (2.0 * timing.num_units_in_tick as f64))
+    }
+
+    fn read<R>(reader: &mut R) -> Result<Self>
+    where
+        R: BitRead<Output = u64> + ?Sized,
+    {
+        let mut vui = Vui::default();
+        if read_flag(reader)? {
+            let idc = reader.read_bits(8)? as u8;
+            vui.aspect_ratio_idc = Some(idc);
+            if idc == EXTENDED_SAR {
+                vui.sar = Some((reader.read_bits(16)? as u16, reader.read_bits(16)? as u16));
+            }
+        }
+        if read_flag(reader)? {
+            vui.overscan_appropriate = Some(read_flag(reader)?);
+        }
+        if read_flag(reader)? {
+            let video_format = reader.read_bits(3)? as u8;
+            let video_full_range = read_flag(reader)?;
+            let colour_description = if read_flag(reader)? {
+                let primaries = reader.read_bits(8)? as u8;
+                let transfer = reader.read_bits(8)? as u8;
+                let matrix = reader.read_bits(8)? as u8;
+                Some((primaries, transfer, matrix))
+            } else {
+                None
+            };
+            vui.video_signal_type = Some(VideoSignalType {
+                video_format,
+                video_full_range,
+                colour_description,
+            });
+        }
+        if read_flag(reader)? {
+            vui.chroma_sample_loc = Some((read_ue32(reader)?, read_ue32(reader)?));
+        }
+        if read_flag(reader)? {
+            vui.timing = Some(Timing {
+                num_units_in_tick: reader.read_bits(32)? as u32,
+                time_scale: reader.read_bits(32)? as u32,
+                fixed_frame_rate: read_flag(reader)?,
+            });
+        }
+        Ok(vui)
+    }
+}
+
+/// A decoded sequence parameter set
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sps {
+    /// 66 Baseline, 77 Main, 88 Extended, 100 High, 110 High 10, 122 High 4:2:2, ...
+    pub profile_idc: u8,
+    /// `constraint_set0_flag` in the MSB through `constraint_set5_flag`, then 2 reserved bits
+    pub constraint_set_flags: u8,
+    /// Level times 10, e.g. 31 for level 3.1
+    pub level_idc: u8,
+    pub seq_parameter_set_id: u32,
+    /// 0 monochrome, 1 4:2:0, 2 4:2:2, 3 4:4:4; 1 unless signalled by a high profile
+    pub chroma_format_idc: u32,
+    pub separate_colour_plane: bool,
+    pub bit_depth_luma_minus8: u32,
+    pub bit_depth_chroma_minus8: u32,
+    pub qpprime_y_zero_transform_bypass: bool,
+    /// Whether scaling matrices were signalled (their contents are skipped)
+    pub seq_scaling_matrix_present: bool,
+    pub log2_max_frame_num_minus4: u32,
+    pub pic_order_cnt: PicOrderCount,
+    pub max_num_ref_frames: u32,
+    pub gaps_in_frame_num_value_allowed: bool,
+    pub pic_width_in_mbs_minus1: u32,
+    pub pic_height_in_map_units_minus1: u32,
+    pub frame_mbs_only: bool,
+    pub mb_adaptive_frame_field: bool,
+    pub direct_8x8_inference: bool,
+    pub frame_cropping: Option<FrameCropping>,
+    pub vui: Option<Vui>,
+}
+
+impl Sps {
+    /// Reads an SPS from its RBSP, the payload after the NAL header with emulation prevention
+    /// bytes removed
+    ///
+    /// # Errors
+    /// - `InvalidData` for values out of the range the parser supports
+    /// - Any error of the reader, e.g. `UnexpectedEof` when the stream is truncated
+    pub fn read<R>(reader: &mut R) -> Result<Self>
+    where
+        R: BitRead<Output = u64> + ?Sized,
+    {
+        let profile_idc = reader.read_bits(8)? as u8;
+        let constraint_set_flags = reader.read_bits(8)? as u8;
+        let level_idc = reader.read_bits(8)? as u8;
+        let seq_parameter_set_id = read_ue32(reader)?;
+
+        let mut chroma_format_idc = 1;
+        let mut separate_colour_plane = false;
+        let mut bit_depth_luma_minus8 = 0;
+        let mut bit_depth_chroma_minus8 = 0;
+        let mut qpprime_y_zero_transform_bypass = false;
+        let mut seq_scaling_matrix_present = false;
+        if HIGH_PROFILES.contains(&profile_idc) {
+            chroma_format_idc = read_ue32(reader)?;
+            if chroma_format_idc > 3 {
+                return Err(BitReadWriteError::ChromaFormatOutOfRange(chroma_format_idc).into());
+            }
+            if chroma_format_idc == 3 {
+                separate_colour_plane = read_flag(reader)?;
+            }
+            bit_depth_luma_minus8 = read_ue32(reader)?;
+            bit_depth_chroma_minus8 = read_ue32(reader)?;
+            qpprime_y_zero_transform_bypass = read_flag(reader)?;
+            seq_scaling_matrix_present = read_flag(reader)?;
+            if seq_scaling_matrix_present {
+                skip_scaling_matrix(reader, if chroma_format_idc == 3 { 12 } else { 8 })?;
+            }
+        }
+
+        let log2_max_frame_num_minus4 = read_ue32(reader)?;
+        let pic_order_cnt = match reader.read_ue()? {
+            0 => PicOrderCount::Type0 {
+                log2_max_pic_order_cnt_lsb_minus4: read_ue32(reader)?,
+            },
+            1 => {
+                let delta_pic_order_always_zero = read_flag(reader)?;
+                let offset_for_non_ref_pic = read_se32(reader)?;
+                let offset_for_top_to_bottom_field = read_se32(reader)?;
+                let cycle = reader.read_ue()?;
+                if cycle > 255 {
+                    return Err(BitReadWriteError::RefFrameCycleOutOfRange(cycle).into());
+                }
+                let offset_for_ref_frame = (0..cycle)
+                    .map(|_| read_se32(reader))
+                    .collect::<Result<_>>()?;
+                PicOrderCount::Type1 {
+                    delta_pic_order_always_zero,
+                    offset_for_non_ref_pic,
+                    offset_for_top_to_bottom_field,
+                    offset_for_ref_frame,
+                }
+            }
+            2 => PicOrderCount::Type2,
+            n => return Err(BitReadWriteError::PicOrderCntTypeOutOfRange(n).into()),
+        };
+
+        let max_num_ref_frames = read_ue32(reader)?;
+        let gaps_in_frame_num_value_allowed = read_flag(reader)?;
+        let pic_width_in_mbs_minus1 = read_ue32(reader)?;
+        let pic_height_in_map_units_minus1 = read_ue32(reader)?;
+        let frame_mbs_only = read_flag(reader)?;
+        let mb_adaptive_frame_field = !frame_mbs_only && read_flag(reader)?;
+        let direct_8x8_inference = read_flag(reader)?;
+        let frame_cropping = if read_flag(reader)? {
+            Some(FrameCropping {
+                left: read_ue32(reader)?,
+                right: read_ue32(reader)?,
+                top: read_ue32(reader)?,
+                bottom: read_ue32(reader)?,
+            })
+        } else {
+            None
+        };
+        let vui = if read_flag(reader)? {
+            Some(Vui::read(reader)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            profile_idc,
+            constraint_set_flags,
+            level_idc,
+            seq_parameter_set_id,
+            chroma_format_idc,
+            separate_colour_plane,
+            bit_depth_luma_minus8,
+            bit_depth_chroma_minus8,
+            qpprime_y_zero_transform_bypass,
+            seq_scaling_matrix_present,
+            log2_max_frame_num_minus4,
+            pic_order_cnt,
+            max_num_ref_frames,
+            gaps_in_frame_num_value_allowed,
+            pic_width_in_mbs_minus1,
+            pic_height_in_map_units_minus1,
+            frame_mbs_only,
+            mb_adaptive_frame_field,
+            direct_8x8_inference,
+            frame_cropping,
+            vui,
+        })
+    }
+
+    /// Parses an SPS NAL unit, header included and without start code
+    ///
+    /// # Errors
+    /// - `InvalidInput` if the NAL unit is not an SPS
+    /// - `InvalidData` if the header or the SPS is invalid
+    /// - `UnexpectedEof` if the NAL unit is truncated
+    pub fn from_nal(nal: &[u8]) -> Result<Self> {
+        let rbsp = nal_payload(nal, NAL_TYPE_SPS)?;
+        Self::read(&mut BitCursor::new(&rbsp[..]))
+    }
+
+    /// `ChromaArrayType`: 0 when the colour planes are coded separately
+    fn chroma_array_type(&self) -> u32 {
+        if self.separate_colour_plane {
+            0
+        } else {
+            self.chroma_format_idc
+        }
+    }
+
+    /// Crop units in luma samples, `(CropUnitX, CropUnitY)`
+    fn crop_units(&self) -> (u32, u32) {
+        let frame_factor = 2 - self.frame_mbs_only as u32;
+        match self.chroma_array_type() {
+            0 => (1, frame_factor),
+            1 => (2, 2 * frame_factor),
+            2 => (2, frame_factor),
+            _ => (1, frame_factor),
+        }
+    }
+
+    /// Width of the decoded frames in luma samples, after cropping
+    pub fn width(&self) -> u32 {
+        let width = self
+            .pic_width_in_mbs_minus1
+            .saturating_add(1)
+            .saturating_mul(16);
+        match self.frame_cropping {
+            Some(crop) => {
+                let (unit, _) = self.crop_units();
+                width.saturating_sub(unit.saturating_mul(crop.left.saturating_add(crop.right)))
+            }
+            None => width,
+        }
+    }
+
+    /// Height of the decoded frames in luma samples, after cropping
+    pub fn height(&self) -> u32 {
+        let map_units = self.pic_height_in_map_units_minus1.saturating_add(1);
+        let height = map_units.saturating_mul(16 * (2 - self.frame_mbs_only as u32));
+        match self.frame_cropping {
+            Some(crop) => {
+                let (_, unit) = self.crop_units();
+                height.saturating_sub(unit.saturating_mul(crop.top.saturating_add(crop.bottom)))
+            }
+            None => height,
+        }
+    }
+
+    /// Bit depth of the luma samples, 8 unless signalled by a high profile
+    pub fn bit_depth_luma(&self) -> u32 {
+        self.bit_depth_luma_minus8.saturating_add(8)
+    }
+
+    /// Bit depth of the chroma samples, 8 unless signalled by a high profile
+    pub fn bit_depth_chroma(&self) -> u32 {
+        self.bit_depth_chroma_minus8.saturating_add(8)
+    }
+}
+
+/// A decoded picture parameter set
+///
+/// Slice group maps (FMO, Baseline profile only) are skipped; only their count and map type
+/// are kept.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pps {
+    pub pic_parameter_set_id: u32,
+    pub seq_parameter_set_id: u32,
+    /// CABAC when set, CAVLC otherwise
+    pub entropy_coding_mode: bool,
+    pub bottom_field_pic_order_in_frame_present: bool,
+    pub num_slice_groups_minus1: u32,
+    /// `slice_group_map_type`, present with more than one slice group
+    pub slice_group_map_type: Option<u32>,
+    pub num_ref_idx_l0_default_active_minus1: u32,
+    pub num_ref_idx_l1_default_active_minus1: u32,
+    pub weighted_pred: bool,
+    pub weighted_bipred_idc: u8,
+    pub pic_init_qp_minus26: i32,
+    pub pic_init_qs_minus26: i32,
+    pub chroma_qp_index_offset: i32,
+    pub deblocking_filter_control_present: bool,
+    pub constrained_intra_pred: bool,
+    pub redundant_pic_cnt_present: bool,
+    /// High profile extension; false when absent
+    pub transform_8x8_mode: bool,
+    /// Whether scaling matrices were signalled (their contents are skipped)
+    pub pic_scaling_matrix_present: bool,
+    /// Equal to `chroma_qp_index_offset` when the high profile extension is absent
+    pub second_chroma_qp_index_offset: i32,
+}
+
+impl Pps {
+    /// Parses a PPS from its RBSP, the payload after the NAL header with emulation prevention
+    /// bytes removed
+    ///
+    /// The high profile extension at the end is detected from the position of the RBSP stop
+    /// bit, so the whole RBSP is needed. `chroma_format_idc` comes from the SPS the PPS refers
+    /// to and only matters when it signals 8x8 scaling matrices.
+    ///
+    /// # Errors
+    /// - `InvalidData` for values out of the range the parser supports or a missing stop bit
+    /// - `UnexpectedEof` if the RBSP is truncated
+    pub fn from_rbsp(rbsp: &[u8], chroma_format_idc: u32) -> Result<Self> {
+        // 最后一个置位比特是 rbsp_stop_one_bit，more_rbsp_data() 即当前位置在它之前
+        let stop_bit = rbsp
+            .iter()
+            .rposition(|&b| b != 0)
+            .map(|i| i as u64 * 8 + 7 - rbsp[i].trailing_zeros() as u64)
+            .ok_or(BitReadWriteError::MissingStopBit)?;
+        let reader = &mut BitCursor::new(rbsp);
+
+        let pic_parameter_set_id = read_ue32(reader)?;
+        let seq_parameter_set_id = read_ue32(reader)?;
+        let entropy_coding_mode = read_flag(reader)?;
+        let bottom_field_pic_order_in_frame_present = read_flag(reader)?;
+        let num_slice_groups_minus1 = read_ue32(reader)?;
+        let slice_group_map_type = if num_slice_groups_minus1 > 0 {
+            if num_slice_groups_minus1 > 7 {
+                return Err(
+                    BitReadWriteError::SliceGroupCountOutOfRange(num_slice_groups_minus1).into(),
+                );
+            }
+            let map_type = read_ue32(reader)?;
+            match map_type {
+                0 => {
+                    for _ in 0..=num_slice_groups_minus1 {
+                        reader.read_ue()?;
+                    }
+                }
+                2 => {
+                    for _ in 0..num_slice_groups_minus1 {
+                        reader.read_ue()?;
+                        reader.read_ue()?;
+                    }
+                }
+                3..=5 => {
+                    reader.read_bits(1)?;
+                    reader.read_ue()?;
+                }
+                6 => {
+                    let bits = (32 - num_slice_groups_minus1.leading_zeros()) as usize;
+                    // 截断的数据会在读到末尾时报错，不必先检查 map_units
+                    for _ in 0..=reader.read_ue()? {
+                        reader.read_bits(bits)?;
+                    }
+                }
+                1 => {}
+                n => return Err(BitReadWriteError::SliceGroupMapTypeOutOfRange(n).into()),
+            }
+            Some(map_type)
+        } else {
+            None
+        };
+
+        let num_ref_idx_l0_default_active_minus1 = read_ue32(reader)?;
+        let num_ref_idx_l1_default_active_minus1 = read_ue32(reader)?;
+        let weighted_pred = read_flag(reader)?;
+        let weighted_bipred_idc = reader.read_bits(2)? as u8;
+        let pic_init_qp_minus26 = read_se32(reader)?;
+        let pic_init_qs_minus26 = read_se32(reader)?;
+        let chroma_qp_index_offset = read_se32(reader)?;
+        let deblocking_filter_control_present = read_flag(reader)?;
+        let constrained_intra_pred = read_flag(reader)?;
+        let redundant_pic_cnt_present = read_flag(reader)?;
+
+        let mut transform_8x8_mode = false;
+        let mut pic_scaling_matrix_present = false;
+        let mut second_chroma_qp_index_offset = chroma_qp_index_offset;
+        if reader.position() < stop_bit {
+            transform_8x8_mode = read_flag(reader)?;
+            pic_scaling_matrix_present = read_flag(reader)?;
+            if pic_scaling_matrix_present {
+                let lists_8x8 = match (transform_8x8_mode, chroma_format_idc) {
+                    (false, _) => 0,
+                    (true, 3) => 6,
+                    (true, _) => 2,
+                };
+                skip_scaling_matrix(reader, 6 + lists_8x8)?;
+            }
+            second_chroma_qp_index_offset = read_se32(reader)?;
+        }
+
+        Ok(Self {
+            pic_parameter_set_id,
+            seq_parameter_set_id,
+            entropy_coding_mode,
+            bottom_field_pic_order_in_frame_present,
+            num_slice_groups_minus1,
+            slice_group_map_type,
+            num_ref_idx_l0_default_active_minus1,
+            num_ref_idx_l1_default_active_minus1,
+            weighted_pred,
+            weighted_bipred_idc,
+            pic_init_qp_minus26,
+            pic_init_qs_minus26,
+            chroma_qp_index_offset,
+            deblocking_filter_control_present,
+            constrained_intra_pred,
+            redundant_pic_cnt_present,
+            transform_8x8_mode,
+            pic_scaling_matrix_present,
+            second_chroma_qp_index_offset,
+        })
+    }
+
+    /// Parses a PPS NAL unit, header included and without start code
+    ///
+    /// # Errors
+    /// - `InvalidInput` if the NAL unit is not a PPS
+    /// - Otherwise as [`Pps::from_rbsp`]
+    pub fn from_nal(nal: &[u8], chroma_format_idc: u32) -> Result<Self> {
+        Self::from_rbsp(&nal_payload(nal, NAL_TYPE_PPS)?, chroma_format_idc)
+    }
+
+    /// Initial QP of the slices, `26 + pic_init_qp_minus26`
+    pub fn pic_init_qp(&self) -> i32 {
+        self.pic_init_qp_minus26.saturating_add(26)
+    }
+}
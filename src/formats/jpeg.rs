@@ -0,0 +1,239 @@
+//! JPEG entropy-coded segments, ITU-T T.81 section F.1.2.3 and annex B.1.1.5
+//!
+//! Inside a scan every `0xFF` data byte is followed by a stuffed `0x00`, so any other
+//! `0xFF xx` pair is a marker: a restart marker `RSTn` between intervals, or the marker that
+//! ends the scan (usually `EOI`). [`EntropyReader`] strips the stuffing and stops at markers,
+//! so a Huffman decoder built on [`BitRead`] / [`BitPeek`] can consume the scan data
+//! directly. [`EntropyWriter`] does the reverse for encoders.
+
+use crate::error::{BitReadWriteError, check_bit_count};
+use crate::traits::{BitPeek, BitRead, BitWrite};
+use std::io::{ErrorKind, Result};
+
+/// Start of image
+pub const SOI: u8 = 0xD8;
+/// End of image
+pub const EOI: u8 = 0xD9;
+/// Start of scan
+pub const SOS: u8 = 0xDA;
+/// First restart marker; `RSTn` is `RST0 + n` for n in 0..8
+pub const RST0: u8 = 0xD0;
+
+/// Whether `marker` is one of the restart markers `RST0`-`RST7`
+pub fn is_restart(marker: u8) -> bool {
+    (RST0..RST0 + 8).contains(&marker)
+}
+
+fn mask(n: u32) -> u64 {
+    if n >= 64 { u64::MAX } else { (1 << n) - 1 }
+}
+
+/// Reads the data of an entropy-coded segment, removing stuffed zero bytes and stopping at
+/// the next marker
+///
+/// The reader pulls whole bytes from the inner reader, up to 8 ahead of the data it returns,
+/// but never past a marker: once one is found the inner reader is positioned right after it.
+/// Reading beyond the end of the segment fails with `UnexpectedEof`, while peeking beyond it
+/// pads with zero bits, like libjpeg, so table-driven Huffman decoders can look up the last
+/// codes of a segment.
+#[derive(Debug)]
+pub struct EntropyReader<R> {
+    inner: R,
+    acc: u64,
+    bits: u32,
+    marker: Option<u8>,
+    eof: bool,
+}
+
+impl<R: BitRead<Output = u64>> EntropyReader<R> {
+    /// Wraps `inner`, positioned at the first byte of the scan data (right after the SOS
+    /// header)
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            acc: 0,
+            bits: 0,
+            marker: None,
+            eof: false,
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the inner reader; data bits read ahead are discarded
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// The marker that ended the current segment, once the reader has reached it
+    pub fn marker(&self) -> Option<u8> {
+        self.marker
+    }
+
+    /// Number of data bits left before the marker, if the marker has been reached
+    pub fn remaining_bits(&self) -> Option<u32> {
+        self.marker.map(|_| self.bits)
+    }
+
+    /// Continues with the next segment after a restart marker
+    ///
+    /// Discards the padding bits of the current segment, reading ahead to the marker if needed,
+    /// and returns the marker.
+    ///
+    /// # Errors
+    /// - `InvalidData` if the segment ends with a marker other than `RSTn`
+    /// - `UnexpectedEof` if the stream ends before a marker
+    pub fn restart(&mut self) -> Result<u8> {
+        while self.marker.is_none() && !self.eof {
+            self.bits = 0;
+            self.fill()?;
+        }
+        match self.marker {
+            Some(marker) if is_restart(marker) => {
+                self.marker = None;
+                self.bits = 0;
+                Ok(marker)
+            }
+            Some(marker) => Err(BitReadWriteError::NotRestartMarker(marker).into()),
+            None => Err(BitReadWriteError::UnexpectedEof.into()),
+        }
+    }
+
+    /// 按字节补充到 56 位以上；遇到标记或内部 EOF 时停止
+    fn fill(&mut self) -> Result<()> {
+        while self.bits <= 56 && self.marker.is_none() && !self.eof {
+            let byte = match self.inner.read_bits(8) {
+                Ok(byte) => byte,
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+                    self.eof = true;
+                    break;
+                }
+                Err(e) => return Err(e),
+            };
+            if byte == 0xFF {
+                // 0xFF 之后：0x00 是填充，0xFF 是标记前的填充字节，其余是标记
+                let mut next = self.inner.read_bits(8)?;
+                while next == 0xFF {
+                    next = self.inner.read_bits(8)?;
+                }
+                if next != 0 {
+                    self.marker = Some(next as u8);
+                    break;
+                }
+            }
+            self.acc = (self.acc << 8) | byte;
+            self.bits += 8;
+        }
+        Ok(())
+    }
+
+    /// 取出接下来的 n 位，不消费；数据不足时返回 None
+    fn peek_available(&mut self, n: usize) -> Result<Option<u64>> {
+        check_bit_count(n, 64)?;
+        if (self.bits as usize) < n {
+            self.fill()?;
+        }
+        let n = n as u32;
+        if self.bits < n {
+            return Ok(None);
+        }
+        Ok(Some((self.acc >> (self.bits - n)) & mask(n)))
+    }
+}
+
+impl<R: BitRead<Output = u64>> BitRead for EntropyReader<R> {
+    type Output = u64;
+
+    /// Reads `n` data bits of the current segment
+    ///
+    /// # Errors
+    /// `UnexpectedEof` if the segment ends (at a marker or the end of the stream) first
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        match self.peek_available(n)? {
+            Some(value) => {
+                self.bits -= n as u32;
+                Ok(value)
+            }
+            None => Err(BitReadWriteError::UnexpectedEof.into()),
+        }
+    }
+}
+
+impl<R: BitRead<Output = u64>> BitPeek for EntropyReader<R> {
+    type Output = u64;
+
+    /// Peeks at the next `n` data bits, padding with zeros past the end of the segment
+    fn peek_bits(&mut self, n: usize) -> Result<u64> {
+        if let Some(value) = self.peek_available(n)? {
+            return Ok(value);
+        }
+        let rest = self.acc & mask(self.bits);
+        Ok(rest.checked_shl(n as u32 - self.bits).unwrap_or(0))
+    }
+}
+
+/// Writes entropy-coded data, stuffing a `0x00` after every `0xFF` data byte
+#[derive(Debug)]
+pub struct EntropyWriter<W> {
+    inner: W,
+    byte: u8,
+    bits: u32, // byte 中已有的位数
+}
+
+impl<W: BitWrite> EntropyWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            byte: 0,
+            bits: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the inner writer; a partial byte is dropped, call [`finish`](Self::finish) first
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn push_bit(&mut self, bit: u64) -> Result<()> {
+        self.byte = (self.byte << 1) | bit as u8;
+        self.bits += 1;
+        if self.bits == 8 {
+            self.inner.write_bits(self.byte as u64, 8)?;
+            if self.byte == 0xFF {
+                self.inner.write_bits(0, 8)?;
+            }
+            (self.byte, self.bits) = (0, 0);
+        }
+        Ok(())
+    }
+
+    /// Pads the last byte with 1 bits, as T.81 requires at the end of a segment
+    pub fn finish(&mut self) -> Result<()> {
+        while self.bits != 0 {
+            self.push_bit(1)?;
+        }
+        Ok(())
+    }
+
+    /// Ends the segment with [`finish`](Self::finish) and writes `0xFF marker`
+    pub fn write_marker(&mut self, marker: u8) -> Result<()> {
+        self.finish()?;
+        self.inner.write_bits(0xFF00 | marker as u64, 16)
+    }
+}
+
+impl<W: BitWrite> BitWrite for EntropyWriter<W> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        check_bit_count(n, 64)?;
+        for i in (0..n).rev() {
+            self.push_bit((value >> i) & 1)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+//! Parsers for common media and container formats (requires the `formats` feature)
+//!
+//! Each module reads (and where useful writes) its headers through the crate's
+//! [`BitRead`](crate::traits::BitRead) / [`BitWrite`](crate::traits::BitWrite) traits, so they
+//! work on any reader or writer and double as worked examples. These formats are all MSB-first:
+//! use big-endian readers and writers.
+
+pub mod adts;
+pub mod av1;
+pub mod flac;
+pub mod h264;
+pub mod jpeg;
+pub mod mpegts;
+pub mod vpx;
@@ -0,0 +1,415 @@
+//! MPEG transport stream packets, ISO/IEC 13818-1 section 2.4.3
+//!
+//! ## Packet layout
+//! ```text
+//! sync_byte                       8  0x47
+//! transport_error_indicator       1
+//! payload_unit_start_indicator    1
+//! transport_priority              1
+//! PID                            13
+//! transport_scrambling_control    2
+//! adaptation_field_control        2  bit 1: adaptation field, bit 0: payload
+//! continuity_counter              4  incremented per packet with payload, per PID
+//! adaptation_field              ...  adaptation_field_length (8) + fields + stuffing
+//! payload                       ...  up to the 188th byte
+//! ```
+//!
+//! [`Packet::read`] parses one packet from any reader. [`PacketReader`] frames a byte stream
+//! into packets and resynchronizes on the sync byte after corruption, and
+//! [`ContinuityTracker`] checks the continuity counters of each PID.
+
+use crate::cursor::BitCursor;
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite};
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read, Result};
+
+/// Size of a transport stream packet in bytes
+pub const PACKET_SIZE: usize = 188;
+/// First byte of every packet
+pub const SYNC_BYTE: u8 = 0x47;
+/// PID of null (stuffing) packets
+pub const NULL_PID: u16 = 0x1FFF;
+
+/// The 4-byte header of a packet
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PacketHeader {
+    pub transport_error: bool,
+    pub payload_unit_start: bool,
+    pub transport_priority: bool,
+    /// Packet identifier (13 bits)
+    pub pid: u16,
+    /// 0 = not scrambled (2 bits)
+    pub scrambling_control: u8,
+    /// 1 = payload only, 2 = adaptation field only, 3 = both (2 bits)
+    pub adaptation_field_control: u8,
+    /// 4 bits
+    pub continuity_counter: u8,
+}
+
+impl PacketHeader {
+    /// Reads a header, starting at its sync byte
+    ///
+    /// # Errors
+    /// - `InvalidData` if the sync byte is wrong
+    /// - Any error of the reader
+    pub fn read<R>(reader: &mut R) -> Result<Self>
+    where
+        R: BitRead<Output = u64> + ?Sized,
+    {
+        let sync = reader.read_bits(8)? as u8;
+        if sync != SYNC_BYTE {
+            return Err(BitReadWriteError::TsSyncNotFound(sync).into());
+        }
+        Ok(Self {
+            transport_error: reader.read_bits(1)? == 1,
+            payload_unit_start: reader.read_bits(1)? == 1,
+            transport_priority: reader.read_bits(1)? == 1,
+            pid: reader.read_bits(13)? as u16,
+            scrambling_control: reader.read_bits(2)? as u8,
+            adaptation_field_control: reader.read_bits(2)? as u8,
+            continuity_counter: reader.read_bits(4)? as u8,
+        })
+    }
+
+    /// Writes the header; fields wider than their slot are masked by the writer
+    pub fn write<W: BitWrite + ?Sized>(&self, writer: &mut W) -> Result<()> {
+        writer.write_bits(SYNC_BYTE as u64, 8)?;
+        writer.write_bits(self.transport_error as u64, 1)?;
+        writer.write_bits(self.payload_unit_start as u64, 1)?;
+        writer.write_bits(self.transport_priority as u64, 1)?;
+        writer.write_bits(self.pid as u64, 13)?;
+        writer.write_bits(self.scrambling_control as u64, 2)?;
+        writer.write_bits(self.adaptation_field_control as u64, 2)?;
+        writer.write_bits(self.continuity_counter as u64, 4)
+    }
+
+    pub fn has_adaptation_field(&self) -> bool {
+        self.adaptation_field_control & 0b10 != 0
+    }
+
+    pub fn has_payload(&self) -> bool {
+        self.adaptation_field_control & 0b01 != 0
+    }
+}
+
+/// A program clock reference: a 33-bit 90 kHz base and a 9-bit 27 MHz extension
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Pcr {
+    pub base: u64,
+    pub extension: u16,
+}
+
+impl Pcr {
+    /// 从 6 字节字段读取：base 33 位，保留 6 位，extension 9 位
+    fn read<R>(reader: &mut R) -> Result<Self>
+    where
+        R: BitRead<Output = u64> + ?Sized,
+    {
+        let base = reader.read_bits(33)?;
+        reader.read_bits(6)?;
+        let extension = reader.read_bits(9)? as u16;
+        Ok(Self { base, extension })
+    }
+
+    /// The clock value in 27 MHz ticks, `base * 300 + extension`
+    pub fn ticks(&self) -> u64 {
+        self.base * 300 + self.extension as u64
+    }
+}
+
+/// The optional fields of an adaptation field
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AdaptationField {
+    pub discontinuity: bool,
+    pub random_access: bool,
+    pub elementary_stream_priority: bool,
+    pub pcr: Option<Pcr>,
+    pub opcr: Option<Pcr>,
+    pub splice_countdown: Option<i8>,
+    pub private_data: Option<Vec<u8>>,
+    /// Bytes of the adaptation field extension after its length byte, left unparsed
+    pub extension: Option<Vec<u8>>,
+}
+
+impl AdaptationField {
+    /// Reads an adaptation field, including its length byte and stuffing
+    ///
+    /// Returns the field and its total length in bytes (`adaptation_field_length + 1`).
+    ///
+    /// # Errors
+    /// - `InvalidData` if the flagged fields do not fit in `adaptation_field_length`
+    /// - Any error of the reader
+    pub fn read<R>(reader: &mut R) -> Result<(Self, usize)>
+    where
+        R: BitRead<Output = u64> + ?Sized,
+    {
+        let length = reader.read_bits(8)? as usize;
+        let mut field = Self::default();
+        if length == 0 {
+            return Ok((field, 1));
+        }
+        field.discontinuity = reader.read_bits(1)? == 1;
+        field.random_access = reader.read_bits(1)? == 1;
+        field.elementary_stream_priority = reader.read_bits(1)? == 1;
+        let flags = reader.read_bits(5)?;
+        let mut used = 1;
+        let mut take = |n: usize| {
+            used += n;
+            if used > length {
+                Err(BitReadWriteError::AdaptationFieldOverrun(length))
+            } else {
+                Ok(())
+            }
+        };
+        if flags & 0b10000 != 0 {
+            take(6)?;
+            field.pcr = Some(Pcr::read(reader)?);
+        }
+        if flags & 0b01000 != 0 {
+            take(6)?;
+            field.opcr = Some(Pcr::read(reader)?);
+        }
+        if flags & 0b00100 != 0 {
+            take(1)?;
+            field.splice_countdown = Some(reader.read_bits(8)? as u8 as i8);
+        }
+        if flags & 0b00010 != 0 {
+            take(1)?;
+            let len = reader.read_bits(8)? as usize;
+            take(len)?;
+            field.private_data = Some(read_bytes(reader, len)?);
+        }
+        if flags & 0b00001 != 0 {
+            take(1)?;
+            let len = reader.read_bits(8)? as usize;
+            take(len)?;
+            field.extension = Some(read_bytes(reader, len)?);
+        }
+        // 剩余的是填充字节
+        read_bytes(reader, length - used)?;
+        Ok((field, length + 1))
+    }
+}
+
+fn read_bytes<R>(reader: &mut R, len: usize) -> Result<Vec<u8>>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    (0..len).map(|_| Ok(reader.read_bits(8)? as u8)).collect()
+}
+
+/// A parsed transport stream packet
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Packet {
+    pub header: PacketHeader,
+    pub adaptation_field: Option<AdaptationField>,
+    pub payload: Vec<u8>,
+}
+
+impl Packet {
+    /// Reads one 188-byte packet
+    ///
+    /// # Errors
+    /// - `InvalidData` if the sync byte is wrong or the adaptation field does not fit
+    /// - Any error of the reader, e.g. `UnexpectedEof` for a truncated packet
+    pub fn read<R>(reader: &mut R) -> Result<Self>
+    where
+        R: BitRead<Output = u64> + ?Sized,
+    {
+        let header = PacketHeader::read(reader)?;
+        let mut left = PACKET_SIZE - 4;
+        let adaptation_field = if header.has_adaptation_field() {
+            let (field, len) = AdaptationField::read(reader)?;
+            if len > left {
+                return Err(BitReadWriteError::AdaptationFieldTooLong(len).into());
+            }
+            left -= len;
+            Some(field)
+        } else {
+            None
+        };
+        let rest = read_bytes(reader, left)?;
+        let payload = if header.has_payload() {
+            rest
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            header,
+            adaptation_field,
+            payload,
+        })
+    }
+
+    /// Parses a packet from exactly [`PACKET_SIZE`] bytes
+    pub fn parse(bytes: &[u8; PACKET_SIZE]) -> Result<Self> {
+        Self::read(&mut BitCursor::new(&bytes[..]))
+    }
+}
+
+/// Offset of the first sync byte in `data` that is followed by sync bytes at the next
+/// `confirm` packet boundaries that lie within `data`
+pub fn find_sync(data: &[u8], confirm: usize) -> Option<usize> {
+    (0..data.len()).find(|&start| {
+        data[start] == SYNC_BYTE
+            && (1..=confirm)
+                .map(|i| start + i * PACKET_SIZE)
+                .take_while(|&pos| pos < data.len())
+                .all(|pos| data[pos] == SYNC_BYTE)
+    })
+}
+
+/// Frames a byte stream into 188-byte packets, resynchronizing after lost sync
+///
+/// When a packet does not start with the sync byte, the reader drops bytes up to the next
+/// position where the sync byte repeats at the following packet boundaries (as far as they
+/// have been buffered) and counts the dropped bytes in [`skipped_bytes`](Self::skipped_bytes).
+pub struct PacketReader<R> {
+    inner: R,
+    buf: Vec<u8>,
+    confirm: usize,
+    skipped: u64,
+    eof: bool,
+}
+
+impl<R: Read> PacketReader<R> {
+    /// Wraps `inner`; a new sync position must be confirmed by 2 following packets
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::with_capacity(PACKET_SIZE * 4),
+            confirm: 2,
+            skipped: 0,
+            eof: false,
+        }
+    }
+
+    /// Number of following sync bytes that must confirm a sync position found by resync
+    pub fn confirm(mut self, packets: usize) -> Self {
+        self.confirm = packets;
+        self
+    }
+
+    /// Total bytes dropped while resynchronizing
+    pub fn skipped_bytes(&self) -> u64 {
+        self.skipped
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// 把缓冲补到至少 len 字节，源结束时返回 false
+    fn fill(&mut self, len: usize) -> Result<bool> {
+        let mut chunk = [0u8; PACKET_SIZE];
+        while self.buf.len() < len && !self.eof {
+            match self.inner.read(&mut chunk) {
+                Ok(0) => self.eof = true,
+                Ok(count) => self.buf.extend_from_slice(&chunk[..count]),
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(self.buf.len() >= len)
+    }
+
+    /// Returns the bytes of the next packet, or `None` at the end of the stream
+    ///
+    /// A trailing partial packet is dropped and counted as skipped.
+    pub fn next_packet(&mut self) -> Result<Option<[u8; PACKET_SIZE]>> {
+        loop {
+            if !self.fill(PACKET_SIZE)? {
+                self.skipped += self.buf.len() as u64;
+                self.buf.clear();
+                return Ok(None);
+            }
+            if self.buf[0] == SYNC_BYTE {
+                let mut packet = [0u8; PACKET_SIZE];
+                packet.copy_from_slice(&self.buf[..PACKET_SIZE]);
+                self.buf.drain(..PACKET_SIZE);
+                return Ok(Some(packet));
+            }
+            // 失步：在 confirm 个包的窗口内寻找新的同步位置
+            self.fill(PACKET_SIZE * (self.confirm + 2))?;
+            let skip = find_sync(&self.buf[1..], self.confirm).map_or(self.buf.len(), |i| i + 1);
+            self.skipped += skip as u64;
+            self.buf.drain(..skip);
+        }
+    }
+
+    /// Reads and parses the next packet, or `None` at the end of the stream
+    pub fn read_packet(&mut self) -> Result<Option<Packet>> {
+        self.next_packet()?
+            .map(|bytes| Packet::parse(&bytes))
+            .transpose()
+    }
+}
+
+/// Result of a continuity check for one packet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Continuity {
+    /// First packet seen on its PID
+    First,
+    /// The counter advanced as expected, or the packet carries no payload
+    Continuous,
+    /// Same counter as the previous packet with payload, a permitted duplicate
+    Duplicate,
+    /// Packets were lost or reordered; `expected` is the counter that should have come
+    Discontinuity { expected: u8 },
+}
+
+/// Tracks the continuity counter of every PID
+#[derive(Clone, Debug, Default)]
+pub struct ContinuityTracker {
+    last: HashMap<u16, u8>,
+}
+
+impl ContinuityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks `header` against the previous packet of its PID and records it
+    ///
+    /// Null packets are not tracked, and a packet whose adaptation field sets the
+    /// `discontinuity_indicator` restarts tracking for its PID.
+    pub fn check(&mut self, header: &PacketHeader, discontinuity_indicator: bool) -> Continuity {
+        if header.pid == NULL_PID {
+            return Continuity::Continuous;
+        }
+        let cc = header.continuity_counter & 0x0F;
+        let Some(last) = self.last.insert(header.pid, cc) else {
+            return Continuity::First;
+        };
+        if discontinuity_indicator {
+            return Continuity::First;
+        }
+        if !header.has_payload() {
+            // 无负载的包不递增计数器
+            self.last.insert(header.pid, last);
+            return if cc == last {
+                Continuity::Continuous
+            } else {
+                Continuity::Discontinuity { expected: last }
+            };
+        }
+        let expected = (last + 1) & 0x0F;
+        if cc == expected {
+            Continuity::Continuous
+        } else if cc == last {
+            Continuity::Duplicate
+        } else {
+            Continuity::Discontinuity { expected }
+        }
+    }
+
+    /// Checks a parsed packet, taking the discontinuity indicator from its adaptation field
+    pub fn check_packet(&mut self, packet: &Packet) -> Continuity {
+        let indicator = packet
+            .adaptation_field
+            .as_ref()
+            .is_some_and(|field| field.discontinuity);
+        self.check(&packet.header, indicator)
+    }
+}
@@ -0,0 +1,290 @@
+//! The boolean entropy coder of VP8 (RFC 6386 section 7) and VP9 (section 9.2 of its
+//! specification)
+//!
+//! Both codecs code most of their frame headers and all of their residual data as a sequence
+//! of booleans, each with an 8-bit probability (out of 256) that it is `false`. A
+//! [`BoolDecoder`] reads one such partition from any [`BitRead`] source, consuming exactly the
+//! partition's bytes, and itself implements [`BitRead`] for the literal `L(n)` fields of the
+//! headers (booleans with probability 128, most significant bit first). [`BoolEncoder`]
+//! produces partitions for the decoder.
+
+use crate::error::BitReadWriteError;
+use crate::traits::BitRead;
+use std::io::{Error, Result};
+
+/// Width of the decoder's value window in bits
+const VALUE_BITS: i32 = 64;
+
+/// Arithmetic decoder for one VP8 / VP9 boolean-coded partition
+pub struct BoolDecoder<R> {
+    reader: R,
+    /// Bytes of the partition not yet pulled from the reader
+    remaining: u64,
+    /// 左对齐的窗口：最高 8 位与 split 比较，其下还有 count 位有效数据
+    value: u64,
+    count: i32,
+    range: u32,
+}
+
+impl<R: BitRead<Output = u64>> BoolDecoder<R> {
+    /// Starts decoding a VP8 partition of `size` bytes at the current position of `reader`
+    ///
+    /// Reading past the end of the partition yields zero bits, as in libvpx.
+    ///
+    /// # Errors
+    /// Any error of the reader while loading the first bytes
+    pub fn new(reader: R, size: u64) -> Result<Self> {
+        let mut decoder = Self {
+            reader,
+            remaining: size,
+            value: 0,
+            count: -8,
+            range: 255,
+        };
+        decoder.fill()?;
+        Ok(decoder)
+    }
+
+    /// Starts decoding a VP9 partition of `size` bytes and checks its leading marker bit
+    ///
+    /// # Errors
+    /// - `InvalidData` if the partition is empty or the marker bit is set
+    /// - Any error of the reader while loading the first bytes
+    pub fn new_vp9(reader: R, size: u64) -> Result<Self> {
+        if size == 0 {
+            return Err(BitReadWriteError::EmptyBoolPartition.into());
+        }
+        let mut decoder = Self::new(reader, size)?;
+        if decoder.read_bool(128)? {
+            return Err(BitReadWriteError::BoolMarkerSet.into());
+        }
+        Ok(decoder)
+    }
+
+    /// 从分区补充字节到窗口；分区结束后补零
+    fn fill(&mut self) -> Result<()> {
+        let mut shift = VALUE_BITS - 8 - (self.count + 8);
+        while shift >= 0 {
+            let byte = if self.remaining > 0 {
+                self.remaining -= 1;
+                self.reader.read_bits(8)?
+            } else {
+                0
+            };
+            self.count += 8;
+            self.value |= byte << shift;
+            shift -= 8;
+        }
+        Ok(())
+    }
+
+    /// Decodes one boolean whose probability of being `false` is `prob / 256`
+    pub fn read_bool(&mut self, prob: u8) -> Result<bool> {
+        let split = 1 + (((self.range - 1) * prob as u32) >> 8);
+        if self.count < 0 {
+            self.fill()?;
+        }
+        let big_split = (split as u64) << (VALUE_BITS - 8);
+        let bit = if self.value >= big_split {
+            self.range -= split;
+            self.value -= big_split;
+            true
+        } else {
+            self.range = split;
+            false
+        };
+        // 归一化：range 回到 128..=255
+        let shift = self.range.leading_zeros() - 24;
+        self.range <<= shift;
+        self.value <<= shift;
+        self.count -= shift as i32;
+        Ok(bit)
+    }
+
+    /// Decodes an `n`-bit (1-64) literal, most significant bit first, each with probability 128
+    pub fn read_literal(&mut self, n: usize) -> Result<u64> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        let mut value = 0;
+        for _ in 0..n {
+            value = (value << 1) | self.read_bool(128)? as u64;
+        }
+        Ok(value)
+    }
+
+    /// Decodes a value with a VP8 / VP9 token tree
+    ///
+    /// `tree` holds pairs of entries; a positive entry is the index of the next pair, and a
+    /// leaf `-v` (or `0` for value 0) ends the walk with `v`. The boolean at pair `i` is
+    /// decoded with `probs[i / 2]`.
+    ///
+    /// # Errors
+    /// - `InvalidInput` if the walk leaves `tree` or `probs`
+    /// - Any error of the reader
+    pub fn read_tree(&mut self, tree: &[i8], probs: &[u8]) -> Result<u8> {
+        let mut index = 0usize;
+        loop {
+            let prob = *probs.get(index / 2).ok_or_else(bad_tree)?;
+            let next = *tree
+                .get(index + self.read_bool(prob)? as usize)
+                .ok_or_else(bad_tree)?;
+            if next <= 0 {
+                return Ok(next.unsigned_abs());
+            }
+            if next as usize <= index {
+                // 只允许向后跳转，避免环
+                return Err(bad_tree());
+            }
+            index = next as usize;
+        }
+    }
+
+    /// Skips the unread bytes of the partition and returns the reader, positioned right after
+    /// the partition
+    pub fn finish(mut self) -> Result<R> {
+        for _ in 0..self.remaining {
+            self.reader.read_bits(8)?;
+        }
+        Ok(self.reader)
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+}
+
+fn bad_tree() -> Error {
+    Error::from(BitReadWriteError::InvalidTokenTree)
+}
+
+impl<R: BitRead<Output = u64>> BitRead for BoolDecoder<R> {
+    type Output = u64;
+
+    /// Reads an `L(n)` literal, see [`BoolDecoder::read_literal`]
+    fn read_bits(&mut self, n: usize) -> Result<Self::Output> {
+        self.read_literal(n)
+    }
+}
+
+/// Arithmetic encoder producing VP8 / VP9 boolean-coded partitions
+///
+/// Carries propagate into bytes already produced, so the encoder builds the partition in
+/// memory; write the bytes returned by [`BoolEncoder::finish`] to the stream.
+#[derive(Clone, Debug)]
+pub struct BoolEncoder {
+    output: Vec<u8>,
+    range: u32,
+    bottom: u32,
+    bit_count: i32,
+}
+
+impl Default for BoolEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoolEncoder {
+    pub fn new() -> Self {
+        Self {
+            output: Vec::new(),
+            range: 255,
+            bottom: 0,
+            bit_count: 24,
+        }
+    }
+
+    /// 进位：把已输出的尾部 0xFF 置零并给前一个字节加一
+    fn add_one_to_output(&mut self) {
+        for byte in self.output.iter_mut().rev() {
+            if *byte == 0xFF {
+                *byte = 0;
+            } else {
+                *byte += 1;
+                return;
+            }
+        }
+    }
+
+    /// Encodes `value` with probability `prob / 256` of being `false`
+    pub fn write_bool(&mut self, value: bool, prob: u8) {
+        let split = 1 + (((self.range - 1) * prob as u32) >> 8);
+        if value {
+            self.bottom = self.bottom.wrapping_add(split);
+            self.range -= split;
+        } else {
+            self.range = split;
+        }
+        while self.range < 128 {
+            self.range <<= 1;
+            if self.bottom & (1 << 31) != 0 {
+                self.add_one_to_output();
+            }
+            self.bottom <<= 1;
+            self.bit_count -= 1;
+            if self.bit_count == 0 {
+                self.output.push((self.bottom >> 24) as u8);
+                self.bottom &= (1 << 24) - 1;
+                self.bit_count = 8;
+            }
+        }
+    }
+
+    /// Encodes the low `n` bits (1-64) of `value` as a literal, most significant bit first
+    pub fn write_literal(&mut self, value: u64, n: usize) -> Result<()> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        for i in (0..n).rev() {
+            self.write_bool((value >> i) & 1 == 1, 128);
+        }
+        Ok(())
+    }
+
+    /// Encodes `value` with a token tree, the inverse of [`BoolDecoder::read_tree`]
+    ///
+    /// # Errors
+    /// - `InvalidInput` if `value` is not a leaf of `tree` or the probabilities are too few
+    pub fn write_tree(&mut self, tree: &[i8], probs: &[u8], value: u8) -> Result<()> {
+        let mut path = Vec::new();
+        if !find_leaf(tree, 0, value, &mut path) {
+            return Err(BitReadWriteError::NotInTokenTree(value).into());
+        }
+        for &(index, bit) in &path {
+            let prob = *probs.get(index / 2).ok_or_else(bad_tree)?;
+            self.write_bool(bit, prob);
+        }
+        Ok(())
+    }
+
+    /// Flushes the coder state and returns the partition bytes
+    pub fn finish(mut self) -> Vec<u8> {
+        // RFC 6386 的 flush：补齐 32 个 prob 128 的 0
+        for _ in 0..32 {
+            self.write_bool(false, 128);
+        }
+        self.output
+    }
+}
+
+/// 深度优先查找叶子 value，记录经过的 (下标, 分支)
+fn find_leaf(tree: &[i8], index: usize, value: u8, path: &mut Vec<(usize, bool)>) -> bool {
+    for bit in [false, true] {
+        let Some(&next) = tree.get(index + bit as usize) else {
+            return false;
+        };
+        path.push((index, bit));
+        let found = if next <= 0 {
+            next.unsigned_abs() == value
+        } else {
+            // 只向后跳转，避免环
+            next as usize > index && find_leaf(tree, next as usize, value, path)
+        };
+        if found {
+            return true;
+        }
+        path.pop();
+    }
+    false
+}
@@ -0,0 +1,41 @@
+//! Exponential-Golomb codes, as used by H.264 / H.265 headers for `ue(v)` and `se(v)` fields
+//!
+//! An unsigned code for `k` is `M` zero bits, a one bit and the low `M` bits of `k + 1`, where
+//! `M` is the number of bits of `k + 1` minus one: `0` is `1`, `1` is `010`, `4` is `00101`.
+//! Signed values are mapped onto unsigned codes as `0, 1, -1, 2, -2, ...`. Use
+//! [`BitReadExt::read_ue`] / [`BitReadExt::read_se`] and [`BitWriteExt::write_ue`] /
+//! [`BitWriteExt::write_se`] to read and write them directly.
+//!
+//! [`BitReadExt::read_ue`]: crate::traits::BitReadExt::read_ue
+//! [`BitReadExt::read_se`]: crate::traits::BitReadExt::read_se
+//! [`BitWriteExt::write_ue`]: crate::traits::BitWriteExt::write_ue
+//! [`BitWriteExt::write_se`]: crate::traits::BitWriteExt::write_se
+
+/// Longest run of leading zeros accepted by the readers; longer codes do not fit in a `u64`
+pub const MAX_LEADING_ZEROS: usize = 63;
+
+/// Maps an unsigned code number to its signed value: `0, 1, 2, 3, 4` to `0, 1, -1, 2, -2`
+///
+/// `u64::MAX`, which no reader returns, wraps to `i64::MIN`.
+pub fn ue_to_se(code: u64) -> i64 {
+    if code & 1 == 1 {
+        (code / 2 + 1) as i64
+    } else {
+        -((code / 2) as i64)
+    }
+}
+
+/// Maps a signed value to its unsigned code number, or `None` for `i64::MIN`
+pub fn se_to_ue(value: i64) -> Option<u64> {
+    if value > 0 {
+        Some(value as u64 * 2 - 1)
+    } else {
+        value.unsigned_abs().checked_mul(2)
+    }
+}
+
+/// Length in bits of the unsigned code of `value`, or `None` for `u64::MAX`
+pub fn ue_len(value: u64) -> Option<usize> {
+    let code = value.checked_add(1)?;
+    Some(2 * (63 - code.leading_zeros() as usize) + 1)
+}
@@ -0,0 +1,24 @@
+//! Reflected binary Gray code, as stored by rotary encoders and some sensors
+//!
+//! Successive values differ in exactly one bit. Use [`BitReadExt::read_gray_bits`] /
+//! [`BitWriteExt::write_gray_bits`] to read and write Gray-coded fields directly.
+//!
+//! [`BitReadExt::read_gray_bits`]: crate::traits::BitReadExt::read_gray_bits
+//! [`BitWriteExt::write_gray_bits`]: crate::traits::BitWriteExt::write_gray_bits
+
+/// Converts a binary value to its Gray code
+pub fn to_gray(value: u64) -> u64 {
+    value ^ (value >> 1)
+}
+
+/// Converts a Gray code back to the binary value
+pub fn from_gray(gray: u64) -> u64 {
+    // 前缀异或：每一位等于其自身及所有更高位的异或
+    let mut value = gray;
+    let mut shift = 1;
+    while shift < 64 {
+        value ^= value >> shift;
+        shift <<= 1;
+    }
+    value
+}
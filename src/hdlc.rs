@@ -0,0 +1,257 @@
+//! HDLC bit stuffing as [`BitWrite`] / [`BitRead`] adapters
+//!
+//! [`StuffingWriter`] inserts a 0 after every five consecutive 1s of data, so the flag
+//! `0111_1110` and the abort sequence (seven or more 1s) can only appear where written on
+//! purpose. [`UnstuffingReader`] removes those zeros again and recognizes flags and aborts,
+//! which is enough to build HDLC, AX.25 or similar frame codecs on this crate.
+//!
+//! Bits of each value go in most significant bit first, as for the other adapters. Links that
+//! send each byte LSB first (HDLC, AX.25) can write and read through
+//! [`write_bits_reversed`](crate::traits::BitWriteExt::write_bits_reversed) /
+//! [`read_bits_reversed`](crate::traits::BitReadExt::read_bits_reversed).
+
+use crate::error::{BitReadWriteError, check_bit_count};
+use crate::traits::{BitRead, BitWrite};
+use std::collections::VecDeque;
+use std::io::{ErrorKind, Result};
+
+/// The HDLC flag sequence that opens and closes every frame
+pub const FLAG: u8 = 0x7E;
+
+/// Inserts a 0 after five consecutive 1s of data; flags and aborts are written unstuffed
+#[derive(Debug)]
+pub struct StuffingWriter<W> {
+    inner: W,
+    ones: u32, // 连续 1 的个数
+}
+
+impl<W: BitWrite> StuffingWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, ones: 0 }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes the flag `0111_1110` without stuffing
+    pub fn write_flag(&mut self) -> Result<()> {
+        self.ones = 0;
+        self.inner.write_bits(FLAG as u64, 8)
+    }
+
+    /// Writes seven 1s without stuffing, aborting the current frame
+    pub fn write_abort(&mut self) -> Result<()> {
+        self.ones = 0;
+        self.inner.write_bits(0x7F, 7)
+    }
+
+    /// Writes `data` between two flags, each byte MSB first
+    pub fn write_frame(&mut self, data: &[u8]) -> Result<()> {
+        self.write_flag()?;
+        for &byte in data {
+            self.write_bits(byte as u64, 8)?;
+        }
+        self.write_flag()
+    }
+}
+
+impl<W: BitWrite> BitWrite for StuffingWriter<W> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        check_bit_count(n, 64)?;
+        // 64 个数据比特最多插入 12 个 0，攒满 64 比特就先写出
+        let mut out = 0u64;
+        let mut count = 0;
+        let mut push = |inner: &mut W, bit: bool| -> Result<()> {
+            out = (out << 1) | bit as u64;
+            count += 1;
+            if count == 64 {
+                inner.write_bits(out, 64)?;
+                (out, count) = (0, 0);
+            }
+            Ok(())
+        };
+        for i in (0..n).rev() {
+            let bit = (value >> i) & 1 == 1;
+            push(&mut self.inner, bit)?;
+            self.ones = if bit { self.ones + 1 } else { 0 };
+            if self.ones == 5 {
+                push(&mut self.inner, false)?;
+                self.ones = 0;
+            }
+        }
+        if count > 0 {
+            self.inner.write_bits(out, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// 原始比特流中解析出的符号
+enum Symbol {
+    Bit(bool),
+    Flag,
+    Abort,
+}
+
+/// Removes the zeros inserted by [`StuffingWriter`] and recognizes flags and aborts
+///
+/// The reader looks up to 8 bits ahead of the data it returns, so the inner reader is a
+/// little further along than the unstuffed data.
+#[derive(Debug)]
+pub struct UnstuffingReader<R> {
+    inner: R,
+    window: VecDeque<bool>, // 预读的原始比特
+    ones: u32,
+    eof: bool,
+}
+
+impl<R: BitRead<Output = u64>> UnstuffingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            window: VecDeque::with_capacity(8),
+            ones: 0,
+            eof: false,
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the inner reader; bits read ahead are discarded
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Skips bits up to and including the next flag
+    ///
+    /// # Errors
+    /// `UnexpectedEof` if the stream ends before a flag
+    pub fn find_flag(&mut self) -> Result<()> {
+        loop {
+            match self.next_symbol()? {
+                Some(Symbol::Flag) => return Ok(()),
+                Some(_) => {}
+                None => return Err(BitReadWriteError::UnexpectedEof.into()),
+            }
+        }
+    }
+
+    /// Reads the bytes of one frame up to its closing flag, assuming the opening flag has been
+    /// consumed (e.g. by [`find_flag`](Self::find_flag) or the previous frame)
+    ///
+    /// Flags directly after the opening flag are skipped, so idle fill between frames yields no
+    /// empty frames. The closing flag may open the next frame.
+    ///
+    /// # Errors
+    /// - `InvalidData` if the frame was aborted or its length is not a whole number of bytes
+    /// - `UnexpectedEof` if the stream ends before the closing flag
+    pub fn read_frame(&mut self) -> Result<Vec<u8>> {
+        let mut frame = Vec::new();
+        let (mut byte, mut bits) = (0u8, 0);
+        loop {
+            match self.next_symbol()? {
+                Some(Symbol::Bit(bit)) => {
+                    byte = (byte << 1) | bit as u8;
+                    bits += 1;
+                    if bits == 8 {
+                        frame.push(byte);
+                        (byte, bits) = (0, 0);
+                    }
+                }
+                Some(Symbol::Flag) if frame.is_empty() && bits == 0 => {}
+                Some(Symbol::Flag) if bits == 0 => return Ok(frame),
+                Some(Symbol::Flag) => {
+                    return Err(BitReadWriteError::HdlcPartialByte.into());
+                }
+                Some(Symbol::Abort) => {
+                    return Err(BitReadWriteError::HdlcAbort.into());
+                }
+                None => return Err(BitReadWriteError::UnexpectedEof.into()),
+            }
+        }
+    }
+
+    /// 预读到 8 个原始比特；内部 reader 读完时保留已有的比特
+    fn fill(&mut self) -> Result<()> {
+        while self.window.len() < 8 && !self.eof {
+            match self.inner.read_bits(1) {
+                Ok(bit) => self.window.push_back(bit == 1),
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => self.eof = true,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    fn next_symbol(&mut self) -> Result<Option<Symbol>> {
+        loop {
+            self.fill()?;
+            let Some(&first) = self.window.front() else {
+                return Ok(None);
+            };
+            // 填充后的数据里不会出现 01111110，只能是标志
+            if !first
+                && self.window.len() == 8
+                && self.window.range(1..7).all(|&b| b)
+                && !self.window[7]
+            {
+                self.window.clear();
+                self.ones = 0;
+                return Ok(Some(Symbol::Flag));
+            }
+            if self.ones == 5 {
+                // 前一个 1 已检查过后面的连续 1，这里只能是插入的 0
+                self.window.pop_front();
+                self.ones = 0;
+                continue;
+            }
+            let run = self.window.iter().take_while(|&&b| b).count() as u32;
+            if self.ones + run >= 6 {
+                // 连续 6 个以上的 1：中止，并跳过后面的 1（空闲线路）
+                while self.window.front() == Some(&true) {
+                    self.window.pop_front();
+                    self.fill()?;
+                }
+                self.ones = 0;
+                return Ok(Some(Symbol::Abort));
+            }
+            self.window.pop_front();
+            self.ones = if first { self.ones + 1 } else { 0 };
+            return Ok(Some(Symbol::Bit(first)));
+        }
+    }
+}
+
+impl<R: BitRead<Output = u64>> BitRead for UnstuffingReader<R> {
+    type Output = u64;
+
+    /// Reads `n` unstuffed data bits
+    ///
+    /// # Errors
+    /// - `InvalidData` if a flag or an abort sequence comes before `n` data bits
+    /// - `UnexpectedEof` if the stream ends first
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        check_bit_count(n, 64)?;
+        let mut value = 0u64;
+        for _ in 0..n {
+            match self.next_symbol()? {
+                Some(Symbol::Bit(bit)) => value = (value << 1) | bit as u64,
+                Some(Symbol::Flag) => {
+                    return Err(BitReadWriteError::UnexpectedHdlcFlag.into());
+                }
+                Some(Symbol::Abort) => {
+                    return Err(BitReadWriteError::HdlcAbort.into());
+                }
+                None => return Err(BitReadWriteError::UnexpectedEof.into()),
+            }
+        }
+        Ok(value)
+    }
+}
@@ -0,0 +1,299 @@
+//! Block and convolutional bit interleavers as [`BitWrite`] / [`BitRead`] adapters
+//!
+//! Interleaving spreads neighbouring bits apart on the channel so that a burst of errors turns
+//! into isolated errors that FEC can correct (as in DVB and DAB). The interleavers sit between
+//! the user and an inner writer; the matching deinterleavers sit between an inner reader and
+//! the user. As with [`ecc`](crate::ecc), the bits of each value go in most significant bit
+//! first, whatever the byte order of the inner reader/writer.
+//!
+//! - [`BlockInterleaver`] fills a `rows x cols` matrix row by row and sends it column by column.
+//! - [`ConvolutionalInterleaver`] (Forney) deals bits in turn to `branches` shift registers,
+//!   branch `i` delaying its bits by `i * delay` of its own turns. The deinterleaver uses the
+//!   reverse delays, so every bit comes out `branches * (branches - 1) * delay` bits late,
+//!   after that many zero bits (see [`ConvolutionalDeinterleaver::latency`]).
+
+use crate::error::check_bit_count;
+use crate::traits::{BitRead, BitWrite};
+use std::collections::VecDeque;
+use std::io::Result;
+
+/// 把比特依次写入 writer，每次最多合并 64 比特
+fn write_bool_bits<W: BitWrite + ?Sized>(
+    writer: &mut W,
+    bits: impl Iterator<Item = bool>,
+) -> Result<()> {
+    let mut value = 0u64;
+    let mut count = 0;
+    for bit in bits {
+        value = (value << 1) | bit as u64;
+        count += 1;
+        if count == 64 {
+            writer.write_bits(value, 64)?;
+            value = 0;
+            count = 0;
+        }
+    }
+    if count > 0 {
+        writer.write_bits(value, count)?;
+    }
+    Ok(())
+}
+
+// ------------------------------- Block ------------------------------- //
+
+/// Writes bits row by row into a `rows x cols` block and sends each full block column by column
+///
+/// A partial last block is padded with zeros by [`finish`](Self::finish) or
+/// [`into_inner`](Self::into_inner); nothing is written on drop.
+#[derive(Debug)]
+pub struct BlockInterleaver<W> {
+    inner: W,
+    rows: usize,
+    cols: usize,
+    block: Vec<bool>,
+}
+
+impl<W: BitWrite> BlockInterleaver<W> {
+    /// # Panics
+    /// Panics if `rows` or `cols` is zero
+    pub fn new(inner: W, rows: usize, cols: usize) -> Self {
+        assert!(rows > 0 && cols > 0, "interleaver block must not be empty");
+        Self {
+            inner,
+            rows,
+            cols,
+            block: Vec::with_capacity(rows * cols),
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Pads a partial block with zeros and sends it
+    ///
+    /// # Errors
+    /// Any error of the inner writer
+    pub fn finish(&mut self) -> Result<()> {
+        if !self.block.is_empty() {
+            self.block.resize(self.rows * self.cols, false);
+            self.send_block()?;
+        }
+        Ok(())
+    }
+
+    /// Calls [`finish`](Self::finish) and returns the inner writer
+    pub fn into_inner(mut self) -> Result<W> {
+        self.finish()?;
+        Ok(self.inner)
+    }
+
+    fn send_block(&mut self) -> Result<()> {
+        let (rows, cols) = (self.rows, self.cols);
+        let block = &self.block;
+        let column_major = (0..cols).flat_map(|c| (0..rows).map(move |r| block[r * cols + c]));
+        write_bool_bits(&mut self.inner, column_major)?;
+        self.block.clear();
+        Ok(())
+    }
+}
+
+impl<W: BitWrite> BitWrite for BlockInterleaver<W> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        check_bit_count(n, 64)?;
+        for i in (0..n).rev() {
+            self.block.push((value >> i) & 1 == 1);
+            if self.block.len() == self.rows * self.cols {
+                self.send_block()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads `rows x cols` blocks sent column by column and returns their bits row by row
+#[derive(Debug)]
+pub struct BlockDeinterleaver<R> {
+    inner: R,
+    rows: usize,
+    cols: usize,
+    block: Vec<bool>, // 按行存放
+    next: usize,      // 下一个要返回的比特在 block 中的下标
+}
+
+impl<R: BitRead<Output = u64>> BlockDeinterleaver<R> {
+    /// # Panics
+    /// Panics if `rows` or `cols` is zero
+    pub fn new(inner: R, rows: usize, cols: usize) -> Self {
+        assert!(rows > 0 && cols > 0, "interleaver block must not be empty");
+        Self {
+            inner,
+            rows,
+            cols,
+            block: vec![false; rows * cols],
+            next: rows * cols,
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the inner reader; unread bits of the current block are discarded
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn receive_block(&mut self) -> Result<()> {
+        for c in 0..self.cols {
+            for r in 0..self.rows {
+                self.block[r * self.cols + c] = self.inner.read_bits(1)? == 1;
+            }
+        }
+        self.next = 0;
+        Ok(())
+    }
+}
+
+impl<R: BitRead<Output = u64>> BitRead for BlockDeinterleaver<R> {
+    type Output = u64;
+
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        check_bit_count(n, 64)?;
+        let mut value = 0u64;
+        for _ in 0..n {
+            if self.next == self.block.len() {
+                self.receive_block()?;
+            }
+            value = (value << 1) | self.block[self.next] as u64;
+            self.next += 1;
+        }
+        Ok(value)
+    }
+}
+
+// ------------------------------- Convolutional ------------------------------- //
+
+/// 每个分支是一个预先填满零的移位寄存器：压入一个比特，同时移出最早的比特
+fn branches(delays: impl Iterator<Item = usize>) -> Vec<VecDeque<bool>> {
+    delays.map(|d| VecDeque::from(vec![false; d])).collect()
+}
+
+fn shift(branch: &mut VecDeque<bool>, bit: bool) -> bool {
+    branch.push_back(bit);
+    branch.pop_front().unwrap_or(bit)
+}
+
+/// Convolutional (Forney) interleaver: branch `i` of `branches` delays its bits by
+/// `i * delay` turns
+///
+/// Call [`finish`](Self::finish) to push the bits still inside the shift registers out to the
+/// inner writer; nothing is written on drop.
+#[derive(Debug)]
+pub struct ConvolutionalInterleaver<W> {
+    inner: W,
+    branches: Vec<VecDeque<bool>>,
+    next: usize,
+}
+
+impl<W: BitWrite> ConvolutionalInterleaver<W> {
+    /// # Panics
+    /// Panics if `branches` is zero
+    pub fn new(inner: W, branches: usize, delay: usize) -> Self {
+        assert!(branches > 0, "interleaver needs at least one branch");
+        Self {
+            inner,
+            branches: self::branches((0..branches).map(|i| i * delay)),
+            next: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Writes zero bits until every bit written so far has left the shift registers
+    ///
+    /// # Errors
+    /// Any error of the inner writer
+    pub fn finish(&mut self) -> Result<()> {
+        let longest = self.branches.iter().map(VecDeque::len).max().unwrap_or(0);
+        let mut padding = longest * self.branches.len();
+        while padding > 0 {
+            let n = padding.min(64);
+            self.write_bits(0, n)?;
+            padding -= n;
+        }
+        Ok(())
+    }
+
+    /// Calls [`finish`](Self::finish) and returns the inner writer
+    pub fn into_inner(mut self) -> Result<W> {
+        self.finish()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: BitWrite> BitWrite for ConvolutionalInterleaver<W> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        check_bit_count(n, 64)?;
+        let mut bits = Vec::with_capacity(n);
+        for i in (0..n).rev() {
+            bits.push(shift(&mut self.branches[self.next], (value >> i) & 1 == 1));
+            self.next = (self.next + 1) % self.branches.len();
+        }
+        write_bool_bits(&mut self.inner, bits.into_iter())
+    }
+}
+
+/// Inverse of [`ConvolutionalInterleaver`] with the same `branches` and `delay`: branch `i`
+/// delays its bits by `(branches - 1 - i) * delay` turns
+#[derive(Debug)]
+pub struct ConvolutionalDeinterleaver<R> {
+    inner: R,
+    branches: Vec<VecDeque<bool>>,
+    next: usize,
+}
+
+impl<R: BitRead<Output = u64>> ConvolutionalDeinterleaver<R> {
+    /// # Panics
+    /// Panics if `branches` is zero
+    pub fn new(inner: R, branches: usize, delay: usize) -> Self {
+        assert!(branches > 0, "interleaver needs at least one branch");
+        Self {
+            inner,
+            branches: self::branches((0..branches).map(|i| (branches - 1 - i) * delay)),
+            next: 0,
+        }
+    }
+
+    /// Returns the number of zero bits read before the first interleaved bit comes out
+    pub fn latency(&self) -> usize {
+        let count = self.branches.len();
+        count * self.branches.first().map_or(0, VecDeque::len)
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BitRead<Output = u64>> BitRead for ConvolutionalDeinterleaver<R> {
+    type Output = u64;
+
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        check_bit_count(n, 64)?;
+        let mut value = 0u64;
+        for _ in 0..n {
+            let bit = self.inner.read_bits(1)? == 1;
+            let bit = shift(&mut self.branches[self.next], bit);
+            self.next = (self.next + 1) % self.branches.len();
+            value = (value << 1) | bit as u64;
+        }
+        Ok(value)
+    }
+}
@@ -3,6 +3,67 @@ pub mod error;
 
 pub mod traits;
 
+pub mod cursor;
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+mod async_core;
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub mod async_reader;
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub mod async_writer;
+
+pub mod bcd;
+#[cfg(feature = "binrw")]
+pub mod binrw_adapter;
+pub mod bit_plane;
+#[cfg(feature = "serde")]
+pub mod bit_serde;
+pub mod bitfield;
+#[cfg(feature = "bitvec")]
+pub mod bitvec;
+#[cfg(feature = "bytes")]
+pub mod buf;
+#[cfg(feature = "can")]
+pub mod can;
+pub mod codec;
+pub mod copy;
+#[cfg(feature = "deku")]
+pub mod deku_adapter;
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod derive_support;
+pub mod dump;
+pub mod ecc;
 pub mod fast;
+pub mod fixed;
+pub mod float;
+#[cfg(feature = "formats")]
+pub mod formats;
+pub mod golomb;
+pub mod gray;
+pub mod hdlc;
+pub mod interleave;
+pub mod line_code;
+pub mod morton;
+#[cfg(feature = "nom")]
+pub mod nom_adapter;
+pub mod packing;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod parity;
+pub mod pcm;
+pub mod raw_video;
 pub mod reader;
+pub mod reverse;
+pub mod schema;
+pub mod scramble;
+pub mod string;
+pub mod tee;
+#[cfg(feature = "trace")]
+pub mod trace;
 pub mod writer;
+
+#[cfg(feature = "derive")]
+pub use bitio_derive::{BitRead, BitWrite};
+pub use copy::copy_bits;
+pub use error::{Error, Result};
@@ -0,0 +1,277 @@
+//! Manchester and NRZI line coding, as word-level functions and [`BitWrite`] / [`BitRead`]
+//! adapters
+//!
+//! The word-level functions ([`Manchester::encode`], [`Nrzi::decode`], …) work on whole `u64`
+//! words, so pipelines built on the fast readers and writers, which don't implement the
+//! traits, can decode a word returned by `read_bits_fast` directly. The adapters do the same
+//! for any reader/writer that implements the traits. Bits go in most significant bit first.
+
+use crate::error::{BitReadWriteError, check_bit_count};
+use crate::traits::{BitRead, BitWrite};
+use std::io::Result;
+
+const EVEN: u64 = 0x5555_5555_5555_5555;
+
+fn mask(n: usize) -> u64 {
+    if n == 64 { u64::MAX } else { (1u64 << n) - 1 }
+}
+
+/// 把 32 比特展开到 64 比特的偶数位上
+fn spread(value: u64) -> u64 {
+    let mut x = value & 0xFFFF_FFFF;
+    x = (x | (x << 16)) & 0x0000_FFFF_0000_FFFF;
+    x = (x | (x << 8)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    (x | (x << 1)) & EVEN
+}
+
+/// spread 的逆操作：收集偶数位
+fn compact(value: u64) -> u64 {
+    let mut x = value & EVEN;
+    x = (x | (x >> 1)) & 0x3333_3333_3333_3333;
+    x = (x | (x >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x >> 4)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x >> 8)) & 0x0000_FFFF_0000_FFFF;
+    (x | (x >> 16)) & 0xFFFF_FFFF
+}
+
+/// Manchester convention: which half-bit pair stands for a 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Manchester {
+    /// IEEE 802.3: 0 is sent as `10`, 1 as `01`
+    Ieee,
+    /// G. E. Thomas: 0 is sent as `01`, 1 as `10`
+    Thomas,
+}
+
+impl Manchester {
+    /// Encodes the low `n` bits of `value` (1..=32) into `2 * n` bits
+    ///
+    /// # Errors
+    /// `InvalidBitCount` if `n` is not in 1..=32
+    pub fn encode(self, value: u64, n: usize) -> Result<u64> {
+        check_bit_count(n, 32)?;
+        let ones = spread(value & mask(n));
+        let zeros = spread(!value & mask(n));
+        Ok(match self {
+            Manchester::Ieee => (zeros << 1) | ones,
+            Manchester::Thomas => (ones << 1) | zeros,
+        })
+    }
+
+    /// Decodes `n` data bits (1..=32) from the low `2 * n` bits of `raw`
+    ///
+    /// # Errors
+    /// - `InvalidBitCount` if `n` is not in 1..=32
+    /// - `InvalidData` if a half-bit pair is `00` or `11`
+    pub fn decode(self, raw: u64, n: usize) -> Result<u64> {
+        check_bit_count(n, 32)?;
+        let pairs = EVEN & mask(2 * n);
+        let (first, second) = ((raw >> 1) & pairs, raw & pairs);
+        if first ^ second != pairs {
+            return Err(BitReadWriteError::InvalidManchesterSymbol.into());
+        }
+        Ok(match self {
+            Manchester::Ieee => compact(second),
+            Manchester::Thomas => compact(first),
+        })
+    }
+}
+
+/// NRZI convention: which data bit toggles the line level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nrzi {
+    /// NRZ-M: a 1 toggles the level, a 0 keeps it
+    Mark,
+    /// NRZ-S (USB, HDLC): a 0 toggles the level, a 1 keeps it
+    Space,
+}
+
+impl Nrzi {
+    /// Encodes the low `n` bits of `value` (1..=64) starting from line level `level`
+    ///
+    /// The level after the word is the lowest bit of the result.
+    ///
+    /// # Errors
+    /// `InvalidBitCount` if `n` is not in 1..=64
+    pub fn encode(self, value: u64, n: usize, level: bool) -> Result<u64> {
+        check_bit_count(n, 64)?;
+        let mut toggles = match self {
+            Nrzi::Mark => value & mask(n),
+            Nrzi::Space => !value & mask(n),
+        };
+        // 前缀异或：每一位变成它及更高位的翻转次数的奇偶
+        for shift in [1, 2, 4, 8, 16, 32] {
+            toggles ^= toggles >> shift;
+        }
+        Ok(if level { !toggles & mask(n) } else { toggles })
+    }
+
+    /// Decodes the low `n` bits of `raw` (1..=64), the line level before them being `level`
+    ///
+    /// The level after the word is the lowest bit of `raw`.
+    ///
+    /// # Errors
+    /// `InvalidBitCount` if `n` is not in 1..=64
+    pub fn decode(self, raw: u64, n: usize, level: bool) -> Result<u64> {
+        check_bit_count(n, 64)?;
+        let raw = raw & mask(n);
+        let previous = (raw >> 1) | ((level as u64) << (n - 1));
+        let toggles = raw ^ previous;
+        Ok(match self {
+            Nrzi::Mark => toggles,
+            Nrzi::Space => !toggles & mask(n),
+        })
+    }
+}
+
+/// Writes each data bit as two Manchester half-bits
+#[derive(Debug)]
+pub struct ManchesterWriter<W> {
+    inner: W,
+    code: Manchester,
+}
+
+impl<W: BitWrite> ManchesterWriter<W> {
+    pub fn new(inner: W, code: Manchester) -> Self {
+        Self { inner, code }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: BitWrite> BitWrite for ManchesterWriter<W> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        check_bit_count(n, 64)?;
+        // 一次最多编码 32 比特，先写高位
+        let mut remaining = n;
+        while remaining > 0 {
+            let chunk = remaining.min(32);
+            remaining -= chunk;
+            let raw = self.code.encode(value >> remaining, chunk)?;
+            self.inner.write_bits(raw, 2 * chunk)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads Manchester half-bit pairs and returns the data bits
+#[derive(Debug)]
+pub struct ManchesterReader<R> {
+    inner: R,
+    code: Manchester,
+}
+
+impl<R: BitRead<Output = u64>> ManchesterReader<R> {
+    pub fn new(inner: R, code: Manchester) -> Self {
+        Self { inner, code }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BitRead<Output = u64>> BitRead for ManchesterReader<R> {
+    type Output = u64;
+
+    /// # Errors
+    /// - `InvalidData` if a half-bit pair is `00` or `11`
+    /// - Any error of the inner reader
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        check_bit_count(n, 64)?;
+        let mut value = 0u64;
+        let mut remaining = n;
+        while remaining > 0 {
+            let chunk = remaining.min(32);
+            remaining -= chunk;
+            let raw = self.inner.read_bits(2 * chunk)?;
+            value = (value << chunk) | self.code.decode(raw, chunk)?;
+        }
+        Ok(value)
+    }
+}
+
+/// Writes data bits as NRZI line levels, starting from level 0
+#[derive(Debug)]
+pub struct NrziWriter<W> {
+    inner: W,
+    code: Nrzi,
+    level: bool,
+}
+
+impl<W: BitWrite> NrziWriter<W> {
+    pub fn new(inner: W, code: Nrzi) -> Self {
+        Self {
+            inner,
+            code,
+            level: false,
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: BitWrite> BitWrite for NrziWriter<W> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        let raw = self.code.encode(value, n, self.level)?;
+        self.inner.write_bits(raw, n)?;
+        self.level = raw & 1 == 1;
+        Ok(())
+    }
+}
+
+/// Reads NRZI line levels and returns the data bits, assuming the line starts at level 0
+#[derive(Debug)]
+pub struct NrziReader<R> {
+    inner: R,
+    code: Nrzi,
+    level: bool,
+}
+
+impl<R: BitRead<Output = u64>> NrziReader<R> {
+    pub fn new(inner: R, code: Nrzi) -> Self {
+        Self {
+            inner,
+            code,
+            level: false,
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BitRead<Output = u64>> BitRead for NrziReader<R> {
+    type Output = u64;
+
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        check_bit_count(n, 64)?;
+        let raw = self.inner.read_bits(n)?;
+        let value = self.code.decode(raw, n, self.level)?;
+        self.level = raw & 1 == 1;
+        Ok(value)
+    }
+}
@@ -0,0 +1,154 @@
+//! Morton (Z-order) codes: the bits of 2 or 3 coordinates interleaved into one integer
+//!
+//! Bit `i` of `x` becomes bit `2i` of the 2D code and bit `i` of `y` bit `2i + 1`; in 3D the
+//! coordinates take bits `3i`, `3i + 1` and `3i + 2`. Sorting by the code keeps points that
+//! are close in space mostly close in order, which is what quadtree / octree and other spatial
+//! index builders rely on.
+//!
+//! [`write_morton`] / [`read_morton`] interleave any number of coordinates of any width
+//! straight into a bit stream, most significant bits first, so codes wider than 64 bits need
+//! no big integer type.
+
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite};
+use std::io::Result;
+
+/// Largest coordinate [`encode_3d`] accepts (21 bits)
+pub const MAX_3D: u32 = (1 << 21) - 1;
+
+/// 把 32 位值的各位分散到偶数位上
+fn spread_2d(value: u32) -> u64 {
+    let mut v = value as u64;
+    v = (v | (v << 16)) & 0x0000_FFFF_0000_FFFF;
+    v = (v | (v << 8)) & 0x00FF_00FF_00FF_00FF;
+    v = (v | (v << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    v = (v | (v << 2)) & 0x3333_3333_3333_3333;
+    (v | (v << 1)) & 0x5555_5555_5555_5555
+}
+
+/// spread_2d 的逆运算：收集偶数位
+fn compact_2d(code: u64) -> u32 {
+    let mut v = code & 0x5555_5555_5555_5555;
+    v = (v | (v >> 1)) & 0x3333_3333_3333_3333;
+    v = (v | (v >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    v = (v | (v >> 4)) & 0x00FF_00FF_00FF_00FF;
+    v = (v | (v >> 8)) & 0x0000_FFFF_0000_FFFF;
+    ((v | (v >> 16)) & 0xFFFF_FFFF) as u32
+}
+
+/// 把 21 位值的各位分散到每三位的最低位上
+fn spread_3d(value: u32) -> u64 {
+    let mut v = (value & MAX_3D) as u64;
+    v = (v | (v << 32)) & 0x001F_0000_0000_FFFF;
+    v = (v | (v << 16)) & 0x001F_0000_FF00_00FF;
+    v = (v | (v << 8)) & 0x100F_00F0_0F00_F00F;
+    v = (v | (v << 4)) & 0x10C3_0C30_C30C_30C3;
+    (v | (v << 2)) & 0x1249_2492_4924_9249
+}
+
+/// spread_3d 的逆运算
+fn compact_3d(code: u64) -> u32 {
+    let mut v = code & 0x1249_2492_4924_9249;
+    v = (v | (v >> 2)) & 0x10C3_0C30_C30C_30C3;
+    v = (v | (v >> 4)) & 0x100F_00F0_0F00_F00F;
+    v = (v | (v >> 8)) & 0x001F_0000_FF00_00FF;
+    v = (v | (v >> 16)) & 0x001F_0000_0000_FFFF;
+    ((v | (v >> 32)) & MAX_3D as u64) as u32
+}
+
+/// Interleaves `x` and `y` into a 64-bit Morton code
+pub fn encode_2d(x: u32, y: u32) -> u64 {
+    spread_2d(x) | (spread_2d(y) << 1)
+}
+
+/// Splits a 2D Morton code into `(x, y)`
+pub fn decode_2d(code: u64) -> (u32, u32) {
+    (compact_2d(code), compact_2d(code >> 1))
+}
+
+/// Interleaves `x`, `y` and `z` into a 63-bit Morton code, or `None` if a coordinate exceeds
+/// [`MAX_3D`]
+pub fn encode_3d(x: u32, y: u32, z: u32) -> Option<u64> {
+    if x > MAX_3D || y > MAX_3D || z > MAX_3D {
+        return None;
+    }
+    Some(spread_3d(x) | (spread_3d(y) << 1) | (spread_3d(z) << 2))
+}
+
+/// Splits a 3D Morton code into `(x, y, z)`; bit 63 is ignored
+pub fn decode_3d(code: u64) -> (u32, u32, u32) {
+    (
+        compact_3d(code),
+        compact_3d(code >> 1),
+        compact_3d(code >> 2),
+    )
+}
+
+/// Writes the low `bits` bits (1-64) of every coordinate interleaved, most significant bits
+/// first: bit `bits - 1` of the last coordinate, then of the one before, down to bit 0 of the
+/// first
+///
+/// For 2 or 3 coordinates this is the Morton code of [`encode_2d`] / [`encode_3d`] written as
+/// one `coords.len() * bits` wide field.
+///
+/// # Errors
+/// - `InvalidBitCount` if `bits` is not between 1-64
+/// - `ValueOutOfRange` if a coordinate does not fit in `bits` bits; nothing is written then
+/// - Any error of the writer
+pub fn write_morton<W: BitWrite + ?Sized>(
+    writer: &mut W,
+    coords: &[u64],
+    bits: usize,
+) -> Result<()> {
+    if bits == 0 || bits > 64 {
+        return Err(BitReadWriteError::InvalidBitCount(bits).into());
+    }
+    if let Some(&value) = coords.iter().find(|&&c| bits < 64 && c >> bits != 0) {
+        return Err(BitReadWriteError::ValueOutOfRange { value, bits }.into());
+    }
+    // 攒满 64 位再交给写入器
+    let (mut acc, mut filled) = (0u64, 0);
+    for i in (0..bits).rev() {
+        for &coord in coords.iter().rev() {
+            acc = (acc << 1) | ((coord >> i) & 1);
+            filled += 1;
+            if filled == 64 {
+                writer.write_bits(acc, 64)?;
+                (acc, filled) = (0, 0);
+            }
+        }
+    }
+    if filled > 0 {
+        writer.write_bits(acc, filled)?;
+    }
+    Ok(())
+}
+
+/// Reads `coords.len()` coordinates of `bits` bits (1-64) written by [`write_morton`]
+///
+/// # Errors
+/// - `InvalidBitCount` if `bits` is not between 1-64
+/// - Any error of the reader
+pub fn read_morton<R>(reader: &mut R, coords: &mut [u64], bits: usize) -> Result<()>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    if bits == 0 || bits > 64 {
+        return Err(BitReadWriteError::InvalidBitCount(bits).into());
+    }
+    coords.fill(0);
+    let mut remaining = coords.len() * bits;
+    let (mut acc, mut available) = (0u64, 0);
+    for _ in 0..bits {
+        for coord in coords.iter_mut().rev() {
+            if available == 0 {
+                available = remaining.min(64);
+                acc = reader.read_bits(available)?;
+                remaining -= available;
+            }
+            available -= 1;
+            *coord = (*coord << 1) | ((acc >> available) & 1);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! Interoperability with [nom](https://docs.rs/nom)'s bit-level parsers (requires the `nom` feature)
+//!
+//! nom's bit parsers (`nom::bits::streaming::*`, `nom::bits::complete::*`) work on
+//! `(&[u8], usize)` inputs: a byte slice plus a bit offset into its first byte, consuming bits
+//! MSB-first. That is the layout of a `BigEndian` stream, so use big-endian readers and cursors
+//! with these adapters.
+//!
+//! - [`parse_cursor`] runs a parser directly on the bytes of a [`BitCursor`] and advances it.
+//! - [`NomBitReader`] feeds a streaming parser from any [`BitRead`], pulling bytes on
+//!   `Incomplete` until the parser succeeds.
+
+use crate::byte_order::ByteOrder;
+use crate::cursor::BitCursor;
+use crate::error::BitReadWriteError;
+use crate::traits::BitRead;
+use nom::error::Error;
+use nom::{Err, IResult};
+use std::io::{self, Result};
+
+/// Bit-level nom input: the remaining bytes and the bit offset into the first one
+pub type BitInput<'a> = (&'a [u8], usize);
+
+fn parse_error(err: Error<BitInput<'_>>) -> io::Error {
+    io::Error::from(BitReadWriteError::Nom(err.code))
+}
+
+/// Runs `parser` on the unread bits of a big-endian `cursor` and advances it past the bits the
+/// parser consumed
+///
+/// # Errors
+/// - `InvalidInput` if the cursor is little-endian
+/// - `InvalidData` if the parser fails
+/// - `UnexpectedEof` if the parser reports `Incomplete`
+pub fn parse_cursor<T, O, F>(cursor: &mut BitCursor<T>, mut parser: F) -> Result<O>
+where
+    T: AsRef<[u8]>,
+    F: for<'a> FnMut(BitInput<'a>) -> IResult<BitInput<'a>, O>,
+{
+    if cursor.byte_order() == ByteOrder::LittleEndian {
+        return Err(BitReadWriteError::BigEndianRequired.into());
+    }
+    let pos = cursor.position();
+    let data = cursor.get_ref().as_ref();
+    let start = ((pos / 8) as usize).min(data.len());
+    let input = (&data[start..], (pos % 8) as usize);
+    match parser(input) {
+        Ok(((rest, offset), value)) => {
+            let consumed = (data.len() - start - rest.len()) as u64;
+            cursor.set_position(start as u64 * 8 + consumed * 8 + offset as u64);
+            Ok(value)
+        }
+        Err(Err::Incomplete(_)) => Err(io::ErrorKind::UnexpectedEof.into()),
+        Err(Err::Error(e) | Err::Failure(e)) => Err(parse_error(e)),
+    }
+}
+
+/// Drives nom streaming bit parsers from a [`BitRead`] source
+///
+/// Bits are pulled from the reader one byte at a time whenever the parser returns
+/// `Incomplete`, and the parser is then re-run on the larger buffer, so it never reads past the
+/// end of the message it parses. Bits left over after a successful parse stay buffered for the
+/// next call to [`parse`](Self::parse).
+///
+/// Parsers built from `nom::bits::complete` never return `Incomplete`; use the
+/// `nom::bits::streaming` variants with this adapter.
+#[derive(Debug)]
+pub struct NomBitReader<R> {
+    inner: R,
+    buf: Vec<u8>,
+    offset: usize, // buf[0] 中已被消费的比特数
+}
+
+impl<R: BitRead<Output = u64>> NomBitReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            offset: 0,
+        }
+    }
+
+    /// Runs `parser` on the stream, reading more bytes as long as it reports `Incomplete`
+    ///
+    /// # Errors
+    /// - `InvalidData` if the parser fails
+    /// - Any error of the reader, e.g. `UnexpectedEof` when the stream ends first
+    pub fn parse<O, F>(&mut self, mut parser: F) -> Result<O>
+    where
+        F: for<'a> FnMut(BitInput<'a>) -> IResult<BitInput<'a>, O>,
+    {
+        loop {
+            match parser((&self.buf[..], self.offset)) {
+                Ok(((rest, offset), value)) => {
+                    let consumed = self.buf.len() - rest.len();
+                    self.buf.drain(..consumed);
+                    self.offset = offset;
+                    return Ok(value);
+                }
+                Err(Err::Incomplete(_)) => {}
+                Err(Err::Error(e) | Err::Failure(e)) => return Err(parse_error(e)),
+            }
+            let byte = self.inner.read_bits(8)?;
+            self.buf.push(byte as u8);
+        }
+    }
+
+    /// Returns the number of bits read from the inner reader but not yet consumed by a parser
+    pub fn buffered_bits(&self) -> usize {
+        self.buf.len() * 8 - self.offset
+    }
+
+    /// Returns the inner reader; any [`buffered_bits`](Self::buffered_bits) are discarded
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
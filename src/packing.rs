@@ -0,0 +1,107 @@
+//! Whole-array fixed-width integer packing on top of [`BitWrite`] / [`BitRead`]
+//!
+//! Every value of the array occupies exactly `width` bits, back to back, in the byte order of
+//! the writer/reader. There is no header: the caller stores `width` and the element count.
+
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite};
+use std::io::Result;
+
+/// Packs every value of `values` into `width` bits (1..=32)
+///
+/// All values are validated before anything is written, so an out-of-range value never leaves a
+/// half-written array behind.
+///
+/// # Errors
+/// - `InvalidBitCount` if `width` is not in 1..=32
+/// - `ValueOutOfRange` if a value needs more than `width` bits
+pub fn pack_u32s<W: BitWrite + ?Sized>(values: &[u32], width: usize, writer: &mut W) -> Result<()> {
+    if width == 0 || width > 32 {
+        return Err(BitReadWriteError::InvalidBitCount(width).into());
+    }
+    check_values(values.iter().map(|&v| v as u64), width)?;
+    for &v in values {
+        writer.write_bits(v as u64, width)?;
+    }
+    Ok(())
+}
+
+/// Packs every value of `values` into `width` bits (1..=64)
+///
+/// # Errors
+/// - `InvalidBitCount` if `width` is not in 1..=64
+/// - `ValueOutOfRange` if a value needs more than `width` bits
+pub fn pack_u64s<W: BitWrite + ?Sized>(values: &[u64], width: usize, writer: &mut W) -> Result<()> {
+    if width == 0 || width > 64 {
+        return Err(BitReadWriteError::InvalidBitCount(width).into());
+    }
+    check_values(values.iter().copied(), width)?;
+    for &v in values {
+        writer.write_bits(v, width)?;
+    }
+    Ok(())
+}
+
+/// Fills `out` with `out.len()` values of `width` bits (1..=32), the inverse of [`pack_u32s`]
+///
+/// # Errors
+/// - `InvalidBitCount` if `width` is not in 1..=32
+/// - Any error of the reader, e.g. `UnexpectedEof` when the stream is too short
+pub fn unpack_u32s<R>(reader: &mut R, width: usize, out: &mut [u32]) -> Result<()>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    if width == 0 || width > 32 {
+        return Err(BitReadWriteError::InvalidBitCount(width).into());
+    }
+    for slot in out.iter_mut() {
+        *slot = reader.read_bits(width)? as u32;
+    }
+    Ok(())
+}
+
+/// Fills `out` with `out.len()` values of `width` bits (1..=64), the inverse of [`pack_u64s`]
+///
+/// # Errors
+/// - `InvalidBitCount` if `width` is not in 1..=64
+/// - Any error of the reader, e.g. `UnexpectedEof` when the stream is too short
+pub fn unpack_u64s<R>(reader: &mut R, width: usize, out: &mut [u64]) -> Result<()>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    if width == 0 || width > 64 {
+        return Err(BitReadWriteError::InvalidBitCount(width).into());
+    }
+    for slot in out.iter_mut() {
+        *slot = reader.read_bits(width)?;
+    }
+    Ok(())
+}
+
+/// Returns the minimal width (1..=32) able to hold every value of `values`
+///
+/// An empty slice or an all-zero slice needs 1 bit.
+pub fn required_width_u32(values: &[u32]) -> usize {
+    let max = values.iter().fold(0, |acc, &v| acc | v);
+    (32 - max.leading_zeros() as usize).max(1)
+}
+
+/// Returns the minimal width (1..=64) able to hold every value of `values`
+///
+/// An empty slice or an all-zero slice needs 1 bit.
+pub fn required_width_u64(values: &[u64]) -> usize {
+    let max = values.iter().fold(0, |acc, &v| acc | v);
+    (64 - max.leading_zeros() as usize).max(1)
+}
+
+fn check_values(values: impl Iterator<Item = u64>, width: usize) -> Result<()> {
+    if width == 64 {
+        return Ok(());
+    }
+    for value in values {
+        if value >> width != 0 {
+            return Err(BitReadWriteError::ValueOutOfRange { value, bits: width }.into());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! Parallel decoding of independent byte-aligned records with rayon
+//!
+//! The input is cut into records, either at known offsets ([`decode_at`]) or by a splitter
+//! that reports the length of each record ([`decode_split`]). Each record then gets its own
+//! [`BitReader`] over its slice, the records are decoded on the rayon thread pool and the
+//! results come back in input order.
+
+use crate::byte_order::ByteOrder;
+use crate::error::BitReadWriteError;
+use crate::reader::BitReader;
+use rayon::prelude::*;
+use std::io::Result;
+
+/// 并行解码各段，按输入顺序返回结果；有错误时返回顺序上最靠前的错误
+fn decode_records<'a, T, F>(
+    records: Vec<&'a [u8]>,
+    byte_order: ByteOrder,
+    decode: F,
+) -> Result<Vec<T>>
+where
+    T: Send,
+    F: Fn(&mut BitReader<&'a [u8]>) -> Result<T> + Sync,
+{
+    let results: Vec<Result<T>> = records
+        .into_par_iter()
+        .map(|record| decode(&mut BitReader::unbuffered(byte_order, record)))
+        .collect();
+    results.into_iter().collect()
+}
+
+/// Decodes the records starting at each offset of `boundaries` in parallel
+///
+/// Record `i` spans `data[boundaries[i]..boundaries[i + 1]]`, the last one running to the end
+/// of `data`; bytes before the first offset are ignored. `decode` gets a fresh reader over
+/// each record and need not consume all of it.
+///
+/// # Errors
+/// - `InvalidInput` if the offsets are not increasing or go past the end of `data`
+/// - The first error returned by `decode`, in record order
+pub fn decode_at<'a, T, F>(
+    data: &'a [u8],
+    boundaries: &[usize],
+    byte_order: ByteOrder,
+    decode: F,
+) -> Result<Vec<T>>
+where
+    T: Send,
+    F: Fn(&mut BitReader<&'a [u8]>) -> Result<T> + Sync,
+{
+    let ends = boundaries.iter().skip(1).copied().chain([data.len()]);
+    let mut records = Vec::with_capacity(boundaries.len());
+    for (&start, end) in boundaries.iter().zip(ends) {
+        if start > end || end > data.len() {
+            return Err(BitReadWriteError::InvalidRecordBoundaries.into());
+        }
+        records.push(&data[start..end]);
+    }
+    decode_records(records, byte_order, decode)
+}
+
+/// Cuts `data` into records with `split` and decodes them in parallel
+///
+/// `split` is called sequentially on the rest of the data and returns the length in bytes of
+/// the record at its start, typically by reading a length field. Splitting stops when the data
+/// is used up.
+///
+/// # Errors
+/// - `InvalidData` if `split` returns 0 or a length past the end of the data
+/// - The first error returned by `split`, or by `decode` in record order
+pub fn decode_split<'a, T, S, F>(
+    data: &'a [u8],
+    byte_order: ByteOrder,
+    mut split: S,
+    decode: F,
+) -> Result<Vec<T>>
+where
+    T: Send,
+    S: FnMut(&'a [u8]) -> Result<usize>,
+    F: Fn(&mut BitReader<&'a [u8]>) -> Result<T> + Sync,
+{
+    let mut records = Vec::new();
+    let mut rest = data;
+    while !rest.is_empty() {
+        let len = split(rest)?;
+        if len == 0 || len > rest.len() {
+            return Err(BitReadWriteError::InvalidRecordLength {
+                len,
+                remaining: rest.len(),
+            }
+            .into());
+        }
+        let (record, tail) = rest.split_at(len);
+        records.push(record);
+        rest = tail;
+    }
+    decode_records(records, byte_order, decode)
+}
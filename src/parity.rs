@@ -0,0 +1,30 @@
+//! Single-bit parity, as used by legacy telemetry and smart-card protocols
+//!
+//! Use [`BitReadExt::read_bits_with_parity`] / [`BitWriteExt::write_bits_with_parity`] to read and
+//! write a field followed by its parity bit.
+//!
+//! [`BitReadExt::read_bits_with_parity`]: crate::traits::BitReadExt::read_bits_with_parity
+//! [`BitWriteExt::write_bits_with_parity`]: crate::traits::BitWriteExt::write_bits_with_parity
+
+/// Which total number of set bits (data plus parity bit) is valid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+impl Parity {
+    /// Returns the parity bit for the low `n` bits (0-64) of `value`
+    pub fn bit(self, value: u64, n: usize) -> u64 {
+        let value = if n < 64 {
+            value & ((1u64 << n) - 1)
+        } else {
+            value
+        };
+        let odd_ones = (value.count_ones() % 2) as u64;
+        match self {
+            Parity::Even => odd_ones,
+            Parity::Odd => odd_ones ^ 1,
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! 24-bit PCM samples in byte buffers
+//!
+//! Each sample takes 3 bytes in the given byte order and is sign-extended to an `i32`.
+//! Interleaved buffers (`L R L R ...`) convert as one flat run of samples. The
+//! [`read_i24`](crate::traits::BitReadExt::read_i24) /
+//! [`read_i24_samples`](crate::traits::BitReadExt::read_i24_samples) trait methods and their
+//! write counterparts do the same on any bit stream.
+
+use crate::byte_order::ByteOrder;
+use crate::error::BitReadWriteError;
+use std::io::Result;
+
+/// Smallest 24-bit sample value
+pub const I24_MIN: i32 = -(1 << 23);
+/// Largest 24-bit sample value
+pub const I24_MAX: i32 = (1 << 23) - 1;
+
+/// Sign-extends the low 24 bits of `value`
+pub fn sign_extend_i24(value: u32) -> i32 {
+    ((value << 8) as i32) >> 8
+}
+
+/// 检查所有样本都在 24 位有符号范围内
+pub(crate) fn check_i24(samples: &[i32]) -> Result<()> {
+    match samples.iter().find(|&&v| !(I24_MIN..=I24_MAX).contains(&v)) {
+        Some(&value) => Err(BitReadWriteError::ValueOutOfRange {
+            value: value as i64 as u64,
+            bits: 24,
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Decodes `out.len()` samples from the first `3 * out.len()` bytes of `data`
+///
+/// # Errors
+/// `BufferTooSmall` if `data` is shorter than `3 * out.len()` bytes
+pub fn decode_i24(data: &[u8], order: ByteOrder, out: &mut [i32]) -> Result<()> {
+    if data.len() < out.len() * 3 {
+        return Err(BitReadWriteError::BufferTooSmall {
+            bits: out.len() * 24,
+            capacity: data.len() * 8,
+        }
+        .into());
+    }
+    let samples = data.chunks_exact(3).zip(out.iter_mut());
+    match order {
+        ByteOrder::BigEndian => {
+            for (bytes, sample) in samples {
+                *sample = sign_extend_i24(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]));
+            }
+        }
+        ByteOrder::LittleEndian => {
+            for (bytes, sample) in samples {
+                *sample = sign_extend_i24(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Encodes `samples` into the first `3 * samples.len()` bytes of `out`
+///
+/// # Errors
+/// - `BufferTooSmall` if `out` is shorter than `3 * samples.len()` bytes
+/// - `ValueOutOfRange` if a sample does not fit in 24 signed bits; nothing is written then
+pub fn encode_i24(samples: &[i32], order: ByteOrder, out: &mut [u8]) -> Result<()> {
+    if out.len() < samples.len() * 3 {
+        return Err(BitReadWriteError::BufferTooSmall {
+            bits: samples.len() * 24,
+            capacity: out.len() * 8,
+        }
+        .into());
+    }
+    check_i24(samples)?;
+    let pairs = samples.iter().zip(out.chunks_exact_mut(3));
+    match order {
+        ByteOrder::BigEndian => {
+            for (&sample, bytes) in pairs {
+                bytes.copy_from_slice(&sample.to_be_bytes()[1..]);
+            }
+        }
+        ByteOrder::LittleEndian => {
+            for (&sample, bytes) in pairs {
+                bytes.copy_from_slice(&sample.to_le_bytes()[..3]);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,155 @@
+//! Packed 10-bit and 12-bit video sample layouts: MIPI CSI-2 RAW10 / RAW12 Bayer data and
+//! V210 4:2:2 video
+//!
+//! ## Layouts
+//! ```text
+//! RAW10: 4 pixels in 5 bytes   P0[9:2] P1[9:2] P2[9:2] P3[9:2] | P3[1:0] P2[1:0] P1[1:0] P0[1:0]
+//! RAW12: 2 pixels in 3 bytes   P0[11:4] P1[11:4] | P1[3:0] P0[3:0]
+//! V210:  6 pixels in 16 bytes  four little-endian 32-bit words, each holding three 10-bit
+//!                              components at bits 0, 10 and 20:
+//!                              Cb0 Y0 Cr0 | Y1 Cb1 Y2 | Cr1 Y3 Cb2 | Y4 Cr2 Y5
+//! ```
+//!
+//! The low bits of each group are gathered into a shared byte (RAW) or padding interrupts the
+//! fields (V210), so these are not plain bit streams for [`fast::simd`](crate::fast). The
+//! functions instead convert whole groups at a time with fixed-size chunks, which the compiler
+//! turns into vector code, and check all lengths once up front.
+
+use crate::error::BitReadWriteError;
+use std::io::Result;
+
+/// Bytes per V210 line of `width` pixels, padded to a multiple of 128 bytes (48 pixels)
+pub fn v210_stride(width: usize) -> usize {
+    width.div_ceil(48) * 128
+}
+
+/// 检查样本数是 group 的整数倍、打包数据至少有 bytes 字节
+fn check_lengths(samples: usize, group: usize, data: usize, bytes: usize) -> Result<()> {
+    if !samples.is_multiple_of(group) {
+        return Err(BitReadWriteError::SampleCountNotMultiple { samples, group }.into());
+    }
+    if data < bytes {
+        return Err(BitReadWriteError::BufferTooSmall {
+            bits: bytes * 8,
+            capacity: data * 8,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+fn check_range(samples: &[u16], bits: usize) -> Result<()> {
+    match samples.iter().find(|&&v| v >> bits != 0) {
+        Some(&value) => Err(BitReadWriteError::ValueOutOfRange {
+            value: value as u64,
+            bits,
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Unpacks `out.len()` RAW10 pixels (a multiple of 4) from the start of `data`
+///
+/// # Errors
+/// - `InvalidInput` if `out.len()` is not a multiple of 4
+/// - `BufferTooSmall` if `data` is shorter than `out.len() / 4 * 5` bytes
+pub fn unpack_raw10(data: &[u8], out: &mut [u16]) -> Result<()> {
+    check_lengths(out.len(), 4, data.len(), out.len() / 4 * 5)?;
+    for (group, pixels) in data.chunks_exact(5).zip(out.chunks_exact_mut(4)) {
+        let low = group[4] as u16;
+        for (i, pixel) in pixels.iter_mut().enumerate() {
+            *pixel = ((group[i] as u16) << 2) | ((low >> (2 * i)) & 0b11);
+        }
+    }
+    Ok(())
+}
+
+/// Packs RAW10 pixels (a multiple of 4, each below 1024) into the start of `out`
+///
+/// # Errors
+/// - `InvalidInput` if `samples.len()` is not a multiple of 4
+/// - `BufferTooSmall` if `out` is shorter than `samples.len() / 4 * 5` bytes
+/// - `ValueOutOfRange` if a sample needs more than 10 bits; nothing is written then
+pub fn pack_raw10(samples: &[u16], out: &mut [u8]) -> Result<()> {
+    check_lengths(samples.len(), 4, out.len(), samples.len() / 4 * 5)?;
+    check_range(samples, 10)?;
+    for (pixels, group) in samples.chunks_exact(4).zip(out.chunks_exact_mut(5)) {
+        let mut low = 0u8;
+        for (i, &pixel) in pixels.iter().enumerate() {
+            group[i] = (pixel >> 2) as u8;
+            low |= ((pixel & 0b11) as u8) << (2 * i);
+        }
+        group[4] = low;
+    }
+    Ok(())
+}
+
+/// Unpacks `out.len()` RAW12 pixels (a multiple of 2) from the start of `data`
+///
+/// # Errors
+/// - `InvalidInput` if `out.len()` is not a multiple of 2
+/// - `BufferTooSmall` if `data` is shorter than `out.len() / 2 * 3` bytes
+pub fn unpack_raw12(data: &[u8], out: &mut [u16]) -> Result<()> {
+    check_lengths(out.len(), 2, data.len(), out.len() / 2 * 3)?;
+    for (group, pixels) in data.chunks_exact(3).zip(out.chunks_exact_mut(2)) {
+        let low = group[2] as u16;
+        pixels[0] = ((group[0] as u16) << 4) | (low & 0x0F);
+        pixels[1] = ((group[1] as u16) << 4) | (low >> 4);
+    }
+    Ok(())
+}
+
+/// Packs RAW12 pixels (a multiple of 2, each below 4096) into the start of `out`
+///
+/// # Errors
+/// - `InvalidInput` if `samples.len()` is not a multiple of 2
+/// - `BufferTooSmall` if `out` is shorter than `samples.len() / 2 * 3` bytes
+/// - `ValueOutOfRange` if a sample needs more than 12 bits; nothing is written then
+pub fn pack_raw12(samples: &[u16], out: &mut [u8]) -> Result<()> {
+    check_lengths(samples.len(), 2, out.len(), samples.len() / 2 * 3)?;
+    check_range(samples, 12)?;
+    for (pixels, group) in samples.chunks_exact(2).zip(out.chunks_exact_mut(3)) {
+        group[0] = (pixels[0] >> 4) as u8;
+        group[1] = (pixels[1] >> 4) as u8;
+        group[2] = ((pixels[0] & 0x0F) | ((pixels[1] & 0x0F) << 4)) as u8;
+    }
+    Ok(())
+}
+
+/// Unpacks `out.len()` V210 components (a multiple of 12, i.e. of 6 pixels) from the start of
+/// `data`, in stream order `Cb Y Cr Y Cb Y ...`
+///
+/// Line padding is not skipped: call this once per line, see [`v210_stride`].
+///
+/// # Errors
+/// - `InvalidInput` if `out.len()` is not a multiple of 12
+/// - `BufferTooSmall` if `data` is shorter than `out.len() / 12 * 16` bytes
+pub fn unpack_v210(data: &[u8], out: &mut [u16]) -> Result<()> {
+    check_lengths(out.len(), 12, data.len(), out.len() / 12 * 16)?;
+    for (word, components) in data.chunks_exact(4).zip(out.chunks_exact_mut(3)) {
+        let word = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+        components[0] = (word & 0x3FF) as u16;
+        components[1] = ((word >> 10) & 0x3FF) as u16;
+        components[2] = ((word >> 20) & 0x3FF) as u16;
+    }
+    Ok(())
+}
+
+/// Packs V210 components (a multiple of 12, each below 1024) into the start of `out`, with
+/// zero padding bits
+///
+/// # Errors
+/// - `InvalidInput` if `samples.len()` is not a multiple of 12
+/// - `BufferTooSmall` if `out` is shorter than `samples.len() / 12 * 16` bytes
+/// - `ValueOutOfRange` if a component needs more than 10 bits; nothing is written then
+pub fn pack_v210(samples: &[u16], out: &mut [u8]) -> Result<()> {
+    check_lengths(samples.len(), 12, out.len(), samples.len() / 12 * 16)?;
+    check_range(samples, 10)?;
+    for (components, word) in samples.chunks_exact(3).zip(out.chunks_exact_mut(4)) {
+        let value =
+            components[0] as u32 | (components[1] as u32) << 10 | (components[2] as u32) << 20;
+        word.copy_from_slice(&value.to_le_bytes());
+    }
+    Ok(())
+}
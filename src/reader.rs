@@ -1,16 +1,63 @@
-use crate::byte_order::ByteOrder;
-use crate::error::BitReadWriteError;
-use crate::traits::{BitPeek, BitRead};
-use std::io::{BufReader, Read};
+use crate::byte_order::{self, Big, ByteOrder, Endianness, Little};
+use crate::error::{self, BitReadWriteError};
+use crate::traits::{BitExpect, BitPeek, BitRead, BitReadExt};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Seek, SeekFrom};
 
 // ------------------------------- BitReader ------------------------------- //
 
-pub struct BitReader<R: Read> {
-    byte_order: ByteOrder,
-    inner: BufReader<R>,
+/// 底层数据源：默认套一层 BufReader，已有缓冲或在内存中的数据源可以直接使用
+enum Source<R: Read> {
+    Buffered(BufReader<R>),
+    Direct(R),
+}
+
+impl<R: Read> Read for Source<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Source::Buffered(inner) => inner.read(buf),
+            Source::Direct(inner) => inner.read(buf),
+        }
+    }
+}
+
+impl<R: Read + Seek> Seek for Source<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        match self {
+            Source::Buffered(inner) => inner.seek(pos),
+            Source::Direct(inner) => inner.seek(pos),
+        }
+    }
+
+    /// BufReader 的 stream_position 不会丢弃缓冲区
+    fn stream_position(&mut self) -> std::io::Result<u64> {
+        match self {
+            Source::Buffered(inner) => inner.stream_position(),
+            Source::Direct(inner) => inner.stream_position(),
+        }
+    }
+}
+
+/// Standard bit reader for BigEndian streams with the byte order fixed at compile time
+pub type BitReaderBig<R> = BitReader<R, Big>;
+
+/// Standard bit reader for LittleEndian streams with the byte order fixed at compile time
+pub type BitReaderLittle<R> = BitReader<R, Little>;
+
+/// Standard bit reader, generic over its byte order
+///
+/// The default `E = ByteOrder` picks the order at runtime. With the [`Big`] / [`Little`]
+/// markers ([`BitReaderBig`] / [`BitReaderLittle`]) every byte order branch is resolved at
+/// compile time; the API and trait implementations are the same.
+pub struct BitReader<R: Read, E: Endianness = ByteOrder> {
+    order: E,
+    inner: Source<R>,
 
     bits_buffer: u64, // 比特缓冲区：rust 中并没有表达 "一系列比特" 的具名数据结构，但是事实上 u64 就可以表达一系列比特
     bits_in_buffer: usize, // 当前比特缓冲区中持有的比特数
+    bytes_read: u64,  // 从数据源取得的字节总数
+    max_bits: Option<u64>, // 最多允许消费的比特数
+    lookahead: Vec<u8>, // peek_bytes 预读但尚未放入比特缓冲区的字节
 }
 
 impl<R: Read> BitReader<R> {
@@ -19,75 +66,437 @@ impl<R: Read> BitReader<R> {
     }
 
     pub fn with_byte_order(byte_order: ByteOrder, inner: R) -> Self {
-        Self {
+        Self::from_source(byte_order, Source::Buffered(BufReader::new(inner)))
+    }
+
+    /// Creates a reader whose internal `BufReader` holds `capacity` bytes
+    pub fn with_capacity(byte_order: ByteOrder, capacity: usize, inner: R) -> Self {
+        Self::from_source(
             byte_order,
-            inner: BufReader::new(inner),
+            Source::Buffered(BufReader::with_capacity(capacity, inner)),
+        )
+    }
+
+    /// Creates a reader without an internal `BufReader`
+    ///
+    /// Use it for sources that are already buffered or in memory (`BufReader`, `&[u8]`,
+    /// `Cursor`), where a second buffer would only add copying. Every refill of the bit buffer
+    /// calls `read` on `inner` directly, so avoid it for unbuffered files and sockets.
+    pub fn unbuffered(byte_order: ByteOrder, inner: R) -> Self {
+        Self::from_source(byte_order, Source::Direct(inner))
+    }
+}
+
+impl<R: Read, E: Endianness> BitReader<R, E> {
+    /// Creates a reader with the byte order given by `order`, e.g. `Big` for a
+    /// [`BitReaderBig`]
+    pub fn with_endianness(order: E, inner: R) -> Self {
+        Self::from_source(order, Source::Buffered(BufReader::new(inner)))
+    }
+
+    fn from_source(order: E, inner: Source<R>) -> Self {
+        Self {
+            order,
+            inner,
             bits_buffer: 0,
             bits_in_buffer: 0,
+            bytes_read: 0,
+            max_bits: None,
+            lookahead: Vec::new(),
+        }
+    }
+
+    /// Limits the reader to `limit` bits in total, counted from the start of the stream
+    ///
+    /// Any bit read that would go past the limit fails with
+    /// [`LimitExceeded`](BitReadWriteError::LimitExceeded) without consuming anything, so
+    /// parsers of untrusted input can bound the work spent on one message. Byte reads through
+    /// [`Read`] return at most the whole bytes left in the budget and fail once it is used up.
+    pub fn max_bits(mut self, limit: u64) -> Self {
+        self.max_bits = Some(limit);
+        self
+    }
+
+    /// Discards the first `bit_offset` bits (0-7) of the first byte, for attaching a reader
+    /// mid-byte after an outer framer has consumed part of it
+    ///
+    /// The discarded bits count as consumed, so bit positions, [`max_bits`](Self::max_bits)
+    /// and exported states stay relative to the start of the first byte. In little-endian
+    /// order the discarded bits are the low bits of the byte.
+    ///
+    /// # Errors
+    /// - `InvalidInput` if `bit_offset` exceeds 7
+    /// - Any error of the inner reader while reading the first byte
+    pub fn with_initial_offset(mut self, bit_offset: usize) -> std::io::Result<Self> {
+        if bit_offset > 7 {
+            return Err(BitReadWriteError::InvalidInitialOffset(bit_offset).into());
+        }
+        if bit_offset > 0 {
+            self.read_bits(bit_offset)?;
         }
+        Ok(self)
+    }
+
+    /// Returns the number of bytes taken from the inner reader so far
+    ///
+    /// This includes bytes whose bits are still sitting unconsumed in the bit buffer, so it is
+    /// how far into the source stream the reader has progressed. Read-ahead held by the
+    /// internal `BufReader`, or by [`PeekableBitReader::peek_bytes`], is not counted.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// 当前字节序；使用 Big / Little 标记时是常量，相关分支在编译期消除
+    #[inline(always)]
+    fn byte_order(&self) -> ByteOrder {
+        self.order.byte_order()
     }
 }
 
-impl<R: Read> BitReader<R> {
+/// Forks the reader at its current bit position, for parsers that try one interpretation and
+/// keep whichever copy succeeds
+///
+/// The inner reader is cloned, so this works for sources whose clone carries the position,
+/// such as `&[u8]` and `Cursor`. Readers made with [`unbuffered`](BitReader::unbuffered) clone
+/// in constant time; for buffered ones the bytes still held by the `BufReader` are copied too.
+impl<R: Read + Clone, E: Endianness> Clone for BitReader<R, E> {
+    fn clone(&self) -> Self {
+        let mut lookahead = self.lookahead.clone();
+        let inner = match &self.inner {
+            Source::Direct(inner) => Source::Direct(inner.clone()),
+            // BufReader 中已缓冲的字节排在 lookahead 之后，由副本的 lookahead 接着提供
+            Source::Buffered(inner) => {
+                lookahead.extend_from_slice(inner.buffer());
+                Source::Buffered(BufReader::with_capacity(
+                    inner.capacity(),
+                    inner.get_ref().clone(),
+                ))
+            }
+        };
+        Self {
+            order: self.order,
+            inner,
+            bits_buffer: self.bits_buffer,
+            bits_in_buffer: self.bits_in_buffer,
+            bytes_read: self.bytes_read,
+            max_bits: self.max_bits,
+            lookahead,
+        }
+    }
+}
+
+/// Snapshot of a [`BitReader`] position, taken by [`BitReader::export_state`]
+///
+/// With the `serde` feature it can be serialized, so a long-running parse can be checkpointed
+/// and resumed after a restart on a new reader over the same seekable source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReaderState {
+    /// Byte order of the reader the state was taken from
+    pub byte_order: ByteOrder,
+    /// Offset in the source of the first byte not yet taken into the bit buffer
+    pub byte_offset: u64,
+    /// Bits taken from the source but not consumed yet, in the reader's internal layout
+    pub bits_buffer: u64,
+    /// Number of valid bits in `bits_buffer`
+    pub bits_in_buffer: usize,
+    /// Value of [`BitReader::bytes_read`]
+    pub bytes_read: u64,
+    /// Limit set with [`BitReader::max_bits`], if any
+    pub max_bits: Option<u64>,
+}
+
+impl<R: Read + Seek, E: Endianness> BitReader<R, E> {
+    /// Captures the current position, including bits buffered but not consumed yet
+    ///
+    /// # Errors
+    /// Any error of the source while querying its position
+    pub fn export_state(&mut self) -> std::io::Result<ReaderState> {
+        // BufReader 的 stream_position 已扣除其缓冲的字节，这里再扣除 lookahead
+        let position = self.inner.stream_position()?;
+        Ok(ReaderState {
+            byte_order: self.byte_order(),
+            byte_offset: position - self.lookahead.len() as u64,
+            bits_buffer: self.bits_buffer,
+            bits_in_buffer: self.bits_in_buffer,
+            bytes_read: self.bytes_read,
+            max_bits: self.max_bits,
+        })
+    }
+
+    /// Restores a state from [`export_state`](Self::export_state), seeking the source to where
+    /// the state was taken
+    ///
+    /// # Errors
+    /// - `InvalidInput` if the state was taken with another byte order or is malformed
+    /// - Any error of the source while seeking
+    pub fn import_state(&mut self, state: &ReaderState) -> std::io::Result<()> {
+        if state.byte_order != self.byte_order() || state.bits_in_buffer > 64 {
+            return Err(BitReadWriteError::StateMismatch.into());
+        }
+        self.inner.seek(SeekFrom::Start(state.byte_offset))?;
+        self.lookahead.clear();
+        self.bits_buffer = state.bits_buffer;
+        self.bits_in_buffer = state.bits_in_buffer;
+        self.bytes_read = state.bytes_read;
+        self.max_bits = state.max_bits;
+        Ok(())
+    }
+}
+
+impl<R: Read, E: Endianness> BitReader<R, E> {
+    /// 已消费的比特数
+    fn bits_consumed(&self) -> u64 {
+        self.bytes_read * 8 - self.bits_in_buffer as u64
+    }
+
+    /// max_bits 限额中剩余可消费的比特数
+    fn remaining_limit(&self) -> Option<u64> {
+        let consumed = self.bits_consumed();
+        self.max_bits.map(|limit| limit.saturating_sub(consumed))
+    }
+
+    /// 给从 start 开始、请求 requested 比特的操作失败附上位置
+    ///
+    /// 遇到 EOF 时已取得的比特都还在（两段读取时第一段已消费，一并计入；peek_bytes 预读的字节在 lookahead
+    /// 中）；超出限额时给出限额内剩余的比特数
+    fn locate(&self, err: std::io::Error, start: u64, requested: Option<usize>) -> std::io::Error {
+        let available = match error::typed_root(&err) {
+            Some(BitReadWriteError::UnexpectedEof) => Some(
+                self.bits_consumed() - start
+                    + self.bits_in_buffer as u64
+                    + self.lookahead.len() as u64 * 8,
+            ),
+            Some(BitReadWriteError::LimitExceeded { .. }) => self.remaining_limit(),
+            _ => None,
+        };
+        error::locate(err, start, requested, available)
+    }
+
+    /// read_bits 的实现，错误不带位置
+    fn read_bits_unlocated(&mut self, n: usize) -> std::io::Result<u64> {
+        // 校验 n
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        self.check_limit(n as u64)?;
+
+        // 填充比特缓冲区
+        self.put_into_bits_buffer(n)?;
+        if self.bits_in_buffer >= n {
+            // 从比特缓冲区取 n 比特，并且消费掉
+            return self.get_from_bits_buffer(n, true);
+        }
+
+        // 比特缓冲区中的零头比特加上所需的整字节超过 64 位：分两段读取再拼接
+        let first_bits = self.bits_in_buffer;
+        let first = self.get_from_bits_buffer(first_bits, true)?;
+        let second_bits = n - first_bits;
+        self.put_into_bits_buffer(second_bits)?;
+        let second = self.get_from_bits_buffer(second_bits, true)?;
+        Ok(match self.byte_order() {
+            ByteOrder::BigEndian => (first << second_bits) | second,
+            ByteOrder::LittleEndian => first | (second << first_bits),
+        })
+    }
+
+    /// 校验再消费 n 比特是否会超出 max_bits 限额
+    fn check_limit(&self, n: u64) -> std::io::Result<()> {
+        match (self.max_bits, self.remaining_limit()) {
+            (Some(limit), Some(remaining)) if n > remaining => {
+                Err(BitReadWriteError::LimitExceeded { limit }.into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 从数据源读取并计数
+    fn read_inner(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        // 优先取出 peek_bytes 预读的字节
+        let count = if self.lookahead.is_empty() {
+            self.inner.read(buf)?
+        } else {
+            let count = buf.len().min(self.lookahead.len());
+            buf[..count].copy_from_slice(&self.lookahead[..count]);
+            self.lookahead.drain(..count);
+            count
+        };
+        self.bytes_read += count as u64;
+        Ok(count)
+    }
+
+    /// 不消费地检查数据源是否已读完，读到的字节放入 lookahead
+    fn source_exhausted(&mut self) -> std::io::Result<bool> {
+        if !self.lookahead.is_empty() {
+            return Ok(false);
+        }
+        let mut byte = [0u8; 1];
+        loop {
+            match self.inner.read(&mut byte) {
+                Ok(0) => return Ok(true),
+                Ok(_) => {
+                    self.lookahead.push(byte[0]);
+                    return Ok(false);
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// 当前字节中尚未消费的比特数，字节对齐时为 0
+    pub(crate) fn pending_bits(&self) -> usize {
+        self.bits_in_buffer % 8
+    }
+
+    /// 不消费地取出接下来的 buf.len() 个字节，调用方保证字节对齐
+    fn peek_bytes(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        // 先取比特缓冲区中的整字节
+        let buffered = (self.bits_in_buffer / 8).min(buf.len());
+        for (i, byte) in buf[..buffered].iter_mut().enumerate() {
+            *byte = match self.byte_order() {
+                ByteOrder::BigEndian => (self.bits_buffer >> (56 - 8 * i)) as u8,
+                ByteOrder::LittleEndian => (self.bits_buffer >> (8 * i)) as u8,
+            };
+        }
+
+        // 其余字节从数据源预读到 lookahead，之后的读取会先消费它们
+        let wanted = buf.len() - buffered;
+        while self.lookahead.len() < wanted {
+            let mut chunk = [0u8; 64];
+            let want = (wanted - self.lookahead.len()).min(chunk.len());
+            match self.inner.read(&mut chunk[..want]) {
+                Ok(0) => return Err(BitReadWriteError::UnexpectedEof.into()),
+                Ok(count) => self.lookahead.extend_from_slice(&chunk[..count]),
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        buf[buffered..].copy_from_slice(&self.lookahead[..wanted]);
+        Ok(())
+    }
+
+    /// 把比特缓冲区中剩余的整字节移回 lookahead 前端，已消费的比特数不变；调用方保证字节对齐
+    fn spill_buffered_bytes(&mut self) {
+        let count = self.bits_in_buffer / 8;
+        if count == 0 {
+            return;
+        }
+        let mut front = [0u8; 8];
+        for (i, byte) in front[..count].iter_mut().enumerate() {
+            *byte = match self.byte_order() {
+                ByteOrder::BigEndian => (self.bits_buffer >> (56 - 8 * i)) as u8,
+                ByteOrder::LittleEndian => (self.bits_buffer >> (8 * i)) as u8,
+            };
+        }
+        self.bytes_read -= count as u64;
+        self.bits_buffer = 0;
+        self.bits_in_buffer = 0;
+        self.lookahead.splice(0..0, front[..count].iter().copied());
+    }
+
+    /// 把已消费的字节退回到数据源前端，之后的读取会先读到它们；调用方保证字节对齐
+    ///
+    /// 比特缓冲区中剩余的整字节排在退回的字节之后，一并移入 lookahead，已消费的比特数不变
+    #[cfg(feature = "binrw")]
+    pub(crate) fn unread_bytes(&mut self, bytes: &[u8]) {
+        self.spill_buffered_bytes();
+        self.bytes_read -= bytes.len() as u64;
+        self.lookahead.splice(0..0, bytes.iter().copied());
+    }
+
     fn put_into_bits_buffer(&mut self, n: usize) -> std::io::Result<()> {
+        if !self.put_available_into_bits_buffer(n)? {
+            return Err(BitReadWriteError::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+
+    /// 尽量填充比特缓冲区直到至少有 n 比特（或再放不下一个字节），返回所需字节是否全部读到
+    ///
+    /// 遇到 EOF 时不报错，只放入实际读到的字节，这样这些比特不会丢失
+    fn put_available_into_bits_buffer(&mut self, n: usize) -> std::io::Result<bool> {
         let bits_needed = n.saturating_sub(self.bits_in_buffer); // 使用 saturating_sub 防止下溢
-        let mut bytes_needed = (bits_needed + 7) / 8; // 这是一种常见的 向上取整除法技巧（Ceiling Division Trick），用于计算容纳指定位数所需的最小字节数（当`bits_needed`不是8的倍数时，加上7就会使得总和至少达到下一个8的倍数，从而在除以8时得到正确地向上取整的结果）
+        let mut bytes_needed = bits_needed.div_ceil(8); // 这是一种常见的 向上取整除法技巧（Ceiling Division Trick），用于计算容纳指定位数所需的最小字节数（当`bits_needed`不是8的倍数时，加上7就会使得总和至少达到下一个8的倍数，从而在除以8时得到正确地向上取整的结果）
         let max_bytes_needed = (64 - self.bits_in_buffer) / 8;
         if bytes_needed > max_bytes_needed {
             bytes_needed = max_bytes_needed;
         }
-        if bytes_needed > 0 {
-            let mut buf = [0u8; 8]; // 注意这里没有用 vector（堆上分配） 而是使用了栈上分配数组，这是一个性能优化
-            let slice = &mut buf[..bytes_needed];
-            if self.inner.read(slice)? < bytes_needed {
-                return Err(BitReadWriteError::UnexpectedEof.into());
+        if bytes_needed == 0 {
+            return Ok(true);
+        }
+        let order = self.byte_order();
+        // 快速路径：BufReader 的缓冲区里已有至少 8 个字节时，直接整块载入 8 字节，只保留所需的字节
+        if self.lookahead.is_empty()
+            && let Source::Buffered(inner) = &mut self.inner
+            && let Some(chunk) = inner.buffer().first_chunk::<8>()
+        {
+            let keep = bytes_needed * 8;
+            let word = match order {
+                ByteOrder::BigEndian => {
+                    u64::from_be_bytes(*chunk) & !u64::MAX.checked_shr(keep as u32).unwrap_or(0)
+                }
+                ByteOrder::LittleEndian => {
+                    u64::from_le_bytes(*chunk) & u64::MAX.checked_shr(64 - keep as u32).unwrap_or(0)
+                }
             };
-            for &mut b in slice {
-                // 所谓低地址就是如果顺序的将一块字流读取出来，首个字节索引是 0，第二个字节索引是 1，以此类推，0 就是低地址，也就是最读到的（索引最大的那个）必然是高地址
-                // 大端序时来的数据越晚，左移的位数就越少，这样最后一个数据（最高地址数据）就在最右边（最低位）
-                // 小端序时来的数据越晚，左移的位数就越多，这样最后一个数据（最高地址数据）就在最左边（最高位）
-                let shift = match self.byte_order {
-                    ByteOrder::BigEndian => {
-                        // 大端序的低位字节存储在高地址，高位字节存储在低地址
-                        // 大端序读取时，新读到数据（高地址数据）总是放置在比特缓冲区剩余空间的最低位（最右边）
-                        let s = 64u32 - 8u32 - self.bits_in_buffer as u32; // shift = 64 - 8 - available_bits
-                        s
-                    }
-                    ByteOrder::LittleEndian => {
-                        // 小端序的低位字节存储在低地址，高位字节存储在高地址
-                        // 小端序读取时，新读到数据（高地址数据）总是要放置在比特缓冲区的最高位（最左边）
-                        let s = self.bits_in_buffer as u32;
-                        s
-                    }
-                };
-                // 将新读到数据（高地址数据）左移 shift 位，然后与比特缓冲区进行或运算，这样就是将新数据放到了比特缓冲区的最高位（最左边）
-                self.bits_buffer |= u64::from(b).wrapping_shl(shift);
-                // 更新比特缓冲区可用比特数
-                self.bits_in_buffer = (self.bits_in_buffer + 8).min(64);
+            inner.consume(bytes_needed);
+            self.bytes_read += bytes_needed as u64;
+            self.push_word(word, bytes_needed);
+            return Ok(true);
+        }
+
+        let mut buf = [0u8; 8]; // 注意这里没有用 vector（堆上分配） 而是使用了栈上分配数组，这是一个性能优化
+        let mut filled = 0;
+        // socket、管道等可能一次只返回部分字节，或被信号打断，所以要循环读取直到读满或 EOF
+        while filled < bytes_needed {
+            match self.read_inner(&mut buf[filled..bytes_needed]) {
+                Ok(0) => break, // EOF
+                Ok(count) => filled += count,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
             }
         }
-        Ok(())
+        let word = match order {
+            ByteOrder::BigEndian => u64::from_be_bytes(buf),
+            ByteOrder::LittleEndian => u64::from_le_bytes(buf),
+        };
+        self.push_word(word, filled);
+        Ok(filled == bytes_needed)
+    }
+
+    /// 将按字节序载入的 count 个字节（其余字节为 0）用一次移位放入比特缓冲区，调用方保证放得下
+    #[inline]
+    fn push_word(&mut self, word: u64, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.bits_buffer |= match self.byte_order() {
+            // 大端序缓冲区左对齐：先读到的字节在高位，新数据接在已有比特的右边
+            ByteOrder::BigEndian => word >> self.bits_in_buffer,
+            // 小端序缓冲区右对齐：先读到的字节在低位，新数据接在已有比特的左边
+            ByteOrder::LittleEndian => word << self.bits_in_buffer,
+        };
+        self.bits_in_buffer += count * 8;
     }
 
     fn get_from_bits_buffer(&mut self, n: usize, take: bool) -> std::io::Result<u64> {
-        let bit_value = match self.byte_order {
+        let bit_value = match self.byte_order() {
             ByteOrder::BigEndian => {
                 // 提取比特缓冲区高位 n 位（从左数的 n 位）
-                let value = self.bits_buffer >> (64 - n);
-                value
+                self.bits_buffer >> (64 - n)
             }
             ByteOrder::LittleEndian => {
                 // 用位掩码提取低 n 位
                 let mask = if n == 64 { u64::MAX } else { (1u64 << n) - 1 };
-                let value = self.bits_buffer & mask;
-                value
+                self.bits_buffer & mask
             }
         };
         if take {
             if n == 64 {
                 self.bits_buffer = 0;
             } else {
-                match self.byte_order {
+                match self.byte_order() {
                     ByteOrder::BigEndian => {
                         self.bits_buffer <<= n;
                     }
@@ -103,18 +512,328 @@ impl<R: Read> BitReader<R> {
     }
 }
 
-impl<R: Read> BitReader<R> {
+impl<R: Read, E: Endianness> BitReader<R, E> {
     /// Returns `true` if at byte boundary (no pending bits)
     ///
     /// When true:
     /// - `read()` operations are permitted
     /// - Next bit read will start from a fresh byte
     pub fn is_byte_aligned(&self) -> bool {
-        self.bits_in_buffer % 8 == 0
+        self.bits_in_buffer.is_multiple_of(8)
+    }
+
+    /// Reads `bit_len` bits into `buf`, packing them byte by byte
+    ///
+    /// Full bytes are stored as-is; a trailing partial byte keeps its bits where they would sit
+    /// in the stream (high bits for BigEndian, low bits for LittleEndian), so the result can be
+    /// written back unchanged with [`BitWriter::write_bits_from_slice`](crate::writer::BitWriter::write_bits_from_slice).
+    ///
+    /// # Errors
+    /// Returns error if `buf` is shorter than `bit_len` bits or not enough bits are available
+    pub fn read_bits_into_slice(&mut self, buf: &mut [u8], bit_len: usize) -> std::io::Result<()> {
+        let start = self.bits_consumed();
+        self.read_bits_into_slice_unlocated(buf, bit_len)
+            .map_err(|e| self.locate(e, start, Some(bit_len)))
+    }
+
+    /// read_bits_into_slice 的实现，错误不带位置
+    fn read_bits_into_slice_unlocated(
+        &mut self,
+        buf: &mut [u8],
+        bit_len: usize,
+    ) -> std::io::Result<()> {
+        let full_bytes = bit_len / 8;
+        let residual_bits = bit_len % 8;
+        if bit_len.div_ceil(8) > buf.len() {
+            return Err(BitReadWriteError::BufferTooSmall {
+                bits: bit_len,
+                capacity: buf.len() * 8,
+            }
+            .into());
+        }
+
+        // 用不带位置的读取，错误统一定位在整个读取的起点
+        self.read_bytes_into_unlocated(&mut buf[..full_bytes])?;
+
+        if residual_bits > 0 {
+            let value = self.read_bits_unlocated(residual_bits)? as u8;
+            buf[full_bytes] = match self.byte_order() {
+                ByteOrder::BigEndian => value << (8 - residual_bits),
+                ByteOrder::LittleEndian => value,
+            };
+        }
+        Ok(())
     }
 }
 
-impl<R: Read> BitRead for BitReader<R> {
+impl<R: Read, E: Endianness> BitReader<R, E> {
+    /// Reads `n` whole bytes, whether or not the reader is byte-aligned
+    ///
+    /// See [`read_bytes_into`](Self::read_bytes_into).
+    ///
+    /// # Errors
+    /// Returns error if not enough bits are available
+    pub fn read_bytes(&mut self, n: usize) -> std::io::Result<Vec<u8>> {
+        let mut buf = vec![0; n];
+        self.read_bytes_into(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Fills `buf` with the next `buf.len() * 8` bits, whether or not the reader is byte-aligned
+    ///
+    /// Unlike [`Read::read`], which fails with `UnalignedAccess` mid-byte, this shifts the bits
+    /// across byte boundaries: each output byte is the next 8 bits of the stream, as
+    /// `read_bits(8)` would return them. Byte-aligned reads go straight to the inner reader.
+    ///
+    /// # Errors
+    /// Returns error if not enough bits are available
+    pub fn read_bytes_into(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        let start = self.bits_consumed();
+        self.read_bytes_into_unlocated(buf)
+            .map_err(|e| self.locate(e, start, Some(buf.len() * 8)))
+    }
+
+    /// read_bytes_into 的实现，错误不带位置
+    fn read_bytes_into_unlocated(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        self.check_limit(buf.len() as u64 * 8)?;
+        if self.is_byte_aligned() {
+            // 字节对齐时直接走字节读取的快速路径，数据不足时换成带类型的 EOF 错误以便附上位置
+            return self.read_exact(buf).map_err(|e| match e.kind() {
+                ErrorKind::UnexpectedEof => BitReadWriteError::UnexpectedEof.into(),
+                _ => e,
+            });
+        }
+
+        // 非对齐时每次取 64 位再拆成字节：按各自字节序展开即为流中的字节顺序
+        let mut chunks = buf.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            let value = self.read_bits_unlocated(64)?;
+            chunk.copy_from_slice(&match self.byte_order() {
+                ByteOrder::BigEndian => value.to_be_bytes(),
+                ByteOrder::LittleEndian => value.to_le_bytes(),
+            });
+        }
+        for byte in chunks.into_remainder() {
+            *byte = self.read_bits_unlocated(8)? as u8;
+        }
+        Ok(())
+    }
+
+    /// Reads up to `n` bits (1-64), returning fewer only when the stream ends first
+    ///
+    /// Returns the bits read together with their count, which is `0` once the stream is
+    /// exhausted. The value holds the `count` bits as if `read_bits(count)` had been called, so
+    /// decoders of streams that end mid-field can drain the tail instead of failing.
+    ///
+    /// # Errors
+    /// Returns error if `n` is not between 1-64 or the inner reader fails
+    pub fn read_bits_partial(&mut self, n: usize) -> std::io::Result<(u64, usize)> {
+        let start = self.bits_consumed();
+        self.read_bits_partial_unlocated(n)
+            .map_err(|e| self.locate(e, start, Some(n)))
+    }
+
+    /// read_bits_partial 的实现，错误不带位置
+    fn read_bits_partial_unlocated(&mut self, n: usize) -> std::io::Result<(u64, usize)> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        self.check_limit(n as u64)?;
+
+        let mut value = 0u64;
+        let mut count = 0;
+        // 比特缓冲区一次放不下时分多轮读取拼接
+        while count < n {
+            let wanted = n - count;
+            self.put_available_into_bits_buffer(wanted)?;
+            let k = wanted.min(self.bits_in_buffer);
+            if k == 0 {
+                break; // EOF
+            }
+            let part = self.get_from_bits_buffer(k, true)?;
+            value = match self.byte_order() {
+                ByteOrder::BigEndian if count == 0 => part,
+                ByteOrder::BigEndian => (value << k) | part,
+                ByteOrder::LittleEndian => value | (part << count),
+            };
+            count += k;
+        }
+        Ok((value, count))
+    }
+
+    /// Reads `n` bits (1-64) assembled in `order` instead of the reader's own byte order
+    ///
+    /// Lets mixed-endian formats (e.g. a little-endian length inside a big-endian header) use a
+    /// single reader. When `order` differs from the reader's byte order, the field is read as
+    /// 8-bit groups (the last one possibly shorter) in stream order, and the groups are combined
+    /// with the first one most significant for `BigEndian`, least significant for `LittleEndian`.
+    ///
+    /// # Errors
+    /// Returns error if `n` is not between 1-64 or not enough bits are available
+    pub fn read_bits_with(&mut self, order: ByteOrder, n: usize) -> std::io::Result<u64> {
+        let start = self.bits_consumed();
+        self.read_bits_with_unlocated(order, n)
+            .map_err(|e| self.locate(e, start, Some(n)))
+    }
+
+    /// read_bits_with 的实现，错误不带位置
+    fn read_bits_with_unlocated(&mut self, order: ByteOrder, n: usize) -> std::io::Result<u64> {
+        if order == self.byte_order() {
+            return self.read_bits(n);
+        }
+        self.check_limit(n as u64)?;
+        byte_order::read_bits_in_order(self, order, n)
+    }
+
+    /// Appends everything left in the stream to `buf`, returning the exact number of bits read
+    ///
+    /// Bytes are packed like [`read_bits_into_slice`](Self::read_bits_into_slice): a trailing
+    /// partial byte keeps its bits where they sit in the stream (high bits for BigEndian, low
+    /// bits for LittleEndian), so the tail can be re-emitted with
+    /// [`BitWriter::write_bits_from_slice`](crate::writer::BitWriter::write_bits_from_slice).
+    ///
+    /// # Errors
+    /// Returns error if the inner reader fails, or `LimitExceeded` if the stream goes on past
+    /// [`max_bits`](Self::max_bits); the bits read before the error are still in `buf`
+    pub fn read_to_end_bits(&mut self, buf: &mut Vec<u8>) -> std::io::Result<usize> {
+        let start = self.bits_consumed();
+        self.read_to_end_bits_unlocated(buf)
+            .map_err(|e| self.locate(e, start, None))
+    }
+
+    /// read_to_end_bits 的实现，错误不带位置
+    fn read_to_end_bits_unlocated(&mut self, buf: &mut Vec<u8>) -> std::io::Result<usize> {
+        let mut bits = 0;
+        loop {
+            let n = match self.remaining_limit() {
+                Some(0) => {
+                    // 限额已用完：流恰好结束则正常返回，否则报错
+                    self.put_available_into_bits_buffer(1)?;
+                    self.check_limit(self.bits_in_buffer as u64)?;
+                    return Ok(bits);
+                }
+                // 保持整字节读取，只有限额内最后不足 1 字节时才读零头
+                Some(remaining) if remaining < 8 => remaining as usize,
+                Some(remaining) => (remaining.min(64) / 8 * 8) as usize,
+                None => 64,
+            };
+            let (value, count) = self.read_bits_partial(n)?;
+            if count == 0 {
+                return Ok(bits);
+            }
+            // 将读到的 count 比特按流中的顺序展开为字节
+            let bytes = match self.byte_order() {
+                ByteOrder::BigEndian => (value << (64 - count)).to_be_bytes(),
+                ByteOrder::LittleEndian => value.to_le_bytes(),
+            };
+            buf.extend_from_slice(&bytes[..count.div_ceil(8)]);
+            bits += count;
+            if count < n {
+                return Ok(bits);
+            }
+        }
+    }
+
+    /// Reads exactly `n` bits (1-64), or returns `None` if the stream ended cleanly before them
+    ///
+    /// `None` means no bits at all were left, so loop-until-EOF parsers can stop. A stream that
+    /// ends part-way through the field is a truncated field and fails with `UnexpectedEof`.
+    ///
+    /// # Errors
+    /// Returns error if `n` is not between 1-64, the field is truncated or the inner reader fails
+    pub fn read_bits_opt(&mut self, n: usize) -> std::io::Result<Option<u64>> {
+        let start = self.bits_consumed();
+        match self.read_bits_partial(n)? {
+            (_, 0) => Ok(None),
+            (value, count) if count == n => Ok(Some(value)),
+            _ => Err(self.locate(BitReadWriteError::UnexpectedEof.into(), start, Some(n))),
+        }
+    }
+}
+
+impl<R: Read, E: Endianness> BitReader<R, E> {
+    /// Skips forward bit by bit until the next `pattern_bits` bits (1-56) equal the low
+    /// `pattern_bits` bits of `pattern`, e.g. `find_sync(0xFFF, 12)` for an ADTS sync word
+    ///
+    /// The sync word itself is not consumed, so the next read starts with it. Candidates are
+    /// compared by shifting a window over the bit buffer, without a read call per bit.
+    ///
+    /// # Returns
+    /// The number of bits skipped before the match
+    ///
+    /// # Errors
+    /// - `InvalidBitCount` if `pattern_bits` is not between 1-56
+    /// - `UnexpectedEof` if the stream ends before a match; the last bits shorter than the
+    ///   pattern are left unread
+    /// - `LimitExceeded` if the match would end past [`max_bits`](Self::max_bits)
+    pub fn find_sync(&mut self, pattern: u64, pattern_bits: usize) -> std::io::Result<u64> {
+        let start = self.bits_consumed();
+        self.find_sync_unlocated(pattern, pattern_bits)
+            .map_err(|e| self.locate(e, start, Some(pattern_bits)))
+    }
+
+    /// find_sync 的实现，错误不带位置
+    fn find_sync_unlocated(&mut self, pattern: u64, pattern_bits: usize) -> std::io::Result<u64> {
+        if pattern_bits == 0 || pattern_bits > 56 {
+            return Err(BitReadWriteError::InvalidBitCount(pattern_bits).into());
+        }
+        let mask = (1u64 << pattern_bits) - 1;
+        let pattern = pattern & mask;
+
+        let mut skipped = 0u64;
+        loop {
+            // 比特缓冲区总能填到 57 位以上，足以容纳 56 位的同步字
+            self.put_available_into_bits_buffer(64)?;
+            if self.bits_in_buffer < pattern_bits {
+                return Err(BitReadWriteError::UnexpectedEof.into());
+            }
+
+            // 在比特缓冲区内逐位平移比较
+            let candidates = self.bits_in_buffer - pattern_bits + 1;
+            let found = (0..candidates).find(|&k| {
+                let window = match self.byte_order() {
+                    ByteOrder::BigEndian => (self.bits_buffer << k) >> (64 - pattern_bits),
+                    ByteOrder::LittleEndian => (self.bits_buffer >> k) & mask,
+                };
+                window == pattern
+            });
+            let k = found.unwrap_or(candidates);
+            self.check_limit((k + if found.is_some() { pattern_bits } else { 0 }) as u64)?;
+            if k > 0 {
+                self.get_from_bits_buffer(k, true)?;
+            }
+            skipped += k as u64;
+            if found.is_some() {
+                return Ok(skipped);
+            }
+        }
+    }
+
+    /// Reads `N` bits (1..=64, checked at compile time)
+    ///
+    /// Same as [`read_bits`](BitRead::read_bits) with the width fixed at compile time, so the
+    /// range check and the shifts and masks can be folded away in hot fixed-width loops.
+    #[inline]
+    pub fn read_bits_const<const N: usize>(&mut self) -> std::io::Result<u64> {
+        const { assert!(N >= 1 && N <= 64, "bit count must be between 1-64") };
+        self.read_bits(N)
+    }
+}
+
+impl<R: Read, E: Endianness> BitExpect for BitReader<R, E> {
+    fn bit_position(&self) -> u64 {
+        self.bits_consumed()
+    }
+
+    /// At the end once the source is exhausted or the [`max_bits`](BitReader::max_bits) limit
+    /// is reached
+    fn is_at_end(&mut self) -> std::io::Result<bool> {
+        Ok(self.remaining_limit() == Some(0)
+            || (self.bits_in_buffer == 0 && self.source_exhausted()?))
+    }
+}
+
+impl<R: Read, E: Endianness> BitRead for BitReader<R, E> {
     type Output = u64;
 
     /// Reads exactly `n` bits from the stream (1-64 bits)
@@ -126,22 +845,120 @@ impl<R: Read> BitRead for BitReader<R> {
     /// Bits read
     ///
     /// # Errors
-    /// Returns error if `n` is not between 1-64 or not enough bits are available
+    /// Returns error if `n` is not between 1-64 or not enough bits are available; errors of
+    /// the reader itself are [`Positioned`](BitReadWriteError::Positioned) at the bit offset of
+    /// the read
     fn read_bits(&mut self, n: usize) -> std::io::Result<Self::Output> {
-        // 校验 n
-        if n == 0 || n > 64 {
-            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        let start = self.bits_consumed();
+        self.read_bits_unlocated(n)
+            .map_err(|e| self.locate(e, start, Some(n)))
+    }
+
+    /// Reads runs of consecutive fields totalling at most 64 bits with a single `read_bits`
+    /// and splits them, so a header of small fields costs a few buffer operations
+    fn read_fields_into(&mut self, widths: &[usize], out: &mut [u64]) -> std::io::Result<()> {
+        let offset = self.bits_consumed();
+        crate::traits::check_fields(widths, out).map_err(|e| self.locate(e, offset, None))?;
+        let mut start = 0;
+        while start < widths.len() {
+            // 尽量多地合并相邻字段，总宽度不超过 64
+            let mut end = start;
+            let mut total = 0;
+            while end < widths.len() && total + widths[end] <= 64 {
+                total += widths[end];
+                end += 1;
+            }
+            let group = self.read_bits(total)?;
+            let mut used = 0;
+            for (slot, &n) in out[start..end].iter_mut().zip(&widths[start..end]) {
+                let mask = if n == 64 { u64::MAX } else { (1u64 << n) - 1 };
+                // 大端序先读的字段在高位，小端序在低位
+                let shift = match self.byte_order() {
+                    ByteOrder::BigEndian => total - used - n,
+                    ByteOrder::LittleEndian => used,
+                };
+                *slot = (group >> shift) & mask;
+                used += n;
+            }
+            start = end;
         }
+        Ok(())
+    }
+}
 
-        // 填充比特缓冲区
-        self.put_into_bits_buffer(n)?;
+impl<R: Read, E: Endianness> BitReader<R, E> {
+    /// Reads a 16-bit unsigned integer in the stream's byte order, copying whole bytes when
+    /// byte-aligned (see [`BitReadExt::read_u16`])
+    pub fn read_u16(&mut self) -> std::io::Result<u16> {
+        Ok(self.read_uint(2)? as u16)
+    }
 
-        // 从比特缓冲区取 n 比特，并且消费掉
-        self.get_from_bits_buffer(n, true)
+    /// Reads a 24-bit unsigned integer, see [`read_u16`](Self::read_u16)
+    pub fn read_u24(&mut self) -> std::io::Result<u32> {
+        Ok(self.read_uint(3)? as u32)
+    }
+
+    /// Reads a 32-bit unsigned integer, see [`read_u16`](Self::read_u16)
+    pub fn read_u32(&mut self) -> std::io::Result<u32> {
+        Ok(self.read_uint(4)? as u32)
+    }
+
+    /// Reads a 48-bit unsigned integer, see [`read_u16`](Self::read_u16)
+    pub fn read_u48(&mut self) -> std::io::Result<u64> {
+        self.read_uint(6)
+    }
+
+    /// Reads a 64-bit unsigned integer, see [`read_u16`](Self::read_u16)
+    pub fn read_u64(&mut self) -> std::io::Result<u64> {
+        self.read_uint(8)
+    }
+
+    /// Fills `out` with sign-extended 24-bit samples, converting whole blocks of bytes when
+    /// byte-aligned (see [`BitReadExt::read_i24_samples`])
+    ///
+    /// # Errors
+    /// Any error of the reader; the samples before the failing one may have been consumed
+    pub fn read_i24_samples(&mut self, out: &mut [i32]) -> std::io::Result<()> {
+        if !self.is_byte_aligned() {
+            for sample in out {
+                *sample = self.read_i24()?;
+            }
+            return Ok(());
+        }
+        // 对齐时按块读取整字节再批量转换；与比特路径一样，数据不足时报带类型的 UnexpectedEof
+        let start = self.bits_consumed();
+        self.check_limit(out.len() as u64 * 24)
+            .map_err(|e| self.locate(e, start, Some(out.len() * 24)))?;
+        let mut buf = [0u8; 3 * 256];
+        for chunk in out.chunks_mut(256) {
+            let bytes = &mut buf[..chunk.len() * 3];
+            self.read_bytes_into(bytes)?;
+            crate::pcm::decode_i24(bytes, self.byte_order(), chunk)?;
+        }
+        Ok(())
+    }
+
+    /// 读取 bytes 字节的无符号整数：字节对齐时直接读取整字节，否则走比特路径
+    fn read_uint(&mut self, bytes: usize) -> std::io::Result<u64> {
+        if !self.is_byte_aligned() {
+            return self.read_bits(bytes * 8);
+        }
+        // read_bytes_into 与比特路径报同样的错误（超出限额、带类型的 UnexpectedEof）
+        let mut buf = [0u8; 8];
+        match self.byte_order() {
+            ByteOrder::BigEndian => {
+                self.read_bytes_into(&mut buf[8 - bytes..])?;
+                Ok(u64::from_be_bytes(buf))
+            }
+            ByteOrder::LittleEndian => {
+                self.read_bytes_into(&mut buf[..bytes])?;
+                Ok(u64::from_le_bytes(buf))
+            }
+        }
     }
 }
 
-impl<R: Read> Read for BitReader<R> {
+impl<R: Read, E: Endianness> Read for BitReader<R, E> {
     /// Reads bytes from the underlying bit stream.
     ///
     /// This method behaves differently depending on the bit buffer state:
@@ -171,13 +988,26 @@ impl<R: Read> Read for BitReader<R> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         let mut written = 0;
 
+        // 设置了 max_bits 时最多读到限额为止，限额已用完则报错
+        let buf = match self.remaining_limit() {
+            Some(remaining) if (buf.len() as u64) > remaining / 8 => {
+                if remaining < 8 && !buf.is_empty() {
+                    let start = self.bits_consumed();
+                    self.check_limit(buf.len() as u64 * 8)
+                        .map_err(|e| self.locate(e, start, Some(buf.len() * 8)))?;
+                }
+                &mut buf[..(remaining / 8) as usize]
+            }
+            _ => buf,
+        };
+
         // 1) 如果完全空，直接读取
         if self.bits_in_buffer == 0 {
-            return self.inner.read(buf);
+            return self.read_inner(buf);
         }
 
         // 2) 如果有残留，但已经是整字节边界（8 的倍数），先拆 buffer
-        if self.bits_in_buffer % 8 == 0 {
+        if self.bits_in_buffer.is_multiple_of(8) {
             // 一直拆，直到 buffer 中不再有整字节或 buf 写满
             while self.bits_in_buffer >= 8 && written < buf.len() {
                 // 每次取 8 位并消费
@@ -189,7 +1019,7 @@ impl<R: Read> Read for BitReader<R> {
             // 拆完后，buffer 要么空，要么剩 <8 位（此处一定是空，因为 bits_in_buffer%8==0）
             // 剩余 buf 空间，再走一次底层读以获取后续字节
             if written < buf.len() {
-                let n = self.inner.read(&mut buf[written..])?;
+                let n = self.read_inner(&mut buf[written..])?;
                 written += n;
             }
 
@@ -197,7 +1027,299 @@ impl<R: Read> Read for BitReader<R> {
         }
 
         // 3) 剩余 bits 不是 8 的倍数 —— 非字节对齐，禁止直接读
-        Err(BitReadWriteError::UnalignedAccess.into())
+        Err(self.locate(
+            BitReadWriteError::UnalignedAccess {
+                pending_bits: self.pending_bits(),
+            }
+            .into(),
+            self.bits_consumed(),
+            None,
+        ))
+    }
+}
+
+/// 无 BufReader 的数据源上 fill_buf 每次最多预读的字节数
+const DIRECT_FILL_SIZE: usize = 8 * 1024;
+
+impl<R: Read, E: Endianness> BufRead for BitReader<R, E> {
+    /// Borrows the next bytes of the stream without copying, for byte parsers working on the
+    /// byte-aligned part of a bit stream
+    ///
+    /// Whole bytes still held in the bit buffer come first. With [`max_bits`](Self::max_bits)
+    /// set, at most the whole bytes left in the budget are returned. Readers made with
+    /// [`unbuffered`](BitReader::unbuffered) read up to 8 KiB into an internal buffer here.
+    ///
+    /// # Errors
+    /// - `UnalignedAccess` if the reader is not byte-aligned
+    /// - Any error of the inner reader
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if !self.is_byte_aligned() {
+            let start = self.bits_consumed();
+            let pending_bits = self.pending_bits();
+            return Err(self.locate(
+                BitReadWriteError::UnalignedAccess { pending_bits }.into(),
+                start,
+                None,
+            ));
+        }
+        self.spill_buffered_bytes();
+        let limit = self.remaining_limit().map_or(usize::MAX, |remaining| {
+            (remaining / 8).min(usize::MAX as u64) as usize
+        });
+
+        // 直接读取的数据源没有自己的缓冲区，预读到 lookahead 中
+        if self.lookahead.is_empty()
+            && let Source::Direct(inner) = &mut self.inner
+        {
+            self.lookahead.resize(DIRECT_FILL_SIZE, 0);
+            let count = loop {
+                match inner.read(&mut self.lookahead) {
+                    Ok(count) => break count,
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    Err(e) => {
+                        self.lookahead.clear();
+                        return Err(e);
+                    }
+                }
+            };
+            self.lookahead.truncate(count);
+        }
+        if !self.lookahead.is_empty() {
+            let len = self.lookahead.len().min(limit);
+            return Ok(&self.lookahead[..len]);
+        }
+        match &mut self.inner {
+            Source::Buffered(inner) => {
+                let buf = inner.fill_buf()?;
+                Ok(&buf[..buf.len().min(limit)])
+            }
+            Source::Direct(_) => Ok(&[]),
+        }
+    }
+
+    /// Marks `amt` bytes returned by [`fill_buf`](Self::fill_buf) as consumed
+    ///
+    /// Like `BufReader`, `amt` is clamped to the bytes already buffered (and to the
+    /// [`max_bits`](Self::max_bits) budget), so a larger `amt` never skips unread data nor
+    /// advances [`bytes_read`](Self::bytes_read) past what was actually consumed.
+    fn consume(&mut self, amt: usize) {
+        if self.is_byte_aligned() {
+            self.spill_buffered_bytes();
+        }
+        let amt = self.remaining_limit().map_or(amt, |remaining| {
+            amt.min((remaining / 8).min(usize::MAX as u64) as usize)
+        });
+        let from_lookahead = amt.min(self.lookahead.len());
+        self.lookahead.drain(..from_lookahead);
+        let mut consumed = from_lookahead;
+        if let Source::Buffered(inner) = &mut self.inner {
+            // BufReader 自己也会截断，这里按它实际缓冲的字节数计数
+            let from_inner = (amt - from_lookahead).min(inner.buffer().len());
+            inner.consume(from_inner);
+            consumed += from_inner;
+        }
+        self.bytes_read += consumed as u64;
+    }
+}
+
+// ------------------------------- Iterator sources ------------------------------- //
+
+/// [`Read`] over an iterator of bytes, see [`BitReader::from_bytes_iter`]
+#[derive(Debug)]
+pub struct ByteIterReader<I> {
+    iter: I,
+}
+
+impl<I: Iterator<Item = u8>> Read for ByteIterReader<I> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut n = 0;
+        for (slot, byte) in buf.iter_mut().zip(&mut self.iter) {
+            *slot = byte;
+            n += 1;
+        }
+        Ok(n)
+    }
+}
+
+/// [`Read`] over an iterator of byte chunks, see [`BitReader::from_chunks`]
+#[derive(Debug)]
+pub struct ChunkIterReader<I, C> {
+    iter: I,
+    chunk: Option<C>, // 当前块
+    pos: usize,       // 当前块中已读的字节数
+}
+
+impl<I, C> Read for ChunkIterReader<I, C>
+where
+    I: Iterator<Item = C>,
+    C: AsRef<[u8]>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            if let Some(chunk) = &self.chunk {
+                let rest = &chunk.as_ref()[self.pos..];
+                if !rest.is_empty() {
+                    let n = rest.len().min(buf.len());
+                    buf[..n].copy_from_slice(&rest[..n]);
+                    self.pos += n;
+                    return Ok(n);
+                }
+            }
+            // 当前块已读完（或为空块），取下一块
+            match self.iter.next() {
+                Some(chunk) => {
+                    self.chunk = Some(chunk);
+                    self.pos = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> BitReader<ByteIterReader<I>> {
+    /// Creates a reader over bytes produced one at a time, e.g. by a generator
+    pub fn from_bytes_iter<T>(byte_order: ByteOrder, bytes: T) -> Self
+    where
+        T: IntoIterator<IntoIter = I>,
+    {
+        let iter = bytes.into_iter();
+        Self::from_source(byte_order, Source::Direct(ByteIterReader { iter }))
+    }
+}
+
+impl<I, C> BitReader<ChunkIterReader<I, C>>
+where
+    I: Iterator<Item = C>,
+    C: AsRef<[u8]>,
+{
+    /// Creates a reader over a sequence of byte chunks (`&[u8]`, `Vec<u8>`, `Bytes`, …)
+    ///
+    /// The chunks are read in turn as one continuous stream, so fields may span chunk
+    /// boundaries, and each chunk is copied straight into the bit buffer without first being
+    /// concatenated. Empty chunks are skipped; the stream ends when the iterator does.
+    pub fn from_chunks<T>(byte_order: ByteOrder, chunks: T) -> Self
+    where
+        T: IntoIterator<IntoIter = I>,
+    {
+        let iter = chunks.into_iter();
+        Self::from_source(
+            byte_order,
+            Source::Direct(ChunkIterReader {
+                iter,
+                chunk: None,
+                pos: 0,
+            }),
+        )
+    }
+}
+
+// ------------------------------- Word-swapping source ------------------------------- //
+
+/// Size of the words a [`WordSwapReader`] reverses
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WordSize {
+    Bits16,
+    Bits32,
+    Bits64,
+}
+
+impl WordSize {
+    /// Number of bytes per word
+    pub fn bytes(self) -> usize {
+        match self {
+            WordSize::Bits16 => 2,
+            WordSize::Bits32 => 4,
+            WordSize::Bits64 => 8,
+        }
+    }
+}
+
+/// [`Read`] adapter that reverses the bytes of every 16/32/64-bit word of the inner stream,
+/// for captures written by middle-endian DSPs or DMA engines that store a byte stream in
+/// native-endian words
+///
+/// Words are counted from the first byte of the inner stream. A trailing partial word at the
+/// end of the stream is passed through unswapped.
+#[derive(Debug)]
+pub struct WordSwapReader<R> {
+    inner: R,
+    size: usize,
+    word: [u8; 8], // 调用方缓冲区装不下整字时暂存的已翻转字
+    pos: usize,
+    len: usize,
+}
+
+impl<R: Read> WordSwapReader<R> {
+    pub fn new(inner: R, word_size: WordSize) -> Self {
+        Self {
+            inner,
+            size: word_size.bytes(),
+            word: [0; 8],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the inner reader; bytes of a partially consumed word are lost
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// 读到 buf 中已有字节数是字长的整数倍（且至少一个字）或到达 EOF 为止
+    fn read_words(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut filled = 0usize;
+        while filled == 0 || !filled.is_multiple_of(self.size) {
+            match self.inner.read(&mut buf[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        for word in buf[..filled].chunks_exact_mut(self.size) {
+            word.reverse();
+        }
+        Ok(filled)
+    }
+}
+
+impl<R: Read> Read for WordSwapReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.pos == self.len {
+            if buf.len() >= self.size {
+                // 整字直接读进调用方缓冲区并就地翻转
+                let whole = buf.len() / self.size * self.size;
+                return self.read_words(&mut buf[..whole]);
+            }
+            let mut word = [0u8; 8];
+            let size = self.size;
+            self.len = self.read_words(&mut word[..size])?;
+            self.word = word;
+            self.pos = 0;
+        }
+        let n = (self.len - self.pos).min(buf.len());
+        buf[..n].copy_from_slice(&self.word[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl<R: Read> BitReader<WordSwapReader<R>> {
+    /// Creates a reader that parses `inner` after reversing the bytes of every `word_size`
+    /// word, see [`WordSwapReader`]
+    pub fn with_word_swap(byte_order: ByteOrder, word_size: WordSize, inner: R) -> Self {
+        Self::with_byte_order(byte_order, WordSwapReader::new(inner, word_size))
     }
 }
 
@@ -207,6 +1329,14 @@ pub struct PeekableBitReader<R: Read> {
     inner: BitReader<R>,
 }
 
+impl<R: Read + Clone> Clone for PeekableBitReader<R> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
 impl<R: Read> PeekableBitReader<R> {
     pub fn new(inner: R) -> Self {
         Self {
@@ -219,6 +1349,53 @@ impl<R: Read> PeekableBitReader<R> {
             inner: BitReader::with_byte_order(ByteOrder::LittleEndian, inner),
         }
     }
+
+    /// Limits the reader to `limit` bits, see [`BitReader::max_bits`]
+    ///
+    /// Peeks count against the limit like reads do: peeking past it fails with
+    /// `LimitExceeded` even though nothing is consumed.
+    pub fn max_bits(mut self, limit: u64) -> Self {
+        self.inner = self.inner.max_bits(limit);
+        self
+    }
+
+    /// Copies the next `buf.len()` bytes into `buf` without consuming them
+    ///
+    /// Lets demuxers sniff magic numbers before choosing a parser. Bytes beyond the bit buffer
+    /// are read ahead from the source and kept until the following reads consume them.
+    ///
+    /// # Errors
+    /// - `UnalignedAccess` if the reader is not byte-aligned
+    /// - `UnexpectedEof` if fewer than `buf.len()` bytes are left
+    pub fn peek_bytes(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        let start = self.inner.bits_consumed();
+        let result = if self.inner.is_byte_aligned() {
+            self.inner
+                .check_limit(buf.len() as u64 * 8)
+                .and_then(|()| self.inner.peek_bytes(buf))
+        } else {
+            Err(BitReadWriteError::UnalignedAccess {
+                pending_bits: self.inner.pending_bits(),
+            }
+            .into())
+        };
+        result.map_err(|e| self.inner.locate(e, start, Some(buf.len() * 8)))
+    }
+
+    /// peek_bits 的实现，错误不带位置
+    fn peek_bits_unlocated(&mut self, n: usize) -> std::io::Result<u64> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        // 预览也不能越过 max_bits 限额
+        self.inner.check_limit(n as u64)?;
+
+        // 填充比特缓冲区
+        self.inner.put_into_bits_buffer(n)?;
+
+        // 从比特缓冲区取 n 比特，但是并不消费掉
+        self.inner.get_from_bits_buffer(n, false)
+    }
 }
 
 impl<R: Read> BitRead for PeekableBitReader<R> {
@@ -233,15 +1410,9 @@ impl<R: Read> BitPeek for PeekableBitReader<R> {
     type Output = u64;
 
     fn peek_bits(&mut self, n: usize) -> std::io::Result<Self::Output> {
-        if n == 0 || n > 64 {
-            return Err(BitReadWriteError::InvalidBitCount(n).into());
-        }
-
-        // 填充比特缓冲区
-        self.inner.put_into_bits_buffer(n)?;
-
-        // 从比特缓冲区取 n 比特，但是并不消费掉
-        self.inner.get_from_bits_buffer(n, false)
+        let start = self.inner.bits_consumed();
+        self.peek_bits_unlocated(n)
+            .map_err(|e| self.inner.locate(e, start, Some(n)))
     }
 }
 
@@ -263,22 +1434,241 @@ impl<R: Read> BulkBitReader<R> {
             inner: BitReader::with_byte_order(endianness, inner),
         }
     }
-}
-
-impl<R: Read> BitRead for BulkBitReader<R> {
-    type Output = Vec<u64>;
 
-    fn read_bits(&mut self, n: usize) -> std::io::Result<Self::Output> {
+    /// Reads `n` bits into caller-provided 64-bit chunks without allocating
+    ///
+    /// Uses the same layout as [`BitRead::read_bits`]: full chunks carry 64 bits and the last
+    /// chunk carries the remaining bits in its low bits.
+    ///
+    /// # Returns
+    /// Number of chunks filled
+    ///
+    /// # Errors
+    /// Returns error if `n` is 0, `chunks` cannot hold `n` bits, or not enough bits are available
+    pub fn read_bits_into(&mut self, chunks: &mut [u64], n: usize) -> std::io::Result<usize> {
         if n == 0 {
             return Err(BitReadWriteError::InvalidBitCount(n).into());
         }
+        let chunk_count = n.div_ceil(64);
+        if chunk_count > chunks.len() {
+            return Err(BitReadWriteError::BufferTooSmall {
+                bits: n,
+                capacity: chunks.len() * 64,
+            }
+            .into());
+        }
         let mut remaining = n;
-        let mut chunks = Vec::with_capacity((n + 63) / 64);
-        while remaining > 0 {
+        for chunk in &mut chunks[..chunk_count] {
             let take = remaining.min(64);
-            chunks.push(self.inner.read_bits(take)?);
+            *chunk = self.inner.read_bits(take)?;
             remaining -= take;
         }
+        Ok(chunk_count)
+    }
+
+    /// Reads `n` bits into a caller-provided byte buffer without allocating
+    ///
+    /// See [`BitReader::read_bits_into_slice`] for the byte layout.
+    pub fn read_bits_into_bytes(&mut self, buf: &mut [u8], n: usize) -> std::io::Result<()> {
+        self.inner.read_bits_into_slice(buf, n)
+    }
+}
+
+impl<R: Read> BitRead for BulkBitReader<R> {
+    type Output = Vec<u64>;
+
+    fn read_bits(&mut self, n: usize) -> std::io::Result<Self::Output> {
+        let mut chunks = vec![0u64; n.div_ceil(64)];
+        self.read_bits_into(&mut chunks, n)?;
         Ok(chunks)
     }
 }
+
+// ------------------------------- CompactBitReader ------------------------------- //
+
+/// Small bit reader with a 32-bit bit buffer and no internal `BufReader`, for 32-bit and
+/// embedded targets where 64-bit shifts are split into several instructions
+///
+/// It implements [`BitRead`] and [`Read`] like [`BitReader`], so the typed and coded reads
+/// (`read_u16`, `read_ue`, ...) work unchanged. Reads of up to 32 bits only use 32-bit
+/// operations; wider ones are assembled from two halves. Each refill reads just the bytes it
+/// needs straight from `inner`, so give it a source that is cheap to read from (a slice, a
+/// peripheral FIFO, your own buffer) and nothing is read ahead of the bits consumed.
+pub struct CompactBitReader<R: Read> {
+    byte_order: ByteOrder,
+    inner: R,
+    bits_buffer: u32,      // 大端序左对齐，小端序右对齐，与 BitReader 相同
+    bits_in_buffer: usize, // 当前比特缓冲区中持有的比特数
+    bytes_read: u64,       // 已从 inner 读取的字节数，用于给错误附上位置
+}
+
+impl<R: Read> CompactBitReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_byte_order(ByteOrder::BigEndian, inner)
+    }
+
+    pub fn with_byte_order(byte_order: ByteOrder, inner: R) -> Self {
+        Self {
+            byte_order,
+            inner,
+            bits_buffer: 0,
+            bits_in_buffer: 0,
+            bytes_read: 0,
+        }
+    }
+
+    pub fn is_byte_aligned(&self) -> bool {
+        self.bits_in_buffer.is_multiple_of(8)
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the inner reader; bits still in the bit buffer are lost
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// 已消费的比特数
+    fn bits_consumed(&self) -> u64 {
+        self.bytes_read * 8 - self.bits_in_buffer as u64
+    }
+
+    /// 给从 start 开始、请求 requested 比特的操作失败附上位置，规则与 BitReader 相同
+    fn locate(&self, err: std::io::Error, start: u64, requested: Option<usize>) -> std::io::Error {
+        let available = match error::typed_root(&err) {
+            Some(BitReadWriteError::UnexpectedEof) => {
+                Some(self.bits_consumed() - start + self.bits_in_buffer as u64)
+            }
+            _ => None,
+        };
+        error::locate(err, start, requested, available)
+    }
+
+    /// 补充比特缓冲区直到至少有 n 比特（或再放不下一个字节），只读取所需的字节
+    fn refill(&mut self, n: usize) -> std::io::Result<()> {
+        let bytes_needed = n
+            .saturating_sub(self.bits_in_buffer)
+            .div_ceil(8)
+            .min((32 - self.bits_in_buffer) / 8);
+        let mut buf = [0u8; 4];
+        let mut filled = 0;
+        while filled < bytes_needed {
+            match self.inner.read(&mut buf[filled..bytes_needed]) {
+                Ok(0) => break,
+                Ok(count) => filled += count,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        // 先放入已读到的字节，EOF 时这些比特不会丢失
+        self.bytes_read += filled as u64;
+        for &byte in &buf[..filled] {
+            self.bits_buffer |= match self.byte_order {
+                ByteOrder::BigEndian => (byte as u32) << (24 - self.bits_in_buffer),
+                ByteOrder::LittleEndian => (byte as u32) << self.bits_in_buffer,
+            };
+            self.bits_in_buffer += 8;
+        }
+        if filled < bytes_needed {
+            return Err(BitReadWriteError::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+
+    /// 从比特缓冲区取出 n 比特，调用方保证 1 <= n <= bits_in_buffer
+    fn take(&mut self, n: usize) -> u32 {
+        let value = match self.byte_order {
+            ByteOrder::BigEndian => self.bits_buffer >> (32 - n),
+            ByteOrder::LittleEndian => self.bits_buffer & (u32::MAX >> (32 - n)),
+        };
+        self.bits_buffer = match self.byte_order {
+            ByteOrder::BigEndian => self.bits_buffer.checked_shl(n as u32),
+            ByteOrder::LittleEndian => self.bits_buffer.checked_shr(n as u32),
+        }
+        .unwrap_or(0);
+        self.bits_in_buffer -= n;
+        value
+    }
+
+    /// 读取 1-32 比特，只用 32 位运算
+    fn read_small(&mut self, n: usize) -> std::io::Result<u32> {
+        self.refill(n)?;
+        if self.bits_in_buffer >= n {
+            return Ok(self.take(n));
+        }
+        // 缓冲区中的零头（25-31 比特）再放不下一个字节：分两段读取再拼接
+        let first_bits = self.bits_in_buffer;
+        let first = self.take(first_bits);
+        let second_bits = n - first_bits;
+        self.refill(second_bits)?;
+        let second = self.take(second_bits);
+        Ok(match self.byte_order {
+            ByteOrder::BigEndian => (first << second_bits) | second,
+            ByteOrder::LittleEndian => first | (second << first_bits),
+        })
+    }
+
+    /// read_bits 的实现，错误不带位置
+    fn read_bits_unlocated(&mut self, n: usize) -> std::io::Result<u64> {
+        if n == 0 || n > 64 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        if n <= 32 {
+            return Ok(self.read_small(n)? as u64);
+        }
+        // 超过 32 比特时按字节序拆成高低两半
+        Ok(match self.byte_order {
+            ByteOrder::BigEndian => {
+                let high = self.read_small(n - 32)? as u64;
+                (high << 32) | self.read_small(32)? as u64
+            }
+            ByteOrder::LittleEndian => {
+                let low = self.read_small(32)? as u64;
+                low | (self.read_small(n - 32)? as u64) << 32
+            }
+        })
+    }
+}
+
+impl<R: Read> BitRead for CompactBitReader<R> {
+    type Output = u64;
+
+    /// Reads `n` bits (1-64); errors are [`Positioned`](BitReadWriteError::Positioned) at the
+    /// bit offset of the read like [`BitReader`]'s
+    fn read_bits(&mut self, n: usize) -> std::io::Result<Self::Output> {
+        let start = self.bits_consumed();
+        self.read_bits_unlocated(n)
+            .map_err(|e| self.locate(e, start, Some(n)))
+    }
+}
+
+impl<R: Read> Read for CompactBitReader<R> {
+    /// Reads bytes once the reader is byte-aligned, draining whole buffered bytes first
+    ///
+    /// # Errors
+    /// `UnalignedAccess` if a partial byte is buffered
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if !self.is_byte_aligned() {
+            let start = self.bits_consumed();
+            let pending_bits = self.bits_in_buffer % 8;
+            return Err(self.locate(
+                BitReadWriteError::UnalignedAccess { pending_bits }.into(),
+                start,
+                None,
+            ));
+        }
+        let mut written = 0;
+        while self.bits_in_buffer >= 8 && written < buf.len() {
+            buf[written] = self.take(8) as u8;
+            written += 1;
+        }
+        if written < buf.len() {
+            let count = self.inner.read(&mut buf[written..])?;
+            self.bytes_read += count as u64;
+            written += count;
+        }
+        Ok(written)
+    }
+}
@@ -0,0 +1,19 @@
+//! Bit reversal, for fields transmitted LSB-first inside an otherwise MSB-first stream
+//!
+//! Use [`BitReadExt::read_bits_reversed`] / [`BitWriteExt::write_bits_reversed`] to read and write
+//! such fields directly.
+//!
+//! [`BitReadExt::read_bits_reversed`]: crate::traits::BitReadExt::read_bits_reversed
+//! [`BitWriteExt::write_bits_reversed`]: crate::traits::BitWriteExt::write_bits_reversed
+
+/// Reverses the order of the low `n` bits (0-64) of `value`; higher bits are dropped
+///
+/// # Panics
+/// Panics if `n` is greater than 64
+pub fn reverse_bits(value: u64, n: usize) -> u64 {
+    assert!(n <= 64, "cannot reverse more than 64 bits, got {}", n);
+    if n == 0 {
+        return 0;
+    }
+    value.reverse_bits() >> (64 - n)
+}
@@ -0,0 +1,406 @@
+//! Runtime schema-driven record reader
+//!
+//! A [`BitLayout`] is a list of named [`Field`]s built at runtime, so tools can parse
+//! user-described formats without recompiling. Each field has:
+//! - a width of 1-64 bits, unsigned or two's complement signed
+//! - an optional byte order overriding the stream's; such a field is read in 8-bit groups
+//!   assembled in that order
+//! - an optional repeat count, fixed or taken from an earlier field
+//! - an optional [`Condition`] on an earlier field; the field is absent when it does not hold
+//!
+//! Fields are read in order. References to earlier fields resolve to the nearest preceding
+//! field with that name that is present in the record.
+//!
+//! # Text description
+//!
+//! [`BitLayout::parse`] (or `str::parse`) compiles a whitespace-separated list of
+//! `name:type[count]?condition` fields, e.g.
+//! `"version:u4 flags:u3 pad:1 length:u16le payload:bytes[length] ext:u8?flags"`:
+//!
+//! | Part        | Syntax                                                                   |
+//! |-------------|--------------------------------------------------------------------------|
+//! | `type`      | `uN` unsigned, `iN` signed, bare `N` unsigned, `bytes` (`u8` elements)   |
+//! | byte order  | `le` / `be` suffix on `uN` / `iN`, e.g. `u16le`                          |
+//! | `[count]`   | optional; a number or the name of an earlier field                       |
+//! | `?condition`| optional; `?field` (non-zero), `?field=N` or `?field!=N`                 |
+
+use crate::byte_order::{ByteOrder, read_bits_in_order};
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, sign_extend};
+use std::collections::HashMap;
+use std::io::{self, Result};
+use std::str::FromStr;
+
+/// How many times a field is repeated
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Repeat {
+    /// A single scalar value
+    Once,
+    /// An array of a fixed number of elements
+    Fixed(usize),
+    /// An array whose length is the value of an earlier field
+    Field(String),
+}
+
+/// Presence test of a field against an earlier field's value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Condition {
+    /// The earlier field is present and equals the value
+    Equals(String, u64),
+    /// The earlier field is present and does not equal the value
+    NotEquals(String, u64),
+    /// The earlier field is present and not zero
+    NonZero(String),
+}
+
+impl Condition {
+    fn field(&self) -> &str {
+        match self {
+            Condition::Equals(name, _) | Condition::NotEquals(name, _) => name,
+            Condition::NonZero(name) => name,
+        }
+    }
+
+    /// 引用的字段缺失或是数组时条件不成立
+    fn holds(&self, value: Option<&Value>) -> bool {
+        let Some(value) = value.and_then(Value::as_u64) else {
+            return false;
+        };
+        match self {
+            Condition::Equals(_, expected) => value == *expected,
+            Condition::NotEquals(_, expected) => value != *expected,
+            Condition::NonZero(_) => value != 0,
+        }
+    }
+}
+
+/// One named field of a [`BitLayout`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+    /// Width in bits (1-64)
+    pub bits: usize,
+    /// Whether the field is two's complement
+    pub signed: bool,
+    /// Byte order of the field, `None` for the stream's own order
+    pub byte_order: Option<ByteOrder>,
+    pub repeat: Repeat,
+    pub condition: Option<Condition>,
+}
+
+impl Field {
+    /// An unsigned field of `bits` bits
+    pub fn unsigned(name: &str, bits: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            bits,
+            signed: false,
+            byte_order: None,
+            repeat: Repeat::Once,
+            condition: None,
+        }
+    }
+
+    /// A two's complement signed field of `bits` bits
+    pub fn signed(name: &str, bits: usize) -> Self {
+        Self {
+            signed: true,
+            ..Self::unsigned(name, bits)
+        }
+    }
+
+    /// Reads the field in `byte_order` instead of the stream's order
+    pub fn byte_order(mut self, byte_order: ByteOrder) -> Self {
+        self.byte_order = Some(byte_order);
+        self
+    }
+
+    /// Makes the field an array of `count` elements
+    pub fn repeat(mut self, count: usize) -> Self {
+        self.repeat = Repeat::Fixed(count);
+        self
+    }
+
+    /// Makes the field an array whose length is the value of the earlier field `name`
+    pub fn repeat_by(mut self, name: &str) -> Self {
+        self.repeat = Repeat::Field(name.to_string());
+        self
+    }
+
+    /// Reads the field only when `condition` holds
+    pub fn when(mut self, condition: Condition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    fn read_scalar<R>(&self, reader: &mut R) -> Result<Value>
+    where
+        R: BitRead<Output = u64> + ?Sized,
+    {
+        let raw = match self.byte_order {
+            Some(order) => read_bits_in_order(reader, order, self.bits)?,
+            None => reader.read_bits(self.bits)?,
+        };
+        Ok(if self.signed {
+            Value::Signed(sign_extend(raw, self.bits))
+        } else {
+            Value::Unsigned(raw)
+        })
+    }
+}
+
+/// A decoded field value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    Unsigned(u64),
+    Signed(i64),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    /// The value as `u64`, if it is a scalar that is not negative
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Value::Unsigned(v) => Some(v),
+            Value::Signed(v) => u64::try_from(v).ok(),
+            Value::Array(_) => None,
+        }
+    }
+
+    /// The value as `i64`, if it is a scalar that fits
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Value::Unsigned(v) => i64::try_from(v).ok(),
+            Value::Signed(v) => Some(v),
+            Value::Array(_) => None,
+        }
+    }
+
+    /// The elements, if the value is an array
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// An ordered list of fields describing one record
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitLayout {
+    fields: Vec<Field>,
+}
+
+impl BitLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a field
+    pub fn field(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Reads one record, returning one entry per field in layout order (`None` for fields
+    /// whose condition does not hold)
+    ///
+    /// # Errors
+    /// - `InvalidBitCount` if a field width is not between 1-64
+    /// - `InvalidInput` if a repeat count refers to a field that is absent, an array, or negative
+    /// - Any error of the reader, e.g. `UnexpectedEof` when the stream is too short
+    pub fn read_vec<R>(&self, reader: &mut R) -> Result<Vec<Option<Value>>>
+    where
+        R: BitRead<Output = u64> + ?Sized,
+    {
+        let mut values: Vec<Option<Value>> = Vec::with_capacity(self.fields.len());
+        for field in &self.fields {
+            if field.bits == 0 || field.bits > 64 {
+                return Err(BitReadWriteError::InvalidBitCount(field.bits).into());
+            }
+            let lookup = |name: &str| {
+                self.fields[..values.len()]
+                    .iter()
+                    .zip(&values)
+                    .rev()
+                    .find(|(f, v)| f.name == name && v.is_some())
+                    .and_then(|(_, v)| v.as_ref())
+            };
+            if let Some(condition) = &field.condition
+                && !condition.holds(lookup(condition.field()))
+            {
+                values.push(None);
+                continue;
+            }
+            let count = match &field.repeat {
+                Repeat::Once => None,
+                Repeat::Fixed(count) => Some(*count),
+                Repeat::Field(name) => {
+                    let count = lookup(name).and_then(Value::as_u64).ok_or_else(|| {
+                        io::Error::from(BitReadWriteError::NotACount {
+                            field: field.name.clone(),
+                            count: name.clone(),
+                        })
+                    })?;
+                    Some(count as usize)
+                }
+            };
+            let value = match count {
+                None => field.read_scalar(reader)?,
+                Some(count) => {
+                    // 计数来自数据流，不按它预分配
+                    let mut items = Vec::new();
+                    for _ in 0..count {
+                        items.push(field.read_scalar(reader)?);
+                    }
+                    Value::Array(items)
+                }
+            };
+            values.push(Some(value));
+        }
+        Ok(values)
+    }
+
+    /// Reads one record into a map from field name to value, leaving out absent fields
+    ///
+    /// When several present fields share a name, the last one wins.
+    ///
+    /// # Errors
+    /// See [`read_vec`](Self::read_vec)
+    pub fn read_map<R>(&self, reader: &mut R) -> Result<HashMap<String, Value>>
+    where
+        R: BitRead<Output = u64> + ?Sized,
+    {
+        let values = self.read_vec(reader)?;
+        Ok(self
+            .fields
+            .iter()
+            .zip(values)
+            .filter_map(|(field, value)| value.map(|v| (field.name.clone(), v)))
+            .collect())
+    }
+}
+
+impl BitLayout {
+    /// Compiles a text description (see the [module docs](self)) into a layout
+    ///
+    /// # Errors
+    /// - `InvalidInput` if a field is malformed, naming the offending field
+    pub fn parse(text: &str) -> Result<Self> {
+        text.split_whitespace()
+            .try_fold(Self::new(), |layout, token| {
+                Ok(layout.field(parse_field(token)?))
+            })
+    }
+}
+
+impl FromStr for BitLayout {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+/// 字段语法错误，错误中带上整个字段文本
+fn syntax_error(variant: fn(String) -> BitReadWriteError, token: &str) -> io::Error {
+    variant(token.to_string()).into()
+}
+
+/// 解析单个 `name:type[count]?condition` 字段
+fn parse_field(token: &str) -> Result<Field> {
+    let (name, rest) = token
+        .split_once(':')
+        .ok_or_else(|| syntax_error(BitReadWriteError::MissingFieldType, token))?;
+    if name.is_empty() {
+        return Err(syntax_error(BitReadWriteError::EmptyFieldName, token));
+    }
+    let (rest, condition) = match rest.split_once('?') {
+        Some((rest, condition)) => (rest, Some(parse_condition(token, condition)?)),
+        None => (rest, None),
+    };
+    let (ty, repeat) = match rest.split_once('[') {
+        Some((ty, count)) => {
+            let count = count
+                .strip_suffix(']')
+                .filter(|c| !c.is_empty())
+                .ok_or_else(|| syntax_error(BitReadWriteError::InvalidRepeatCount, token))?;
+            let repeat = match count.parse::<usize>() {
+                Ok(n) => Repeat::Fixed(n),
+                Err(_) => Repeat::Field(count.to_string()),
+            };
+            (ty, repeat)
+        }
+        None => (rest, Repeat::Once),
+    };
+
+    let mut field = if ty == "bytes" {
+        if repeat == Repeat::Once {
+            return Err(syntax_error(BitReadWriteError::MissingByteCount, token));
+        }
+        Field::unsigned(name, 8)
+    } else {
+        // 可选的 le / be 后缀
+        let (ty, byte_order) = if let Some(ty) = ty.strip_suffix("le") {
+            (ty, Some(ByteOrder::LittleEndian))
+        } else if let Some(ty) = ty.strip_suffix("be") {
+            (ty, Some(ByteOrder::BigEndian))
+        } else {
+            (ty, None)
+        };
+        let (signed, width) = match ty.as_bytes().first() {
+            Some(b'u') => (false, &ty[1..]),
+            Some(b'i') => (true, &ty[1..]),
+            _ if byte_order.is_none() => (false, ty),
+            _ => {
+                return Err(syntax_error(
+                    BitReadWriteError::ByteOrderNeedsInteger,
+                    token,
+                ));
+            }
+        };
+        let bits = width
+            .parse::<usize>()
+            .ok()
+            .filter(|bits| (1..=64).contains(bits))
+            .ok_or_else(|| syntax_error(BitReadWriteError::InvalidFieldWidth, token))?;
+        let field = if signed {
+            Field::signed(name, bits)
+        } else {
+            Field::unsigned(name, bits)
+        };
+        match byte_order {
+            Some(order) => field.byte_order(order),
+            None => field,
+        }
+    };
+    field.repeat = repeat;
+    field.condition = condition;
+    Ok(field)
+}
+
+fn parse_condition(token: &str, condition: &str) -> Result<Condition> {
+    let parse_value = |value: &str| {
+        value
+            .parse::<u64>()
+            .map_err(|_| syntax_error(BitReadWriteError::InvalidConditionValue, token))
+    };
+    let condition = if let Some((name, value)) = condition.split_once("!=") {
+        Condition::NotEquals(name.to_string(), parse_value(value)?)
+    } else if let Some((name, value)) = condition.split_once('=') {
+        Condition::Equals(name.to_string(), parse_value(value)?)
+    } else {
+        Condition::NonZero(condition.to_string())
+    };
+    if condition.field().is_empty() {
+        return Err(syntax_error(
+            BitReadWriteError::MissingConditionField,
+            token,
+        ));
+    }
+    Ok(condition)
+}
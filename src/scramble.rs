@@ -0,0 +1,192 @@
+//! LFSR scramblers as [`BitWrite`] / [`BitRead`] adapters
+//!
+//! [`Lfsr`] is a Fibonacci shift register of `degree` stages with a configurable feedback
+//! polynomial. Bit `k - 1` of `taps` stands for the term `x^k` (the constant term is implied),
+//! and bit `k - 1` of the state is stage `k`, the value shifted in `k` clocks ago. DVB energy
+//! dispersal (`1 + x^14 + x^15`) is `taps = 0x6000`, `degree = 15`; 802.15.4 / BLE style PN9
+//! and PN7 whitening only differ in polynomial and seed.
+//!
+//! [`Scrambling::Additive`] XORs the data with the register output and needs both ends to
+//! start from the same seed. [`Scrambling::Multiplicative`] feeds the scrambled bits back into
+//! the register, so the descrambler synchronizes itself after `degree` bits whatever its seed.
+
+use crate::error::{BitReadWriteError, check_bit_count};
+use crate::traits::{BitRead, BitWrite};
+use std::io::Result;
+
+/// Fibonacci linear-feedback shift register
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lfsr {
+    taps: u64,
+    degree: usize,
+    state: u64,
+}
+
+impl Lfsr {
+    /// Creates a register of `degree` stages (1..=64); only the low `degree` bits of `seed` are
+    /// kept
+    ///
+    /// # Errors
+    /// - `InvalidBitCount` if `degree` is not in 1..=64
+    /// - `InvalidInput` if `taps` is zero or has bits at or above `degree`
+    pub fn new(taps: u64, degree: usize, seed: u64) -> Result<Self> {
+        check_bit_count(degree, 64)?;
+        let mask = Self::mask(degree);
+        if taps == 0 || taps & !mask != 0 {
+            return Err(BitReadWriteError::InvalidTaps { taps, degree }.into());
+        }
+        Ok(Self {
+            taps,
+            degree,
+            state: seed & mask,
+        })
+    }
+
+    fn mask(degree: usize) -> u64 {
+        if degree == 64 {
+            u64::MAX
+        } else {
+            (1u64 << degree) - 1
+        }
+    }
+
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// Reloads the register, e.g. at the start of each DVB super-frame
+    pub fn set_state(&mut self, seed: u64) {
+        self.state = seed & Self::mask(self.degree);
+    }
+
+    /// 抽头位置的异或
+    fn feedback(&self) -> bool {
+        (self.state & self.taps).count_ones() & 1 == 1
+    }
+
+    /// 移入一个比特
+    fn shift(&mut self, bit: bool) {
+        self.state = ((self.state << 1) | bit as u64) & Self::mask(self.degree);
+    }
+
+    /// Clocks the register once and returns its output bit
+    pub fn next_bit(&mut self) -> bool {
+        let bit = self.feedback();
+        self.shift(bit);
+        bit
+    }
+
+    /// Clocks the register `n` times (1..=64) and returns the output, first bit most significant
+    ///
+    /// # Errors
+    /// `InvalidBitCount` if `n` is not in 1..=64
+    pub fn next_bits(&mut self, n: usize) -> Result<u64> {
+        check_bit_count(n, 64)?;
+        Ok((0..n).fold(0, |value, _| (value << 1) | self.next_bit() as u64))
+    }
+}
+
+/// How the register output is combined with the data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scrambling {
+    /// Data XOR a free-running register sequence (synchronous scrambler)
+    Additive,
+    /// Data XOR register feedback, with the scrambled bits shifted into the register
+    /// (self-synchronizing scrambler)
+    Multiplicative,
+}
+
+/// Scrambles each bit written before passing it to the inner writer
+#[derive(Debug)]
+pub struct Scrambler<W> {
+    inner: W,
+    lfsr: Lfsr,
+    mode: Scrambling,
+}
+
+impl<W: BitWrite> Scrambler<W> {
+    pub fn new(inner: W, lfsr: Lfsr, mode: Scrambling) -> Self {
+        Self { inner, lfsr, mode }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn lfsr_mut(&mut self) -> &mut Lfsr {
+        &mut self.lfsr
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: BitWrite> BitWrite for Scrambler<W> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        check_bit_count(n, 64)?;
+        let mut out = 0u64;
+        for i in (0..n).rev() {
+            let bit = (value >> i) & 1 == 1;
+            let scrambled = match self.mode {
+                Scrambling::Additive => bit ^ self.lfsr.next_bit(),
+                Scrambling::Multiplicative => {
+                    let scrambled = bit ^ self.lfsr.feedback();
+                    self.lfsr.shift(scrambled);
+                    scrambled
+                }
+            };
+            out = (out << 1) | scrambled as u64;
+        }
+        self.inner.write_bits(out, n)
+    }
+}
+
+/// Descrambles each bit read from the inner reader
+#[derive(Debug)]
+pub struct Descrambler<R> {
+    inner: R,
+    lfsr: Lfsr,
+    mode: Scrambling,
+}
+
+impl<R: BitRead<Output = u64>> Descrambler<R> {
+    pub fn new(inner: R, lfsr: Lfsr, mode: Scrambling) -> Self {
+        Self { inner, lfsr, mode }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn lfsr_mut(&mut self) -> &mut Lfsr {
+        &mut self.lfsr
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BitRead<Output = u64>> BitRead for Descrambler<R> {
+    type Output = u64;
+
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        check_bit_count(n, 64)?;
+        let raw = self.inner.read_bits(n)?;
+        let mut value = 0u64;
+        for i in (0..n).rev() {
+            let bit = (raw >> i) & 1 == 1;
+            let plain = match self.mode {
+                Scrambling::Additive => bit ^ self.lfsr.next_bit(),
+                Scrambling::Multiplicative => {
+                    let plain = bit ^ self.lfsr.feedback();
+                    self.lfsr.shift(bit);
+                    plain
+                }
+            };
+            value = (value << 1) | plain as u64;
+        }
+        Ok(value)
+    }
+}
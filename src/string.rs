@@ -0,0 +1,288 @@
+//! String fields on top of [`BitRead`] / [`BitWrite`]
+//!
+//! Every byte is transferred as an 8-bit field, so the helpers work at any bit offset: on a
+//! byte-aligned stream they read and write plain bytes, and inside bit-packed data they extract
+//! the bytes across byte boundaries in the stream's bit order. The raw-byte variants leave the
+//! encoding to the caller; [`read_string`] additionally validates UTF-8.
+
+use crate::error::BitReadWriteError;
+use crate::traits::{BitRead, BitWrite};
+use std::io::{self, Result};
+
+/// Reads exactly `len` bytes
+///
+/// # Errors
+/// Any error of the reader, e.g. `UnexpectedEof` when the stream is too short
+pub fn read_string_bytes<R>(reader: &mut R, len: usize) -> Result<Vec<u8>>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    // len 来自数据时可能很大，预分配设上限，数据不足时照常报 UnexpectedEof
+    let mut bytes = Vec::with_capacity(len.min(1 << 16));
+    for _ in 0..len {
+        bytes.push(reader.read_bits(8)? as u8);
+    }
+    Ok(bytes)
+}
+
+/// Reads exactly `len` bytes as a UTF-8 string
+///
+/// # Errors
+/// - `InvalidData` if the bytes are not valid UTF-8
+/// - Any error of the reader
+pub fn read_string<R>(reader: &mut R, len: usize) -> Result<String>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    String::from_utf8(read_string_bytes(reader, len)?)
+        .map_err(|e| BitReadWriteError::InvalidUtf8(e).into())
+}
+
+/// Reads a null-terminated string of at most `max_len` bytes, consuming the terminator
+///
+/// The returned bytes do not include the terminator.
+///
+/// # Errors
+/// - `InvalidData` if no terminator is found within `max_len` bytes
+/// - Any error of the reader
+pub fn read_cstring<R>(reader: &mut R, max_len: usize) -> Result<Vec<u8>>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let mut bytes = Vec::new();
+    loop {
+        let byte = reader.read_bits(8)? as u8;
+        if byte == 0 {
+            return Ok(bytes);
+        }
+        if bytes.len() == max_len {
+            return Err(BitReadWriteError::MissingNulTerminator { max_len }.into());
+        }
+        bytes.push(byte);
+    }
+}
+
+/// Reads a fixed-size field of `len` bytes and strips the trailing `pad` bytes
+///
+/// Typical padding is `0` for C-style fields and `b' '` for space-padded text.
+///
+/// # Errors
+/// Any error of the reader
+pub fn read_padded_string<R>(reader: &mut R, len: usize, pad: u8) -> Result<Vec<u8>>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let mut bytes = read_string_bytes(reader, len)?;
+    let end = bytes.iter().rposition(|&b| b != pad).map_or(0, |i| i + 1);
+    bytes.truncate(end);
+    Ok(bytes)
+}
+
+/// Writes every byte of `bytes`
+///
+/// # Errors
+/// Any error of the writer
+pub fn write_string_bytes<W>(writer: &mut W, bytes: &[u8]) -> Result<()>
+where
+    W: BitWrite + ?Sized,
+{
+    for &b in bytes {
+        writer.write_bits(b as u64, 8)?;
+    }
+    Ok(())
+}
+
+/// Writes `bytes` followed by a null terminator
+///
+/// # Errors
+/// - `InvalidInput` if `bytes` contains a null byte (nothing is written)
+/// - Any error of the writer
+pub fn write_cstring<W>(writer: &mut W, bytes: &[u8]) -> Result<()>
+where
+    W: BitWrite + ?Sized,
+{
+    if bytes.contains(&0) {
+        return Err(BitReadWriteError::InteriorNul.into());
+    }
+    write_string_bytes(writer, bytes)?;
+    writer.write_bits(0, 8)
+}
+
+/// Writes `bytes` into a fixed-size field of `len` bytes, filling the rest with `pad`
+///
+/// # Errors
+/// - `BufferTooSmall` if `bytes` is longer than `len` (nothing is written)
+/// - Any error of the writer
+pub fn write_padded_string<W>(writer: &mut W, bytes: &[u8], len: usize, pad: u8) -> Result<()>
+where
+    W: BitWrite + ?Sized,
+{
+    if bytes.len() > len {
+        return Err(BitReadWriteError::BufferTooSmall {
+            bits: bytes.len() * 8,
+            capacity: len * 8,
+        }
+        .into());
+    }
+    write_string_bytes(writer, bytes)?;
+    for _ in bytes.len()..len {
+        writer.write_bits(pad as u64, 8)?;
+    }
+    Ok(())
+}
+
+/// Character set of 6-bit text fields
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SixBitCharset {
+    /// ITU-R M.1371 (AIS) 6-bit ASCII: 0-31 are `@A-Z[\]^_`, 32-63 are ` !"#$%&'()*+,-./0-9:;<=>?`.
+    /// `@` (0) is the padding character
+    Ais,
+    /// DEC SIXBIT: 0-63 are ASCII 32-95 (space to `_`); space (0) is the padding character
+    Dec,
+}
+
+impl SixBitCharset {
+    /// The character of a 6-bit code (only the low 6 bits are used)
+    pub fn decode(self, code: u8) -> char {
+        let code = code & 0x3F;
+        match self {
+            SixBitCharset::Ais if code < 32 => (code + 64) as char,
+            SixBitCharset::Ais => code as char,
+            SixBitCharset::Dec => (code + 32) as char,
+        }
+    }
+
+    /// The 6-bit code of `c`, or `None` if the charset has no such character
+    pub fn encode(self, c: char) -> Option<u8> {
+        let c = u8::try_from(c).ok()?;
+        match (self, c) {
+            (SixBitCharset::Ais, 64..=95) => Some(c - 64),
+            (SixBitCharset::Ais, 32..=63) => Some(c),
+            (SixBitCharset::Dec, 32..=95) => Some(c - 32),
+            _ => None,
+        }
+    }
+}
+
+/// Reads `len` 6-bit characters
+///
+/// Padding is returned as is; AIS names are typically trimmed with
+/// `trim_end_matches(['@', ' '])`.
+///
+/// # Errors
+/// Any error of the reader
+pub fn read_sixbit_string<R>(reader: &mut R, len: usize, charset: SixBitCharset) -> Result<String>
+where
+    R: BitRead<Output = u64> + ?Sized,
+{
+    let mut text = String::with_capacity(len.min(1 << 16));
+    for _ in 0..len {
+        text.push(charset.decode(reader.read_bits(6)? as u8));
+    }
+    Ok(text)
+}
+
+/// Writes `text` as a field of `len` 6-bit characters, filling the rest with code 0 (`@` for
+/// AIS, space for DEC)
+///
+/// Lowercase letters are not part of either charset and must be converted by the caller.
+///
+/// # Errors
+/// - `InvalidInput` if a character is not in the charset (nothing is written)
+/// - `BufferTooSmall` if `text` has more than `len` characters (nothing is written)
+/// - Any error of the writer
+pub fn write_sixbit_string<W>(
+    writer: &mut W,
+    text: &str,
+    len: usize,
+    charset: SixBitCharset,
+) -> Result<()>
+where
+    W: BitWrite + ?Sized,
+{
+    let codes = text
+        .chars()
+        .map(|c| {
+            charset
+                .encode(c)
+                .ok_or_else(|| io::Error::from(BitReadWriteError::InvalidSixBitChar(c)))
+        })
+        .collect::<Result<Vec<u8>>>()?;
+    if codes.len() > len {
+        return Err(BitReadWriteError::BufferTooSmall {
+            bits: codes.len() * 6,
+            capacity: len * 6,
+        }
+        .into());
+    }
+    for &code in &codes {
+        writer.write_bits(code as u64, 6)?;
+    }
+    for _ in codes.len()..len {
+        writer.write_bits(0, 6)?;
+    }
+    Ok(())
+}
+
+/// Converts the armored payload of an AIS `!AIVDM` sentence to the bits it carries, packed
+/// MSB-first into bytes (the last byte zero padded)
+///
+/// Each payload character carries 6 bits; the message is `6 * payload.len() - fill_bits` bits
+/// long, where `fill_bits` is the field after the payload in the sentence.
+///
+/// # Errors
+/// `InvalidData` for a character outside the armoring alphabet (`0`-`W`, `` ` ``-`w`)
+pub fn unarmor_ais(payload: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity((payload.len() * 6).div_ceil(8));
+    let (mut acc, mut bits) = (0u32, 0);
+    for c in payload.bytes() {
+        let code = match c {
+            48..=87 => c - 48,
+            96..=119 => c - 56,
+            _ => {
+                return Err(BitReadWriteError::InvalidAisChar(c as char).into());
+            }
+        };
+        acc = (acc << 6) | code as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+        }
+    }
+    if bits > 0 {
+        bytes.push((acc << (8 - bits)) as u8);
+    }
+    Ok(bytes)
+}
+
+/// Armors the first `bits` bits of `data` (MSB-first) as an AIS payload
+///
+/// Returns the payload and its fill bit count, the zero bits added to reach a multiple of 6.
+///
+/// # Errors
+/// `BufferTooSmall` if `data` holds fewer than `bits` bits
+pub fn armor_ais(data: &[u8], bits: usize) -> Result<(String, usize)> {
+    if data.len() * 8 < bits {
+        return Err(BitReadWriteError::BufferTooSmall {
+            bits,
+            capacity: data.len() * 8,
+        }
+        .into());
+    }
+    let chars = bits.div_ceil(6);
+    let payload = (0..chars)
+        .map(|i| {
+            // 取出第 i 个 6 位组（跨越至多两个字节）
+            let start = i * 6;
+            let word =
+                u16::from_be_bytes([data[start / 8], *data.get(start / 8 + 1).unwrap_or(&0)]);
+            let mut code = ((word >> (10 - start % 8)) & 0x3F) as u8;
+            if start + 6 > bits {
+                code &= 0x3F << (start + 6 - bits);
+            }
+            (if code < 40 { code + 48 } else { code + 56 }) as char
+        })
+        .collect();
+    Ok((payload, chars * 6 - bits))
+}
@@ -0,0 +1,73 @@
+//! Reader adapter that records the bits it consumes
+
+use crate::traits::{BitPeek, BitRead, BitWrite};
+use std::io::Result;
+
+/// Forwards every bit read from `R` to the attached writer `W`
+///
+/// Wrap the reader handed to a parser to capture exactly the bits it consumed, e.g. for golden
+/// tests or to re-emit passthrough sections of a stream unchanged. Each `read_bits(n)` is
+/// mirrored as `write_bits(value, n)`, so with the same byte order on both sides the writer
+/// receives the input bit for bit. Peeking is not recorded.
+#[derive(Debug)]
+pub struct TeeBitReader<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R, W> TeeBitReader<R, W>
+where
+    R: BitRead<Output = u64>,
+    W: BitWrite,
+{
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    pub fn reader(&self) -> &R {
+        &self.reader
+    }
+
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns the writer mutably, e.g. to drain what has been recorded so far
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+}
+
+impl<R, W> BitRead for TeeBitReader<R, W>
+where
+    R: BitRead<Output = u64>,
+    W: BitWrite,
+{
+    type Output = u64;
+
+    /// Reads `n` bits and writes them to the attached writer
+    ///
+    /// # Errors
+    /// Any error of the reader, in which case nothing is written, or of the writer
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        let value = self.reader.read_bits(n)?;
+        self.writer.write_bits(value, n)?;
+        Ok(value)
+    }
+}
+
+impl<R, W> BitPeek for TeeBitReader<R, W>
+where
+    R: BitRead<Output = u64> + BitPeek<Output = u64>,
+    W: BitWrite,
+{
+    type Output = u64;
+
+    fn peek_bits(&mut self, n: usize) -> Result<u64> {
+        self.reader.peek_bits(n)
+    }
+}
@@ -0,0 +1,164 @@
+//! Tracing wrapper that logs every bit operation (requires the `trace` feature)
+//!
+//! Wrap a reader or writer in [`Trace`] to record the offset, width and value of each field,
+//! optionally with a label, then compare the [`dump`](Trace::dump) of an encoder against the one
+//! of a reference decoder to find the first field where they disagree.
+
+use crate::traits::{BitPeek, BitRead, BitWrite};
+use std::fmt;
+use std::io::Result;
+
+/// Direction of a traced operation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceOp {
+    Read,
+    Write,
+}
+
+/// One successful read or write
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub op: TraceOp,
+    /// Bit offset of the field, counted from where tracing started
+    pub offset: u64,
+    pub width: usize,
+    pub value: u64,
+    pub label: Option<String>,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op = match self.op {
+            TraceOp::Read => "R",
+            TraceOp::Write => "W",
+        };
+        write!(
+            f,
+            "{} @{:>8} {:>2}b {:#x}",
+            op, self.offset, self.width, self.value
+        )?;
+        if let Some(label) = &self.label {
+            write!(f, " {}", label)?;
+        }
+        Ok(())
+    }
+}
+
+/// Records every [`BitRead::read_bits`] / [`BitWrite::write_bits`] passing through
+///
+/// Only successful operations are logged; peeks are not. Use
+/// [`read_bits_labeled`](Self::read_bits_labeled) / [`write_bits_labeled`](Self::write_bits_labeled)
+/// to name fields in the log.
+#[derive(Debug)]
+pub struct Trace<T> {
+    inner: T,
+    offset: u64,
+    entries: Vec<TraceEntry>,
+}
+
+impl<T> Trace<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            offset: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns the bits read or written since tracing started
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    /// Removes and returns the log recorded so far
+    pub fn take_entries(&mut self) -> Vec<TraceEntry> {
+        std::mem::take(&mut self.entries)
+    }
+
+    /// Formats the log one entry per line, e.g. `R @       3 12b 0xabc length`
+    pub fn dump(&self) -> String {
+        self.entries.iter().map(|e| format!("{}\n", e)).collect()
+    }
+
+    fn record(&mut self, op: TraceOp, value: u64, width: usize) {
+        self.entries.push(TraceEntry {
+            op,
+            offset: self.offset,
+            width,
+            value,
+            label: None,
+        });
+        self.offset += width as u64;
+    }
+}
+
+impl<T: BitRead<Output = u64>> Trace<T> {
+    /// Reads `n` bits and logs them under `label`
+    pub fn read_bits_labeled(&mut self, label: impl Into<String>, n: usize) -> Result<u64> {
+        let value = self.read_bits(n)?;
+        if let Some(entry) = self.entries.last_mut() {
+            entry.label = Some(label.into());
+        }
+        Ok(value)
+    }
+}
+
+impl<T: BitWrite> Trace<T> {
+    /// Writes the low `n` bits of `value` and logs them under `label`
+    pub fn write_bits_labeled(
+        &mut self,
+        label: impl Into<String>,
+        value: u64,
+        n: usize,
+    ) -> Result<()> {
+        self.write_bits(value, n)?;
+        if let Some(entry) = self.entries.last_mut() {
+            entry.label = Some(label.into());
+        }
+        Ok(())
+    }
+}
+
+impl<T: BitRead<Output = u64>> BitRead for Trace<T> {
+    type Output = u64;
+
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        let value = self.inner.read_bits(n)?;
+        self.record(TraceOp::Read, value, n);
+        Ok(value)
+    }
+}
+
+impl<T: BitPeek<Output = u64>> BitPeek for Trace<T> {
+    type Output = u64;
+
+    fn peek_bits(&mut self, n: usize) -> Result<u64> {
+        self.inner.peek_bits(n)
+    }
+}
+
+impl<T: BitWrite> BitWrite for Trace<T> {
+    fn write_bits(&mut self, value: u64, n: usize) -> Result<()> {
+        self.inner.write_bits(value, n)?;
+        // 只记录实际写入的低 n 位
+        let value = if n == 64 {
+            value
+        } else {
+            value & ((1u64 << n) - 1)
+        };
+        self.record(TraceOp::Write, value, n);
+        Ok(())
+    }
+}
@@ -3,6 +3,38 @@ pub trait BitRead {
 
     /// Reads exactly `n` bits, consuming them from the stream
     fn read_bits(&mut self, n: usize) -> std::io::Result<Self::Output>;
+
+    /// Reads fields of the given widths (each 1-64) back to back into `out`
+    ///
+    /// Readers may group consecutive fields into fewer buffer operations, which pays off for
+    /// headers made of many small fields.
+    ///
+    /// # Errors
+    /// - `InvalidInput` if `out` is shorter than `widths`
+    /// - `InvalidBitCount` if a width is not between 1-64; nothing is read
+    /// - Any error of [`read_bits`](Self::read_bits); some of the fields before the failing one
+    ///   may have been consumed
+    fn read_fields_into(&mut self, widths: &[usize], out: &mut [u64]) -> std::io::Result<()>
+    where
+        Self::Output: Into<u64>,
+    {
+        check_fields(widths, out)?;
+        for (slot, &n) in out.iter_mut().zip(widths) {
+            *slot = self.read_bits(n)?.into();
+        }
+        Ok(())
+    }
+
+    /// Reads fields of the given widths (each 1-64) back to back, see
+    /// [`read_fields_into`](Self::read_fields_into)
+    fn read_fields(&mut self, widths: &[usize]) -> std::io::Result<Vec<u64>>
+    where
+        Self::Output: Into<u64>,
+    {
+        let mut out = vec![0; widths.len()];
+        self.read_fields_into(widths, &mut out)?;
+        Ok(out)
+    }
 }
 
 pub trait BitPeek {
@@ -15,3 +47,519 @@ pub trait BitPeek {
 pub trait BitWrite {
     fn write_bits(&mut self, value: u64, n: usize) -> std::io::Result<()>;
 }
+
+/// Typed and encoded reads on top of [`BitRead`], implemented for every reader with `u64`
+/// output
+///
+/// Fixed-width integers, floats, 24-bit PCM samples, Gray, BCD, Exp-Golomb, reversed and
+/// parity-protected fields are all built on [`read_bits`](BitRead::read_bits); `BitReader`
+/// also has inherent byte-aligned fast paths for the fixed-width integers and PCM samples.
+pub trait BitReadExt: BitRead<Output = u64> {
+    /// Reads a 16-bit unsigned integer in the stream's byte order
+    fn read_u16(&mut self) -> std::io::Result<u16> {
+        Ok(self.read_bits(16)? as u16)
+    }
+
+    /// Reads a 24-bit unsigned integer in the stream's byte order, as used by many media
+    /// containers
+    fn read_u24(&mut self) -> std::io::Result<u32> {
+        Ok(self.read_bits(24)? as u32)
+    }
+
+    /// Reads a 24-bit two's complement integer in the stream's byte order, sign-extended, as
+    /// in 24-bit PCM audio
+    fn read_i24(&mut self) -> std::io::Result<i32> {
+        Ok(crate::pcm::sign_extend_i24(self.read_u24()?))
+    }
+
+    /// Fills `out` with [`read_i24`](Self::read_i24) samples, e.g. a block of interleaved
+    /// 24-bit PCM frames
+    ///
+    /// # Errors
+    /// Any error of the reader; the samples before the failing one have been consumed
+    fn read_i24_samples(&mut self, out: &mut [i32]) -> std::io::Result<()> {
+        for sample in out {
+            *sample = self.read_i24()?;
+        }
+        Ok(())
+    }
+
+    /// Reads a 32-bit unsigned integer in the stream's byte order
+    fn read_u32(&mut self) -> std::io::Result<u32> {
+        Ok(self.read_bits(32)? as u32)
+    }
+
+    /// Reads a 48-bit unsigned integer in the stream's byte order
+    fn read_u48(&mut self) -> std::io::Result<u64> {
+        self.read_bits(48)
+    }
+
+    /// Reads a 64-bit unsigned integer in the stream's byte order
+    fn read_u64(&mut self) -> std::io::Result<u64> {
+        self.read_bits(64)
+    }
+
+    /// Reads an `n`-bit (1-64) Gray-coded field and returns its binary value
+    fn read_gray_bits(&mut self, n: usize) -> std::io::Result<u64> {
+        Ok(crate::gray::from_gray(self.read_bits(n)?))
+    }
+
+    /// Reads a packed BCD field of `digits` decimal digits (1-19), 4 bits each, in the stream's
+    /// byte order
+    ///
+    /// # Errors
+    /// - `InvalidInput` if `digits` is not between 1-19
+    /// - `InvalidData` if a nibble is not a decimal digit
+    fn read_bcd(&mut self, digits: usize) -> std::io::Result<u64> {
+        crate::bcd::check_digits(digits)?;
+        // 每次最多读 16 位数字（64 位）
+        let (mut value, mut left) = (0u64, digits);
+        while left > 0 {
+            let take = left.min(16);
+            let part = crate::bcd::from_bcd(self.read_bits(4 * take)?)
+                .ok_or(crate::error::BitReadWriteError::InvalidBcdDigit)?;
+            value = value * 10u64.pow(take as u32) + part;
+            left -= take;
+        }
+        Ok(value)
+    }
+
+    /// Reads an unsigned Exp-Golomb code, the `ue(v)` fields of H.264 / H.265
+    ///
+    /// Codes with more than 63 leading zeros do not fit in a `u64` and are rejected with
+    /// `InvalidData`; see [`golomb`](crate::golomb).
+    fn read_ue(&mut self) -> std::io::Result<u64> {
+        let mut zeros = 0;
+        while self.read_bits(1)? == 0 {
+            zeros += 1;
+            if zeros > crate::golomb::MAX_LEADING_ZEROS {
+                return Err(crate::error::BitReadWriteError::ExpGolombTooLong.into());
+            }
+        }
+        if zeros == 0 {
+            return Ok(0);
+        }
+        Ok((1u64 << zeros) - 1 + self.read_bits(zeros)?)
+    }
+
+    /// Reads a signed Exp-Golomb code, the `se(v)` fields of H.264 / H.265
+    fn read_se(&mut self) -> std::io::Result<i64> {
+        Ok(crate::golomb::ue_to_se(self.read_ue()?))
+    }
+
+    /// Reads `n` bits (1-64) with their order reversed, for fields sent LSB-first
+    fn read_bits_reversed(&mut self, n: usize) -> std::io::Result<u64> {
+        Ok(crate::reverse::reverse_bits(self.read_bits(n)?, n))
+    }
+
+    /// Reads `n` data bits (1-64) followed by one parity bit and checks it
+    ///
+    /// Returns the data bits, or `ParityMismatch` (kind `InvalidData`) if the parity bit does
+    /// not match; both are consumed either way.
+    fn read_bits_with_parity(
+        &mut self,
+        n: usize,
+        parity: crate::parity::Parity,
+    ) -> std::io::Result<u64> {
+        let value = self.read_bits(n)?;
+        if self.read_bits(1)? != parity.bit(value, n) {
+            return Err(crate::error::BitReadWriteError::ParityMismatch.into());
+        }
+        Ok(value)
+    }
+
+    /// Reads a bit-exact IEEE 754 single-precision float from the next 32 bits
+    fn read_f32(&mut self) -> std::io::Result<f32> {
+        Ok(f32::from_bits(self.read_bits(32)? as u32))
+    }
+
+    /// Reads a bit-exact IEEE 754 double-precision float from the next 64 bits
+    fn read_f64(&mut self) -> std::io::Result<f64> {
+        Ok(f64::from_bits(self.read_bits(64)?))
+    }
+
+    /// Reads an IEEE 754 half-precision float from the next 16 bits, widened to `f32`
+    ///
+    /// The conversion is exact; see [`f16_to_f32`](crate::float::f16_to_f32).
+    fn read_f16(&mut self) -> std::io::Result<f32> {
+        Ok(crate::float::f16_to_f32(self.read_bits(16)? as u16))
+    }
+}
+
+impl<R: BitRead<Output = u64> + ?Sized> BitReadExt for R {}
+
+/// Checked, typed and encoded writes on top of [`BitWrite`], implemented for every writer
+///
+/// Fixed-width integers, floats, 24-bit PCM samples, Gray, BCD, Exp-Golomb, reversed and
+/// parity-protected fields are all built on [`write_bits`](BitWrite::write_bits); `BitWriter`
+/// also has inherent byte-aligned fast paths for the fixed-width integers and PCM samples.
+pub trait BitWriteExt: BitWrite {
+    /// Writes the low `n` bits (1-64) of `value`, failing instead of truncating when `value`
+    /// needs more than `n` bits
+    ///
+    /// # Errors
+    /// - `InvalidBitCount` if `n` is not between 1-64; nothing is written
+    /// - `ValueOutOfRange` if `value >= 2^n`; nothing is written
+    /// - Any error of [`write_bits`](BitWrite::write_bits)
+    fn write_bits_checked(&mut self, value: u64, n: usize) -> std::io::Result<()> {
+        if n == 0 || n > 64 {
+            return Err(crate::error::BitReadWriteError::InvalidBitCount(n).into());
+        }
+        if n < 64 && value >> n != 0 {
+            return Err(crate::error::BitReadWriteError::ValueOutOfRange { value, bits: n }.into());
+        }
+        self.write_bits(value, n)
+    }
+
+    /// Writes `value` as 16 bits in the stream's byte order
+    fn write_u16(&mut self, value: u16) -> std::io::Result<()> {
+        self.write_bits(value as u64, 16)
+    }
+
+    /// Writes `value` as 24 bits in the stream's byte order
+    ///
+    /// Returns `ValueOutOfRange` if `value` does not fit in 24 bits
+    fn write_u24(&mut self, value: u32) -> std::io::Result<()> {
+        if value >> 24 != 0 {
+            return Err(crate::error::BitReadWriteError::ValueOutOfRange {
+                value: value as u64,
+                bits: 24,
+            }
+            .into());
+        }
+        self.write_bits(value as u64, 24)
+    }
+
+    /// Writes `value` as a 24-bit two's complement integer in the stream's byte order
+    ///
+    /// Returns `ValueOutOfRange` if `value` is outside `-2^23..2^23`
+    fn write_i24(&mut self, value: i32) -> std::io::Result<()> {
+        crate::pcm::check_i24(&[value])?;
+        self.write_u24(value as u32 & 0xFF_FFFF)
+    }
+
+    /// Writes `samples` with [`write_i24`](Self::write_i24), e.g. a block of interleaved
+    /// 24-bit PCM frames
+    ///
+    /// Returns `ValueOutOfRange` if a sample is out of range; nothing is written then.
+    fn write_i24_samples(&mut self, samples: &[i32]) -> std::io::Result<()> {
+        crate::pcm::check_i24(samples)?;
+        for &sample in samples {
+            self.write_u24(sample as u32 & 0xFF_FFFF)?;
+        }
+        Ok(())
+    }
+
+    /// Writes `value` as 32 bits in the stream's byte order
+    fn write_u32(&mut self, value: u32) -> std::io::Result<()> {
+        self.write_bits(value as u64, 32)
+    }
+
+    /// Writes `value` as 48 bits in the stream's byte order
+    ///
+    /// Returns `ValueOutOfRange` if `value` does not fit in 48 bits
+    fn write_u48(&mut self, value: u64) -> std::io::Result<()> {
+        if value >> 48 != 0 {
+            return Err(
+                crate::error::BitReadWriteError::ValueOutOfRange { value, bits: 48 }.into(),
+            );
+        }
+        self.write_bits(value, 48)
+    }
+
+    /// Writes `value` as 64 bits in the stream's byte order
+    fn write_u64(&mut self, value: u64) -> std::io::Result<()> {
+        self.write_bits(value, 64)
+    }
+
+    /// Writes the low `n` bits (1-64) of `value` as an `n`-bit Gray-coded field
+    fn write_gray_bits(&mut self, value: u64, n: usize) -> std::io::Result<()> {
+        let value = if n < 64 {
+            value & ((1u64 << n) - 1)
+        } else {
+            value
+        };
+        self.write_bits(crate::gray::to_gray(value), n)
+    }
+
+    /// Writes `value` as a packed BCD field of `digits` decimal digits (1-19), zero padded on
+    /// the left
+    ///
+    /// # Errors
+    /// `InvalidInput` if `digits` is not between 1-19, `ValueOutOfRange` if `value` has more
+    /// than `digits` digits
+    fn write_bcd(&mut self, value: u64, digits: usize) -> std::io::Result<()> {
+        crate::bcd::check_digits(digits)?;
+        if value >= 10u64.pow(digits as u32) {
+            return Err(crate::error::BitReadWriteError::ValueOutOfRange {
+                value,
+                bits: 4 * digits,
+            }
+            .into());
+        }
+        let (high, low) = (value / 10u64.pow(16), value % 10u64.pow(16));
+        if digits > 16 {
+            self.write_bits(crate::bcd::to_bcd(high).unwrap_or(0), 4 * (digits - 16))?;
+            self.write_bits(crate::bcd::to_bcd(low).unwrap_or(0), 64)
+        } else {
+            self.write_bits(crate::bcd::to_bcd(low).unwrap_or(0), 4 * digits)
+        }
+    }
+
+    /// Writes `value` as an unsigned Exp-Golomb code (`ue(v)`)
+    ///
+    /// `u64::MAX` has no code that fits the readers and is rejected with `ValueOutOfRange`.
+    fn write_ue(&mut self, value: u64) -> std::io::Result<()> {
+        let code = value.checked_add(1).ok_or_else(|| {
+            std::io::Error::from(crate::error::BitReadWriteError::ValueOutOfRange {
+                value,
+                bits: 64,
+            })
+        })?;
+        let len = 64 - code.leading_zeros() as usize;
+        if len > 1 {
+            self.write_bits(0, len - 1)?;
+        }
+        self.write_bits(code, len)
+    }
+
+    /// Writes `value` as a signed Exp-Golomb code (`se(v)`)
+    ///
+    /// `i64::MIN` has no code that fits the readers and is rejected with `ValueOutOfRange`.
+    fn write_se(&mut self, value: i64) -> std::io::Result<()> {
+        let code = crate::golomb::se_to_ue(value).ok_or_else(|| {
+            std::io::Error::from(crate::error::BitReadWriteError::ValueOutOfRange {
+                value: value as u64,
+                bits: 64,
+            })
+        })?;
+        self.write_ue(code)
+    }
+
+    /// Writes the low `n` bits (1-64) of `value` in reversed order, for fields sent LSB-first
+    fn write_bits_reversed(&mut self, value: u64, n: usize) -> std::io::Result<()> {
+        if n == 0 || n > 64 {
+            return Err(crate::error::BitReadWriteError::InvalidBitCount(n).into());
+        }
+        self.write_bits(crate::reverse::reverse_bits(value, n), n)
+    }
+
+    /// Writes the low `n` bits (1-64) of `value` followed by their parity bit
+    fn write_bits_with_parity(
+        &mut self,
+        value: u64,
+        n: usize,
+        parity: crate::parity::Parity,
+    ) -> std::io::Result<()> {
+        self.write_bits(value, n)?;
+        self.write_bits(parity.bit(value, n), 1)
+    }
+
+    /// Writes `value` bit-exactly as 32 bits
+    fn write_f32(&mut self, value: f32) -> std::io::Result<()> {
+        self.write_bits(value.to_bits() as u64, 32)
+    }
+
+    /// Writes `value` bit-exactly as 64 bits
+    fn write_f64(&mut self, value: f64) -> std::io::Result<()> {
+        self.write_bits(value.to_bits(), 64)
+    }
+
+    /// Writes `value` as a 16-bit IEEE 754 half-precision float
+    ///
+    /// Rounds to nearest, ties to even; see [`f32_to_f16`](crate::float::f32_to_f16).
+    fn write_f16(&mut self, value: f32) -> std::io::Result<()> {
+        self.write_bits(crate::float::f32_to_f16(value) as u64, 16)
+    }
+}
+
+impl<W: BitWrite + ?Sized> BitWriteExt for W {}
+
+/// Checks of magic numbers, reserved fields and framing, for readers that know their bit
+/// position
+///
+/// Implemented by [`BitReader`](crate::reader::BitReader) and
+/// [`BitCursor`](crate::cursor::BitCursor); the errors carry the bit offset of the check.
+pub trait BitExpect: BitRead<Output = u64> {
+    /// Returns the number of bits consumed so far
+    fn bit_position(&self) -> u64;
+
+    /// Returns `true` if no bits are left to read, without consuming anything
+    ///
+    /// # Errors
+    /// Any error of the source while checking for more data
+    fn is_at_end(&mut self) -> std::io::Result<bool>;
+
+    /// Reads `n` bits (1..=64) and checks that they equal `expected`, e.g. a magic number or
+    /// a reserved field
+    ///
+    /// # Errors
+    /// - `InvalidBitCount` if `n` is not between 1-64
+    /// - `ValueOutOfRange` if `expected` does not fit in `n` bits (nothing is read)
+    /// - `UnexpectedValue` with the bit offset and the value found on mismatch; the field is
+    ///   consumed
+    /// - Any error of the read, e.g. `UnexpectedEof`
+    fn expect_bits(&mut self, expected: u64, n: usize) -> std::io::Result<()> {
+        crate::error::check_expected_fits(expected, n)?;
+        let offset = self.bit_position();
+        let actual = self.read_bits(n)?;
+        crate::error::check_expected(offset, expected, actual, n)
+    }
+
+    /// Reads `expected.len()` bytes (8 bits each, at any alignment) and checks that they equal
+    /// `expected`
+    ///
+    /// # Errors
+    /// - `UnexpectedValue` for the first byte that differs, with its bit offset; the bytes up to
+    ///   and including it are consumed
+    /// - Any error of the read, e.g. `UnexpectedEof`
+    fn expect_bytes(&mut self, expected: &[u8]) -> std::io::Result<()> {
+        for &byte in expected {
+            let offset = self.bit_position();
+            let actual = self.read_bits(8)?;
+            crate::error::check_expected(offset, byte as u64, actual, 8)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the position is on a byte boundary, e.g. after padding the parser believes
+    /// ends a section
+    ///
+    /// # Errors
+    /// - `NotByteAligned` with the current bit offset otherwise
+    fn expect_byte_aligned(&self) -> std::io::Result<()> {
+        let offset = self.bit_position();
+        if !offset.is_multiple_of(8) {
+            return Err(crate::error::BitReadWriteError::NotByteAligned { offset }.into());
+        }
+        Ok(())
+    }
+
+    /// Checks that every bit has been consumed, without consuming anything
+    ///
+    /// # Errors
+    /// - `TrailingData` with the current bit offset if bits are left
+    /// - Any error of [`is_at_end`](Self::is_at_end)
+    fn expect_eof(&mut self) -> std::io::Result<()> {
+        if !self.is_at_end()? {
+            return Err(crate::error::BitReadWriteError::TrailingData {
+                offset: self.bit_position(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// 为 &mut T 和 Box<T> 转发全部方法，保留 T 自己覆盖的快速路径
+macro_rules! forward_bit_traits {
+    ($($ty:ty),*) => {$(
+        impl<T: BitRead + ?Sized> BitRead for $ty {
+            type Output = T::Output;
+
+            #[inline]
+            fn read_bits(&mut self, n: usize) -> std::io::Result<Self::Output> {
+                (**self).read_bits(n)
+            }
+
+            #[inline]
+            fn read_fields_into(&mut self, widths: &[usize], out: &mut [u64]) -> std::io::Result<()>
+            where
+                Self::Output: Into<u64>,
+            {
+                (**self).read_fields_into(widths, out)
+            }
+
+            #[inline]
+            fn read_fields(&mut self, widths: &[usize]) -> std::io::Result<Vec<u64>>
+            where
+                Self::Output: Into<u64>,
+            {
+                (**self).read_fields(widths)
+            }
+        }
+
+        impl<T: BitPeek + ?Sized> BitPeek for $ty {
+            type Output = T::Output;
+
+            #[inline]
+            fn peek_bits(&mut self, n: usize) -> std::io::Result<Self::Output> {
+                (**self).peek_bits(n)
+            }
+        }
+
+        impl<T: BitWrite + ?Sized> BitWrite for $ty {
+            #[inline]
+            fn write_bits(&mut self, value: u64, n: usize) -> std::io::Result<()> {
+                (**self).write_bits(value, n)
+            }
+        }
+    )*};
+}
+
+forward_bit_traits!(&mut T, Box<T>);
+
+/// Object-safe shorthand for `BitRead<Output = u64>`, for plugin-style decoders that take any
+/// bit source at runtime as `&mut dyn DynBitRead` or `Box<dyn DynBitRead>`
+///
+/// Implemented for every reader with `u64` output. A `dyn DynBitRead` is itself a
+/// `BitRead<Output = u64>`, so it can be passed on to generic helpers taking
+/// `R: BitRead<Output = u64> + ?Sized`, and overridden methods such as `read_fields_into` keep
+/// the fast path of the concrete reader.
+pub trait DynBitRead: BitRead<Output = u64> {}
+
+impl<T: BitRead<Output = u64> + ?Sized> DynBitRead for T {}
+
+/// Object-safe counterpart of [`DynBitRead`] for writers, usable as `&mut dyn DynBitWrite` or
+/// `Box<dyn DynBitWrite>`
+pub trait DynBitWrite: BitWrite {}
+
+impl<T: BitWrite + ?Sized> DynBitWrite for T {}
+
+/// Types that can be decoded from a bit stream, usually via `#[derive(BitRead)]`
+pub trait BitDecode: Sized {
+    fn from_reader<R: BitRead<Output = u64> + ?Sized>(reader: &mut R) -> std::io::Result<Self>;
+}
+
+/// Types that can be encoded into a bit stream, usually via `#[derive(BitWrite)]`
+pub trait BitEncode {
+    fn to_writer<W: BitWrite + ?Sized>(&self, writer: &mut W) -> std::io::Result<()>;
+}
+
+/// read_fields_into 的参数检查：out 足够长且每个位宽都在 1..=64
+pub(crate) fn check_fields(widths: &[usize], out: &[u64]) -> std::io::Result<()> {
+    if out.len() < widths.len() {
+        return Err(crate::error::BitReadWriteError::OutputTooShort {
+            fields: widths.len(),
+            len: out.len(),
+        }
+        .into());
+    }
+    if let Some(&n) = widths.iter().find(|&&n| n == 0 || n > 64) {
+        return Err(crate::error::BitReadWriteError::InvalidBitCount(n).into());
+    }
+    Ok(())
+}
+
+/// 把 n 比特（1..=64）的补码符号扩展为 i64
+pub(crate) fn sign_extend(bits: u64, n: usize) -> i64 {
+    let shift = 64 - n;
+    ((bits << shift) as i64) >> shift
+}
+
+/// 检查 value 落在 n 比特（1..=64）补码的范围内，返回要写出的低 n 位
+pub(crate) fn signed_bits(value: i64, n: usize) -> std::io::Result<u64> {
+    crate::error::check_bit_count(n, 64)?;
+    if n == 64 {
+        return Ok(value as u64);
+    }
+    let (min, max) = (-(1i64 << (n - 1)), (1i64 << (n - 1)) - 1);
+    if value < min || value > max {
+        return Err(crate::error::BitReadWriteError::ValueOutOfRange {
+            value: value as u64,
+            bits: n,
+        }
+        .into());
+    }
+    Ok(value as u64 & ((1u64 << n) - 1))
+}
@@ -1,7 +1,9 @@
-use crate::byte_order::ByteOrder;
+use crate::byte_order::{self, ByteOrder};
 use crate::error::BitReadWriteError;
 use crate::traits::BitWrite;
-use std::io::{BufWriter, Result, Write};
+use std::io::{BufWriter, IoSlice, Read, Result, Seek, SeekFrom, Write};
+
+// ------------------------------- BitWriter ------------------------------- //
 
 pub struct BitWriter<W: Write> {
     byte_order: ByteOrder,
@@ -9,6 +11,21 @@ pub struct BitWriter<W: Write> {
 
     bits_buffer: u64,
     bits_in_buffer: usize,
+    sections: Vec<Section>, // begin_section 打开、尚未结束的长度前缀段
+    overflow: OverflowPolicy,
+    pad_on_flush: bool, // flush 是否像 finish 一样补齐并写出残余的不足 1 字节
+}
+
+/// What [`BitWriter::write_bits`] does with a value that needs more than `n` bits
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Keep the low `n` bits (the default)
+    #[default]
+    Mask,
+    /// Fail with `ValueOutOfRange` and write nothing
+    Error,
+    /// Write the largest `n`-bit value, `2^n - 1`
+    Saturate,
 }
 
 impl<W: Write> BitWriter<W> {
@@ -22,66 +39,528 @@ impl<W: Write> BitWriter<W> {
             inner: Option::from(BufWriter::new(inner)),
             bits_buffer: 0,
             bits_in_buffer: 0,
+            sections: Vec::new(),
+            overflow: OverflowPolicy::Mask,
+            pad_on_flush: false,
         }
     }
+
+    /// Sets how [`write_bits`](BitWrite::write_bits) handles values wider than `n` bits
+    ///
+    /// The default, [`OverflowPolicy::Mask`], silently keeps the low `n` bits. Use
+    /// [`OverflowPolicy::Error`] to catch encoder bugs that would otherwise corrupt the stream;
+    /// [`write_bits_checked`](crate::traits::BitWriteExt::write_bits_checked) does the same for a single call.
+    pub fn overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow = policy;
+        self
+    }
+
+    /// Makes [`flush`](Write::flush) pad and emit a pending partial byte like
+    /// [`finish`](Self::finish), the behavior of earlier versions
+    ///
+    /// Off by default: `flush` only writes completed bytes, so flushing in the middle of a
+    /// byte does not insert padding into the stream.
+    pub fn pad_on_flush(mut self, pad: bool) -> Self {
+        self.pad_on_flush = pad;
+        self
+    }
+}
+
+impl<W: Write> BitWriter<W> {
+    /// Returns `true` if at byte boundary (no bits of an unfinished byte are pending)
+    ///
+    /// When true:
+    /// - `write()` sends bytes straight to the inner writer
+    /// - No padding is needed before switching to byte writes
+    pub fn is_byte_aligned(&self) -> bool {
+        self.bits_in_buffer.is_multiple_of(8)
+    }
+
+    /// Returns the number of bits written into the unfinished byte (0..=7)
+    ///
+    /// [`finish`](Self::finish) would pad `8 - pending_bits()` zero bits when this is not 0.
+    pub fn pending_bits(&self) -> usize {
+        self.bits_in_buffer % 8
+    }
 }
 
 impl<W: Write> BitWriter<W> {
     fn inner_mut(&mut self) -> Result<&mut BufWriter<W>> {
         self.inner
             .as_mut()
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "inner writer is gone"))
+            .ok_or_else(|| std::io::Error::from(BitReadWriteError::InnerTaken))
     }
 }
 
 impl<W: Write> BitWriter<W> {
     /// 将对齐的（完整的）字节写入底层的写入器
+    ///
+    /// 一次性把比特缓冲区按字节序转成字节数组，整字节部分用一次 write_all 写出，不做逐字节循环和堆分配
     fn write_aligned_bytes_to_inner(&mut self) -> Result<()> {
-        // 先算出有多少对齐的字节待写入底层
         // 注意本操作只会处理对齐的字节
-        let count = self.bits_in_buffer / 8;
+        let (bytes, count) = self.take_aligned_bytes();
         if count == 0 {
             return Ok(());
         }
+        self.inner_mut()?.write_all(&bytes[..count])
+    }
+
+    /// 从比特缓冲区取出所有整字节，返回字节数组和其中有效的字节数
+    fn take_aligned_bytes(&mut self) -> ([u8; 8], usize) {
+        let count = self.bits_in_buffer / 8;
+        // 大端序的字节从比特缓冲区左边开始，小端序从右边开始
+        let bytes = match self.byte_order {
+            ByteOrder::BigEndian => self.bits_buffer.to_be_bytes(),
+            ByteOrder::LittleEndian => self.bits_buffer.to_le_bytes(),
+        };
+        let shift = (count * 8) as u32;
+        self.bits_buffer = match self.byte_order {
+            ByteOrder::BigEndian => self.bits_buffer.checked_shl(shift).unwrap_or(0),
+            ByteOrder::LittleEndian => self.bits_buffer.checked_shr(shift).unwrap_or(0),
+        };
+        self.bits_in_buffer -= count * 8;
+        (bytes, count)
+    }
 
-        let mut buf = Vec::with_capacity(count);
-        for _ in 0..count {
+    /// 将比特缓冲区尾部的不足 1 字节的数据写入底层的写入器，注意，这个函数只能在比特缓冲区中剩余位不足 1 字节（8 比特）时调用才有意义
+    fn write_residual_partial_byte_to_inner(&mut self) -> Result<()> {
+        if self.bits_in_buffer > 0 && self.bits_in_buffer < 8 {
             let byte = match self.byte_order {
-                ByteOrder::BigEndian => (self.bits_buffer >> 56) as u8, // 大端序每次都从比特缓冲区左边取 8 位，也就是 1 字节，注意这里没有改变比特缓冲区本身
-                ByteOrder::LittleEndian => self.bits_buffer as u8, // 小端序每次都从比特缓冲区右边取 8 位，也就是 1 字节，注意这里没有改变比特缓冲区本身
+                ByteOrder::BigEndian => (self.bits_buffer >> 56) as u8, // 对于大端序，将比特缓冲区最左边剩余的不足 1 字节的位写入底层的写入器
+                ByteOrder::LittleEndian => self.bits_buffer as u8, // 对于小端序，将比特缓冲区最右边剩余的不足 1 字节的位写入底层的写入器
+            };
+            self.inner_mut()?.write_all(&[byte])?;
+            self.bits_buffer = 0; // 清零比特缓冲区
+            self.bits_in_buffer = 0; // 清零比特缓冲区计数
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> BitWriter<W> {
+    /// Writes the first `bit_len` bits of `data`
+    ///
+    /// Full bytes are written as-is. For a trailing partial byte, the bits taken are the ones
+    /// that come first in the stream's bit order: the high bits for BigEndian and the low bits
+    /// for LittleEndian.
+    ///
+    /// # Errors
+    /// Returns error if `data` holds fewer than `bit_len` bits or the inner writer fails
+    pub fn write_bits_from_slice(&mut self, data: &[u8], bit_len: usize) -> Result<()> {
+        let full_bytes = bit_len / 8;
+        let residual_bits = bit_len % 8;
+        if bit_len.div_ceil(8) > data.len() {
+            return Err(BitReadWriteError::BufferTooSmall {
+                bits: bit_len,
+                capacity: data.len() * 8,
+            }
+            .into());
+        }
+
+        // 整字节部分交给字节写入逻辑，它会自行处理对齐与非对齐两种情况
+        self.write_all(&data[..full_bytes])?;
+
+        if residual_bits > 0 {
+            let byte = data[full_bytes];
+            let value = match self.byte_order {
+                ByteOrder::BigEndian => byte >> (8 - residual_bits),
+                ByteOrder::LittleEndian => byte & ((1u8 << residual_bits) - 1),
+            };
+            self.write_bits(value as u64, residual_bits)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> BitWriter<W> {
+    /// Writes every byte of `data` at the current bit offset, whether or not it is byte-aligned
+    ///
+    /// Each byte is shifted into the stream as the next 8 bits, exactly as `write_bits(byte, 8)`
+    /// would put it, so payload blobs can sit inside bit-packed frames. The inverse of
+    /// [`BitReader::read_bytes_into`](crate::reader::BitReader::read_bytes_into).
+    ///
+    /// # Errors
+    /// Returns error if the inner writer fails
+    pub fn write_bytes_unaligned(&mut self, data: &[u8]) -> Result<()> {
+        // 每次合并 8 个字节写 64 位：按各自字节序组装后，写出的字节顺序与输入一致
+        let mut chunks = data.chunks_exact(8);
+        for chunk in &mut chunks {
+            let bytes: [u8; 8] = chunk.try_into().expect("chunk of 8 bytes");
+            let value = match self.byte_order {
+                ByteOrder::BigEndian => u64::from_be_bytes(bytes),
+                ByteOrder::LittleEndian => u64::from_le_bytes(bytes),
+            };
+            self.write_bits(value, 64)?;
+        }
+        for &b in chunks.remainder() {
+            self.write_bits(b as u64, 8)?;
+        }
+        Ok(())
+    }
+
+    /// Writes `count` copies of `bit`
+    ///
+    /// Once byte-aligned, the run is emitted as whole `0x00` / `0xFF` bytes straight to the inner
+    /// writer, so long runs (fax, RLE-style encoders) cost far less than a loop of 64-bit writes.
+    ///
+    /// # Errors
+    /// Returns error if the inner writer fails
+    pub fn write_run(&mut self, bit: bool, count: u64) -> Result<()> {
+        let fill = |n: usize| if bit { (1u64 << n) - 1 } else { 0 }; // n <= 8
+        let mut remaining = count;
+
+        // 先补齐到字节边界（write_bits 返回后比特缓冲区中不会有整字节残留）
+        let head = ((8 - self.bits_in_buffer % 8) % 8).min(remaining as usize);
+        if head > 0 {
+            self.write_bits(fill(head), head)?;
+            remaining -= head as u64;
+        }
+
+        // 整字节部分直接写入底层写入器
+        let mut bytes = remaining / 8;
+        if bytes > 0 {
+            let chunk = [fill(8) as u8; 512];
+            while bytes > 0 {
+                let k = bytes.min(chunk.len() as u64) as usize;
+                self.inner_mut()?.write_all(&chunk[..k])?;
+                bytes -= k as u64;
+            }
+            remaining %= 8;
+        }
+
+        if remaining > 0 {
+            self.write_bits(fill(remaining as usize), remaining as usize)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the low `n` bits (1-64) of `value` in `order` instead of the writer's own byte order
+    ///
+    /// The inverse of [`BitReader::read_bits_with`](crate::reader::BitReader::read_bits_with).
+    ///
+    /// # Errors
+    /// Returns error if `n` is not between 1-64 or the inner writer fails
+    pub fn write_bits_with(&mut self, order: ByteOrder, value: u64, n: usize) -> Result<()> {
+        if order == self.byte_order {
+            return self.write_bits(value, n);
+        }
+        byte_order::write_bits_in_order(self, order, value, n)
+    }
+}
+
+/// begin_section 打开的段：长度字段、内容起点（比特偏移）、长度是否以字节计
+#[derive(Clone, Copy, Debug)]
+struct Section {
+    length: Placeholder,
+    start: u64,
+    bytes: bool,
+}
+
+/// A field reserved by [`BitWriter::reserve_bits`], to be filled in by [`BitWriter::patch`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Placeholder {
+    offset: u64,
+    bits: usize,
+}
+
+impl Placeholder {
+    /// Returns the bit offset of the field in the inner writer
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the width of the field in bits
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+}
+
+impl<W: Write + Seek> BitWriter<W> {
+    /// 当前写入位置在底层写入器中的比特偏移
+    fn bit_position(&mut self) -> Result<u64> {
+        let inner = self.inner_mut()?;
+        let buffered = inner.buffer().len() as u64;
+        let position = inner.get_mut().stream_position()? + buffered;
+        Ok(position * 8 + self.bits_in_buffer as u64)
+    }
+
+    /// Overwrites the `n`-bit field (8, 16, ..., 64) starting at `byte_offset` with `value`,
+    /// in the writer's byte order, and continues writing where it was
+    ///
+    /// Use it to update byte-aligned header fields (sizes, counts, checksums) once the body
+    /// is written. Unlike [`patch`](Self::patch) the inner writer does not need to be readable,
+    /// but the field must cover whole bytes that have already been written.
+    ///
+    /// # Errors
+    /// - `InvalidBitCount` if `n` is not a multiple of 8 between 8-64
+    /// - `ValueOutOfRange` if `value` does not fit in `n` bits
+    /// - `InvalidInput` if the field extends past the bytes written so far
+    /// - Any error of the inner writer while flushing, seeking or writing
+    pub fn write_bits_at(&mut self, byte_offset: u64, value: u64, n: usize) -> Result<()> {
+        if n == 0 || n > 64 || !n.is_multiple_of(8) {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        if n < 64 && value >> n != 0 {
+            return Err(BitReadWriteError::ValueOutOfRange { value, bits: n }.into());
+        }
+        let len = n / 8;
+        let bytes: Vec<u8> = (0..len)
+            .map(|i| match self.byte_order {
+                ByteOrder::BigEndian => (value >> (8 * (len - 1 - i))) as u8,
+                ByteOrder::LittleEndian => (value >> (8 * i)) as u8,
+            })
+            .collect();
+
+        // 比特缓冲区中的整字节先写出，字段只能落在已写出的字节上
+        self.write_aligned_bytes_to_inner()?;
+        let inner = self.inner_mut()?;
+        inner.flush()?;
+        let inner = inner.get_mut();
+        let end = inner.stream_position()?;
+        if byte_offset.saturating_add(len as u64) > end {
+            return Err(BitReadWriteError::PatchPastEnd {
+                end: byte_offset.saturating_add(len as u64),
+                written: end,
+            }
+            .into());
+        }
+        inner.seek(SeekFrom::Start(byte_offset))?;
+        inner.write_all(&bytes)?;
+        inner.seek(SeekFrom::Start(end))?;
+        Ok(())
+    }
+}
+
+impl<W: Read + Write + Seek> BitWriter<W> {
+    /// Creates a writer that overwrites the existing data of `inner` starting at `bit_offset`,
+    /// for patching bit fields in place
+    ///
+    /// The bits before `bit_offset` in its byte are read back and kept. End with
+    /// [`finish_in_place`](Self::finish_in_place) so the bits after the last written one are
+    /// kept as well; [`finish`](Self::finish) and dropping the writer zero them. For in-memory
+    /// buffers, [`BitCursor`](crate::cursor::BitCursor) writes at any bit position directly.
+    ///
+    /// # Errors
+    /// Any error of `inner` while seeking or reading the first byte
+    pub fn at_bit_offset(byte_order: ByteOrder, inner: W, bit_offset: u64) -> Result<Self> {
+        let mut writer = Self::with_byte_order(byte_order, inner);
+        writer
+            .inner_mut()?
+            .get_mut()
+            .seek(SeekFrom::Start(bit_offset / 8))?;
+        let kept = (bit_offset % 8) as usize;
+        if kept > 0 {
+            let existing = writer.read_existing_byte()? as u64;
+            writer.bits_buffer = match byte_order {
+                ByteOrder::BigEndian => (existing >> (8 - kept)) << (64 - kept),
+                ByteOrder::LittleEndian => existing & ((1 << kept) - 1),
             };
-            buf.push(byte);
+            writer.bits_in_buffer = kept;
+        }
+        Ok(writer)
+    }
 
+    /// Like [`finish`](Self::finish), but fills the rest of a pending partial byte with the
+    /// bits already stored there instead of zeros
+    ///
+    /// # Errors
+    /// Any error of the inner writer while flushing, seeking, reading or writing
+    pub fn finish_in_place(&mut self) -> Result<()> {
+        self.write_aligned_bytes_to_inner()?;
+        let pending = self.bits_in_buffer;
+        if pending > 0 {
+            let existing = self.read_existing_byte()? as u64;
             match self.byte_order {
                 ByteOrder::BigEndian => {
-                    self.bits_buffer <<= 8; // 大端序每次从左边取完比特缓冲区 1 字节后，要从左边消除掉已经取出的 8 位，注意这里改变了比特缓冲区本身
+                    self.bits_buffer |= (existing & (0xFF >> pending)) << 56;
                 }
                 ByteOrder::LittleEndian => {
-                    self.bits_buffer >>= 8; // 小端序每次从右边取完比特缓冲区 1 字节后，要从右边消除掉已经取出的 8 位，注意这里改变了比特缓冲区本身
+                    self.bits_buffer |= existing & (0xFF << pending) & 0xFF;
                 }
             }
-            self.bits_in_buffer -= 8; // 更改比特缓冲区位计数
+            self.bits_in_buffer = 8;
+            self.write_aligned_bytes_to_inner()?;
         }
-        self.inner_mut()?.write_all(&buf)?; // 一次写多个字节能减少潜在的系统调用
+        self.inner_mut()?.flush()
+    }
+
+    /// 读出当前写入位置上已有的字节（超出末尾时为 0），写入位置不变
+    fn read_existing_byte(&mut self) -> Result<u8> {
+        let inner = self.inner_mut()?;
+        inner.flush()?;
+        let inner = inner.get_mut();
+        let position = inner.stream_position()?;
+        let mut byte = [0u8];
+        let read = inner.read(&mut byte)?;
+        if read > 0 {
+            inner.seek(SeekFrom::Start(position))?;
+        }
+        Ok(byte[0])
+    }
+
+    /// Writes `n` zero bits (1..=64) and returns a placeholder to fill them in later
+    ///
+    /// Use it for length or CRC fields that are only known once the body has been written.
+    ///
+    /// # Errors
+    /// - `InvalidBitCount` if `n` is not between 1-64
+    /// - Any error of the inner writer while querying its position
+    pub fn reserve_bits(&mut self, n: usize) -> Result<Placeholder> {
+        let offset = self.bit_position()?;
+        self.write_bits(0, n)?;
+        Ok(Placeholder { offset, bits: n })
+    }
+
+    /// Opens a section prefixed by a `len_field_bits` wide field (1..=64) that
+    /// [`end_section`](Self::end_section) fills with the length of the content in bits
+    ///
+    /// Sections can be nested; each `end_section` closes the innermost open one. The prefix
+    /// is filled in with [`patch`](Self::patch), so the content is not buffered in memory.
+    ///
+    /// # Errors
+    /// Same as [`reserve_bits`](Self::reserve_bits)
+    pub fn begin_section(&mut self, len_field_bits: usize) -> Result<()> {
+        self.open_section(len_field_bits, false)
+    }
+
+    /// Like [`begin_section`](Self::begin_section), but the prefix holds the length in bytes
+    /// and the content must be a whole number of bytes
+    pub fn begin_byte_section(&mut self, len_field_bits: usize) -> Result<()> {
+        self.open_section(len_field_bits, true)
+    }
+
+    fn open_section(&mut self, len_field_bits: usize, bytes: bool) -> Result<()> {
+        let length = self.reserve_bits(len_field_bits)?;
+        let start = self.bit_position()?;
+        self.sections.push(Section {
+            length,
+            start,
+            bytes,
+        });
         Ok(())
     }
 
-    /// 将比特缓冲区尾部的不足 1 字节的数据写入底层的写入器，注意，这个函数只能在比特缓冲区中剩余位不足 1 字节（8 比特）时调用才有意义
-    fn write_residual_partial_byte_to_inner(&mut self) -> Result<()> {
-        if self.bits_in_buffer > 0 && self.bits_in_buffer < 8 {
-            let byte = match self.byte_order {
-                ByteOrder::BigEndian => (self.bits_buffer >> 56) as u8, // 对于大端序，将比特缓冲区最左边剩余的不足 1 字节的位写入底层的写入器
-                ByteOrder::LittleEndian => self.bits_buffer as u8, // 对于小端序，将比特缓冲区最右边剩余的不足 1 字节的位写入底层的写入器
+    /// Closes the innermost open section, writes its length into the prefix and returns it
+    ///
+    /// # Errors
+    /// - `InvalidInput` if no section is open
+    /// - `UnalignedAccess` if a byte section's content is not a whole number of bytes
+    /// - `ValueOutOfRange` if the length does not fit in the prefix
+    /// - Any error of the inner writer
+    ///
+    /// The section stays open if the length cannot be written.
+    pub fn end_section(&mut self) -> Result<u64> {
+        let Some(&Section {
+            length,
+            start,
+            bytes,
+        }) = self.sections.last()
+        else {
+            return Err(BitReadWriteError::NoOpenSection.into());
+        };
+        let mut len = self.bit_position()? - start;
+        if bytes {
+            if len % 8 != 0 {
+                return Err(BitReadWriteError::UnalignedAccess {
+                    pending_bits: (len % 8) as usize,
+                }
+                .into());
+            }
+            len /= 8;
+        }
+        self.patch(length, len)?;
+        self.sections.pop();
+        Ok(len)
+    }
+
+    /// Writes `value` into a field reserved by [`reserve_bits`](Self::reserve_bits)
+    ///
+    /// Pending bytes are flushed first. The bytes covering the field are read back, updated and
+    /// rewritten, so bits of neighbouring fields sharing those bytes are kept; the inner writer
+    /// must therefore be readable (a file opened for reading and writing, a `Cursor`). Writing
+    /// then continues where it was.
+    ///
+    /// # Errors
+    /// - `ValueOutOfRange` if `value` does not fit in the field
+    /// - Any error of the inner writer while flushing, seeking, reading or writing
+    pub fn patch(&mut self, placeholder: Placeholder, value: u64) -> Result<()> {
+        let Placeholder { offset, bits } = placeholder;
+        if bits < 64 && value >> bits != 0 {
+            return Err(BitReadWriteError::ValueOutOfRange { value, bits }.into());
+        }
+        let order = self.byte_order;
+        let inner = self.inner_mut()?;
+        inner.flush()?;
+        let inner = inner.get_mut();
+        let end = inner.stream_position()?; // 比特缓冲区中残余的不足 1 字节将写到这里
+
+        let first = offset / 8;
+        let last = (offset + bits as u64).div_ceil(8);
+        let flushed = last.min(end).saturating_sub(first) as usize;
+        let mut bytes = vec![0u8; (last - first) as usize];
+        if flushed > 0 {
+            inner.seek(SeekFrom::Start(first))?;
+            inner.read_exact(&mut bytes[..flushed])?;
+        }
+        if bytes.len() > flushed {
+            // 字段的最后一个字节还在比特缓冲区中
+            bytes[flushed] = match order {
+                ByteOrder::BigEndian => (self.bits_buffer >> 56) as u8,
+                ByteOrder::LittleEndian => self.bits_buffer as u8,
             };
-            self.inner_mut()?.write_all(&[byte])?;
-            self.bits_buffer = 0; // 清零比特缓冲区
-            self.bits_in_buffer = 0; // 清零比特缓冲区计数
+        }
+
+        // 逐比特写入字段：大端序先写高位、字节内从高位排起，小端序反之
+        let start = (offset % 8) as usize;
+        for i in 0..bits {
+            let pos = start + i;
+            let (bit, shift) = match order {
+                ByteOrder::BigEndian => ((value >> (bits - 1 - i)) & 1, 7 - pos % 8),
+                ByteOrder::LittleEndian => ((value >> i) & 1, pos % 8),
+            };
+            let byte = &mut bytes[pos / 8];
+            *byte = (*byte & !(1 << shift)) | ((bit as u8) << shift);
+        }
+
+        if flushed > 0 {
+            let inner = self.inner_mut()?.get_mut();
+            inner.seek(SeekFrom::Start(first))?;
+            inner.write_all(&bytes[..flushed])?;
+            inner.seek(SeekFrom::Start(end))?;
+        }
+        if bytes.len() > flushed {
+            let byte = bytes[flushed] as u64;
+            match order {
+                ByteOrder::BigEndian => {
+                    self.bits_buffer = (self.bits_buffer & !(0xFF << 56)) | (byte << 56)
+                }
+                ByteOrder::LittleEndian => self.bits_buffer = (self.bits_buffer & !0xFF) | byte,
+            }
         }
         Ok(())
     }
 }
 
 impl<W: Write> BitWriter<W> {
+    /// Pads any pending partial byte with zero bits, writes it and flushes the inner writer
+    ///
+    /// Call it at the end of a stream, or wherever the format wants padding to a byte
+    /// boundary; writing can continue afterwards from the next byte.
+    ///
+    /// # Errors
+    /// Returns error if writing the residual byte or flushing the inner writer fails
+    pub fn finish(&mut self) -> Result<()> {
+        self.write_residual_partial_byte_to_inner()?;
+        self.inner_mut()?.flush()
+    }
+
+    /// Pads and writes any pending partial byte, flushes, and returns the underlying writer
+    ///
+    /// Unlike dropping the writer (which flushes on a best-effort basis and swallows errors),
+    /// this surfaces any I/O error that occurs while emitting the trailing bits.
+    ///
+    /// # Errors
+    /// Returns error if writing the residual byte or flushing the inner writer fails
     pub fn into_inner(mut self) -> Result<W> {
         self.write_residual_partial_byte_to_inner()?;
         if let Some(mut inner) = self.inner.take() {
@@ -89,10 +568,7 @@ impl<W: Write> BitWriter<W> {
             inner.into_inner().map_err(|e| e.into_error())
         } else {
             // 如果已经被取走了，返回错误或自定义错误
-            Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "inner already taken",
-            ))
+            Err(BitReadWriteError::InnerTaken.into())
         }
     }
 }
@@ -104,26 +580,84 @@ impl<W: Write> Write for BitWriter<W> {
 
         if self.bits_in_buffer == 0 {
             // 如果执行完将比特缓冲区中所有对齐字节都写入底层的写入器后，如果比特缓冲区已经清零（此时已是干净的状态），那么就可以将新来的字节组直接写入底层的写入器（高速）
-            self.inner_mut()?.write(buf)?;
-            return Ok(buf.len());
+            // 底层写入器可能只写出一部分，如实返回写出的字节数
+            return self.inner_mut()?.write(buf);
         }
 
-        // 如果执行完将比特缓冲区中所有对齐字节都写入底层的写入器后，比特缓冲区中还有剩余的位（也就是未对齐为 1 字节的位，比如 3 比特），那么就需要将字节组的每个字节都执行 “比特写”（在这个过程中实际上是先将所有自己组的字节都写到比特缓冲区然后由后续逻辑从比特缓冲区写到底层写入器，也就是不允许绕过比特缓冲区） 这样才能保证底层写入器是无空隙的（这样速度较字节组直写要慢，但是我们的底层写入器保证是 BufWriter 因此不会慢太多）
-        for &b in buf {
-            self.write_bits(b as u64, 8)?;
-        }
+        // 如果执行完将比特缓冲区中所有对齐字节都写入底层的写入器后，比特缓冲区中还有剩余的位（也就是未对齐为 1 字节的位，比如 3 比特），那么就需要将字节组都执行 “比特写”（在这个过程中实际上是先将所有自己组的字节都写到比特缓冲区然后由后续逻辑从比特缓冲区写到底层写入器，也就是不允许绕过比特缓冲区） 这样才能保证底层写入器是无空隙的（这样速度较字节组直写要慢，但是我们的底层写入器保证是 BufWriter 因此不会慢太多）
+        self.write_bytes_unaligned(buf)?;
 
         Ok(buf.len())
     }
 
+    /// Writes several buffers with one vectored write to the inner writer when byte-aligned
+    ///
+    /// Completed bytes never wait in the bit buffer (`write_bits` hands them to the inner
+    /// `BufWriter` as soon as they form), so there is nothing to batch in front of `bufs`: they
+    /// go to the inner writer as they are. Batches larger than the internal `BufWriter` pass
+    /// through it uncopied to writers with native vectored I/O (files, sockets). In the middle
+    /// of a byte every buffer goes through the bit buffer, as with [`write`](Self::write).
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        if !self.bits_in_buffer.is_multiple_of(8) {
+            let mut total = 0;
+            for buf in bufs {
+                self.write_bytes_unaligned(buf)?;
+                total += buf.len();
+            }
+            return Ok(total);
+        }
+
+        // 与 write 相同：对齐时比特缓冲区中没有未写出的整字节，直接交给底层写入器
+        self.write_aligned_bytes_to_inner()?;
+        self.inner_mut()?.write_vectored(bufs)
+    }
+
+    /// Writes the completed bytes and flushes the inner writer
+    ///
+    /// Bits of an unfinished byte stay buffered so the stream is not corrupted by padding;
+    /// use [`finish`](BitWriter::finish) (or [`pad_on_flush`](BitWriter::pad_on_flush)) to pad
+    /// and emit them.
     fn flush(&mut self) -> Result<()> {
-        // 注意冲刷操作一定要把比特缓冲区的残尾字节写入底层写入器，否则底层写入器就少尾部数据了
-        self.write_residual_partial_byte_to_inner()?;
+        if self.pad_on_flush {
+            return self.finish();
+        }
+        self.write_aligned_bytes_to_inner()?;
         self.inner_mut()?.flush()
     }
 }
 
+impl<W: Write + Seek> Seek for BitWriter<W> {
+    /// Pads and writes any pending partial byte like [`finish`](BitWriter::finish), then seeks
+    /// the inner writer to a byte position
+    ///
+    /// Writing continues from the new position. The padding overwrites the rest of the byte
+    /// that was being written, so seek away only at byte boundaries to keep data that follows
+    /// it. Open [sections](Self::begin_section) keep their recorded positions.
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.finish()?;
+        self.inner_mut()?.seek(pos)
+    }
+
+    /// Returns the byte position of the next write without flushing
+    ///
+    /// # Errors
+    /// - `UnalignedAccess` if the writer is in the middle of a byte
+    fn stream_position(&mut self) -> Result<u64> {
+        let bits = self.bit_position()?;
+        if !bits.is_multiple_of(8) {
+            return Err(BitReadWriteError::UnalignedAccess {
+                pending_bits: (bits % 8) as usize,
+            }
+            .into());
+        }
+        Ok(bits / 8)
+    }
+}
+
 impl<W: Write> Drop for BitWriter<W> {
+    /// Best-effort flush of pending bits (padded to a full byte) when the writer goes out of scope
+    ///
+    /// Errors are ignored here; use [`BitWriter::into_inner`] to observe them.
     fn drop(&mut self) {
         // 先尝试写入残余的比特数据，忽略错误
         // 注意这里显式的忽略了错误因为 Rust 规定 Drop 里不允许 panic，同样的，不能直接 self.flush().unwrap(); 因为 .unwrap() 可能会 panic
@@ -146,13 +680,30 @@ impl<W: Write> BitWrite for BitWriter<W> {
 
         let mut remaining = n;
         let mask = if n == 64 { u64::MAX } else { (1u64 << n) - 1 }; // (1u64 << n) - 1 就是低位连续 n 个 1，高位全是 0
+        let value = if value & !mask == 0 {
+            value
+        } else {
+            // 值超出 n 位时按溢出策略处理
+            match self.overflow {
+                OverflowPolicy::Mask => value,
+                OverflowPolicy::Error => {
+                    return Err(BitReadWriteError::ValueOutOfRange { value, bits: n }.into());
+                }
+                OverflowPolicy::Saturate => mask,
+            }
+        };
         let mut val = value & mask; // 用掩码取出 n 位有效位，无效的位被丢弃
 
         while remaining > 0 {
             let available = 64 - self.bits_in_buffer;
             let to_insert = remaining.min(available);
             let insert_at_next_round = remaining - to_insert;
-            let to_insert_val = val >> insert_at_next_round; // 注意这里没有改变 val 本身，而是用 val 的一部分建立了新值
+            // 注意这里没有改变 val 本身，而是用 val 的一部分建立了新值：大端序先写高位部分，小端序先写低位部分
+            let to_insert_val = match self.byte_order {
+                ByteOrder::BigEndian => val >> insert_at_next_round,
+                ByteOrder::LittleEndian if to_insert == 64 => val,
+                ByteOrder::LittleEndian => val & ((1u64 << to_insert) - 1),
+            };
 
             match self.byte_order {
                 ByteOrder::BigEndian => {
@@ -167,7 +718,14 @@ impl<W: Write> BitWrite for BitWriter<W> {
             remaining -= to_insert; // 更新剩余的要插入的位数
 
             if insert_at_next_round > 0 {
-                val &= (1u64 << insert_at_next_round) - 1; //  (1u64 << insert_at_next_round) - 1 又是一个掩码，用下一轮要插入的位数来更新 val，相当于丢弃了 val 中本轮已经插入过的位，注意这里是直接修改了 val 本身
+                match self.byte_order {
+                    ByteOrder::BigEndian => {
+                        val &= (1u64 << insert_at_next_round) - 1; //  (1u64 << insert_at_next_round) - 1 又是一个掩码，用下一轮要插入的位数来更新 val，相当于丢弃了 val 中本轮已经插入过的位，注意这里是直接修改了 val 本身
+                    }
+                    ByteOrder::LittleEndian => {
+                        val >>= to_insert; // 小端序本轮插入的是低位部分，右移丢弃它们
+                    }
+                }
             }
 
             // 每凑够（包括大于的情况）1 字节就触发一次写入底层写入器的操作
@@ -179,3 +737,153 @@ impl<W: Write> BitWrite for BitWriter<W> {
         Ok(())
     }
 }
+
+impl<W: Write> BitWriter<W> {
+    /// Writes `value` as 16 bits in the stream's byte order, copying whole bytes when
+    /// byte-aligned (see [`BitWriteExt::write_u16`](crate::traits::BitWriteExt::write_u16))
+    pub fn write_u16(&mut self, value: u16) -> Result<()> {
+        self.write_uint(value as u64, 2)
+    }
+
+    /// Writes `value` as 24 bits, see [`write_u16`](Self::write_u16)
+    ///
+    /// Returns `ValueOutOfRange` if `value` does not fit in 24 bits
+    pub fn write_u24(&mut self, value: u32) -> Result<()> {
+        if value >> 24 != 0 {
+            return Err(BitReadWriteError::ValueOutOfRange {
+                value: value as u64,
+                bits: 24,
+            }
+            .into());
+        }
+        self.write_uint(value as u64, 3)
+    }
+
+    /// Writes `value` as 32 bits, see [`write_u16`](Self::write_u16)
+    pub fn write_u32(&mut self, value: u32) -> Result<()> {
+        self.write_uint(value as u64, 4)
+    }
+
+    /// Writes `value` as 48 bits, see [`write_u16`](Self::write_u16)
+    ///
+    /// Returns `ValueOutOfRange` if `value` does not fit in 48 bits
+    pub fn write_u48(&mut self, value: u64) -> Result<()> {
+        if value >> 48 != 0 {
+            return Err(BitReadWriteError::ValueOutOfRange { value, bits: 48 }.into());
+        }
+        self.write_uint(value, 6)
+    }
+
+    /// Writes `value` as 64 bits, see [`write_u16`](Self::write_u16)
+    pub fn write_u64(&mut self, value: u64) -> Result<()> {
+        self.write_uint(value, 8)
+    }
+
+    /// Writes 24-bit PCM `samples`, converting whole blocks of bytes when byte-aligned (see
+    /// [`BitWriteExt::write_i24_samples`](crate::traits::BitWriteExt::write_i24_samples))
+    ///
+    /// Returns `ValueOutOfRange` if a sample is out of range; nothing is written then.
+    pub fn write_i24_samples(&mut self, samples: &[i32]) -> Result<()> {
+        crate::pcm::check_i24(samples)?;
+        if self.bits_in_buffer != 0 {
+            for &sample in samples {
+                self.write_bits(sample as u64 & 0xFF_FFFF, 24)?;
+            }
+            return Ok(());
+        }
+        // 对齐时按块批量转换再写出整字节
+        let mut buf = [0u8; 3 * 256];
+        for chunk in samples.chunks(256) {
+            let bytes = &mut buf[..chunk.len() * 3];
+            crate::pcm::encode_i24(chunk, self.byte_order, bytes)?;
+            self.inner_mut()?.write_all(bytes)?;
+        }
+        Ok(())
+    }
+
+    /// 写入 bytes 字节的无符号整数：字节对齐时直接写入整字节，否则走比特路径
+    fn write_uint(&mut self, value: u64, bytes: usize) -> Result<()> {
+        // write_bits 每次返回前都会写出整字节，所以对齐时比特缓冲区为空
+        if self.bits_in_buffer != 0 {
+            return self.write_bits(value, bytes * 8);
+        }
+        match self.byte_order {
+            ByteOrder::BigEndian => self
+                .inner_mut()?
+                .write_all(&value.to_be_bytes()[8 - bytes..]),
+            ByteOrder::LittleEndian => self.inner_mut()?.write_all(&value.to_le_bytes()[..bytes]),
+        }
+    }
+}
+
+// ------------------------------- BulkBitWriter ------------------------------- //
+
+pub struct BulkBitWriter<W: Write> {
+    inner: BitWriter<W>,
+}
+
+impl<W: Write> BulkBitWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: BitWriter::new(inner),
+        }
+    }
+
+    pub fn with_endianness(endianness: ByteOrder, inner: W) -> Self {
+        Self {
+            inner: BitWriter::with_byte_order(endianness, inner),
+        }
+    }
+
+    /// Writes `n` bits taken from 64-bit `chunks`
+    ///
+    /// The layout matches the output of
+    /// [`BulkBitReader::read_bits`](crate::reader::BulkBitReader): every chunk but the last
+    /// carries 64 bits, and the last chunk carries the remaining `n % 64` bits (or 64) in its
+    /// low bits.
+    ///
+    /// # Errors
+    /// Returns error if `n` is 0, `chunks` holds fewer than `n` bits, or the inner writer fails
+    pub fn write_bits(&mut self, chunks: &[u64], n: usize) -> Result<()> {
+        if n == 0 {
+            return Err(BitReadWriteError::InvalidBitCount(n).into());
+        }
+        if n.div_ceil(64) > chunks.len() {
+            return Err(BitReadWriteError::BufferTooSmall {
+                bits: n,
+                capacity: chunks.len() * 64,
+            }
+            .into());
+        }
+        let mut remaining = n;
+        for &chunk in chunks {
+            if remaining == 0 {
+                break;
+            }
+            let take = remaining.min(64);
+            self.inner.write_bits(chunk, take)?;
+            remaining -= take;
+        }
+        Ok(())
+    }
+
+    /// Pads and writes pending bits, see [`BitWriter::finish`]
+    pub fn finish(&mut self) -> Result<()> {
+        self.inner.finish()
+    }
+
+    /// Flushes pending bits and returns the underlying writer, see [`BitWriter::into_inner`]
+    pub fn into_inner(self) -> Result<W> {
+        self.inner.into_inner()
+    }
+}
+
+impl<W: Write> Write for BulkBitWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "tokio")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::async_reader::AsyncBitReader;
+    use bitio_rs::byte_order::ByteOrder;
+    use std::io::ErrorKind;
+
+    #[tokio::test]
+    async fn test_read_big_endian() {
+        let data: &[u8] = &[0b1010_1100, 0b1111_0000];
+        let mut reader = AsyncBitReader::new(data);
+        assert_eq!(reader.read_bits(4).await.unwrap(), 0b1010);
+        assert!(!reader.is_byte_aligned());
+        assert_eq!(reader.read_bits(4).await.unwrap(), 0b1100);
+        assert_eq!(reader.read_bits(8).await.unwrap(), 0b1111_0000);
+    }
+
+    #[tokio::test]
+    async fn test_read_little_endian() {
+        let data: &[u8] = &[0b1010_1100, 0b1111_0000];
+        let mut reader = AsyncBitReader::with_byte_order(ByteOrder::LittleEndian, data);
+        assert_eq!(reader.read_bits(4).await.unwrap(), 0b1100);
+        assert_eq!(reader.read_bits(4).await.unwrap(), 0b1010);
+        assert_eq!(reader.read_bits(8).await.unwrap(), 0b1111_0000);
+    }
+
+    #[tokio::test]
+    async fn test_unaligned_64_bit_read() {
+        let data: &[u8] = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x00];
+        let mut reader = AsyncBitReader::new(data);
+        assert_eq!(reader.read_bits(3).await.unwrap(), 0);
+        assert_eq!(reader.read_bits(64).await.unwrap(), 0x091A2B3C4D5E6F78);
+
+        let data: &[u8] = &[0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        let mut reader = AsyncBitReader::with_byte_order(ByteOrder::LittleEndian, data);
+        assert_eq!(reader.read_bits(4).await.unwrap(), 0);
+        assert_eq!(reader.read_bits(64).await.unwrap(), 0x8070605040302010);
+    }
+
+    #[tokio::test]
+    async fn test_reads_across_chunked_input() {
+        // 数据分多次到达时，read_bits 只等待实际需要的字节
+        let (client, mut server) = tokio::io::duplex(4);
+        let writer = tokio::spawn(async move {
+            use tokio::io::AsyncWriteExt;
+            for chunk in [[0xAB, 0xCD], [0xEF, 0x12]] {
+                server.write_all(&chunk).await.unwrap();
+                tokio::task::yield_now().await;
+            }
+        });
+        let mut reader = AsyncBitReader::new(client);
+        assert_eq!(reader.read_bits(12).await.unwrap(), 0xABC);
+        assert_eq!(reader.read_bits(20).await.unwrap(), 0xDEF12);
+        writer.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let data: &[u8] = &[0xFF];
+        let mut reader = AsyncBitReader::new(data);
+        assert!(reader.read_bits(0).await.is_err());
+        assert!(reader.read_bits(65).await.is_err());
+        let err = reader.read_bits(9).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert!(err.to_string().contains("Unexpected end of stream"));
+    }
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "tokio")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::async_reader::AsyncBitReader;
+    use bitio_rs::async_writer::AsyncBitWriter;
+    use bitio_rs::byte_order::ByteOrder;
+
+    #[tokio::test]
+    async fn test_write_big_endian_with_padding() {
+        let mut writer = AsyncBitWriter::new(Vec::new());
+        writer.write_bits(0b1010, 4).await.unwrap();
+        writer.write_bits(0b1100, 4).await.unwrap();
+        writer.write_bits(0b101, 3).await.unwrap();
+        assert_eq!(writer.bits_written(), 11);
+        assert!(!writer.is_byte_aligned());
+        assert_eq!(writer.into_inner().await.unwrap(), vec![0xAC, 0xA0]);
+    }
+
+    #[tokio::test]
+    async fn test_write_little_endian_with_padding() {
+        let mut writer = AsyncBitWriter::with_byte_order(ByteOrder::LittleEndian, Vec::new());
+        writer.write_bits(0b1010, 4).await.unwrap();
+        writer.write_bits(0b1100, 4).await.unwrap();
+        writer.write_bits(0b101, 3).await.unwrap();
+        assert_eq!(writer.into_inner().await.unwrap(), vec![0xCA, 0x05]);
+    }
+
+    #[tokio::test]
+    async fn test_unaligned_64_bit_write() {
+        let mut writer = AsyncBitWriter::new(Vec::new());
+        writer.write_bits(0b1, 1).await.unwrap();
+        writer.write_bits(0x0123456789ABCDEF, 64).await.unwrap();
+        writer.write_bits(0x7F, 7).await.unwrap();
+        assert_eq!(writer.bits_written(), 72);
+        assert!(writer.is_byte_aligned());
+        assert_eq!(
+            writer.into_inner().await.unwrap(),
+            vec![0x80, 0x91, 0xA2, 0xB3, 0xC4, 0xD5, 0xE6, 0xF7, 0xFF]
+        );
+
+        let mut writer = AsyncBitWriter::with_byte_order(ByteOrder::LittleEndian, Vec::new());
+        writer.write_bits(0x0F, 4).await.unwrap();
+        writer.write_bits(0x0123456789ABCDEF, 64).await.unwrap();
+        assert_eq!(
+            writer.into_inner().await.unwrap(),
+            vec![0xFF, 0xDE, 0xBC, 0x9A, 0x78, 0x56, 0x34, 0x12, 0x00]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_with_async_reader() {
+        let widths = [3usize, 13, 64, 1, 27, 40, 7, 64, 5];
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut writer = AsyncBitWriter::with_byte_order(byte_order, Vec::new());
+            for (i, &w) in widths.iter().enumerate() {
+                let v = 0x9E3779B97F4A7C15u64.rotate_left(i as u32);
+                writer.write_bits(v, w).await.unwrap();
+            }
+            let data = writer.into_inner().await.unwrap();
+            let mut reader = AsyncBitReader::with_byte_order(byte_order, &data[..]);
+            for (i, &w) in widths.iter().enumerate() {
+                let mask = if w == 64 { u64::MAX } else { (1u64 << w) - 1 };
+                let v = 0x9E3779B97F4A7C15u64.rotate_left(i as u32) & mask;
+                assert_eq!(reader.read_bits(w).await.unwrap(), v);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_bit_count() {
+        let mut writer = AsyncBitWriter::new(Vec::new());
+        assert!(writer.write_bits(0, 0).await.is_err());
+        assert!(writer.write_bits(0, 65).await.is_err());
+        assert_eq!(writer.bits_written(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_flush_keeps_partial_byte() {
+        let mut writer = AsyncBitWriter::new(Vec::new());
+        writer.write_bits(0b1010, 4).await.unwrap();
+        writer.flush().await.unwrap();
+        assert!(!writer.is_byte_aligned());
+        writer.write_bits(0b1100, 4).await.unwrap();
+        assert_eq!(writer.into_inner().await.unwrap(), vec![0xAC]);
+
+        // finish 补齐当前字节，之后从下一个字节继续写
+        let mut writer = AsyncBitWriter::new(Vec::new());
+        writer.write_bits(0b1010, 4).await.unwrap();
+        writer.finish().await.unwrap();
+        assert!(writer.is_byte_aligned());
+        writer.write_bits(0b1100, 4).await.unwrap();
+        assert_eq!(writer.into_inner().await.unwrap(), vec![0xA0, 0xC0]);
+
+        let mut writer = AsyncBitWriter::new(Vec::new()).pad_on_flush(true);
+        writer.write_bits(0b1010, 4).await.unwrap();
+        writer.flush().await.unwrap();
+        writer.write_bits(0b1100, 4).await.unwrap();
+        assert_eq!(writer.into_inner().await.unwrap(), vec![0xA0, 0xC0]);
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::bcd::{decode_semi_octets, encode_semi_octets, from_bcd, to_bcd};
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::traits::{BitReadExt, BitWrite, BitWriteExt};
+    use std::io::ErrorKind;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(to_bcd(1234), Some(0x1234));
+        assert_eq!(to_bcd(0), Some(0));
+        assert_eq!(to_bcd(9_999_999_999_999_999), Some(0x9999_9999_9999_9999));
+        assert_eq!(to_bcd(10_000_000_000_000_000), None);
+        assert_eq!(from_bcd(0x0042), Some(42));
+        assert_eq!(from_bcd(0x1A), None);
+    }
+
+    #[test]
+    fn test_bcd_fields() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_bits(0b1, 1).unwrap();
+        cursor.write_bcd(987, 3).unwrap();
+        cursor.write_bcd(5, 4).unwrap();
+        cursor.write_bits(0, 3).unwrap();
+        assert_eq!(
+            cursor.get_ref(),
+            &[0b1100_1100, 0b0011_1000, 0x00, 0b0010_1000]
+        );
+
+        cursor.set_position(1);
+        assert_eq!(cursor.read_bcd(3).unwrap(), 987);
+        assert_eq!(cursor.read_bcd(4).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_wide_bcd_fields() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_bcd(9_999_999_999_999_999_999, 19).unwrap();
+        cursor.write_bcd(12_345_678_901_234_567, 18).unwrap();
+        assert_eq!(
+            cursor.get_ref()[..10],
+            [0x99, 0x99, 0x99, 0x99, 0x99, 0x99, 0x99, 0x99, 0x99, 0x90]
+        );
+        cursor.set_position(0);
+        assert_eq!(cursor.read_bcd(19).unwrap(), 9_999_999_999_999_999_999);
+        assert_eq!(cursor.read_bcd(18).unwrap(), 12_345_678_901_234_567);
+    }
+
+    #[test]
+    fn test_bcd_errors() {
+        let mut cursor = BitCursor::new(Vec::new());
+        assert_eq!(
+            cursor.write_bcd(100, 2).unwrap_err().to_string(),
+            "Value 100 does not fit in 8 bits"
+        );
+        assert!(cursor.write_bcd(u64::MAX, 19).is_err());
+        assert_eq!(
+            cursor.write_bcd(1, 20).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+        assert_eq!(cursor.position(), 0);
+
+        let mut cursor = BitCursor::new(vec![0x1F]);
+        assert_eq!(
+            cursor.read_bcd(2).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+        assert!(cursor.read_bcd(0).is_err());
+    }
+
+    #[test]
+    fn test_semi_octets() {
+        // GSM 03.40 地址 +31641600986 的号码部分
+        let encoded = [0x13, 0x46, 0x61, 0x00, 0x89, 0xF6];
+        assert_eq!(decode_semi_octets(&encoded).unwrap(), "31641600986");
+        assert_eq!(encode_semi_octets("31641600986").unwrap(), encoded);
+        assert_eq!(encode_semi_octets("*#12").unwrap(), [0xBA, 0x21]);
+        assert_eq!(decode_semi_octets(&[0xBA, 0x21]).unwrap(), "*#12");
+        assert_eq!(decode_semi_octets(&[]).unwrap(), "");
+
+        assert!(decode_semi_octets(&[0xF1, 0x23]).is_err());
+        assert!(decode_semi_octets(&[0x1F]).is_err());
+        assert!(encode_semi_octets("12x").is_err());
+    }
+}
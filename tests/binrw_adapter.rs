@@ -0,0 +1,80 @@
+#![cfg(feature = "binrw")]
+
+#[cfg(test)]
+mod tests {
+    use binrw::{BinRead, BinReaderExt, Endian};
+    use bitio_rs::binrw_adapter::{BinrwReader, read_binrw};
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::traits::BitRead;
+    use std::io::{ErrorKind, Seek, SeekFrom};
+
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Header {
+        length: u16,
+        flags: u8,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    enum Packet {
+        #[br(magic = 1u8)]
+        Short(u8),
+        #[br(magic = 2u8)]
+        Long(u32),
+    }
+
+    #[test]
+    fn test_read_between_bit_fields() {
+        let data = [0xA5, 0x12, 0x34, 0x0F, 0x02, 0xDE, 0xAD, 0xBE, 0xEF, 0xC3];
+        let mut reader = BitReader::new(&data[..]);
+        assert_eq!(reader.read_bits(4).unwrap(), 0xA);
+        assert_eq!(reader.read_bits(4).unwrap(), 0x5);
+
+        let header: Header = read_binrw(&mut reader, Endian::Big, ()).unwrap();
+        assert_eq!(
+            header,
+            Header {
+                length: 0x1234,
+                flags: 0x0F
+            }
+        );
+        // 先尝试 Short 失败后回退再尝试 Long
+        let packet: Packet = read_binrw(&mut reader, Endian::Big, ()).unwrap();
+        assert_eq!(packet, Packet::Long(0xDEAD_BEEF));
+        assert_eq!(reader.read_bits(2).unwrap(), 0b11);
+        assert_eq!(reader.read_bits(6).unwrap(), 0x03);
+    }
+
+    #[test]
+    fn test_failed_parse_leaves_reader_in_place() {
+        let data = [0x07, 0x01, 0x02];
+        let mut reader = BitReader::new(&data[..]);
+        let err = read_binrw::<Packet, _>(&mut reader, Endian::Big, ()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(reader.read_bits(8).unwrap(), 0x07);
+
+        reader.read_bits(3).unwrap();
+        let err = read_binrw::<Packet, _>(&mut reader, Endian::Big, ()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_adapter_seek() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05];
+        let mut reader = BitReader::new(&data[..]);
+        {
+            let mut adapter = BinrwReader::new(&mut reader).unwrap();
+            let value: u16 = adapter.read_le().unwrap();
+            assert_eq!(value, 0x0201);
+            adapter.seek(SeekFrom::Current(-1)).unwrap();
+            let value: u16 = adapter.read_be().unwrap();
+            assert_eq!(value, 0x0203);
+            assert!(adapter.seek(SeekFrom::End(0)).is_err());
+            assert!(adapter.seek(SeekFrom::Current(-4)).is_err());
+            adapter.seek(SeekFrom::Start(1)).unwrap();
+        }
+        // 适配器只消费到最终位置
+        assert_eq!(reader.read_bits(16).unwrap(), 0x0203);
+        assert_eq!(reader.read_bits(16).unwrap(), 0x0405);
+    }
+}
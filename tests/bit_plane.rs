@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::bit_plane::{
+        merge_bit_planes, read_bit_planes, split_bit_planes, write_bit_planes,
+    };
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn test_split_layout() {
+        let symbols = [
+            0b101, 0b011, 0b110, 0b000, 0b111, 0b001, 0b010, 0b100, 0b111,
+        ];
+        let planes = split_bit_planes(&symbols, 3).unwrap();
+        assert_eq!(
+            planes,
+            [
+                vec![0b1010_1001, 0b1000_0000],
+                vec![0b0110_1010, 0b1000_0000],
+                vec![0b1100_1100, 0b1000_0000],
+            ]
+        );
+        assert_eq!(merge_bit_planes(&planes, 9, 3).unwrap(), symbols);
+    }
+
+    #[test]
+    fn test_partial_planes() {
+        let symbols = [0b1011, 0b0110, 0b1111];
+        let planes = split_bit_planes(&symbols, 4).unwrap();
+        // 只有最高两个平面时低位补零
+        assert_eq!(
+            merge_bit_planes(&planes[..2], 3, 4).unwrap(),
+            [0b1000, 0b0100, 0b1100]
+        );
+        assert_eq!(merge_bit_planes::<Vec<u8>>(&[], 3, 4).unwrap(), [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_stream_round_trip() {
+        let symbols: Vec<u64> = (0..150).map(|i| (i * 2654435761u64) & 0xFFF).collect();
+        for order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut cursor = BitCursor::with_byte_order(order, Vec::new());
+            write_bit_planes(&mut cursor, &symbols, 12).unwrap();
+            assert_eq!(cursor.position(), 150 * 12);
+
+            cursor.set_position(0);
+            let mut out = vec![0; 150];
+            read_bit_planes(&mut cursor, &mut out, 12, 12).unwrap();
+            assert_eq!(out, symbols);
+
+            cursor.set_position(0);
+            read_bit_planes(&mut cursor, &mut out, 12, 5).unwrap();
+            let coarse: Vec<u64> = symbols.iter().map(|s| s & 0xF80).collect();
+            assert_eq!(out, coarse);
+        }
+
+        let mut cursor = BitCursor::new(Vec::new());
+        write_bit_planes(&mut cursor, &[u64::MAX, 1 << 63], 64).unwrap();
+        cursor.set_position(0);
+        let mut out = [0; 2];
+        read_bit_planes(&mut cursor, &mut out, 64, 64).unwrap();
+        assert_eq!(out, [u64::MAX, 1 << 63]);
+    }
+
+    #[test]
+    fn test_stream_matches_packed_planes() {
+        let symbols = [3, 1, 2, 0, 3, 3, 1, 0, 2, 2];
+        let mut cursor = BitCursor::new(Vec::new());
+        write_bit_planes(&mut cursor, &symbols, 2).unwrap();
+        let planes = split_bit_planes(&symbols, 2).unwrap();
+        // 两个 10 位平面首尾相接
+        let high = u16::from_be_bytes([planes[0][0], planes[0][1]]) >> 6;
+        let low = u16::from_be_bytes([planes[1][0], planes[1][1]]) >> 6;
+        let expected = ((high as u32) << 10 | low as u32) << 4;
+        assert_eq!(cursor.get_ref()[..], expected.to_be_bytes()[1..]);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(split_bit_planes(&[4], 2).is_err());
+        assert!(split_bit_planes(&[0], 65).is_err());
+        let err = merge_bit_planes(&[[0u8]; 3], 8, 2).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(merge_bit_planes(&[vec![0u8]], 9, 2).is_err());
+
+        let mut cursor = BitCursor::new(Vec::new());
+        assert!(write_bit_planes(&mut cursor, &[1, 2, 4], 2).is_err());
+        assert_eq!(cursor.position(), 0);
+
+        let mut cursor = BitCursor::new(vec![0xFF]);
+        let mut out = [0; 5];
+        assert!(read_bit_planes(&mut cursor, &mut out, 2, 3).is_err());
+        assert!(read_bit_planes(&mut cursor, &mut out, 2, 2).is_err());
+    }
+}
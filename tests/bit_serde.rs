@@ -0,0 +1,164 @@
+#![cfg(feature = "serde")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::bit_serde::{Schema, from_reader, to_writer};
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::reader::{BitReader, ReaderState};
+    use bitio_rs::traits::BitRead;
+    use bitio_rs::writer::BitWriter;
+    use serde::{Deserialize, Serialize};
+    use std::io::Cursor;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Header {
+        version: u8,
+        flag: bool,
+        kind: u16,
+        offset: i8,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Command {
+        Stop,
+        Move(i16),
+        Turn { degrees: u16 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Packet {
+        header: Header,
+        samples: Vec<u16>,
+        label: Option<String>,
+        command: Command,
+        ratio: f32,
+    }
+
+    fn header_schema() -> Schema {
+        Schema::new()
+            .field("version", 3)
+            .field("kind", 12)
+            .field("offset", 4)
+    }
+
+    #[test]
+    fn test_field_widths_pack_tightly() {
+        let header = Header {
+            version: 5,
+            flag: true,
+            kind: 0xABC,
+            offset: -3,
+        };
+        let mut cursor = BitCursor::new(Vec::new());
+        to_writer(&header, &header_schema(), &mut cursor).unwrap();
+        // 3 + 1 + 12 + 4 = 20 比特
+        assert_eq!(cursor.position(), 20);
+        assert_eq!(
+            cursor.get_ref(),
+            &vec![0b1011_1010, 0b1011_1100, 0b1101_0000]
+        );
+
+        cursor.set_position(0);
+        let decoded: Header = from_reader(&mut cursor, &header_schema()).unwrap();
+        assert_eq!(decoded, header);
+    }
+
+    #[test]
+    fn test_round_trip_through_writer_and_reader() {
+        let schema = header_schema()
+            .field("samples", 10)
+            .field("Turn.degrees", 9)
+            .field("degrees", 1)
+            .length_bits(8)
+            .variant_bits(2);
+        let packet = Packet {
+            header: Header {
+                version: 7,
+                flag: false,
+                kind: 1,
+                offset: 7,
+            },
+            samples: vec![0, 1023, 512, 7],
+            label: Some("bit".to_string()),
+            command: Command::Turn { degrees: 359 },
+            ratio: 0.75,
+        };
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut writer = BitWriter::with_byte_order(byte_order, Vec::new());
+            to_writer(&packet, &schema, &mut writer).unwrap();
+            let data = writer.into_inner().unwrap();
+
+            let mut reader = BitReader::with_byte_order(byte_order, Cursor::new(data));
+            let decoded: Packet = from_reader(&mut reader, &schema).unwrap();
+            assert_eq!(decoded, packet);
+        }
+    }
+
+    #[test]
+    fn test_enum_and_option_variants() {
+        let schema = Schema::new().variant_bits(2);
+        for command in [
+            Command::Stop,
+            Command::Move(-300),
+            Command::Turn { degrees: 90 },
+        ] {
+            let value = (command, None::<u8>);
+            let mut cursor = BitCursor::new(Vec::new());
+            to_writer(&value, &schema, &mut cursor).unwrap();
+            cursor.set_position(0);
+            let decoded: (Command, Option<u8>) = from_reader(&mut cursor, &schema).unwrap();
+            assert_eq!(decoded, value);
+        }
+    }
+
+    #[test]
+    fn test_value_out_of_range() {
+        let header = Header {
+            version: 8, // 3 比特放不下
+            flag: false,
+            kind: 0,
+            offset: 0,
+        };
+        let mut cursor = BitCursor::new(Vec::new());
+        assert!(to_writer(&header, &header_schema(), &mut cursor).is_err());
+
+        let header = Header {
+            version: 0,
+            flag: false,
+            kind: 0,
+            offset: -9, // 4 比特补码的范围是 -8..=7
+        };
+        let mut cursor = BitCursor::new(Vec::new());
+        assert!(to_writer(&header, &header_schema(), &mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_truncated_input() {
+        let mut cursor = BitCursor::new(vec![0xFFu8, 0xFF]);
+        let result: std::io::Result<Header> = from_reader(&mut cursor, &header_schema());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_reader_state_checkpoint() {
+        let data: Vec<u8> = (0..64u8).collect();
+        let mut reader = BitReader::new(Cursor::new(data.clone()));
+        reader.read_bits(21).unwrap();
+        let state = reader.export_state().unwrap();
+
+        // 用本库的格式保存检查点，再在新的 reader 上恢复
+        let mut cursor = BitCursor::new(Vec::new());
+        to_writer(&state, &Schema::new(), &mut cursor).unwrap();
+        cursor.set_position(0);
+        let restored: ReaderState = from_reader(&mut cursor, &Schema::new()).unwrap();
+        assert_eq!(restored, state);
+
+        let mut resumed = BitReader::new(Cursor::new(data));
+        resumed.import_state(&restored).unwrap();
+        assert_eq!(
+            resumed.read_bits(11).unwrap(),
+            reader.read_bits(11).unwrap()
+        );
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::bitfield;
+    use bitio_rs::bitfield::BitfieldValue;
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::traits::{BitDecode, BitRead};
+    use bitio_rs::writer::BitWriter;
+    use std::io::Cursor;
+
+    bitfield! {
+        /// MPEG-like header used by the tests
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct Header {
+            pub version: u8 => 3,
+            pub flag: bool => 1,
+            pub kind: u16 => 12,
+            pub offset: i8 => 5,
+        }
+    }
+
+    bitfield! {
+        struct Wide {
+            small: u8 => 12,
+            big: u64 => 64,
+        }
+    }
+
+    fn header() -> Header {
+        Header {
+            version: 5,
+            flag: true,
+            kind: 0xABC,
+            offset: -7,
+        }
+    }
+
+    #[test]
+    fn test_round_trip_through_writer_and_reader() {
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut writer = BitWriter::with_byte_order(byte_order, Vec::new());
+            header().write_to(&mut writer).unwrap();
+            let data = writer.into_inner().unwrap();
+            assert_eq!(data.len(), 3);
+
+            let mut reader = BitReader::with_byte_order(byte_order, Cursor::new(data));
+            assert_eq!(Header::read_from(&mut reader).unwrap(), header());
+        }
+    }
+
+    #[test]
+    fn test_layout() {
+        let mut cursor = BitCursor::new(Vec::new());
+        header().write_to(&mut cursor).unwrap();
+        let data = cursor.into_inner();
+        let mut cursor = BitCursor::new(&data);
+        assert_eq!(cursor.read_bits(3).unwrap(), 5);
+        assert_eq!(cursor.read_bits(1).unwrap(), 1);
+        assert_eq!(cursor.read_bits(12).unwrap(), 0xABC);
+        assert_eq!(cursor.read_bits(5).unwrap(), 0b11001);
+
+        let mut cursor = BitCursor::new(&data);
+        assert_eq!(Header::from_reader(&mut cursor).unwrap(), header());
+    }
+
+    #[test]
+    fn test_value_out_of_range() {
+        let mut cursor = BitCursor::new(Vec::new());
+        let mut bad = header();
+        bad.version = 8;
+        assert!(bad.write_to(&mut cursor).is_err());
+        let mut bad = header();
+        bad.offset = 16;
+        assert!(bad.write_to(&mut cursor).is_err());
+
+        // 12 比特的字段读出 0xFFF，放不进 u8
+        let data = [0xFF; 10];
+        let mut cursor = BitCursor::new(&data[..]);
+        assert!(Wide::read_from(&mut cursor).is_err());
+        let wide = Wide {
+            small: 200,
+            big: u64::MAX,
+        };
+        let mut cursor = BitCursor::new(Vec::new());
+        wide.write_to(&mut cursor).unwrap();
+        cursor.set_position(0);
+        let back = Wide::read_from(&mut cursor).unwrap();
+        assert_eq!((back.small, back.big), (200, u64::MAX));
+    }
+
+    #[test]
+    fn test_value_conversions() {
+        assert_eq!(i8::from_bits(0b10000, 5).unwrap(), -16);
+        assert_eq!((-16i8).to_bits(5).unwrap(), 0b10000);
+        assert_eq!(i64::from_bits(u64::MAX, 64).unwrap(), -1);
+        assert!(true.to_bits(0).is_err());
+        assert!(u32::from_bits(1 << 40, 41).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "bitvec")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::bitvec::{read_bitvec, read_into_bitslice, write_bitslice};
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::traits::{BitRead, BitWrite};
+    use bitvec::prelude::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_bitvec_stream_order() {
+        let data = [0b1011_0000u8, 0xFF];
+        let mut reader = BitReader::new(Cursor::new(data));
+        let bits: BitVec<u8, Msb0> = read_bitvec(&mut reader, 4).unwrap();
+        assert_eq!(bits, bits![u8, Msb0; 1, 0, 1, 1]);
+
+        // 小端序流先读字节的低位
+        let mut reader = BitReader::with_byte_order(ByteOrder::LittleEndian, Cursor::new(data));
+        let bits: BitVec<u64, Lsb0> = read_bitvec(&mut reader, 6).unwrap();
+        assert_eq!(bits, bits![0, 0, 0, 0, 1, 1]);
+        assert_eq!(reader.read_bits(2).unwrap(), 0b10);
+    }
+
+    #[test]
+    fn test_write_bitslice_round_trip() {
+        let source = bits![u8, Lsb0; 1, 1, 0, 1, 0, 0, 1, 0, 1, 1, 1];
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut cursor = BitCursor::with_byte_order(byte_order, Vec::new());
+            cursor.write_bits(0b10, 2).unwrap();
+            write_bitslice(&mut cursor, source).unwrap();
+            cursor.set_position(2);
+
+            let mut back = bitvec![u16, Msb0; 0; source.len()];
+            read_into_bitslice(&mut cursor, &mut back).unwrap();
+            assert_eq!(back, source);
+        }
+    }
+
+    #[test]
+    fn test_read_past_end() {
+        let mut reader = BitReader::new(Cursor::new([0u8]));
+        assert!(read_bitvec::<u8, Msb0, _>(&mut reader, 9).is_err());
+    }
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "bytes")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::buf::{BufBitReader, BufBitWriter};
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::traits::{BitRead, BitWrite};
+    use bytes::{Bytes, BytesMut};
+
+    const FIELDS: [(u64, usize); 7] = [
+        (0b101, 3),
+        (0x0123_4567_89AB_CDEF, 64),
+        (1, 1),
+        (0x7F, 7),
+        (0xDEAD_BEEF, 32),
+        (0, 5),
+        (0x1_FFFF_FFFF_FFFF, 49),
+    ];
+
+    #[test]
+    fn test_matches_cursor_layout() {
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut writer = BufBitWriter::with_byte_order(byte_order, BytesMut::new());
+            let mut cursor = BitCursor::with_byte_order(byte_order, Vec::new());
+            for (value, n) in FIELDS {
+                writer.write_bits(value, n).unwrap();
+                cursor.write_bits(value, n).unwrap();
+            }
+            let bytes = writer.into_inner().unwrap().freeze();
+            assert_eq!(&bytes[..], &cursor.into_inner()[..]);
+
+            let mut reader = BufBitReader::with_byte_order(byte_order, bytes);
+            for (value, n) in FIELDS {
+                assert_eq!(reader.read_bits(n).unwrap(), value);
+            }
+            assert_eq!(reader.bits_remaining(), 7);
+        }
+    }
+
+    #[test]
+    fn test_eof_consumes_nothing() {
+        let mut reader = BufBitReader::new(Bytes::from_static(&[0xAB, 0xCD]));
+        assert_eq!(reader.read_bits(4).unwrap(), 0xA);
+        assert!(reader.read_bits(13).is_err());
+        assert_eq!(reader.bits_remaining(), 12);
+        assert_eq!(reader.read_bits(12).unwrap(), 0xBCD);
+        assert!(reader.read_bits(0).is_err());
+    }
+
+    #[test]
+    fn test_writer_into_fixed_slice() {
+        let mut storage = [0u8; 2];
+        let mut writer = BufBitWriter::new(&mut storage[..]);
+        writer.write_bits(0xABC, 12).unwrap();
+        assert!(!writer.is_byte_aligned());
+        assert!(writer.write_bits(0xFFFF, 16).is_err());
+        let mut storage = [0u8; 2];
+        let mut writer = BufBitWriter::new(&mut storage[..]);
+        writer.write_bits(0xABC, 12).unwrap();
+        writer.into_inner().unwrap();
+        assert_eq!(storage, [0xAB, 0xC0]);
+    }
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "can")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::can::Signal;
+
+    fn signal(start_bit: usize, length: usize, byte_order: ByteOrder) -> Signal {
+        Signal {
+            start_bit,
+            length,
+            byte_order,
+            signed: false,
+            scale: 1.0,
+            offset: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_intel_signals() {
+        let frame = [0x34, 0x12, 0xAB, 0xCD, 0, 0, 0, 0];
+        assert_eq!(
+            signal(0, 16, ByteOrder::LittleEndian)
+                .extract_raw(&frame)
+                .unwrap(),
+            0x1234
+        );
+        // 从字节 0 的第 4 位开始，跨入字节 1
+        assert_eq!(
+            signal(4, 12, ByteOrder::LittleEndian)
+                .extract_raw(&frame)
+                .unwrap(),
+            0x123
+        );
+        assert_eq!(
+            signal(20, 4, ByteOrder::LittleEndian)
+                .extract_raw(&frame)
+                .unwrap(),
+            0xA
+        );
+    }
+
+    #[test]
+    fn test_motorola_signals() {
+        let frame = [0x12, 0x34, 0xAB, 0xCD, 0, 0, 0, 0];
+        assert_eq!(
+            signal(7, 16, ByteOrder::BigEndian)
+                .extract_raw(&frame)
+                .unwrap(),
+            0x1234
+        );
+        // 最高位在字节 0 的第 3 位，低 8 位为字节 1
+        assert_eq!(
+            signal(3, 12, ByteOrder::BigEndian)
+                .extract_raw(&frame)
+                .unwrap(),
+            0x234
+        );
+        assert_eq!(
+            signal(23, 4, ByteOrder::BigEndian)
+                .extract_raw(&frame)
+                .unwrap(),
+            0xA
+        );
+    }
+
+    #[test]
+    fn test_encode_decode_physical() {
+        let temperature = Signal {
+            start_bit: 8,
+            length: 8,
+            byte_order: ByteOrder::LittleEndian,
+            signed: true,
+            scale: 0.5,
+            offset: -10.0,
+        };
+        let speed = Signal {
+            start_bit: 39,
+            length: 16,
+            byte_order: ByteOrder::BigEndian,
+            signed: false,
+            scale: 0.01,
+            offset: 0.0,
+        };
+        let mut frame = [0xFFu8; 8];
+        temperature.encode(&mut frame, -25.5).unwrap();
+        speed.encode(&mut frame, 123.45).unwrap();
+        assert_eq!(frame, [0xFF, 0xE1, 0xFF, 0xFF, 0x30, 0x39, 0xFF, 0xFF]);
+        assert_eq!(temperature.decode(&frame).unwrap(), -25.5);
+        assert!((speed.decode(&frame).unwrap() - 123.45).abs() < 1e-9);
+
+        assert!(temperature.encode(&mut frame, 60.0).is_err());
+        assert!(speed.encode(&mut frame, -1.0).is_err());
+        assert!(speed.encode(&mut frame, f64::NAN).is_err());
+        assert!(
+            signal(60, 8, ByteOrder::LittleEndian)
+                .extract_raw(&frame)
+                .is_err()
+        );
+        assert!(
+            signal(0, 0, ByteOrder::LittleEndian)
+                .encode(&mut frame, 0.0)
+                .is_err()
+        );
+    }
+}
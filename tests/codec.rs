@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::codec::frame_of_reference::{BLOCK_SIZE, Mode, decode_u32s, encode_u32s};
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::traits::BitWrite;
+    use bitio_rs::writer::BitWriter;
+    use std::io::Cursor;
+
+    fn encode(values: &[u32], mode: Mode) -> Vec<u8> {
+        let mut writer = BitWriter::new(Vec::new());
+        encode_u32s(values, mode, &mut writer).unwrap();
+        writer.into_inner().unwrap()
+    }
+
+    fn decode(data: Vec<u8>) -> Vec<u32> {
+        decode_u32s(&mut BitReader::new(Cursor::new(data))).unwrap()
+    }
+
+    #[test]
+    fn test_for_round_trip() {
+        let values: Vec<u32> = (0..1000).map(|i| 1_000_000 + (i * 37) % 500).collect();
+        let data = encode(&values, Mode::For);
+        // 每块 128 个 9 比特的差值，远小于原始的 32 比特
+        assert!(data.len() < values.len() * 2);
+        assert_eq!(decode(data), values);
+    }
+
+    #[test]
+    fn test_pfor_round_trip_with_outliers() {
+        let mut values: Vec<u32> = (0..1000).map(|i| 5000 + i % 16).collect();
+        values[3] = u32::MAX;
+        values[200] = 0;
+        values[999] = 1 << 30;
+        let pfor = encode(&values, Mode::PFor);
+        let plain = encode(&values, Mode::For);
+        assert!(pfor.len() < plain.len());
+        assert_eq!(decode(pfor), values);
+        assert_eq!(decode(plain), values);
+    }
+
+    #[test]
+    fn test_constant_and_empty_input() {
+        let values = vec![7u32; BLOCK_SIZE * 2 + 3];
+        let data = encode(&values, Mode::PFor);
+        // 32 比特总数 + 3 个块头（各 32 + 6 + 8 比特）
+        assert_eq!(data.len(), (32 + 3 * 46usize).div_ceil(8));
+        assert_eq!(decode(data), values);
+
+        assert_eq!(decode(encode(&[], Mode::PFor)), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_little_endian_stream() {
+        let values: Vec<u32> = (0..300).map(|i| i * i).collect();
+        let mut cursor = BitCursor::with_byte_order(ByteOrder::LittleEndian, Vec::new());
+        encode_u32s(&values, Mode::PFor, &mut cursor).unwrap();
+        cursor.set_position(0);
+        assert_eq!(decode_u32s(&mut cursor).unwrap(), values);
+    }
+
+    #[test]
+    fn test_corrupt_header() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_bits(1, 32).unwrap(); // count
+        cursor.write_bits(0, 32).unwrap(); // reference
+        cursor.write_bits(40, 6).unwrap(); // width > 32
+        cursor.write_bits(0, 8).unwrap();
+        cursor.set_position(0);
+        let err = decode_u32s(&mut cursor).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        let mut data = encode(&[1, 2, 3], Mode::For);
+        data.truncate(5);
+        assert!(decode_u32s(&mut BitReader::new(Cursor::new(data))).is_err());
+    }
+
+    #[test]
+    fn test_run_length_round_trip() {
+        use bitio_rs::codec::run_length::{decode_bits, encode_bits};
+
+        let mut mask = vec![false; 5];
+        mask.extend(vec![true; 40]);
+        mask.extend([false, true, true, false]);
+        for width in [1, 3, 6, 32] {
+            let mut cursor = BitCursor::new(Vec::new());
+            encode_bits(&mask, width, &mut cursor).unwrap();
+            cursor.set_position(0);
+            assert_eq!(decode_bits(&mut cursor).unwrap(), mask);
+        }
+
+        // 游程长度 40 在 3 位宽时拆分为 7 + 0 + 7 + ...
+        let mut cursor = BitCursor::new(Vec::new());
+        encode_bits(&mask, 3, &mut cursor).unwrap();
+        assert_eq!(cursor.position(), 32 + 6 + 1 + 3 * (1 + 11 + 3));
+
+        let mut cursor = BitCursor::new(Vec::new());
+        encode_bits(&[], 4, &mut cursor).unwrap();
+        encode_bits(&[true], 4, &mut cursor).unwrap();
+        cursor.set_position(0);
+        assert!(decode_bits(&mut cursor).unwrap().is_empty());
+        assert_eq!(decode_bits(&mut cursor).unwrap(), vec![true]);
+    }
+
+    #[test]
+    fn test_run_length_errors() {
+        use bitio_rs::codec::run_length::{decode_bits, encode_bits};
+
+        let mut cursor = BitCursor::new(Vec::new());
+        assert!(encode_bits(&[true], 0, &mut cursor).is_err());
+        assert!(encode_bits(&[true], 33, &mut cursor).is_err());
+
+        // 游程总长超过比特数
+        let mut writer = BitCursor::new(Vec::new());
+        writer.write_bits(3, 32).unwrap();
+        writer.write_bits(4, 6).unwrap();
+        writer.write_bits(1, 1).unwrap();
+        writer.write_bits(5, 4).unwrap();
+        writer.set_position(0);
+        assert!(decode_bits(&mut writer).is_err());
+    }
+
+    #[test]
+    fn test_rice_codes() {
+        use bitio_rs::codec::rice::{
+            read_rice, read_rice_signed, read_unary, write_rice, write_rice_signed, write_unary,
+            zigzag_decode, zigzag_encode,
+        };
+
+        let folded: Vec<u64> = [0, -1, 1, -2, 2].into_iter().map(zigzag_encode).collect();
+        assert_eq!(folded, [0, 1, 2, 3, 4]);
+        for v in [i64::MIN, i64::MAX, -12345, 0] {
+            assert_eq!(zigzag_decode(zigzag_encode(v)), v);
+        }
+
+        // 13 = 0b1101，k = 2：商 3 → 0001，余数 01
+        let mut cursor = BitCursor::new(Vec::new());
+        write_rice(&mut cursor, 13, 2).unwrap();
+        write_unary(&mut cursor, 0).unwrap();
+        assert_eq!(cursor.get_ref(), &[0b0001_0110]);
+
+        let mut cursor = BitCursor::new(Vec::new());
+        write_unary(&mut cursor, 130).unwrap();
+        for (value, k) in [(0, 0), (7, 0), (1000, 5), (u64::MAX, 63)] {
+            write_rice(&mut cursor, value, k).unwrap();
+        }
+        write_rice_signed(&mut cursor, -9, 3).unwrap();
+        cursor.set_position(0);
+        assert_eq!(read_unary(&mut cursor).unwrap(), 130);
+        for (value, k) in [(0, 0), (7, 0), (1000, 5), (u64::MAX, 63)] {
+            assert_eq!(read_rice(&mut cursor, k).unwrap(), value);
+        }
+        assert_eq!(read_rice_signed(&mut cursor, 3).unwrap(), -9);
+
+        assert!(write_rice(&mut cursor, 1, 64).is_err());
+        // 商左移 k 位后溢出
+        let mut cursor = BitCursor::new(Vec::new());
+        write_unary(&mut cursor, 4).unwrap();
+        cursor.set_position(0);
+        assert!(read_rice(&mut cursor, 62).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::copy_bits;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::traits::{BitRead, BitWrite};
+    use bitio_rs::writer::BitWriter;
+
+    #[test]
+    fn test_copy_bits_between_offsets() {
+        let data: Vec<u8> = (0..40u8).map(|i| i.wrapping_mul(71) ^ 0xA5).collect();
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            for (skip, lead, n) in [(0, 0, 320), (3, 5, 200), (7, 1, 64), (1, 0, 9)] {
+                let mut reader = BitReader::with_byte_order(byte_order, &data[..]);
+                if skip > 0 {
+                    reader.read_bits(skip).unwrap();
+                }
+                let mut writer = BitCursor::with_byte_order(byte_order, Vec::new());
+                if lead > 0 {
+                    writer.write_bits(0, lead).unwrap();
+                }
+                copy_bits(&mut reader, &mut writer, n).unwrap();
+                assert_eq!(writer.position(), lead as u64 + n);
+
+                // 逐比特比较源与目标
+                let mut source = BitCursor::with_byte_order(byte_order, &data[..]);
+                source.set_position(skip as u64);
+                writer.set_position(lead as u64);
+                for _ in 0..n {
+                    assert_eq!(writer.read_bits(1).unwrap(), source.read_bits(1).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_copy_bits_to_writer() {
+        let data = [0xDE, 0xAD, 0xBE, 0xEF];
+        let mut reader = BitCursor::new(&data[..]);
+        reader.read_bits(4).unwrap();
+        let mut writer = BitWriter::new(Vec::new());
+        copy_bits(&mut reader, &mut writer, 24).unwrap();
+        assert_eq!(writer.into_inner().unwrap(), vec![0xEA, 0xDB, 0xEE]);
+    }
+
+    #[test]
+    fn test_copy_bits_eof() {
+        let data = [0xFF; 4];
+        let mut reader = BitCursor::new(&data[..]);
+        let mut writer = BitCursor::new(Vec::new());
+        copy_bits(&mut reader, &mut writer, 0).unwrap();
+        assert!(writer.get_ref().is_empty());
+        assert!(copy_bits(&mut reader, &mut writer, 33).is_err());
+    }
+}
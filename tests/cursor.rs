@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::traits::{BitExpect, BitPeek, BitRead, BitReadExt, BitWrite, BitWriteExt};
+    use std::io::SeekFrom;
+
+    #[test]
+    fn test_write_then_read_big_endian() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_bits(0b101, 3).unwrap();
+        cursor.write_bits(0xABCD, 16).unwrap();
+        assert_eq!(cursor.position(), 19);
+        assert_eq!(
+            cursor.get_ref(),
+            &vec![0b1011_0101, 0b0111_1001, 0b1010_0000]
+        );
+
+        cursor.seek_bits(SeekFrom::Start(0)).unwrap();
+        assert_eq!(cursor.read_bits(3).unwrap(), 0b101);
+        assert_eq!(cursor.read_bits(16).unwrap(), 0xABCD);
+    }
+
+    #[test]
+    fn test_write_then_read_little_endian() {
+        let mut cursor = BitCursor::with_byte_order(ByteOrder::LittleEndian, Vec::new());
+        cursor.write_bits(0b1010, 4).unwrap();
+        cursor.write_bits(0b1100, 4).unwrap();
+        cursor.write_bits(0x0123456789ABCDEF, 64).unwrap();
+        assert_eq!(
+            cursor.get_ref(),
+            &vec![0xCA, 0xEF, 0xCD, 0xAB, 0x89, 0x67, 0x45, 0x23, 0x01]
+        );
+
+        cursor.set_position(4);
+        assert_eq!(cursor.read_bits(4).unwrap(), 0b1100);
+        assert_eq!(cursor.read_bits(64).unwrap(), 0x0123456789ABCDEF);
+    }
+
+    #[test]
+    fn test_overwrite_in_place() {
+        let mut cursor = BitCursor::new(vec![0xFF, 0xFF]);
+        cursor.seek_bits(SeekFrom::Start(6)).unwrap();
+        cursor.write_bits(0, 4).unwrap();
+        assert_eq!(cursor.into_inner(), vec![0b1111_1100, 0b0011_1111]);
+    }
+
+    #[test]
+    fn test_peek_does_not_advance() {
+        let mut cursor = BitCursor::new([0b1100_1111u8]);
+        assert_eq!(cursor.peek_bits(4).unwrap(), 0b1100);
+        assert_eq!(cursor.position(), 0);
+        assert_eq!(cursor.read_bits(4).unwrap(), 0b1100);
+        assert_eq!(cursor.peek_bits(4).unwrap(), 0b1111);
+    }
+
+    #[test]
+    fn test_seek_relative_and_from_end() {
+        let mut cursor = BitCursor::new(vec![0x12, 0x34]);
+        assert_eq!(cursor.seek_bits(SeekFrom::End(-4)).unwrap(), 12);
+        assert_eq!(cursor.read_bits(4).unwrap(), 0x4);
+        assert_eq!(cursor.seek_bits(SeekFrom::Current(-12)).unwrap(), 4);
+        assert_eq!(cursor.read_bits(8).unwrap(), 0x23);
+        assert!(cursor.seek_bits(SeekFrom::Current(-13)).is_err());
+        assert_eq!(cursor.position(), 12);
+    }
+
+    #[test]
+    fn test_read_past_end() {
+        let mut cursor = BitCursor::new(vec![0xAA]);
+        assert_eq!(cursor.read_bits(7).unwrap(), 0b1010101);
+        assert!(cursor.read_bits(2).is_err());
+        assert_eq!(cursor.read_bits(1).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_fixed_slice_does_not_grow() {
+        let mut buf = [0u8; 1];
+        let mut cursor = BitCursor::new(&mut buf[..]);
+        cursor.write_bits(0xF, 4).unwrap();
+        assert!(cursor.write_bits(0x1F, 5).is_err());
+        cursor.write_bits(0x1, 4).unwrap();
+        assert_eq!(buf, [0xF1]);
+
+        // 与 BitWriter 写满固定缓冲区一样报错，而不是 UnexpectedEof
+        let mut buf = [0u8; 2];
+        let mut cursor = BitCursor::new(&mut buf[..]);
+        cursor.write_bits(0, 12).unwrap();
+        let err = bitio_rs::Error::from(cursor.write_bits(0x1F, 5).unwrap_err());
+        assert_eq!(err.offset(), Some(12));
+        assert!(matches!(
+            err.root(),
+            bitio_rs::Error::BufferTooSmall {
+                bits: 5,
+                capacity: 4
+            }
+        ));
+        assert_eq!(cursor.position(), 12);
+    }
+
+    #[test]
+    fn test_position_overflow() {
+        let mut cursor = BitCursor::new(vec![0xFFu8; 2]);
+        cursor.set_position(u64::MAX - 3);
+        let err = bitio_rs::Error::from(cursor.read_bits(8).unwrap_err());
+        assert!(matches!(err.root(), bitio_rs::Error::UnexpectedEof));
+        assert!(cursor.peek_bits(64).is_err());
+        let err = bitio_rs::Error::from(cursor.write_bits(0, 8).unwrap_err());
+        assert!(matches!(
+            err.root(),
+            bitio_rs::Error::BufferTooSmall { bits: 8, .. }
+        ));
+        assert_eq!(cursor.get_ref(), &[0xFF, 0xFF]);
+
+        let mut buf = [0u8; 2];
+        let mut cursor = BitCursor::new(&mut buf[..]);
+        cursor.set_position(u64::MAX - 3);
+        let err = bitio_rs::Error::from(cursor.write_bits(0, 8).unwrap_err());
+        assert!(matches!(
+            err.root(),
+            bitio_rs::Error::BufferTooSmall {
+                bits: 8,
+                capacity: 0
+            }
+        ));
+        assert_eq!(cursor.position(), u64::MAX - 3);
+    }
+
+    #[test]
+    fn test_invalid_bit_count() {
+        let mut cursor = BitCursor::new(Vec::new());
+        assert!(cursor.write_bits(0, 0).is_err());
+        assert!(cursor.write_bits(0, 65).is_err());
+        assert!(cursor.read_bits(0).is_err());
+    }
+
+    #[test]
+    fn test_mixed_endian_fields() {
+        // 大端序头部中嵌一个小端序的 16 位长度
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_bits(0xA, 4).unwrap();
+        cursor.write_bits(0xB, 4).unwrap();
+        cursor
+            .write_bits_with(ByteOrder::LittleEndian, 0x1234, 16)
+            .unwrap();
+        cursor
+            .write_bits_with(ByteOrder::BigEndian, 0x5678, 16)
+            .unwrap();
+        assert_eq!(cursor.get_ref(), &vec![0xAB, 0x34, 0x12, 0x56, 0x78]);
+
+        cursor.set_position(8);
+        assert_eq!(
+            cursor.read_bits_with(ByteOrder::LittleEndian, 16).unwrap(),
+            0x1234
+        );
+        assert_eq!(
+            cursor.read_bits_with(ByteOrder::BigEndian, 16).unwrap(),
+            0x5678
+        );
+        assert!(cursor.read_bits_with(ByteOrder::LittleEndian, 0).is_err());
+    }
+
+    #[test]
+    fn test_typed_accessors() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_bits(1, 1).unwrap();
+        cursor.write_u24(0xABCDEF).unwrap();
+        cursor.write_u16(0x0102).unwrap();
+        assert_eq!(cursor.position(), 41);
+        cursor.set_position(1);
+        assert_eq!(cursor.read_u24().unwrap(), 0xABCDEF);
+        assert_eq!(cursor.read_u16().unwrap(), 0x0102);
+    }
+
+    #[test]
+    fn test_expect_bits_and_bytes() {
+        let mut cursor =
+            BitCursor::with_byte_order(ByteOrder::LittleEndian, vec![0x5A, 0x34, 0x12]);
+        cursor.expect_bits(0xA, 4).unwrap();
+        cursor.expect_bits(0x5, 4).unwrap();
+        let err = cursor.expect_bits(0x1235, 16).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "Expected 0x1235 (16 bits) at bit offset 8, found 0x1234"
+        );
+        cursor.set_position(8);
+        cursor.expect_bytes(&[0x34, 0x12]).unwrap();
+        assert!(cursor.expect_bytes(&[0]).is_err());
+    }
+
+    #[test]
+    fn test_expect_aligned_and_eof() {
+        let mut cursor = BitCursor::new(vec![0xAB]);
+        cursor.read_bits(5).unwrap();
+        let err = cursor.expect_byte_aligned().unwrap_err();
+        assert_eq!(err.to_string(), "Expected a byte boundary at bit offset 5");
+        let err = cursor.expect_eof().unwrap_err();
+        assert_eq!(err.to_string(), "Expected end of stream at bit offset 5");
+        cursor.read_bits(3).unwrap();
+        cursor.expect_byte_aligned().unwrap();
+        cursor.expect_eof().unwrap();
+    }
+}
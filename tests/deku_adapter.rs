@@ -0,0 +1,65 @@
+#![cfg(feature = "deku")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::deku_adapter::{DekuBitReader, parse_cursor};
+    use bitio_rs::traits::{BitPeek, BitRead};
+    use deku::bitvec::{BitView, Msb0};
+    use deku::prelude::*;
+    use std::io::ErrorKind;
+
+    #[derive(DekuRead, Debug, PartialEq)]
+    #[deku(endian = "big")]
+    struct Flags {
+        #[deku(bits = 3)]
+        kind: u8,
+        #[deku(bits = 9)]
+        length: u16,
+    }
+
+    #[test]
+    fn test_reader_over_bit_slice() {
+        let data = [0b1011_0011, 0x5A, 0xFF];
+        let bits = &data.view_bits::<Msb0>()[3..20];
+        let mut reader = DekuBitReader::from(bits);
+        assert_eq!(reader.peek_bits(5).unwrap(), 0b10011);
+        assert_eq!(reader.read_bits(5).unwrap(), 0b10011);
+        assert_eq!(reader.read_bits(12).unwrap(), 0x5AF);
+        assert_eq!(reader.position(), 17);
+        assert!(reader.remaining().is_empty());
+        assert_eq!(
+            reader.read_bits(1).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn test_parse_cursor() {
+        let data = [0xF5, 0x2A, 0xC0];
+        let mut cursor = BitCursor::new(&data[..]);
+        assert_eq!(cursor.read_bits(4).unwrap(), 0xF);
+        let flags: Flags = parse_cursor(&mut cursor).unwrap();
+        // 0101 0010 1010 1100 -> kind 010, length 1_0010_1010
+        assert_eq!(
+            flags,
+            Flags {
+                kind: 0b010,
+                length: 0b1_0010_1010
+            }
+        );
+        assert_eq!(cursor.position(), 16);
+        assert_eq!(cursor.read_bits(8).unwrap(), 0xC0);
+
+        assert_eq!(
+            parse_cursor::<Flags, _>(&mut cursor).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+        let mut little = BitCursor::with_byte_order(ByteOrder::LittleEndian, &data[..]);
+        assert_eq!(
+            parse_cursor::<Flags, _>(&mut little).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+    }
+}
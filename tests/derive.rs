@@ -0,0 +1,111 @@
+#![cfg(feature = "derive")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::traits::{BitDecode, BitEncode, BitRead as _};
+    use bitio_rs::{BitRead, BitWrite};
+
+    #[derive(Debug, PartialEq, BitRead, BitWrite)]
+    struct Header {
+        #[bits(3)]
+        version: u8,
+        flag: bool,
+        #[bits(12)]
+        kind: u16,
+        #[bits(5)]
+        offset: i8,
+    }
+
+    #[derive(Debug, PartialEq, BitRead, BitWrite)]
+    struct Packet {
+        header: Header,
+        #[bits(4)]
+        samples: [u8; 3],
+        #[align(8)]
+        #[bits(16, endian = "little")]
+        length: u16,
+        #[bits(24, endian = "big")]
+        checksum: u32,
+    }
+
+    #[derive(Debug, PartialEq, BitRead, BitWrite)]
+    struct Pair(#[bits(7)] u8, i32);
+
+    fn round_trip<T: BitDecode + BitEncode>(byte_order: ByteOrder, value: &T) -> T {
+        let mut cursor = BitCursor::with_byte_order(byte_order, Vec::new());
+        value.to_writer(&mut cursor).unwrap();
+        let mut cursor = BitCursor::with_byte_order(byte_order, cursor.into_inner());
+        T::from_reader(&mut cursor).unwrap()
+    }
+
+    fn packet() -> Packet {
+        Packet {
+            header: Header {
+                version: 5,
+                flag: true,
+                kind: 0xABC,
+                offset: -7,
+            },
+            samples: [1, 15, 9],
+            length: 0x1234,
+            checksum: 0xC0FFEE,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let packet = packet();
+            assert_eq!(round_trip(byte_order, &packet), packet);
+            let pair = Pair(100, -123456);
+            assert_eq!(round_trip(byte_order, &pair), pair);
+        }
+    }
+
+    #[test]
+    fn test_field_widths() {
+        let mut cursor = BitCursor::new(Vec::new());
+        packet().header.to_writer(&mut cursor).unwrap();
+        // 3 + 1 + 12 + 5 = 21 bits -> 3 bytes
+        let bytes = cursor.into_inner();
+        assert_eq!(bytes.len(), 3);
+
+        let mut cursor = BitCursor::new(&bytes);
+        assert_eq!(cursor.read_bits(3).unwrap(), 5);
+        assert_eq!(cursor.read_bits(1).unwrap(), 1);
+        assert_eq!(cursor.read_bits(12).unwrap(), 0xABC);
+        assert_eq!(cursor.read_bits(5).unwrap(), 0b11001);
+    }
+
+    #[test]
+    fn test_align_and_endian() {
+        let mut cursor = BitCursor::new(Vec::new());
+        packet().to_writer(&mut cursor).unwrap();
+        let bytes = cursor.into_inner();
+        // header 21 + samples 12 = 33 bits, aligned to 40, then 2 + 3 bytes
+        assert_eq!(bytes.len(), 10);
+        assert_eq!(&bytes[5..], &[0x34, 0x12, 0xC0, 0xFF, 0xEE]);
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let mut header = packet().header;
+        header.version = 8;
+        let mut cursor = BitCursor::new(Vec::new());
+        let err = header.to_writer(&mut cursor).unwrap_err();
+        assert!(err.to_string().contains("does not fit in 3 bits"));
+
+        header.version = 0;
+        header.offset = -17;
+        assert!(header.to_writer(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_unexpected_eof() {
+        let bytes = [0u8; 2];
+        let mut cursor = BitCursor::new(&bytes);
+        assert!(Header::from_reader(&mut cursor).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::dump;
+    use bitio_rs::traits::BitRead;
+
+    #[test]
+    fn test_binary_marker() {
+        let data = [0b1011_0011, 0b0101_1010, 0b1100_0011];
+        assert_eq!(
+            dump::binary(&data, ByteOrder::BigEndian, Some(11)),
+            "00000000  10110011 01011010 11000011\n                      ^ bit 11\n"
+        );
+        // 小端序从字节最低位开始计数
+        assert_eq!(
+            dump::binary(&data, ByteOrder::LittleEndian, Some(11)),
+            "00000000  10110011 01011010 11000011\n                       ^ bit 11\n"
+        );
+        assert_eq!(
+            dump::binary(&data, ByteOrder::BigEndian, None),
+            "00000000  10110011 01011010 11000011\n"
+        );
+    }
+
+    #[test]
+    fn test_hex_marker() {
+        let data: Vec<u8> = (0x41..0x41 + 18).collect();
+        let out = dump::hex(&data, Some(17 * 8 + 3));
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "00000000  41 42 43 44 45 46 47 48 49 4a 4b 4c 4d 4e 4f 50  |ABCDEFGHIJKLMNOP|"
+        );
+        assert!(lines[1].starts_with("00000010  51 52   "));
+        assert!(lines[1].ends_with("  |QR|"));
+        assert_eq!(lines[2], format!("{}^ bit 139", " ".repeat(13)));
+    }
+
+    #[test]
+    fn test_marker_at_end() {
+        let data = [0xFF; 8];
+        let out = dump::binary(&data, ByteOrder::BigEndian, Some(64));
+        assert!(out.ends_with("00000008 \n          ^ bit 64\n"));
+    }
+
+    #[test]
+    fn test_marker_past_end() {
+        // 远超数据末尾的标记画在末尾，而不是输出到标记所在行为止的空行
+        let data = [0xFF; 8];
+        let out = dump::binary(&data, ByteOrder::BigEndian, Some(1000));
+        assert!(out.ends_with("00000008 \n          ^ bit 1000\n"));
+        let out = dump::hex(&data, Some(u64::MAX));
+        assert_eq!(out.lines().count(), 2);
+        assert!(out.ends_with(&format!("{}^ bit {}\n", " ".repeat(34), u64::MAX)));
+        let out = dump::hex(&[], Some(9));
+        assert_eq!(out.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_cursor_window() {
+        let data: Vec<u8> = (0..64).collect();
+        let mut cursor = BitCursor::new(&data[..]);
+        cursor.set_position(20 * 8);
+        cursor.read_bits(5).unwrap();
+        let out = dump::cursor(&cursor);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("00000008  00001000"));
+        assert!(lines[1].starts_with("00000010  00010000"));
+        assert!(lines[2].ends_with("^ bit 165"));
+        assert!(lines[3].starts_with("00000018  00011000"));
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::ecc::{HammingCode, HammingReader, HammingWriter};
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::traits::{
+        BitPeek, BitRead, BitReadExt, BitWrite, BitWriteExt, DynBitRead, DynBitWrite,
+    };
+    use bitio_rs::writer::BitWriter;
+    use std::io::Result;
+
+    /// 运行时选择的解码器只依赖 dyn 读取器
+    type Decoder = fn(&mut dyn DynBitRead) -> Result<u64>;
+
+    fn header(reader: &mut dyn DynBitRead) -> Result<u64> {
+        let kind = reader.read_bits(4)?;
+        let len = reader.read_u16()? as u64;
+        Ok(kind << 16 | len)
+    }
+
+    /// 泛型辅助函数也接受 dyn 读取器
+    fn skip<R: BitRead<Output = u64> + ?Sized>(reader: &mut R, n: usize) -> Result<()> {
+        reader.read_bits(n).map(|_| ())
+    }
+
+    fn payload(reader: &mut dyn DynBitRead) -> Result<u64> {
+        skip(reader, 20)?;
+        reader.read_bits(12)
+    }
+
+    fn encode(writer: &mut dyn DynBitWrite) -> Result<()> {
+        writer.write_bits(0xA, 4)?;
+        writer.write_u16(0x1234)?;
+        writer.write_bits(0xBCD, 12)
+    }
+
+    #[test]
+    fn test_boxed_sources() {
+        let mut writers: Vec<Box<dyn DynBitWrite>> = vec![
+            Box::new(BitCursor::new(Vec::new())),
+            Box::new(HammingWriter::new(
+                BitCursor::new(Vec::new()),
+                HammingCode::SecDed,
+            )),
+        ];
+        for writer in &mut writers {
+            encode(writer.as_mut()).unwrap();
+        }
+
+        let mut cursor = BitCursor::new(Vec::new());
+        encode(&mut cursor).unwrap();
+        let plain = cursor.into_inner();
+        let mut hamming = HammingWriter::new(BitCursor::new(Vec::new()), HammingCode::Hamming74);
+        encode(&mut hamming).unwrap();
+        let mut coded = hamming.into_inner().unwrap();
+        coded.set_position(0);
+
+        let mut buffered = BitWriter::with_byte_order(ByteOrder::BigEndian, Vec::new());
+        encode(&mut buffered).unwrap();
+        let written = buffered.into_inner().unwrap();
+        assert_eq!(written, plain);
+
+        let decoders: [Decoder; 2] = [header, payload];
+        for (decoder, expected) in decoders.into_iter().zip([0xA_1234, 0xBCD]) {
+            let sources: Vec<Box<dyn DynBitRead + '_>> = vec![
+                Box::new(BitReader::new(&plain[..])),
+                Box::new(BitCursor::new(plain.clone())),
+                Box::new(HammingReader::new(coded.clone(), HammingCode::Hamming74)),
+            ];
+            for mut source in sources {
+                assert_eq!(decoder(source.as_mut()).unwrap(), expected);
+            }
+        }
+    }
+
+    fn read_pair<R: BitRead<Output = u64>>(mut reader: R) -> Result<(u64, u32)> {
+        Ok((reader.read_bits(4)?, reader.read_u32()?))
+    }
+
+    fn write_pair<W: BitWrite>(mut writer: W) -> Result<()> {
+        writer.write_bits(0x5, 4)?;
+        writer.write_u32(0xDEAD_BEEF)
+    }
+
+    #[test]
+    fn test_blanket_impls() {
+        // &mut T：按值接收的泛型函数不会取走 reader
+        let mut cursor = BitCursor::new(Vec::new());
+        write_pair(&mut cursor).unwrap();
+        write_pair(&mut &mut cursor).unwrap();
+        let data = cursor.into_inner();
+
+        let mut reader = BitReader::new(&data[..]);
+        assert_eq!(read_pair(&mut reader).unwrap(), (0x5, 0xDEAD_BEEF));
+        assert_eq!(reader.read_bits(4).unwrap(), 0x5);
+
+        // Box<T> 与 Box<dyn ...>
+        let mut boxed: Box<dyn DynBitRead + '_> = Box::new(BitCursor::new(&data[2..]));
+        assert_eq!(boxed.read_bits(4).unwrap(), 0xD);
+        assert_eq!(
+            read_pair(Box::new(BitReader::new(&data[..]))).unwrap().1,
+            0xDEAD_BEEF
+        );
+        let mut peek = Box::new(BitCursor::new(&data[..]));
+        assert_eq!(BitPeek::peek_bits(&mut peek, 4).unwrap(), 0x5);
+
+        let sink: Box<dyn DynBitWrite> = Box::new(BitCursor::new(Vec::new()));
+        write_pair(sink).unwrap();
+    }
+}
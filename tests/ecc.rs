@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::ecc::{HammingCode, HammingReader, HammingWriter};
+    use bitio_rs::traits::{BitRead, BitWrite};
+    use std::io::ErrorKind;
+
+    #[test]
+    fn test_codewords() {
+        for code in [HammingCode::Hamming74, HammingCode::SecDed] {
+            for nibble in 0..16u8 {
+                let word = code.encode(nibble);
+                assert_eq!(code.decode(word).unwrap(), (nibble, false));
+                // 任一比特翻转都能纠正
+                for bit in 0..code.code_bits() {
+                    assert_eq!(code.decode(word ^ (1 << bit)).unwrap(), (nibble, true));
+                }
+            }
+        }
+        assert_eq!(HammingCode::Hamming74.encode(0b1011), 0b0110011);
+
+        // SEC-DED 检出双比特错误
+        let word = HammingCode::SecDed.encode(0b0110);
+        let err = HammingCode::SecDed.decode(word ^ 0b1001_0000).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_adapters_round_trip_with_errors() {
+        for code in [HammingCode::Hamming74, HammingCode::SecDed] {
+            let mut writer = HammingWriter::new(BitCursor::new(Vec::new()), code);
+            writer.write_bits(0b101, 3).unwrap();
+            writer.write_bits(0xDEAD_BEEF, 32).unwrap();
+            writer.write_bits(0x1, 2).unwrap();
+            let mut cursor = writer.into_inner().unwrap();
+            // 37 个数据比特补齐为 10 组
+            assert_eq!(cursor.position(), 10 * code.code_bits() as u64);
+
+            // 在第 2、5 个码字中各翻转一个比特
+            for (word, bit) in [(1, 3), (4, 0)] {
+                let pos = word * code.code_bits() + bit;
+                cursor.get_mut()[pos / 8] ^= 0x80 >> (pos % 8);
+            }
+
+            cursor.set_position(0);
+            let mut reader = HammingReader::new(cursor, code);
+            assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+            assert_eq!(reader.read_bits(32).unwrap(), 0xDEAD_BEEF);
+            assert_eq!(reader.read_bits(2).unwrap(), 0x1);
+            assert_eq!(reader.read_bits(3).unwrap(), 0);
+            assert_eq!(reader.corrected(), 2);
+            assert!(reader.read_bits(1).is_err());
+        }
+    }
+}
@@ -0,0 +1,344 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::Error;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::error::BitReadWriteError;
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::traits::{BitExpect, BitRead, BitReadExt, BitWrite, BitWriteExt};
+    use std::io::{Cursor, ErrorKind, Read};
+
+    fn typed(err: &std::io::Error) -> Option<&BitReadWriteError> {
+        err.get_ref()?.downcast_ref::<BitReadWriteError>()
+    }
+
+    #[test]
+    fn test_kinds() {
+        let mut reader = BitReader::new(Cursor::new([0xFFu8]));
+        let err = reader.read_bits(0).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(matches!(
+            typed(&err).map(BitReadWriteError::root),
+            Some(BitReadWriteError::InvalidBitCount(0))
+        ));
+
+        let err = reader.read_bits(9).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert!(matches!(
+            typed(&err).map(BitReadWriteError::root),
+            Some(BitReadWriteError::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn test_positioned_errors() {
+        let mut reader = BitReader::new(Cursor::new([0xFF, 0x00, 0xAA]));
+        reader.read_bits(5).unwrap();
+        let err = reader.read_bits(24).unwrap_err();
+        assert!(matches!(
+            typed(&err),
+            Some(BitReadWriteError::Positioned {
+                offset: 5,
+                requested: Some(24),
+                available: Some(19),
+                error,
+            }) if matches!(**error, BitReadWriteError::UnexpectedEof)
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Unexpected end of stream at bit offset 5 (24 bits requested, 19 available)"
+        );
+        // 失败的读取不消费任何比特，位置不变
+        assert_eq!(reader.read_bits(3).unwrap(), 0b111);
+        let err = reader.read_bytes(3).unwrap_err();
+        assert_eq!(typed(&err).and_then(BitReadWriteError::offset), Some(8));
+        assert_eq!(
+            err.to_string(),
+            "Unexpected end of stream at bit offset 8 (24 bits requested, 16 available)"
+        );
+
+        // 嵌套调用只保留最内层的位置
+        let mut reader = BitReader::new(Cursor::new([0x01]));
+        reader.read_bits(4).unwrap();
+        let err = reader.read_ue().unwrap_err();
+        assert!(typed(&err).and_then(BitReadWriteError::offset).unwrap() >= 4);
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        let mut cursor = BitCursor::new(vec![0u8; 2]);
+        cursor.set_position(12);
+        let err = cursor.read_bits(8).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unexpected end of stream at bit offset 12 (8 bits requested, 4 available)"
+        );
+        let err = cursor.write_bits(0, 65).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bit count must be between 1-64, got 65 at bit offset 12 (65 bits requested)"
+        );
+    }
+
+    /// 断言错误被定位在 offset，并带有请求与剩余的比特数
+    fn assert_located(err: &std::io::Error, offset: u64, requested: usize, available: u64) {
+        match typed(err) {
+            Some(BitReadWriteError::Positioned {
+                offset: o,
+                requested: r,
+                available: a,
+                ..
+            }) => assert_eq!((*o, *r, *a), (offset, Some(requested), Some(available))),
+            other => panic!("expected a positioned error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_every_read_entry_point_is_positioned() {
+        use bitio_rs::reader::{CompactBitReader, PeekableBitReader};
+        use bitio_rs::traits::BitPeek;
+
+        let data = [0xAB, 0xCD];
+
+        let mut reader = PeekableBitReader::new(&data[..]);
+        reader.read_bits(3).unwrap();
+        assert_located(&reader.peek_bits(16).unwrap_err(), 3, 16, 13);
+        let mut reader = PeekableBitReader::new(&data[..]);
+        assert_located(&reader.peek_bytes(&mut [0; 3]).unwrap_err(), 0, 24, 16);
+
+        let mut reader = BitReader::new(&data[..]);
+        reader.read_bits(3).unwrap();
+        let err = reader.find_sync(0xFFF, 12).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_located(&err, 3, 12, 13);
+
+        let mut reader = BitReader::new(&data[..]);
+        reader.read_bits(3).unwrap();
+        assert_located(&reader.read_bits_opt(16).unwrap_err(), 3, 16, 13);
+
+        let mut reader = BitReader::new(&data[..]).max_bits(10);
+        reader.read_bits(3).unwrap();
+        let err = reader.read_bits_partial(8).unwrap_err();
+        assert!(matches!(
+            typed(&err).map(BitReadWriteError::root),
+            Some(BitReadWriteError::LimitExceeded { limit: 10 })
+        ));
+        assert_located(&err, 3, 8, 7);
+
+        // 非对齐走比特路径，对齐走整字节路径，错误一致
+        let mut reader = BitReader::new(&data[..]);
+        reader.read_bits(3).unwrap();
+        assert_located(&reader.read_u16().unwrap_err(), 3, 16, 13);
+        let mut reader = BitReader::new(&data[..]);
+        reader.read_bits(8).unwrap();
+        assert_located(&reader.read_u16().unwrap_err(), 8, 16, 8);
+
+        let mut reader = BitReader::new(&data[..]);
+        reader.read_bits(3).unwrap();
+        let err = reader.read_bits_into_slice(&mut [0; 2], 14).unwrap_err();
+        assert_located(&err, 3, 14, 13);
+        let mut reader = BitReader::new(&data[..]);
+        reader.read_bits(3).unwrap();
+        assert_located(&reader.read_bytes(2).unwrap_err(), 3, 16, 13);
+
+        // 超过 32 比特的读取拆成两半，第二半失败时仍报整个读取的起点
+        let mut reader = CompactBitReader::new(&data[..]);
+        reader.read_bits(3).unwrap();
+        assert_located(&reader.read_bits(16).unwrap_err(), 3, 16, 13);
+        let mut reader = CompactBitReader::new(&data[..]);
+        reader.read_bits(3).unwrap();
+        assert_located(&reader.read_bits(40).unwrap_err(), 3, 40, 13);
+    }
+
+    #[test]
+    fn test_downcast_unaligned_access() {
+        let mut reader = BitReader::new(Cursor::new([0xAB, 0xCD]));
+        reader.read_bits(3).unwrap();
+        let err = reader.read(&mut [0u8; 1]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Other);
+        let inner = err.downcast::<BitReadWriteError>().unwrap();
+        assert!(matches!(
+            inner.root(),
+            BitReadWriteError::UnalignedAccess { pending_bits: 5 }
+        ));
+        assert_eq!(inner.offset(), Some(3));
+    }
+
+    #[test]
+    fn test_display_is_preserved() {
+        let mut cursor = BitCursor::new(Vec::new());
+        let err = bitio_rs::packing::pack_u32s(&[8], 3, &mut cursor).unwrap_err();
+        assert_eq!(err.to_string(), "Value 8 does not fit in 3 bits");
+        assert!(matches!(
+            typed(&err),
+            Some(BitReadWriteError::ValueOutOfRange { value: 8, bits: 3 })
+        ));
+        assert!(cursor.write_bits(1, 65).is_err());
+    }
+
+    #[test]
+    fn test_max_bits_limit() {
+        use std::io::Read;
+
+        let data = [0xAB; 16];
+        let mut reader = BitReader::new(&data[..]).max_bits(20);
+        assert_eq!(reader.read_bits(12).unwrap(), 0xABA);
+        let err = reader.read_bits(9).unwrap_err();
+        let typed = err
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<BitReadWriteError>());
+        assert!(matches!(
+            typed.map(BitReadWriteError::root),
+            Some(BitReadWriteError::LimitExceeded { limit: 20 })
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Read would exceed the limit of 20 bits at bit offset 12 (9 bits requested, 8 available)"
+        );
+        // 失败的读取不消费任何比特
+        assert_eq!(reader.read_bits(8).unwrap(), 0xBA);
+        assert!(reader.read_bits(1).is_err());
+
+        let mut reader = BitReader::new(&data[..]).max_bits(28);
+        let mut buf = [0u8; 8];
+        assert_eq!(reader.read(&mut buf).unwrap(), 3);
+        assert!(reader.read_bytes(1).is_err());
+        assert_eq!(reader.read_bits(4).unwrap(), 0xA);
+        assert!(reader.read(&mut buf).is_err());
+        assert!(reader.read_bits_partial(1).is_err());
+    }
+
+    #[test]
+    fn test_unexpected_value() {
+        let mut reader = BitReader::new(Cursor::new(*b"RIFX"));
+        reader.read_bits(4).unwrap();
+        let err = reader.expect_bits(0x3, 4).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(matches!(
+            typed(&err),
+            Some(BitReadWriteError::UnexpectedValue {
+                offset: 4,
+                expected: 0x3,
+                actual: 0x2,
+                bits: 4
+            })
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Expected 0x3 (4 bits) at bit offset 4, found 0x2"
+        );
+    }
+
+    /// 用 crate 的 Result 解析：`?` 把 io::Error 还原成它携带的 Error
+    fn parse_header(data: &[u8]) -> bitio_rs::Result<(u64, u64)> {
+        let mut reader = BitReader::new(data);
+        Ok((reader.read_bits(4)?, reader.read_bits(24)?))
+    }
+
+    #[test]
+    fn test_structured_error() {
+        let err = parse_header(&[0xAB, 0xCD, 0xEF]).unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::Positioned {
+                offset: 4,
+                requested: Some(24),
+                available: Some(20),
+                error,
+            } if matches!(**error, Error::UnexpectedEof)
+        ));
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(
+            err.to_string(),
+            "Unexpected end of stream at bit offset 4 (24 bits requested, 20 available)"
+        );
+
+        // 经过 io::Error 往返后变体和字段不变
+        let io: std::io::Error = err.into();
+        assert_eq!(io.kind(), ErrorKind::UnexpectedEof);
+        let err = Error::from(io);
+        assert_eq!(err.offset(), Some(4));
+        assert!(matches!(err.root(), Error::UnexpectedEof));
+
+        let mut reader = BitReader::new(Cursor::new([0xFFu8; 4]));
+        reader.read_bits(3).unwrap();
+        let err = Error::from(reader.read(&mut [0u8; 2]).unwrap_err());
+        assert_eq!(err.offset(), Some(3));
+        assert!(matches!(
+            err.root(),
+            Error::UnalignedAccess { pending_bits: 5 }
+        ));
+
+        let mut writer = bitio_rs::writer::BitWriter::new(Vec::new());
+        let err = Error::from(writer.write_bits_checked(300, 8).unwrap_err());
+        assert!(matches!(
+            err,
+            Error::ValueOutOfRange {
+                value: 300,
+                bits: 8
+            }
+        ));
+        assert_eq!(err.offset(), None);
+        let io = std::io::Error::from(err);
+        assert!(matches!(
+            typed(&io),
+            Some(BitReadWriteError::ValueOutOfRange {
+                value: 300,
+                bits: 8
+            })
+        ));
+    }
+
+    #[test]
+    fn test_io_errors_pass_through() {
+        let err = Error::from(std::io::Error::new(ErrorKind::BrokenPipe, "pipe closed"));
+        assert!(matches!(&err, Error::Io(e) if e.kind() == ErrorKind::BrokenPipe));
+        assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+        assert_eq!(err.to_string(), "pipe closed");
+        let io = std::io::Error::from(err);
+        assert_eq!(io.kind(), ErrorKind::BrokenPipe);
+        assert!(typed(&io).is_none());
+    }
+
+    #[test]
+    fn test_typed_variants() {
+        let err = Error::from(bitio_rs::bcd::decode_semi_octets(&[0xF1, 0x23]).unwrap_err());
+        assert!(matches!(err, Error::MisplacedFiller));
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut writer = bitio_rs::writer::BitWriter::new(Vec::new());
+        let err = Error::from(writer.write_bcd(100, 2).unwrap_err());
+        assert!(matches!(
+            err,
+            Error::ValueOutOfRange {
+                value: 100,
+                bits: 8
+            }
+        ));
+        let err = Error::from(writer.write_bcd(1, 20).unwrap_err());
+        assert!(matches!(err, Error::InvalidDigitCount(20)));
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        let err = Error::from(bitio_rs::string::unarmor_ais("0!").unwrap_err());
+        assert!(matches!(err, Error::InvalidAisChar('!')));
+        let err = Error::from(bitio_rs::schema::BitLayout::parse("a:u65").unwrap_err());
+        assert!(matches!(&err, Error::InvalidFieldWidth(token) if token == "a:u65"));
+        assert_eq!(
+            err.to_string(),
+            "Invalid field `a:u65`: width must be between 1-64"
+        );
+
+        // 用户构造的错误转成 io::Error 再转回来，字段不丢
+        let err = Error::Positioned {
+            offset: 11,
+            requested: None,
+            available: None,
+            error: Box::new(Error::UnalignedAccess { pending_bits: 3 }),
+        };
+        let io = std::io::Error::from(err);
+        assert_eq!(io.kind(), ErrorKind::Other);
+        let err = Error::from(io);
+        assert_eq!(err.offset(), Some(11));
+        assert!(matches!(
+            err.root(),
+            Error::UnalignedAccess { pending_bits: 3 }
+        ));
+    }
+}
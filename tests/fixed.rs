@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::fixed::{read_q, read_uq, write_q, write_uq};
+    use bitio_rs::traits::{BitRead, BitWrite};
+
+    #[test]
+    fn test_read_fixed_point() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_bits(0b10_1101, 6).unwrap(); // UQ4.2 = 11.25
+        cursor.write_bits(0x8000, 16).unwrap(); // Q1.15 = -1.0
+        cursor.write_bits(0x4000, 16).unwrap(); // Q1.15 = 0.5
+        cursor.write_bits(0b111, 3).unwrap(); // Q3.0 = -1
+        cursor.set_position(0);
+
+        assert_eq!(read_uq(&mut cursor, 4, 2).unwrap(), 11.25);
+        assert_eq!(read_q(&mut cursor, 1, 15).unwrap(), -1.0);
+        assert_eq!(read_q(&mut cursor, 1, 15).unwrap(), 0.5);
+        assert_eq!(read_q(&mut cursor, 3, 0).unwrap(), -1.0);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut cursor = BitCursor::with_byte_order(byte_order, Vec::new());
+            write_uq(&mut cursor, 2.6543, 2, 10).unwrap();
+            write_q(&mut cursor, -0.3, 1, 23).unwrap();
+            write_q(&mut cursor, -12.5, 8, 4).unwrap();
+            write_uq(&mut cursor, 0.75, 0, 2).unwrap();
+            cursor.set_position(0);
+
+            assert!((read_uq(&mut cursor, 2, 10).unwrap() - 2.6543).abs() <= 1.0 / 2048.0);
+            assert!((read_q(&mut cursor, 1, 23).unwrap() + 0.3).abs() <= 1.0 / (1 << 24) as f64);
+            assert_eq!(read_q(&mut cursor, 8, 4).unwrap(), -12.5);
+            assert_eq!(read_uq(&mut cursor, 0, 2).unwrap(), 0.75);
+        }
+    }
+
+    #[test]
+    fn test_rounding_and_range() {
+        let mut cursor = BitCursor::new(Vec::new());
+        assert!(write_q(&mut cursor, 0.999_99, 1, 15).is_err()); // 舍入到 1.0，超出 Q1.15
+        write_q(&mut cursor, -1.0, 1, 15).unwrap();
+        assert!(write_uq(&mut cursor, -0.1, 4, 4).is_err());
+        assert!(write_uq(&mut cursor, 16.0, 4, 4).is_err());
+        assert!(write_uq(&mut cursor, f64::NAN, 4, 4).is_err());
+        assert!(write_q(&mut cursor, 1.0, 0, 0).is_err());
+        assert!(write_q(&mut cursor, 1.0, 40, 30).is_err());
+        write_uq(&mut cursor, 15.96, 4, 4).unwrap(); // 舍入到 15.9375
+        cursor.set_position(0);
+        assert_eq!(cursor.read_bits(16).unwrap(), 0x8000);
+        assert_eq!(cursor.read_bits(8).unwrap(), 0xFF);
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::float::{f16_to_f32, f32_to_f16};
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::traits::{BitRead, BitReadExt, BitWrite, BitWriteExt};
+    use bitio_rs::writer::BitWriter;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_floats_interleaved_with_bit_fields() {
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut writer = BitWriter::with_byte_order(byte_order, Vec::new());
+            writer.write_bits(0b101, 3).unwrap();
+            writer.write_f32(-1.5e-3).unwrap();
+            writer.write_bits(1, 1).unwrap();
+            writer.write_f64(std::f64::consts::PI).unwrap();
+            writer.write_f16(0.333).unwrap();
+            writer.write_f32(f32::NAN).unwrap();
+            let data = writer.into_inner().unwrap();
+
+            let mut reader = BitReader::with_byte_order(byte_order, Cursor::new(data));
+            assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+            assert_eq!(reader.read_f32().unwrap(), -1.5e-3);
+            assert_eq!(reader.read_bits(1).unwrap(), 1);
+            assert_eq!(reader.read_f64().unwrap(), std::f64::consts::PI);
+            assert_eq!(reader.read_f16().unwrap(), 0.333_007_8);
+            assert_eq!(reader.read_f32().unwrap().to_bits(), f32::NAN.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_float_layout() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_f32(1.0).unwrap();
+        assert_eq!(cursor.get_ref(), &vec![0x3F, 0x80, 0x00, 0x00]);
+
+        let mut cursor = BitCursor::with_byte_order(ByteOrder::LittleEndian, Vec::new());
+        cursor.write_f32(1.0).unwrap();
+        assert_eq!(cursor.get_ref(), &vec![0x00, 0x00, 0x80, 0x3F]);
+    }
+
+    #[test]
+    fn test_f16_conversions() {
+        let cases: [(u16, f32); 9] = [
+            (0x0000, 0.0),
+            (0x8000, -0.0),
+            (0x3C00, 1.0),
+            (0xC000, -2.0),
+            (0x7BFF, 65504.0),
+            (0x0001, 5.9604645e-8), // 最小非规格化数
+            (0x0400, 6.1035156e-5), // 最小规格化数
+            (0x7C00, f32::INFINITY),
+            (0xFC00, f32::NEG_INFINITY),
+        ];
+        for (bits, value) in cases {
+            assert_eq!(f16_to_f32(bits).to_bits(), value.to_bits(), "{:#06x}", bits);
+            assert_eq!(f32_to_f16(value), bits, "{}", value);
+        }
+        assert!(f16_to_f32(0x7E00).is_nan());
+        assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+        // 所有 half 值往返不变
+        for bits in 0..=u16::MAX {
+            let value = f16_to_f32(bits);
+            if !value.is_nan() {
+                assert_eq!(f32_to_f16(value), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn test_f16_rounding() {
+        assert_eq!(f32_to_f16(65520.0), 0x7C00); // 上溢
+        assert_eq!(f32_to_f16(1e-10), 0x0000); // 下溢
+        assert_eq!(f32_to_f16(1.0 + 1.0 / 2048.0), 0x3C00); // 平局取偶
+        assert_eq!(f32_to_f16(1.0 + 3.0 / 2048.0), 0x3C02);
+        assert_eq!(f32_to_f16(2.9802322e-8), 0x0000); // 最小非规格化数的一半，取偶
+        assert_eq!(f32_to_f16(4.5e-8), 0x0001);
+    }
+}
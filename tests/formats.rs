@@ -0,0 +1,790 @@
+#![cfg(feature = "formats")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::formats::adts::{AdtsHeader, MpegVersion, read_frame};
+    use bitio_rs::formats::av1::{
+        ObuExtension, ObuHeader, ObuType, leb128_len, read_leb128, read_obu, read_uvlc,
+        write_leb128, write_uvlc,
+    };
+    use bitio_rs::formats::flac::{
+        ChannelAssignment, FrameHeader, crc8, crc16, read_coded_number, read_residual,
+    };
+    use bitio_rs::formats::h264::{
+        FrameCropping, PicOrderCount, Pps, Sps, rbsp_escape, rbsp_unescape,
+    };
+    use bitio_rs::formats::jpeg::{EOI, EntropyReader, EntropyWriter, RST0, is_restart};
+    use bitio_rs::formats::mpegts::{
+        Continuity, ContinuityTracker, PACKET_SIZE, Packet, PacketHeader, PacketReader, Pcr,
+        find_sync,
+    };
+    use bitio_rs::formats::vpx::{BoolDecoder, BoolEncoder};
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::traits::{BitExpect, BitPeek, BitRead, BitReadExt, BitWrite, BitWriteExt};
+    use std::io::ErrorKind;
+
+    // ------------------------------- ADTS ------------------------------- //
+
+    #[test]
+    fn test_adts_frame() {
+        // AAC LC, 44.1 kHz, 立体声，无 CRC，帧长 15 字节
+        let mut data = vec![0xFF, 0xF1, 0x50, 0x80, 0x01, 0xFF, 0xFC];
+        data.extend(1..=8u8);
+        let mut reader = BitReader::new(&data[..]);
+        let (header, payload) = read_frame(&mut reader).unwrap();
+        assert_eq!(header.version, MpegVersion::Mpeg4);
+        assert_eq!(header.audio_object_type(), 2);
+        assert_eq!(header.sampling_frequency(), Some(44100));
+        assert_eq!(header.channel_configuration, 2);
+        assert_eq!(header.frame_length, 15);
+        assert_eq!(header.buffer_fullness, 0x7FF);
+        assert_eq!(header.raw_data_blocks, 0);
+        assert_eq!(header.crc, None);
+        assert_eq!(header.header_length(), 7);
+        assert_eq!(payload, (1..=8).collect::<Vec<u8>>());
+        reader.expect_eof().unwrap();
+    }
+
+    #[test]
+    fn test_adts_round_trip_with_crc() {
+        let header = AdtsHeader {
+            version: MpegVersion::Mpeg2,
+            profile: 1,
+            sampling_frequency_index: 3,
+            private_bit: true,
+            channel_configuration: 6,
+            original_copy: false,
+            home: true,
+            copyright_id_bit: true,
+            copyright_id_start: false,
+            frame_length: 1234,
+            buffer_fullness: 0x155,
+            raw_data_blocks: 3,
+            crc: Some(0xBEEF),
+        };
+        let mut cursor = BitCursor::new(Vec::new());
+        header.write(&mut cursor).unwrap();
+        assert_eq!(cursor.position(), 72);
+        cursor.set_position(0);
+        let decoded = AdtsHeader::read(&mut cursor).unwrap();
+        assert_eq!(decoded, header);
+        assert_eq!(decoded.sampling_frequency(), Some(48000));
+        assert_eq!(decoded.payload_length(), 1225);
+    }
+
+    #[test]
+    fn test_adts_errors() {
+        let err =
+            AdtsHeader::read(&mut BitReader::new(&[0xFF, 0xE1, 0, 0, 0, 0, 0][..])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        // layer 不为 0
+        let err =
+            AdtsHeader::read(&mut BitReader::new(&[0xFF, 0xF3, 0, 0, 0, 0, 0][..])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        // frame_length 比头部还短
+        let err = read_frame(&mut BitReader::new(
+            &[0xFF, 0xF1, 0x50, 0x80, 0x00, 0x9F, 0xFC][..],
+        ))
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = read_frame(&mut BitReader::new(&[0xFF, 0xF1, 0x50, 0x80, 0x01][..])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    // -------------------------------- AV1 -------------------------------- //
+
+    #[test]
+    fn test_av1_temporal_delimiter_and_sequence_header() {
+        let data = [0x12, 0x00, 0x0A, 0x03, 0x00, 0x00, 0x00];
+        let mut reader = BitReader::new(&data[..]);
+        let (header, payload) = read_obu(&mut reader).unwrap();
+        assert_eq!(header.obu_type, ObuType::TemporalDelimiter);
+        assert_eq!(header.extension, None);
+        assert_eq!(header.obu_size, Some(0));
+        assert!(payload.is_empty());
+
+        let (header, payload) = read_obu(&mut reader).unwrap();
+        assert_eq!(header.obu_type, ObuType::SequenceHeader);
+        assert_eq!(payload, [0, 0, 0]);
+        reader.expect_eof().unwrap();
+    }
+
+    #[test]
+    fn test_av1_obu_header_round_trip() {
+        let header = ObuHeader {
+            obu_type: ObuType::Frame,
+            extension: Some(ObuExtension {
+                temporal_id: 5,
+                spatial_id: 2,
+            }),
+            obu_size: Some(300),
+        };
+        let mut cursor = BitCursor::new(Vec::new());
+        header.write(&mut cursor).unwrap();
+        assert_eq!(cursor.get_ref(), &[0x36, 0xB0, 0xAC, 0x02]);
+        assert_eq!(header.header_length(), 4);
+        cursor.set_position(0);
+        assert_eq!(ObuHeader::read(&mut cursor).unwrap(), header);
+
+        // 无 size 字段，保留类型
+        let mut reader = BitReader::new(&[0x48][..]);
+        let header = ObuHeader::read(&mut reader).unwrap();
+        assert_eq!(header.obu_type, ObuType::Reserved(9));
+        assert_eq!(header.obu_size, None);
+        assert_eq!(header.header_length(), 1);
+    }
+
+    #[test]
+    fn test_av1_leb128() {
+        // 补齐到固定 4 字节的编码也能读取
+        let data = [0x80, 0x80, 0x80, 0x00, 0xE5, 0x8E, 0x26];
+        let mut reader = BitReader::new(&data[..]);
+        assert_eq!(read_leb128(&mut reader).unwrap(), 0);
+        assert_eq!(read_leb128(&mut reader).unwrap(), 624_485);
+
+        let mut cursor = BitCursor::new(Vec::new());
+        for value in [0, 127, 128, 624_485, u32::MAX as u64] {
+            write_leb128(&mut cursor, value).unwrap();
+        }
+        assert_eq!(cursor.get_ref().len(), 1 + 1 + 2 + 3 + 5);
+        assert_eq!(leb128_len(u32::MAX as u64), 5);
+        cursor.set_position(0);
+        for value in [0, 127, 128, 624_485, u32::MAX as u64] {
+            assert_eq!(read_leb128(&mut cursor).unwrap(), value);
+        }
+
+        let err = write_leb128(&mut cursor, 1 << 32).unwrap_err();
+        assert_eq!(err.to_string(), "Value 4294967296 does not fit in 32 bits");
+        let err =
+            read_leb128(&mut BitReader::new(&[0x80, 0x80, 0x80, 0x80, 0x10][..])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_av1_uvlc() {
+        let mut cursor = BitCursor::new(Vec::new());
+        for value in [0, 1, 6, 1000, u32::MAX - 1, u32::MAX] {
+            write_uvlc(&mut cursor, value).unwrap();
+        }
+        cursor.set_position(0);
+        for value in [0, 1, 6, 1000, u32::MAX - 1, u32::MAX] {
+            assert_eq!(read_uvlc(&mut cursor).unwrap(), value);
+        }
+
+        // 40 个前导零：在 1 之后停止，不读值比特
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_bits(0, 40).unwrap();
+        cursor.write_bits(0b1011, 4).unwrap();
+        cursor.set_position(0);
+        assert_eq!(read_uvlc(&mut cursor).unwrap(), u32::MAX);
+        assert_eq!(read_uvlc(&mut cursor).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_av1_errors() {
+        let err = ObuHeader::read(&mut BitReader::new(&[0x92, 0x00][..])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = read_obu(&mut BitReader::new(&[0x10][..])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = read_obu(&mut BitReader::new(&[0x32, 0x05, 0x00][..])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    // ------------------------------- H.264 ------------------------------- //
+
+    #[test]
+    fn test_h264_baseline_parameter_sets() {
+        // 128x96 Baseline 示例码流的 SPS / PPS
+        let sps = Sps::from_nal(&[0x67, 0x42, 0x00, 0x0A, 0xF8, 0x41, 0xA2]).unwrap();
+        assert_eq!(sps.profile_idc, 66);
+        assert_eq!(sps.level_idc, 10);
+        assert_eq!(sps.chroma_format_idc, 1);
+        assert_eq!(
+            sps.pic_order_cnt,
+            PicOrderCount::Type0 {
+                log2_max_pic_order_cnt_lsb_minus4: 0
+            }
+        );
+        assert_eq!((sps.width(), sps.height()), (128, 96));
+        assert!(sps.frame_mbs_only);
+        assert_eq!(sps.vui, None);
+
+        let pps = Pps::from_nal(&[0x68, 0xCE, 0x38, 0x80], sps.chroma_format_idc).unwrap();
+        assert_eq!(pps.pic_parameter_set_id, 0);
+        assert!(!pps.entropy_coding_mode);
+        assert_eq!(pps.slice_group_map_type, None);
+        assert_eq!(pps.pic_init_qp(), 26);
+        assert!(!pps.transform_8x8_mode);
+        assert_eq!(pps.second_chroma_qp_index_offset, 0);
+    }
+
+    /// 1080p High profile SPS：缩放矩阵、POC 类型 1、裁剪和 VUI
+    fn high_profile_sps() -> Vec<u8> {
+        let mut w = BitCursor::new(Vec::new());
+        w.write_bits(100, 8).unwrap();
+        w.write_bits(0, 8).unwrap();
+        w.write_bits(40, 8).unwrap();
+        w.write_ue(0).unwrap();
+        w.write_ue(1).unwrap(); // chroma_format_idc
+        w.write_ue(2).unwrap(); // bit_depth_luma_minus8
+        w.write_ue(0).unwrap();
+        w.write_bits(0, 1).unwrap();
+        w.write_bits(1, 1).unwrap(); // seq_scaling_matrix_present_flag
+        w.write_bits(1, 1).unwrap();
+        w.write_se(-8).unwrap(); // nextScale 变为 0，列表结束
+        w.write_bits(0, 7).unwrap();
+        w.write_ue(0).unwrap();
+        w.write_ue(1).unwrap(); // pic_order_cnt_type
+        w.write_bits(0, 1).unwrap();
+        w.write_se(-1).unwrap();
+        w.write_se(2).unwrap();
+        w.write_ue(2).unwrap();
+        w.write_se(3).unwrap();
+        w.write_se(-4).unwrap();
+        w.write_ue(4).unwrap(); // max_num_ref_frames
+        w.write_bits(0, 1).unwrap();
+        w.write_ue(119).unwrap();
+        w.write_ue(67).unwrap();
+        w.write_bits(0b101, 3).unwrap(); // frame_mbs_only, direct_8x8, frame_cropping
+        for crop in [0, 0, 0, 4] {
+            w.write_ue(crop).unwrap();
+        }
+        w.write_bits(1, 1).unwrap(); // vui_parameters_present_flag
+        w.write_bits(1, 1).unwrap();
+        w.write_bits(1, 8).unwrap(); // aspect_ratio_idc 1:1
+        w.write_bits(0, 1).unwrap();
+        w.write_bits(1, 1).unwrap();
+        w.write_bits(0b10101, 5).unwrap(); // video_format, full_range, colour_description
+        w.write_bits(0x01_01_01, 24).unwrap();
+        w.write_bits(0, 1).unwrap();
+        w.write_bits(1, 1).unwrap();
+        w.write_bits(1001, 32).unwrap();
+        w.write_bits(60000, 32).unwrap();
+        w.write_bits(1, 1).unwrap();
+        w.write_bits(0, 1).unwrap(); // 跳过剩余 VUI：nal_hrd_parameters_present_flag
+        w.write_bits(1, 1).unwrap(); // rbsp_stop_one_bit
+        w.into_inner()
+    }
+
+    #[test]
+    fn test_h264_high_profile_sps() {
+        let rbsp = high_profile_sps();
+        let mut nal = vec![0x67];
+        nal.extend(rbsp_escape(&rbsp));
+        assert_eq!(rbsp_unescape(&nal[1..]), rbsp);
+
+        let sps = Sps::from_nal(&nal).unwrap();
+        assert_eq!(sps.profile_idc, 100);
+        assert_eq!(sps.level_idc, 40);
+        assert_eq!(sps.bit_depth_luma(), 10);
+        assert_eq!(sps.bit_depth_chroma(), 8);
+        assert!(sps.seq_scaling_matrix_present);
+        assert_eq!(
+            sps.pic_order_cnt,
+            PicOrderCount::Type1 {
+                delta_pic_order_always_zero: false,
+                offset_for_non_ref_pic: -1,
+                offset_for_top_to_bottom_field: 2,
+                offset_for_ref_frame: vec![3, -4],
+            }
+        );
+        assert_eq!(sps.max_num_ref_frames, 4);
+        assert_eq!(
+            sps.frame_cropping,
+            Some(FrameCropping {
+                bottom: 4,
+                ..Default::default()
+            })
+        );
+        assert_eq!((sps.width(), sps.height()), (1920, 1080));
+
+        let vui = sps.vui.unwrap();
+        assert_eq!(vui.sample_aspect_ratio(), Some((1, 1)));
+        assert_eq!(vui.overscan_appropriate, None);
+        let signal = vui.video_signal_type.unwrap();
+        assert_eq!(signal.video_format, 5);
+        assert!(!signal.video_full_range);
+        assert_eq!(signal.colour_description, Some((1, 1, 1)));
+        assert_eq!(vui.chroma_sample_loc, None);
+        assert!(vui.timing.unwrap().fixed_frame_rate);
+        assert!((vui.frame_rate().unwrap() - 29.97).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_h264_pps_extension() {
+        let mut w = BitCursor::new(Vec::new());
+        w.write_ue(1).unwrap();
+        w.write_ue(0).unwrap();
+        w.write_bits(0b10, 2).unwrap(); // CABAC
+        w.write_ue(1).unwrap(); // 两个 slice group
+        w.write_ue(4).unwrap(); // slice_group_map_type
+        w.write_bits(1, 1).unwrap();
+        w.write_ue(9).unwrap();
+        w.write_ue(2).unwrap();
+        w.write_ue(0).unwrap();
+        w.write_bits(0b110, 3).unwrap(); // weighted_pred, weighted_bipred_idc
+        w.write_se(-3).unwrap();
+        w.write_se(0).unwrap();
+        w.write_se(-2).unwrap();
+        w.write_bits(0b100, 3).unwrap();
+        w.write_bits(0b11, 2).unwrap(); // transform_8x8_mode, pic_scaling_matrix_present
+        for i in 0..8 {
+            w.write_bits((i == 7) as u64, 1).unwrap();
+        }
+        // 第 8 个列表（8x8）的 delta 全为 0，nextScale 一直是 8
+        for _ in 0..64 {
+            w.write_se(0).unwrap();
+        }
+        w.write_se(5).unwrap();
+        w.write_bits(0b1000, 4).unwrap();
+
+        let pps = Pps::from_rbsp(w.get_ref(), 1).unwrap();
+        assert_eq!(pps.pic_parameter_set_id, 1);
+        assert!(pps.entropy_coding_mode);
+        assert_eq!(pps.num_slice_groups_minus1, 1);
+        assert_eq!(pps.slice_group_map_type, Some(4));
+        assert_eq!(pps.num_ref_idx_l0_default_active_minus1, 2);
+        assert!(pps.weighted_pred);
+        assert_eq!(pps.weighted_bipred_idc, 2);
+        assert_eq!(pps.pic_init_qp(), 23);
+        assert_eq!(pps.chroma_qp_index_offset, -2);
+        assert!(pps.deblocking_filter_control_present);
+        assert!(pps.transform_8x8_mode);
+        assert!(pps.pic_scaling_matrix_present);
+        assert_eq!(pps.second_chroma_qp_index_offset, 5);
+    }
+
+    #[test]
+    fn test_h264_emulation_prevention() {
+        let rbsp = [
+            0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00,
+        ];
+        let escaped = rbsp_escape(&rbsp);
+        assert_eq!(
+            escaped,
+            [
+                0x00, 0x00, 0x03, 0x01, 0x00, 0x00, 0x03, 0x00, 0x00, 0x03, 0x00, 0x04, 0x00, 0x00
+            ]
+        );
+        assert_eq!(rbsp_unescape(&escaped), rbsp);
+        // 03 之后零计数重新开始
+        assert_eq!(rbsp_unescape(&[0, 0, 3, 3, 0, 0, 3, 0]), [0, 0, 3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_h264_errors() {
+        let err = Sps::from_nal(&[0x68, 0xCE, 0x38, 0x80]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = Sps::from_nal(&[0xE7, 0x42]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = Sps::from_nal(&[0x67, 0x42, 0x00, 0x0A, 0xF8]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        let err = Pps::from_rbsp(&[0, 0], 1).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        // chroma_format_idc = 4
+        let err = Sps::from_nal(&[0x67, 100, 0, 40, 0b1001_0100]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_h264_maximal_dimensions_saturate() {
+        let mut sps = Sps::from_nal(&[0x67, 0x42, 0x00, 0x0A, 0xF8, 0x41, 0xA2]).unwrap();
+        // ue(v) 可以编码到 u32::MAX，尺寸饱和而不是溢出
+        sps.pic_width_in_mbs_minus1 = u32::MAX;
+        sps.pic_height_in_map_units_minus1 = u32::MAX;
+        assert_eq!((sps.width(), sps.height()), (u32::MAX, u32::MAX));
+    }
+
+    // ---------------------------- VP8 / VP9 ---------------------------- //
+
+    /// VP8 关键帧 ymode 树：DC_PRED=0, V_PRED, H_PRED, TM_PRED, B_PRED
+    const YMODE_TREE: [i8; 8] = [0, 2, 4, 6, -1, -2, -3, -4];
+    const KF_YMODE_PROBS: [u8; 4] = [145, 156, 163, 128];
+
+    #[test]
+    fn test_vpx_bool_round_trip() {
+        let bools: Vec<(bool, u8)> = (0u32..2000)
+            .map(|i| {
+                let x = i.wrapping_mul(2_654_435_761);
+                ((x >> 7) % 5 == 0, (x >> 16) as u8 | 1)
+            })
+            .collect();
+        let mut encoder = BoolEncoder::new();
+        for &(value, prob) in &bools {
+            encoder.write_bool(value, prob);
+        }
+        encoder.write_literal(0xABC, 12).unwrap();
+        for mode in [4, 0, 3, 1, 2] {
+            encoder
+                .write_tree(&YMODE_TREE, &KF_YMODE_PROBS, mode)
+                .unwrap();
+        }
+        let mut data = encoder.finish();
+        let size = data.len() as u64;
+        data.push(0x5A);
+
+        let mut decoder = BoolDecoder::new(BitReader::new(&data[..]), size).unwrap();
+        for &(value, prob) in &bools {
+            assert_eq!(decoder.read_bool(prob).unwrap(), value);
+        }
+        assert_eq!(decoder.read_literal(12).unwrap(), 0xABC);
+        for mode in [4, 0, 3, 1, 2] {
+            assert_eq!(
+                decoder.read_tree(&YMODE_TREE, &KF_YMODE_PROBS).unwrap(),
+                mode
+            );
+        }
+        // finish 跳过分区剩余字节
+        let mut reader = decoder.finish().unwrap();
+        assert_eq!(reader.read_bits(8).unwrap(), 0x5A);
+    }
+
+    #[test]
+    fn test_vpx_literals_through_bit_read() {
+        let mut encoder = BoolEncoder::new();
+        encoder.write_literal(0x55, 7).unwrap();
+        encoder.write_literal(0xBEEF, 16).unwrap();
+        let data = encoder.finish();
+        let mut decoder = BoolDecoder::new(BitReader::new(&data[..]), data.len() as u64).unwrap();
+        assert_eq!(decoder.read_bits(7).unwrap(), 0x55);
+        assert_eq!(decoder.read_u16().unwrap(), 0xBEEF);
+        // 全 0 分区在 prob 128 下解出全 0
+        let mut decoder = BoolDecoder::new(BitReader::new(&[0x00; 2][..]), 2).unwrap();
+        assert_eq!(decoder.read_bits(64).unwrap(), 0);
+        assert!(decoder.read_literal(0).is_err());
+    }
+
+    #[test]
+    fn test_vpx_vp9_marker() {
+        let mut encoder = BoolEncoder::new();
+        encoder.write_bool(false, 128);
+        encoder.write_literal(6, 3).unwrap();
+        let data = encoder.finish();
+        let mut decoder =
+            BoolDecoder::new_vp9(BitReader::new(&data[..]), data.len() as u64).unwrap();
+        assert_eq!(decoder.read_literal(3).unwrap(), 6);
+
+        let mut encoder = BoolEncoder::new();
+        encoder.write_bool(true, 128);
+        let data = encoder.finish();
+        let result = BoolDecoder::new_vp9(BitReader::new(&data[..]), data.len() as u64);
+        assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidData);
+        let result = BoolDecoder::new_vp9(BitReader::new(&data[..]), 0);
+        assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_vpx_tree_errors() {
+        let mut encoder = BoolEncoder::new();
+        let err = encoder
+            .write_tree(&YMODE_TREE, &KF_YMODE_PROBS, 7)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let mut decoder = BoolDecoder::new(BitReader::new(&[0xFF; 2][..]), 2).unwrap();
+        let err = decoder.read_tree(&[0, 2, 0, 0], &[128]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let mut decoder = BoolDecoder::new(BitReader::new(&[0xFF; 2][..]), 2).unwrap();
+        // 指回自身的树
+        let err = decoder.read_tree(&[0, 2, 2, 2], &[128, 128]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    // ------------------------------- JPEG ------------------------------- //
+
+    #[test]
+    fn test_jpeg_unstuffing_and_restart() {
+        // 0xFF 数据字节后跟填充 0x00；段尾用 1 补齐
+        let data = [
+            0x12, 0xFF, 0x00, 0x34, 0xAF, 0xFF, 0xD0, 0xFF, 0x00, 0xFF, 0xFF, 0xD9, 0x77,
+        ];
+        let mut reader = EntropyReader::new(BitReader::new(&data[..]));
+        assert_eq!(reader.read_bits(4).unwrap(), 0x1);
+        assert_eq!(reader.read_bits(12).unwrap(), 0x2FF);
+        assert_eq!(reader.read_bits(8).unwrap(), 0x34);
+        assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+        assert_eq!(reader.marker(), Some(RST0));
+        assert_eq!(reader.remaining_bits(), Some(5));
+        // 窥视越过段尾时补零，读取则报错
+        assert_eq!(reader.peek_bits(8).unwrap(), 0b0111_1000);
+        let err = reader.read_bits(6).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        assert_eq!(reader.restart().unwrap(), RST0);
+        assert_eq!(reader.marker(), None);
+        assert_eq!(reader.read_bits(8).unwrap(), 0xFF);
+        let err = reader.read_bits(1).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        // 标记前的 0xFF 填充字节被跳过
+        assert_eq!(reader.marker(), Some(EOI));
+        assert_eq!(reader.restart().unwrap_err().kind(), ErrorKind::InvalidData);
+        let mut inner = reader.into_inner();
+        assert_eq!(inner.read_bits(8).unwrap(), 0x77);
+    }
+
+    #[test]
+    fn test_jpeg_restart_skips_unread_data() {
+        let data = [0xAB, 0xCD, 0xFF, 0x00, 0xEF, 0xFF, 0xD3, 0x5A, 0xFF, 0xD9];
+        let mut reader = EntropyReader::new(BitReader::new(&data[..]));
+        assert_eq!(reader.read_bits(4).unwrap(), 0xA);
+        assert_eq!(reader.restart().unwrap(), RST0 + 3);
+        assert!(is_restart(RST0 + 3) && !is_restart(EOI));
+        assert_eq!(reader.peek_bits(16).unwrap(), 0x5A00);
+        assert_eq!(reader.read_bits(8).unwrap(), 0x5A);
+        assert_eq!(reader.marker(), Some(EOI));
+    }
+
+    #[test]
+    fn test_jpeg_writer_round_trip() {
+        let mut writer = EntropyWriter::new(BitCursor::new(Vec::new()));
+        writer.write_bits(0x1FF, 9).unwrap();
+        writer.write_bits(0x7F, 7).unwrap();
+        writer.write_bits(0b01, 2).unwrap();
+        writer.write_marker(RST0 + 1).unwrap();
+        writer.write_bits(0xFFFF, 16).unwrap();
+        writer.write_marker(EOI).unwrap();
+        let data = writer.into_inner().into_inner();
+        assert_eq!(
+            data,
+            [
+                0xFF, 0x00, 0xFF, 0x00, 0x7F, 0xFF, 0xD1, 0xFF, 0x00, 0xFF, 0x00, 0xFF, 0xD9
+            ]
+        );
+
+        let mut reader = EntropyReader::new(BitCursor::new(&data[..]));
+        assert_eq!(reader.read_bits(16).unwrap(), 0xFFFF);
+        assert_eq!(reader.read_bits(2).unwrap(), 0b01);
+        assert_eq!(reader.restart().unwrap(), RST0 + 1);
+        assert_eq!(reader.read_u16().unwrap(), 0xFFFF);
+        assert_eq!(reader.restart().unwrap_err().kind(), ErrorKind::InvalidData);
+        assert!(reader.read_bits(65).is_err());
+    }
+
+    // ------------------------------ MPEG-TS ------------------------------ //
+
+    fn ts_packet(pid: u16, cc: u8, adaptation: &[u8], fill: u8) -> [u8; PACKET_SIZE] {
+        let header = PacketHeader {
+            payload_unit_start: true,
+            pid,
+            adaptation_field_control: if adaptation.is_empty() { 1 } else { 3 },
+            continuity_counter: cc,
+            ..Default::default()
+        };
+        let mut cursor = BitCursor::new(Vec::new());
+        header.write(&mut cursor).unwrap();
+        let mut bytes = cursor.into_inner();
+        bytes.extend_from_slice(adaptation);
+        bytes.resize(PACKET_SIZE, fill);
+        bytes.try_into().unwrap()
+    }
+
+    #[test]
+    fn test_mpegts_packet_with_pcr() {
+        // adaptation_field_length 9：标志 0x50（random_access + PCR），6 字节 PCR，1 字节填充
+        let pcr = [0x09, 0x50, 0x00, 0x00, 0x3E, 0x80, 0x7E, 0x2C, 0xFF];
+        let bytes = ts_packet(0x100, 5, &pcr, 0xAB);
+        assert_eq!(&bytes[..4], &[0x47, 0x41, 0x00, 0x35]);
+        let packet = Packet::parse(&bytes).unwrap();
+        assert_eq!(packet.header.pid, 0x100);
+        assert!(packet.header.payload_unit_start);
+        assert_eq!(packet.header.continuity_counter, 5);
+        let field = packet.adaptation_field.unwrap();
+        assert!(field.random_access && !field.discontinuity);
+        // base = 0x7D00 = 32000，extension = 0x2C = 44
+        assert_eq!(
+            field.pcr,
+            Some(Pcr {
+                base: 32000,
+                extension: 44
+            })
+        );
+        assert_eq!(field.pcr.unwrap().ticks(), 32000 * 300 + 44);
+        assert_eq!(field.opcr, None);
+        assert_eq!(packet.payload.len(), PACKET_SIZE - 4 - 10);
+        assert!(packet.payload.iter().all(|&b| b == 0xAB));
+    }
+
+    #[test]
+    fn test_mpegts_adaptation_field_options() {
+        let field = [0x08, 0x87, 0xFD, 0x02, 0xAA, 0xBB, 0x01, 0x99, 0xFF];
+        let packet = Packet::parse(&ts_packet(0x42, 0, &field, 0)).unwrap();
+        let field = packet.adaptation_field.unwrap();
+        assert!(field.discontinuity);
+        assert_eq!(field.splice_countdown, Some(-3));
+        assert_eq!(field.private_data, Some(vec![0xAA, 0xBB]));
+        assert_eq!(field.extension, Some(vec![0x99]));
+
+        // 标志要求的字段超出长度
+        let err = Packet::parse(&ts_packet(0x42, 0, &[0x02, 0x10, 0x00], 0)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let mut bytes = ts_packet(0x42, 0, &[], 0);
+        bytes[0] = 0x48;
+        assert_eq!(
+            Packet::parse(&bytes).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_mpegts_resync() {
+        let mut stream = Vec::new();
+        stream.extend_from_slice(&ts_packet(0x10, 0, &[], 1));
+        // 3 个垃圾字节，其中一个伪同步字节
+        stream.extend_from_slice(&[0x00, 0x47, 0x12]);
+        for cc in 1..4 {
+            stream.extend_from_slice(&ts_packet(0x10, cc, &[], 1));
+        }
+        stream.extend_from_slice(&[0x47, 0x00]);
+        assert_eq!(find_sync(&stream[1..], 2), Some(PACKET_SIZE + 2));
+
+        let mut reader = PacketReader::new(&stream[..]);
+        let mut counters = Vec::new();
+        while let Some(packet) = reader.read_packet().unwrap() {
+            counters.push(packet.header.continuity_counter);
+        }
+        assert_eq!(counters, [0, 1, 2, 3]);
+        assert_eq!(reader.skipped_bytes(), 3 + 2);
+    }
+
+    #[test]
+    fn test_mpegts_continuity() {
+        let mut tracker = ContinuityTracker::new();
+        let mut header = PacketHeader {
+            pid: 0x20,
+            adaptation_field_control: 1,
+            continuity_counter: 14,
+            ..Default::default()
+        };
+        assert_eq!(tracker.check(&header, false), Continuity::First);
+        header.continuity_counter = 15;
+        assert_eq!(tracker.check(&header, false), Continuity::Continuous);
+        assert_eq!(tracker.check(&header, false), Continuity::Duplicate);
+        header.continuity_counter = 0;
+        assert_eq!(tracker.check(&header, false), Continuity::Continuous);
+        // 仅有适配字段的包不递增
+        header.adaptation_field_control = 2;
+        assert_eq!(tracker.check(&header, false), Continuity::Continuous);
+        header.adaptation_field_control = 1;
+        header.continuity_counter = 3;
+        assert_eq!(
+            tracker.check(&header, false),
+            Continuity::Discontinuity { expected: 1 }
+        );
+        header.continuity_counter = 9;
+        assert_eq!(tracker.check(&header, true), Continuity::First);
+        header.pid = 0x1FFF;
+        assert_eq!(tracker.check(&header, false), Continuity::Continuous);
+
+        let packet = Packet::parse(&ts_packet(0x20, 10, &[], 0)).unwrap();
+        assert_eq!(tracker.check_packet(&packet), Continuity::Continuous);
+    }
+
+    // ------------------------------- FLAC ------------------------------- //
+
+    #[test]
+    fn test_flac_frame_header() {
+        // 44.1 kHz 16 位立体声，4096 样本的第 0 帧
+        let data = [0xFF, 0xF8, 0xC9, 0x18, 0x00, 0xC2];
+        let header = FrameHeader::read(&mut BitReader::new(&data[..])).unwrap();
+        assert!(!header.variable_block_size);
+        assert_eq!(header.block_size, 4096);
+        assert_eq!(header.sample_rate, Some(44_100));
+        assert_eq!(header.channel_assignment, ChannelAssignment::Independent(2));
+        assert_eq!(header.channel_assignment.channels(), 2);
+        assert_eq!(header.bits_per_sample, Some(16));
+        assert_eq!(header.number, 0);
+
+        let mut bad = data;
+        bad[5] ^= 1;
+        let err = FrameHeader::read(&mut BitReader::new(&bad[..])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_flac_uncommon_fields() {
+        // 可变块长，样本号 4096，16 位块长 300，8 位采样率 11 kHz，mid/side
+        let mut w = BitCursor::new(Vec::new());
+        w.write_bits(0x3FFE, 14).unwrap();
+        w.write_bits(0b01, 2).unwrap();
+        w.write_bits(0x7C, 8).unwrap();
+        w.write_bits(0xA8, 8).unwrap();
+        for byte in [0xE1, 0x80, 0x80] {
+            w.write_bits(byte, 8).unwrap();
+        }
+        w.write_bits(299, 16).unwrap();
+        w.write_bits(11, 8).unwrap();
+        let crc = crc8(w.get_ref());
+        w.write_bits(crc as u64, 8).unwrap();
+
+        let header = FrameHeader::read(&mut BitCursor::new(w.get_ref())).unwrap();
+        assert!(header.variable_block_size);
+        assert_eq!(header.number, 4096);
+        assert_eq!(header.block_size, 300);
+        assert_eq!(header.sample_rate, Some(11_000));
+        assert_eq!(header.channel_assignment, ChannelAssignment::MidSide);
+        assert_eq!(header.crc8, crc);
+    }
+
+    #[test]
+    fn test_flac_coded_numbers_and_crcs() {
+        let data = [0x7F, 0xC2, 0xA9, 0xFE, 0xBF, 0xBF, 0xBF, 0xBF, 0xBF, 0xBF];
+        let mut reader = BitReader::new(&data[..]);
+        assert_eq!(read_coded_number(&mut reader).unwrap(), 0x7F);
+        assert_eq!(read_coded_number(&mut reader).unwrap(), 0xA9);
+        assert_eq!(read_coded_number(&mut reader).unwrap(), (1 << 36) - 1);
+        for bad in [[0x80, 0x80], [0xC2, 0x29], [0xFF, 0x80]] {
+            let err = read_coded_number(&mut BitReader::new(&bad[..])).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+
+        // 标准校验值
+        assert_eq!(crc8(b"123456789"), 0xF4);
+        assert_eq!(crc16(b"123456789"), 0xFEE8);
+    }
+
+    #[test]
+    fn test_flac_residual() {
+        use bitio_rs::codec::rice::write_rice_signed;
+
+        // 方法 0，分区阶 1，块长 8，预测阶 2：第一个分区 2 个样本，第二个 4 个
+        let mut w = BitCursor::new(Vec::new());
+        w.write_bits(0, 2).unwrap();
+        w.write_bits(1, 4).unwrap();
+        w.write_bits(2, 4).unwrap();
+        for v in [3, -4] {
+            write_rice_signed(&mut w, v, 2).unwrap();
+        }
+        // 转义分区：每个样本 5 位补码
+        w.write_bits(0xF, 4).unwrap();
+        w.write_bits(5, 5).unwrap();
+        for v in [-16i64, 15, 0, -1] {
+            w.write_bits(v as u64 & 0x1F, 5).unwrap();
+        }
+        w.write_bits(1, 2).unwrap();
+        // 方法 1 的 5 位参数，转义位宽 0：全部为 0
+        w.write_bits(1, 2).unwrap();
+        w.write_bits(0, 4).unwrap();
+        w.write_bits(0x1F, 5).unwrap();
+        w.write_bits(0, 5).unwrap();
+        w.set_position(0);
+
+        assert_eq!(
+            read_residual(&mut w, 8, 2).unwrap(),
+            [3, -4, -16, 15, 0, -1]
+        );
+        assert_eq!(w.read_bits(2).unwrap(), 1);
+        assert_eq!(read_residual(&mut w, 3, 0).unwrap(), [0, 0, 0]);
+
+        let mut w = BitCursor::new(vec![0b1100_0000]);
+        assert_eq!(
+            read_residual(&mut w, 8, 0).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+        // 块长不能被分区数整除
+        let mut w = BitCursor::new(vec![0b0000_1000, 0]);
+        assert_eq!(
+            read_residual(&mut w, 6, 0).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+}
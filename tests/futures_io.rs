@@ -0,0 +1,87 @@
+#![cfg(feature = "futures-io")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::async_reader::FuturesBitReader;
+    use bitio_rs::async_writer::FuturesBitWriter;
+    use bitio_rs::byte_order::ByteOrder;
+    use futures::executor::block_on;
+    use futures::io::Cursor;
+
+    #[test]
+    fn test_read_big_and_little_endian() {
+        block_on(async {
+            let data: &[u8] = &[0b1010_1100, 0b1111_0000];
+            let mut reader = FuturesBitReader::new(data);
+            assert_eq!(reader.read_bits(4).await.unwrap(), 0b1010);
+            assert_eq!(reader.read_bits(12).await.unwrap(), 0b1100_1111_0000);
+            assert!(reader.read_bits(1).await.is_err());
+
+            let mut reader = FuturesBitReader::with_byte_order(ByteOrder::LittleEndian, data);
+            assert_eq!(reader.read_bits(4).await.unwrap(), 0b1100);
+            assert_eq!(reader.read_bits(4).await.unwrap(), 0b1010);
+        });
+    }
+
+    #[test]
+    fn test_write_with_padding() {
+        block_on(async {
+            let mut writer = FuturesBitWriter::new(Cursor::new(Vec::new()));
+            writer.write_bits(0b1010, 4).await.unwrap();
+            writer.write_bits(0b1100, 4).await.unwrap();
+            writer.write_bits(0b101, 3).await.unwrap();
+            assert_eq!(writer.bits_written(), 11);
+            let data = writer.into_inner().await.unwrap().into_inner();
+            assert_eq!(data, vec![0xAC, 0xA0]);
+        });
+    }
+
+    #[test]
+    fn test_round_trip() {
+        block_on(async {
+            let widths = [3usize, 13, 64, 1, 27, 40, 7, 64, 5];
+            for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+                let mut writer =
+                    FuturesBitWriter::with_byte_order(byte_order, Cursor::new(Vec::new()));
+                for (i, &w) in widths.iter().enumerate() {
+                    let v = 0x9E3779B97F4A7C15u64.rotate_left(i as u32);
+                    writer.write_bits(v, w).await.unwrap();
+                }
+                let data = writer.into_inner().await.unwrap().into_inner();
+                let mut reader = FuturesBitReader::with_byte_order(byte_order, &data[..]);
+                for (i, &w) in widths.iter().enumerate() {
+                    let mask = if w == 64 { u64::MAX } else { (1u64 << w) - 1 };
+                    let v = 0x9E3779B97F4A7C15u64.rotate_left(i as u32) & mask;
+                    assert_eq!(reader.read_bits(w).await.unwrap(), v);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_flush_keeps_partial_byte() {
+        block_on(async {
+            let mut writer = FuturesBitWriter::new(Cursor::new(Vec::new()));
+            writer.write_bits(0b1010, 4).await.unwrap();
+            writer.flush().await.unwrap();
+            assert!(!writer.is_byte_aligned());
+            writer.write_bits(0b1100, 4).await.unwrap();
+            let data = writer.into_inner().await.unwrap().into_inner();
+            assert_eq!(data, vec![0xAC]);
+
+            let mut writer = FuturesBitWriter::new(Cursor::new(Vec::new()));
+            writer.write_bits(0b1010, 4).await.unwrap();
+            writer.finish().await.unwrap();
+            writer.write_bits(0b1100, 4).await.unwrap();
+            let data = writer.into_inner().await.unwrap().into_inner();
+            assert_eq!(data, vec![0xA0, 0xC0]);
+
+            let mut writer = FuturesBitWriter::new(Cursor::new(Vec::new())).pad_on_flush(true);
+            writer.write_bits(0b1010, 4).await.unwrap();
+            writer.flush().await.unwrap();
+            writer.write_bits(0b1100, 4).await.unwrap();
+            let data = writer.into_inner().await.unwrap().into_inner();
+            assert_eq!(data, vec![0xA0, 0xC0]);
+        });
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::Error;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::golomb::{se_to_ue, ue_len, ue_to_se};
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::traits::{BitReadExt, BitWrite, BitWriteExt};
+    use std::io::ErrorKind;
+
+    #[test]
+    fn test_mappings() {
+        let signed: Vec<i64> = (0..7).map(ue_to_se).collect();
+        assert_eq!(signed, [0, 1, -1, 2, -2, 3, -3]);
+        for v in (-1000..1000).chain([i64::MAX, i64::MIN + 1]) {
+            assert_eq!(ue_to_se(se_to_ue(v).unwrap()), v);
+        }
+        assert_eq!(se_to_ue(i64::MIN), None);
+        assert_eq!(ue_len(0), Some(1));
+        assert_eq!(ue_len(4), Some(5));
+        assert_eq!(ue_len(u64::MAX - 1), Some(127));
+        assert_eq!(ue_len(u64::MAX), None);
+    }
+
+    #[test]
+    fn test_ue_codes() {
+        // 1 | 010 | 011 | 00100 | 0001000 | 1，之后是 11 和补零
+        let data = [0b1010_0110, 0b0100_0001, 0b0001_1100];
+        let mut reader = BitReader::new(&data[..]);
+        for expected in [0, 1, 2, 3, 7, 0] {
+            assert_eq!(reader.read_ue().unwrap(), expected);
+        }
+
+        let mut cursor = BitCursor::new(Vec::new());
+        for value in [0, 1, 2, 3, 7, 0] {
+            cursor.write_ue(value).unwrap();
+        }
+        cursor.write_bits(0b11, 2).unwrap();
+        assert_eq!(cursor.get_ref(), &data);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let values = [0, 1, 254, 255, 1 << 32, u64::MAX - 1];
+        let signed = [0, -1, 1, -65536, i64::MAX, i64::MIN + 1];
+        let mut cursor = BitCursor::new(Vec::new());
+        for (&u, &s) in values.iter().zip(&signed) {
+            cursor.write_ue(u).unwrap();
+            cursor.write_se(s).unwrap();
+        }
+        let end = cursor.position();
+        cursor.set_position(0);
+        for (&u, &s) in values.iter().zip(&signed) {
+            assert_eq!(cursor.read_ue().unwrap(), u);
+            assert_eq!(cursor.read_se().unwrap(), s);
+        }
+        assert_eq!(cursor.position(), end);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut cursor = BitCursor::new(Vec::new());
+        let err = Error::from(cursor.write_ue(u64::MAX).unwrap_err());
+        assert!(matches!(err, Error::ValueOutOfRange { bits: 64, .. }));
+        let err = Error::from(cursor.write_se(i64::MIN).unwrap_err());
+        assert!(matches!(err, Error::ValueOutOfRange { bits: 64, .. }));
+        assert_eq!(cursor.position(), 0);
+
+        // 64 个前导零超出 u64
+        let data = [0u8; 9];
+        let err = BitReader::new(&data[..]).read_ue().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = BitReader::new(&[0x00, 0x01][..]).read_ue().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_forwarding() {
+        let mut cursor = BitCursor::new(Vec::new());
+        let mut boxed: Box<dyn BitWrite> = Box::new(&mut cursor);
+        boxed.write_se(-2).unwrap();
+        drop(boxed);
+        cursor.set_position(0);
+        let reader = &mut cursor;
+        assert_eq!(reader.read_se().unwrap(), -2);
+    }
+}
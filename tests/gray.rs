@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::gray::{from_gray, to_gray};
+    use bitio_rs::traits::{BitReadExt, BitWriteExt};
+
+    #[test]
+    fn test_gray_sequence() {
+        let codes: Vec<u64> = (0..8).map(to_gray).collect();
+        assert_eq!(
+            codes,
+            [0b000, 0b001, 0b011, 0b010, 0b110, 0b111, 0b101, 0b100]
+        );
+        for v in (0..1000).chain([u64::MAX, u64::MAX - 1, 1 << 63]) {
+            assert_eq!(from_gray(to_gray(v)), v);
+            assert_eq!((to_gray(v) ^ to_gray(v.wrapping_add(1))).count_ones(), 1);
+        }
+    }
+
+    #[test]
+    fn test_gray_fields() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_gray_bits(5, 3).unwrap();
+        // 高于 n 位的部分被忽略
+        cursor.write_gray_bits(0b1_0110, 4).unwrap();
+        cursor.write_gray_bits(u64::MAX, 64).unwrap();
+        assert_eq!(cursor.get_ref()[0], 0b1110_1011);
+
+        cursor.set_position(0);
+        assert_eq!(cursor.read_gray_bits(3).unwrap(), 5);
+        assert_eq!(cursor.read_gray_bits(4).unwrap(), 0b0110);
+        assert_eq!(cursor.read_gray_bits(64).unwrap(), u64::MAX);
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::hdlc::{StuffingWriter, UnstuffingReader};
+    use bitio_rs::traits::{BitRead, BitWrite};
+    use std::io::ErrorKind;
+
+    #[test]
+    fn test_stuffing() {
+        let mut writer = StuffingWriter::new(BitCursor::new(Vec::new()));
+        writer.write_bits(0xFF, 8).unwrap();
+        let cursor = writer.into_inner();
+        // 11111 0 111
+        assert_eq!(cursor.position(), 9);
+        assert_eq!(cursor.get_ref()[..2], [0b1111_1011, 0b1000_0000]);
+
+        // 跨越多次写入计数连续的 1
+        let mut writer = StuffingWriter::new(BitCursor::new(Vec::new()));
+        writer.write_bits(0b111, 3).unwrap();
+        writer.write_bits(0b110, 3).unwrap();
+        let cursor = writer.into_inner();
+        assert_eq!(cursor.position(), 7);
+        assert_eq!(cursor.get_ref()[0], 0b1111_1000);
+    }
+
+    #[test]
+    fn test_frames_round_trip() {
+        let frames: [&[u8]; 3] = [&[0x7E, 0xFF, 0x00, 0x3F], &[], &[0xF8, 0x1F, 0x7E]];
+        let mut writer = StuffingWriter::new(BitCursor::new(Vec::new()));
+        writer.write_bits(0b101, 3).unwrap(); // 标志前的噪声
+        for frame in frames {
+            writer.write_frame(frame).unwrap();
+        }
+        let mut cursor = writer.into_inner();
+
+        cursor.set_position(0);
+        let mut reader = UnstuffingReader::new(cursor);
+        reader.find_flag().unwrap();
+        assert_eq!(reader.read_frame().unwrap(), frames[0]);
+        // 空帧被当作空闲标志跳过
+        reader.find_flag().unwrap();
+        assert_eq!(reader.read_frame().unwrap(), frames[2]);
+        let err = reader.read_frame().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_read_bits_stops_at_flag_and_abort() {
+        let mut writer = StuffingWriter::new(BitCursor::new(Vec::new()));
+        writer.write_flag().unwrap();
+        writer.write_bits(0xFFFF, 16).unwrap();
+        writer.write_flag().unwrap();
+        writer.write_bits(0x6, 4).unwrap();
+        writer.write_abort().unwrap();
+        writer.write_flag().unwrap();
+        let mut cursor = writer.into_inner();
+
+        cursor.set_position(0);
+        let mut reader = UnstuffingReader::new(cursor);
+        reader.find_flag().unwrap();
+        assert_eq!(reader.read_bits(16).unwrap(), 0xFFFF);
+        let err = reader.read_bits(1).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(reader.read_bits(4).unwrap(), 0x6);
+        let err = reader.read_bits(1).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        reader.find_flag().unwrap();
+        assert_eq!(
+            // 只剩字节末尾的填充比特
+            reader.read_bits(8).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn test_aborted_and_misaligned_frames() {
+        let mut writer = StuffingWriter::new(BitCursor::new(Vec::new()));
+        writer.write_flag().unwrap();
+        writer.write_bits(0xAB, 8).unwrap();
+        writer.write_abort().unwrap();
+        writer.write_flag().unwrap();
+        writer.write_bits(0xAB, 7).unwrap();
+        writer.write_flag().unwrap();
+        let mut cursor = writer.into_inner();
+
+        cursor.set_position(0);
+        let mut reader = UnstuffingReader::new(cursor);
+        reader.find_flag().unwrap();
+        assert_eq!(
+            reader.read_frame().unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+        reader.find_flag().unwrap();
+        assert_eq!(
+            reader.read_frame().unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+}
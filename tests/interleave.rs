@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::interleave::{
+        BlockDeinterleaver, BlockInterleaver, ConvolutionalDeinterleaver, ConvolutionalInterleaver,
+    };
+    use bitio_rs::traits::{BitRead, BitWrite};
+
+    #[test]
+    fn test_block_layout() {
+        // 2x3 矩阵按行写入 [1 0 1] [1 0 0]，按列发送 11 00 10
+        let mut writer = BlockInterleaver::new(BitCursor::new(Vec::new()), 2, 3);
+        writer.write_bits(0b101100, 6).unwrap();
+        let cursor = writer.into_inner().unwrap();
+        assert_eq!(cursor.position(), 6);
+        assert_eq!(cursor.get_ref()[0], 0b1100_1000);
+
+        // 不满一块时补零
+        let mut writer = BlockInterleaver::new(BitCursor::new(Vec::new()), 2, 3);
+        writer.write_bits(0b11, 2).unwrap();
+        let cursor = writer.into_inner().unwrap();
+        assert_eq!(cursor.position(), 6);
+        assert_eq!(cursor.get_ref()[0], 0b1010_0000);
+    }
+
+    #[test]
+    fn test_block_spreads_burst() {
+        let data: Vec<u8> = (0..8u8).map(|i| i.wrapping_mul(37)).collect();
+        let mut writer = BlockInterleaver::new(BitCursor::new(Vec::new()), 8, 8);
+        for &byte in &data {
+            writer.write_bits(byte as u64, 8).unwrap();
+        }
+        let mut cursor = writer.into_inner().unwrap();
+
+        // 信道上连续 8 个比特出错，解交织后每行只错一个比特
+        cursor.get_mut()[3] ^= 0xFF;
+        cursor.set_position(0);
+        let mut reader = BlockDeinterleaver::new(cursor, 8, 8);
+        for &byte in &data {
+            let got = reader.read_bits(8).unwrap() as u8;
+            assert_eq!((got ^ byte).count_ones(), 1);
+        }
+        assert!(reader.read_bits(1).is_err());
+    }
+
+    #[test]
+    fn test_convolutional_round_trip() {
+        let (branches, delay) = (3, 2);
+        let mut writer = ConvolutionalInterleaver::new(BitCursor::new(Vec::new()), branches, delay);
+        writer.write_bits(0xBEEF, 16).unwrap();
+        writer.write_bits(0b101, 3).unwrap();
+        let mut cursor = writer.into_inner().unwrap();
+        // 19 个数据比特加上 4 * 3 个冲刷比特
+        assert_eq!(cursor.position(), 31);
+
+        cursor.set_position(0);
+        let mut reader = ConvolutionalDeinterleaver::new(cursor, branches, delay);
+        assert_eq!(reader.latency(), 12);
+        assert_eq!(reader.read_bits(12).unwrap(), 0);
+        assert_eq!(reader.read_bits(16).unwrap(), 0xBEEF);
+        assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+    }
+
+    #[test]
+    fn test_convolutional_without_delay_is_identity() {
+        let mut writer = ConvolutionalInterleaver::new(BitCursor::new(Vec::new()), 4, 0);
+        writer.write_bits(0x1234_5678, 32).unwrap();
+        let cursor = writer.into_inner().unwrap();
+        assert_eq!(cursor.get_ref()[..4], [0x12, 0x34, 0x56, 0x78]);
+    }
+}
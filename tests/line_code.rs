@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::fast::slice::FastSliceReaderBig;
+    use bitio_rs::line_code::{
+        Manchester, ManchesterReader, ManchesterWriter, Nrzi, NrziReader, NrziWriter,
+    };
+    use bitio_rs::traits::{BitRead, BitWrite};
+    use std::io::ErrorKind;
+
+    #[test]
+    fn test_manchester_words() {
+        assert_eq!(Manchester::Ieee.encode(0b10, 2).unwrap(), 0b0110);
+        assert_eq!(Manchester::Thomas.encode(0b10, 2).unwrap(), 0b1001);
+        assert_eq!(Manchester::Ieee.encode(u64::MAX, 32).unwrap(), EVEN);
+        for code in [Manchester::Ieee, Manchester::Thomas] {
+            let raw = code.encode(0xDEAD_BEEF, 32).unwrap();
+            assert_eq!(code.decode(raw, 32).unwrap(), 0xDEAD_BEEF);
+        }
+
+        let err = Manchester::Ieee.decode(0b0111, 2).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = Manchester::Ieee.encode(0, 33).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    const EVEN: u64 = 0x5555_5555_5555_5555;
+
+    #[test]
+    fn test_nrzi_words() {
+        assert_eq!(Nrzi::Mark.encode(0b1011, 4, false).unwrap(), 0b1101);
+        assert_eq!(Nrzi::Mark.encode(0b1011, 4, true).unwrap(), 0b0010);
+        assert_eq!(Nrzi::Space.encode(0b1011, 4, false).unwrap(), 0b0111);
+        assert_eq!(Nrzi::Mark.decode(0b1101, 4, false).unwrap(), 0b1011);
+        assert_eq!(Nrzi::Space.decode(0b0111, 4, false).unwrap(), 0b1011);
+
+        let raw = Nrzi::Space.encode(u64::MAX, 64, true).unwrap();
+        assert_eq!(raw, u64::MAX);
+        assert_eq!(Nrzi::Space.decode(raw, 64, true).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_adapters_round_trip() {
+        for code in [Manchester::Ieee, Manchester::Thomas] {
+            let mut writer = ManchesterWriter::new(BitCursor::new(Vec::new()), code);
+            writer.write_bits(0x0123_4567_89AB_CDEF, 64).unwrap();
+            writer.write_bits(0b101, 3).unwrap();
+            let mut cursor = writer.into_inner();
+            assert_eq!(cursor.position(), 134);
+
+            cursor.set_position(0);
+            let mut reader = ManchesterReader::new(cursor, code);
+            assert_eq!(reader.read_bits(64).unwrap(), 0x0123_4567_89AB_CDEF);
+            assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+        }
+
+        for code in [Nrzi::Mark, Nrzi::Space] {
+            let mut writer = NrziWriter::new(BitCursor::new(Vec::new()), code);
+            writer.write_bits(0b1100_1010, 8).unwrap();
+            writer.write_bits(0xFFFF_0000, 32).unwrap();
+            let mut cursor = writer.into_inner();
+
+            cursor.set_position(0);
+            let mut reader = NrziReader::new(cursor, code);
+            // 按不同的分组读出，电平状态跨调用保持
+            assert_eq!(reader.read_bits(3).unwrap(), 0b110);
+            assert_eq!(reader.read_bits(21).unwrap(), 0b0_1010_1111_1111_1111_1111);
+            assert_eq!(reader.read_bits(16).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn test_words_with_fast_reader() {
+        let mut writer = ManchesterWriter::new(BitCursor::new(Vec::new()), Manchester::Ieee);
+        writer.write_bits(0xCAFE_F00D, 32).unwrap();
+        let cursor = writer.into_inner();
+
+        let mut reader = FastSliceReaderBig::new(cursor.get_ref());
+        let raw = reader.read_bits_fast(64).unwrap();
+        assert_eq!(Manchester::Ieee.decode(raw, 32).unwrap(), 0xCAFE_F00D);
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::morton::{
+        MAX_3D, decode_2d, decode_3d, encode_2d, encode_3d, read_morton, write_morton,
+    };
+    use bitio_rs::traits::BitRead;
+
+    #[test]
+    fn test_2d_codes() {
+        // Z 形顺序：(0,0) (1,0) (0,1) (1,1) (2,0) ...
+        let codes: Vec<u64> = [(0, 0), (1, 0), (0, 1), (1, 1), (2, 0), (3, 3)]
+            .iter()
+            .map(|&(x, y)| encode_2d(x, y))
+            .collect();
+        assert_eq!(codes, [0, 1, 2, 3, 4, 15]);
+        assert_eq!(encode_2d(u32::MAX, 0), 0x5555_5555_5555_5555);
+        assert_eq!(encode_2d(0, u32::MAX), 0xAAAA_AAAA_AAAA_AAAA);
+        for (x, y) in [
+            (0, 0),
+            (12345, 67890),
+            (u32::MAX, 1),
+            (0xDEAD_BEEF, 0x1234_5678),
+        ] {
+            assert_eq!(decode_2d(encode_2d(x, y)), (x, y));
+        }
+    }
+
+    #[test]
+    fn test_3d_codes() {
+        assert_eq!(encode_3d(1, 0, 0), Some(0b001));
+        assert_eq!(encode_3d(0, 1, 0), Some(0b010));
+        assert_eq!(encode_3d(0, 0, 1), Some(0b100));
+        assert_eq!(encode_3d(3, 0, 1), Some(0b001_101));
+        assert_eq!(encode_3d(MAX_3D, MAX_3D, MAX_3D), Some(u64::MAX >> 1));
+        assert_eq!(encode_3d(MAX_3D + 1, 0, 0), None);
+        for (x, y, z) in [
+            (0, 0, 0),
+            (1, 2, 3),
+            (MAX_3D, 0, 77_777),
+            (123_456, 654_321, 1),
+        ] {
+            assert_eq!(decode_3d(encode_3d(x, y, z).unwrap()), (x, y, z));
+        }
+    }
+
+    #[test]
+    fn test_streaming_matches_codes() {
+        let mut cursor = BitCursor::new(Vec::new());
+        write_morton(&mut cursor, &[0xBEEF, 0x1234], 16).unwrap();
+        write_morton(&mut cursor, &[5, 6, 7], 21).unwrap();
+        cursor.set_position(0);
+        assert_eq!(cursor.read_bits(32).unwrap(), encode_2d(0xBEEF, 0x1234));
+        assert_eq!(cursor.read_bits(63).unwrap(), encode_3d(5, 6, 7).unwrap());
+    }
+
+    #[test]
+    fn test_streaming_wide_codes_round_trip() {
+        // 4 个 40 位坐标，交织后 160 位，跨越多个 64 位块
+        let coords = [0xAB_CDEF_0123, 0x12_3456_789A, 0xFF_FFFF_FFFF, 1];
+        for order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut cursor = BitCursor::with_byte_order(order, Vec::new());
+            write_morton(&mut cursor, &coords, 40).unwrap();
+            assert_eq!(cursor.position(), 160);
+            cursor.set_position(0);
+            let mut back = [0; 4];
+            read_morton(&mut cursor, &mut back, 40).unwrap();
+            assert_eq!(back, coords);
+        }
+
+        let mut cursor = BitCursor::new(Vec::new());
+        write_morton(&mut cursor, &[u64::MAX, 0], 64).unwrap();
+        cursor.set_position(0);
+        let mut back = [0; 2];
+        read_morton(&mut cursor, &mut back, 64).unwrap();
+        assert_eq!(back, [u64::MAX, 0]);
+    }
+
+    #[test]
+    fn test_streaming_errors() {
+        let mut cursor = BitCursor::new(Vec::new());
+        assert!(write_morton(&mut cursor, &[1, 8], 3).is_err());
+        assert!(write_morton(&mut cursor, &[1], 0).is_err());
+        assert_eq!(cursor.position(), 0);
+
+        let mut cursor = BitCursor::new(vec![0xFF]);
+        let mut coords = [0; 2];
+        assert!(read_morton(&mut cursor, &mut coords, 65).is_err());
+        assert!(read_morton(&mut cursor, &mut coords, 8).is_err());
+    }
+}
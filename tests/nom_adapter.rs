@@ -0,0 +1,66 @@
+#![cfg(feature = "nom")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::nom_adapter::{BitInput, NomBitReader, parse_cursor};
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::traits::{BitRead, BitWrite};
+    use nom::IResult;
+    use nom::bits::{complete, streaming};
+    use std::io::{Cursor, ErrorKind};
+
+    /// 3 比特版本 + 5 比特长度 + 12 比特类型
+    fn header(input: BitInput<'_>) -> IResult<BitInput<'_>, (u8, u8, u16)> {
+        let (input, version) = streaming::take(3usize)(input)?;
+        let (input, length) = streaming::take(5usize)(input)?;
+        let (input, kind) = streaming::take(12usize)(input)?;
+        Ok((input, (version, length, kind)))
+    }
+
+    fn data() -> Vec<u8> {
+        let mut cursor = BitCursor::new(Vec::new());
+        for (value, n) in [(5, 3), (17, 5), (0xABC, 12), (2, 3), (31, 5), (0x123, 12)] {
+            cursor.write_bits(value, n).unwrap();
+        }
+        cursor.into_inner()
+    }
+
+    #[test]
+    fn test_parse_cursor() {
+        let data = data();
+        let mut cursor = BitCursor::new(&data[..]);
+        assert_eq!(cursor.read_bits(3).unwrap(), 5);
+        cursor.set_position(0);
+        assert_eq!(parse_cursor(&mut cursor, header).unwrap(), (5, 17, 0xABC));
+        assert_eq!(cursor.position(), 20);
+        assert_eq!(parse_cursor(&mut cursor, header).unwrap(), (2, 31, 0x123));
+        assert_eq!(cursor.position(), 40);
+        let err = parse_cursor(&mut cursor, header).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_streaming_reader() {
+        let mut reader = NomBitReader::new(BitReader::new(Cursor::new(data())));
+        assert_eq!(reader.parse(header).unwrap(), (5, 17, 0xABC));
+        assert_eq!(reader.buffered_bits(), 4);
+        assert_eq!(reader.parse(header).unwrap(), (2, 31, 0x123));
+        assert_eq!(reader.buffered_bits(), 0);
+        assert!(reader.parse(header).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let data = data();
+        let mut cursor = BitCursor::new(&data[..]);
+        let err = parse_cursor(&mut cursor, |i| complete::tag(0u8, 3usize)(i)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(cursor.position(), 0);
+
+        let mut cursor = BitCursor::with_byte_order(ByteOrder::LittleEndian, &data[..]);
+        let err = parse_cursor(&mut cursor, header).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::packing::{
+        pack_u32s, pack_u64s, required_width_u32, required_width_u64, unpack_u32s, unpack_u64s,
+    };
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::writer::BitWriter;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_pack_u32s_layout() {
+        let mut cursor = BitCursor::new(Vec::new());
+        pack_u32s(&[1, 2, 3, 4], 3, &mut cursor).unwrap();
+        assert_eq!(cursor.into_inner(), vec![0b0010_1001, 0b1100_0000]);
+
+        let mut cursor = BitCursor::with_byte_order(ByteOrder::LittleEndian, Vec::new());
+        pack_u32s(&[1, 2, 3, 4], 3, &mut cursor).unwrap();
+        assert_eq!(cursor.into_inner(), vec![0b1101_0001, 0b0000_1000]);
+    }
+
+    #[test]
+    fn test_round_trip_through_writer_and_reader() {
+        let values: Vec<u32> = (0..1000).map(|i| (i * 7919) % 8192).collect();
+        let width = required_width_u32(&values);
+        assert_eq!(width, 13);
+
+        let mut writer = BitWriter::new(Vec::new());
+        pack_u32s(&values, width, &mut writer).unwrap();
+        let data = writer.into_inner().unwrap();
+        assert_eq!(data.len(), (1000 * 13usize).div_ceil(8));
+
+        let mut reader = BitReader::new(Cursor::new(data));
+        let mut out = vec![0u32; values.len()];
+        unpack_u32s(&mut reader, width, &mut out).unwrap();
+        assert_eq!(out, values);
+    }
+
+    #[test]
+    fn test_round_trip_u64() {
+        let values = [u64::MAX, 0, 0x0123456789ABCDEF, 42];
+        assert_eq!(required_width_u64(&values), 64);
+        let mut cursor = BitCursor::with_byte_order(ByteOrder::LittleEndian, Vec::new());
+        pack_u64s(&values, 64, &mut cursor).unwrap();
+        cursor.set_position(0);
+        let mut out = [0u64; 4];
+        unpack_u64s(&mut cursor, 64, &mut out).unwrap();
+        assert_eq!(out, values);
+    }
+
+    #[test]
+    fn test_value_out_of_range_writes_nothing() {
+        let mut cursor = BitCursor::new(Vec::new());
+        assert!(pack_u32s(&[1, 2, 8], 3, &mut cursor).is_err());
+        assert!(cursor.get_ref().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_width_and_short_input() {
+        let mut cursor = BitCursor::new(Vec::new());
+        assert!(pack_u32s(&[0], 0, &mut cursor).is_err());
+        assert!(pack_u32s(&[0], 33, &mut cursor).is_err());
+        assert!(pack_u64s(&[0], 65, &mut cursor).is_err());
+
+        let mut cursor = BitCursor::new(vec![0xFFu8]);
+        let mut out = [0u32; 3];
+        assert!(unpack_u32s(&mut cursor, 3, &mut out).is_err());
+    }
+
+    #[test]
+    fn test_required_width() {
+        assert_eq!(required_width_u32(&[]), 1);
+        assert_eq!(required_width_u32(&[0, 0]), 1);
+        assert_eq!(required_width_u32(&[1, 255]), 8);
+        assert_eq!(required_width_u32(&[u32::MAX]), 32);
+        assert_eq!(required_width_u64(&[256]), 9);
+    }
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "rayon")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::parallel::{decode_at, decode_split};
+    use bitio_rs::traits::BitRead;
+    use bitio_rs::traits::BitWrite;
+    use bitio_rs::writer::BitWriter;
+    use std::io::ErrorKind;
+
+    /// 记录格式：8 比特长度（含自身，字节）、4 比特类型、12 比特值、填充
+    fn records(count: usize) -> (Vec<u8>, Vec<usize>) {
+        let mut data = Vec::new();
+        let mut offsets = Vec::new();
+        for i in 0..count {
+            offsets.push(data.len());
+            let len = 3 + i % 5;
+            let mut writer = BitWriter::new(Vec::new());
+            writer.write_bits(len as u64, 8).unwrap();
+            writer.write_bits((i % 16) as u64, 4).unwrap();
+            writer.write_bits(i as u64, 12).unwrap();
+            if len > 3 {
+                writer.write_bits(0, 8 * (len - 3)).unwrap();
+            }
+            data.extend(writer.into_inner().unwrap());
+        }
+        (data, offsets)
+    }
+
+    #[test]
+    fn test_decode_at() {
+        let (data, offsets) = records(1000);
+        let values = decode_at(&data, &offsets, ByteOrder::BigEndian, |reader| {
+            reader.read_bits(12)?;
+            reader.read_bits(12)
+        })
+        .unwrap();
+        assert_eq!(values, (0..1000).collect::<Vec<u64>>());
+
+        let err = decode_at(&data, &[4, 2], ByteOrder::BigEndian, |r| r.read_bits(1)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = decode_at(&data, &[data.len() + 1], ByteOrder::BigEndian, |r| {
+            r.read_bits(1)
+        })
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_decode_split() {
+        let (data, _) = records(500);
+        let kinds = decode_split(
+            &data,
+            ByteOrder::BigEndian,
+            |rest| Ok(rest[0] as usize),
+            |reader| {
+                reader.read_bits(8)?;
+                reader.read_bits(4)
+            },
+        )
+        .unwrap();
+        assert_eq!(kinds, (0..500).map(|i| i % 16).collect::<Vec<u64>>());
+
+        // 解码错误按记录顺序返回第一个
+        let err = decode_split(
+            &data,
+            ByteOrder::BigEndian,
+            |rest| Ok(rest[0] as usize),
+            |reader| reader.read_bits(64),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        let err =
+            decode_split(&data, ByteOrder::BigEndian, |_| Ok(0), |r| r.read_bits(1)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::error::BitReadWriteError;
+    use bitio_rs::parity::Parity;
+    use bitio_rs::traits::{BitReadExt, BitWriteExt};
+    use std::io::ErrorKind;
+
+    #[test]
+    fn test_parity_bit() {
+        assert_eq!(Parity::Even.bit(0b1011, 4), 1);
+        assert_eq!(Parity::Odd.bit(0b1011, 4), 0);
+        // 只统计低 n 位
+        assert_eq!(Parity::Even.bit(0b1_0000, 4), 0);
+        assert_eq!(Parity::Even.bit(u64::MAX, 64), 0);
+    }
+
+    #[test]
+    fn test_parity_fields() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor
+            .write_bits_with_parity(0b101_1001, 7, Parity::Even)
+            .unwrap();
+        cursor
+            .write_bits_with_parity(0b101_1001, 7, Parity::Odd)
+            .unwrap();
+        assert_eq!(cursor.get_ref(), &vec![0b1011_0010, 0b1011_0011]);
+
+        cursor.set_position(0);
+        assert_eq!(
+            cursor.read_bits_with_parity(7, Parity::Even).unwrap(),
+            0b101_1001
+        );
+        let err = cursor.read_bits_with_parity(7, Parity::Even).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(matches!(
+            err.get_ref()
+                .and_then(|e| e.downcast_ref::<BitReadWriteError>()),
+            Some(BitReadWriteError::ParityMismatch)
+        ));
+        assert_eq!(cursor.position(), 16);
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::pcm::{I24_MAX, I24_MIN, decode_i24, encode_i24, sign_extend_i24};
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::traits::{BitRead, BitReadExt, BitWrite, BitWriteExt};
+    use bitio_rs::writer::BitWriter;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_sign_extend() {
+        assert_eq!(sign_extend_i24(0x7F_FFFF), I24_MAX);
+        assert_eq!(sign_extend_i24(0x80_0000), I24_MIN);
+        assert_eq!(sign_extend_i24(0xFF_FFFF), -1);
+        assert_eq!(sign_extend_i24(0x12FF_FFFE), -2);
+    }
+
+    #[test]
+    fn test_read_write_i24_both_orders() {
+        let data = [0xFF, 0xFF, 0xFE, 0x80, 0x00, 0x00];
+        let mut reader = BitReader::new(Cursor::new(data));
+        assert_eq!(reader.read_i24().unwrap(), -2);
+        assert_eq!(reader.read_i24().unwrap(), I24_MIN);
+
+        let mut reader = BitReader::with_byte_order(ByteOrder::LittleEndian, Cursor::new(data));
+        assert_eq!(reader.read_i24().unwrap(), -65537);
+        assert_eq!(reader.read_i24().unwrap(), 0x80);
+
+        let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, Vec::new());
+        writer.write_i24(-65537).unwrap();
+        writer.write_i24(0x80).unwrap();
+        assert_eq!(writer.into_inner().unwrap(), data);
+
+        let mut writer = BitWriter::new(Vec::new());
+        let err = writer.write_i24(I24_MAX + 1).unwrap_err();
+        assert_eq!(err.to_string(), "Value 8388608 does not fit in 24 bits");
+        assert!(writer.write_i24(I24_MIN - 1).is_err());
+    }
+
+    #[test]
+    fn test_unaligned_i24() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_bits(0b101, 3).unwrap();
+        cursor.write_i24(-5).unwrap();
+        cursor.write_i24_samples(&[1, -1]).unwrap();
+        cursor.set_position(0);
+        assert_eq!(cursor.read_bits(3).unwrap(), 0b101);
+        assert_eq!(cursor.read_i24().unwrap(), -5);
+        let mut out = [0; 2];
+        cursor.read_i24_samples(&mut out).unwrap();
+        assert_eq!(out, [1, -1]);
+    }
+
+    #[test]
+    fn test_interleaved_samples_round_trip() {
+        // 立体声交错：L R L R ...，超过一个内部块
+        let samples: Vec<i32> = (0..1000)
+            .map(|i| if i % 2 == 0 { i * 8191 } else { -i * 8387 })
+            .collect();
+        for order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut writer = BitWriter::with_byte_order(order, Vec::new());
+            writer.write_i24_samples(&samples).unwrap();
+            let bytes = writer.into_inner().unwrap();
+            assert_eq!(bytes.len(), 3000);
+
+            let mut encoded = vec![0u8; 3000];
+            encode_i24(&samples, order, &mut encoded).unwrap();
+            assert_eq!(encoded, bytes);
+
+            let mut decoded = vec![0; 1000];
+            decode_i24(&bytes, order, &mut decoded).unwrap();
+            assert_eq!(decoded, samples);
+
+            let mut reader = BitReader::with_byte_order(order, Cursor::new(bytes));
+            let mut out = vec![0; 1000];
+            reader.read_i24_samples(&mut out).unwrap();
+            assert_eq!(out, samples);
+        }
+    }
+
+    #[test]
+    fn test_bulk_errors() {
+        let mut writer = BitWriter::new(Vec::new());
+        assert!(writer.write_i24_samples(&[0, 1 << 23]).is_err());
+        assert!(writer.into_inner().unwrap().is_empty());
+
+        let mut reader = BitReader::new(Cursor::new([0u8; 6])).max_bits(40);
+        let mut out = [0; 2];
+        assert!(reader.read_i24_samples(&mut out).is_err());
+
+        let mut out = [0; 3];
+        assert!(decode_i24(&[0; 8], ByteOrder::BigEndian, &mut out).is_err());
+        assert!(encode_i24(&[0; 3], ByteOrder::BigEndian, &mut [0; 8]).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::raw_video::{
+        pack_raw10, pack_raw12, pack_v210, unpack_raw10, unpack_raw12, unpack_v210, v210_stride,
+    };
+    use std::io::ErrorKind;
+
+    #[test]
+    fn test_raw10_layout() {
+        let data = [0xFF, 0x00, 0x80, 0x01, 0b11_10_01_00];
+        let mut pixels = [0u16; 4];
+        unpack_raw10(&data, &mut pixels).unwrap();
+        assert_eq!(pixels, [0x3FC, 0x001, 0x202, 0x007]);
+
+        let mut packed = [0u8; 5];
+        pack_raw10(&pixels, &mut packed).unwrap();
+        assert_eq!(packed, data);
+    }
+
+    #[test]
+    fn test_raw12_layout() {
+        let data = [0xAB, 0x12, 0x4C];
+        let mut pixels = [0u16; 2];
+        unpack_raw12(&data, &mut pixels).unwrap();
+        assert_eq!(pixels, [0xABC, 0x124]);
+
+        let mut packed = [0u8; 3];
+        pack_raw12(&pixels, &mut packed).unwrap();
+        assert_eq!(packed, data);
+    }
+
+    #[test]
+    fn test_v210_layout() {
+        let components: Vec<u16> = (1..=12).map(|i| i * 64 + i).collect();
+        let mut packed = [0u8; 16];
+        pack_v210(&components, &mut packed).unwrap();
+        let first = u32::from_le_bytes(packed[..4].try_into().unwrap());
+        assert_eq!(first, 65 | 130 << 10 | 195 << 20);
+
+        let mut unpacked = [0u16; 12];
+        unpack_v210(&packed, &mut unpacked).unwrap();
+        assert_eq!(unpacked.to_vec(), components);
+
+        // 填充位被忽略
+        packed[3] |= 0xC0;
+        unpack_v210(&packed, &mut unpacked).unwrap();
+        assert_eq!(unpacked.to_vec(), components);
+
+        assert_eq!(v210_stride(1920), 5120);
+        assert_eq!(v210_stride(1280), 3456);
+    }
+
+    #[test]
+    fn test_round_trip_long_lines() {
+        let samples: Vec<u16> = (0..1920u32).map(|i| ((i * 7919) % 4096) as u16).collect();
+
+        let mut raw12 = vec![0u8; 2880];
+        pack_raw12(&samples, &mut raw12).unwrap();
+        let mut back = vec![0u16; 1920];
+        unpack_raw12(&raw12, &mut back).unwrap();
+        assert_eq!(back, samples);
+
+        let ten: Vec<u16> = samples.iter().map(|v| v >> 2).collect();
+        let mut raw10 = vec![0u8; 2400];
+        pack_raw10(&ten, &mut raw10).unwrap();
+        unpack_raw10(&raw10, &mut back).unwrap();
+        assert_eq!(back, ten);
+
+        let mut v210 = vec![0u8; 2560];
+        pack_v210(&ten, &mut v210).unwrap();
+        unpack_v210(&v210, &mut back).unwrap();
+        assert_eq!(back, ten);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut pixels = [0u16; 3];
+        let err = unpack_raw10(&[0; 5], &mut pixels).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        let mut pixels = [0u16; 8];
+        let err = unpack_raw10(&[0; 9], &mut pixels).unwrap_err();
+        assert!(err.to_string().contains("80"), "{}", err);
+
+        let mut out = [0x55u8; 3];
+        let err = pack_raw12(&[0x1000, 0], &mut out).unwrap_err();
+        assert_eq!(err.to_string(), "Value 4096 does not fit in 12 bits");
+        assert_eq!(out, [0x55; 3]);
+
+        let mut out = [0u8; 16];
+        assert!(pack_v210(&[0x400; 12], &mut out).is_err());
+    }
+}
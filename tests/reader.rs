@@ -1,9 +1,15 @@
+#![allow(clippy::unusual_byte_groupings)]
+
 #[cfg(test)]
 mod tests {
-    use bitio_rs::byte_order::ByteOrder;
-    use bitio_rs::reader::{BitReader, BulkBitReader, PeekableBitReader};
-    use bitio_rs::traits::{BitPeek, BitRead};
-    use std::io::{Cursor, ErrorKind, Read};
+    use bitio_rs::byte_order::{Big, ByteOrder, Little};
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::reader::{
+        BitReader, BitReaderBig, BitReaderLittle, BulkBitReader, CompactBitReader,
+        PeekableBitReader, WordSize, WordSwapReader,
+    };
+    use bitio_rs::traits::{BitExpect, BitPeek, BitRead, BitReadExt};
+    use std::io::{BufRead, Cursor, ErrorKind, Read};
     // ------------------------------- BitReader tests ------------------------------- //
 
     #[test]
@@ -195,6 +201,34 @@ mod tests {
         assert_eq!(chunks[1] & 0xFFFF, 0xFFFF);
     }
 
+    #[test]
+    fn bulk_read_bits_into_reuses_buffer() {
+        let data = vec![0xAAu8; 20];
+        let mut reader = BulkBitReader::new(Cursor::new(data));
+        let mut chunks = [0u64; 2];
+        assert_eq!(reader.read_bits_into(&mut chunks, 72).unwrap(), 2);
+        assert_eq!(chunks, [0xAAAA_AAAA_AAAA_AAAA, 0xAA]);
+        assert_eq!(reader.read_bits_into(&mut chunks, 4).unwrap(), 1);
+        assert_eq!(chunks[0], 0xA);
+    }
+
+    #[test]
+    fn bulk_read_bits_into_buffer_too_small() {
+        let data = vec![0u8; 20];
+        let mut reader = BulkBitReader::new(Cursor::new(data));
+        let mut chunks = [0u64; 1];
+        assert!(reader.read_bits_into(&mut chunks, 65).is_err());
+    }
+
+    #[test]
+    fn bulk_read_bits_into_bytes() {
+        let data = vec![0x12, 0x34, 0x56];
+        let mut reader = BulkBitReader::new(Cursor::new(data));
+        let mut buf = [0u8; 3];
+        reader.read_bits_into_bytes(&mut buf, 20).unwrap();
+        assert_eq!(buf, [0x12, 0x34, 0x50]);
+    }
+
     // --------------- Mixed byte/bit read tests --------------- //
 
     #[test]
@@ -276,4 +310,797 @@ mod tests {
         assert_eq!(n, 1);
         assert_eq!(buf, [0x01]);
     }
+
+    // --------------- Slice read tests --------------- //
+
+    #[test]
+    fn test_read_bits_into_slice_aligned() {
+        let data = vec![0xDE, 0xAD, 0xBE, 0xEF, 0xFF];
+        let mut reader = BitReader::new(Cursor::new(data));
+        let mut buf = [0u8; 5];
+        reader.read_bits_into_slice(&mut buf, 36).unwrap();
+        assert_eq!(buf, [0xDE, 0xAD, 0xBE, 0xEF, 0xF0]);
+        assert_eq!(reader.read_bits(4).unwrap(), 0xF);
+    }
+
+    #[test]
+    fn test_read_bits_into_slice_unaligned() {
+        let data = vec![0b1101_0101, 0b1110_0110, 0b1111_0000];
+        let mut reader = BitReader::new(Cursor::new(data));
+        assert_eq!(reader.read_bits(1).unwrap(), 1);
+        let mut buf = [0u8; 3];
+        reader.read_bits_into_slice(&mut buf, 19).unwrap();
+        assert_eq!(buf, [0xAB, 0xCD, 0b1110_0000]);
+    }
+
+    #[test]
+    fn test_read_bits_into_slice_little_endian() {
+        let data = vec![0x12, 0x3F];
+        let mut reader = BitReader::with_byte_order(ByteOrder::LittleEndian, Cursor::new(data));
+        let mut buf = [0u8; 2];
+        reader.read_bits_into_slice(&mut buf, 12).unwrap();
+        assert_eq!(buf, [0x12, 0x0F]);
+        assert_eq!(reader.read_bits(4).unwrap(), 0x3);
+    }
+
+    #[test]
+    fn test_read_bits_into_slice_buffer_too_small() {
+        let data = vec![0xFF, 0xFF];
+        let mut reader = BitReader::new(Cursor::new(data));
+        let mut buf = [0u8; 1];
+        assert!(reader.read_bits_into_slice(&mut buf, 9).is_err());
+    }
+
+    #[test]
+    fn test_read_bits_partial_drains_tail() {
+        let data = [0xAB, 0xCD, 0xEF];
+        let mut reader = BitReader::new(Cursor::new(data));
+        assert_eq!(reader.read_bits_partial(12).unwrap(), (0xABC, 12));
+        // 只剩 12 比特
+        assert_eq!(reader.read_bits_partial(16).unwrap(), (0xDEF, 12));
+        assert_eq!(reader.read_bits_partial(8).unwrap(), (0, 0));
+        assert!(reader.read_bits_partial(0).is_err());
+    }
+
+    #[test]
+    fn test_read_bits_partial_little_endian() {
+        let data = [0xAB, 0xCD];
+        let mut reader = BitReader::with_byte_order(ByteOrder::LittleEndian, Cursor::new(data));
+        assert_eq!(reader.read_bits_partial(4).unwrap(), (0xB, 4));
+        assert_eq!(reader.read_bits_partial(64).unwrap(), (0xCDA, 12));
+    }
+
+    #[test]
+    fn test_read_bits_partial_wide() {
+        let data = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0, 0x11];
+        let mut reader = BitReader::new(Cursor::new(data));
+        assert_eq!(reader.read_bits_partial(4).unwrap(), (0x1, 4));
+        // 零头 4 比特加 64 比特超出比特缓冲区，需要分两轮读取
+        assert_eq!(
+            reader.read_bits_partial(64).unwrap(),
+            (0x23456789ABCDEF01, 64)
+        );
+        assert_eq!(reader.read_bits_partial(64).unwrap(), (0x1, 4));
+    }
+
+    #[test]
+    fn test_read_bits_opt() {
+        let data = [0x12, 0x34, 0x56];
+        let mut reader = BitReader::new(Cursor::new(data));
+        let mut values = Vec::new();
+        while let Some(v) = reader.read_bits_opt(12).unwrap() {
+            values.push(v);
+        }
+        assert_eq!(values, [0x123, 0x456]);
+        assert_eq!(reader.read_bits_opt(1).unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_bits_opt_truncated_field() {
+        let data = [0x12, 0x34];
+        let mut reader = BitReader::new(Cursor::new(data));
+        assert_eq!(reader.read_bits_opt(12).unwrap(), Some(0x123));
+        let err = reader.read_bits_opt(12).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    /// 每次最多返回 1 个字节，并且每隔一次调用就返回 Interrupted，模拟 socket / 管道
+    struct Trickle {
+        data: Vec<u8>,
+        pos: usize,
+        calls: usize,
+    }
+
+    impl Read for Trickle {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.calls += 1;
+            if self.calls % 2 == 1 {
+                return Err(ErrorKind::Interrupted.into());
+            }
+            if self.pos == self.data.len() || buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = self.data[self.pos];
+            self.pos += 1;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn test_short_and_interrupted_reads() {
+        let data: Vec<u8> = (1..=20).collect();
+        let trickle = Trickle {
+            data: data.clone(),
+            pos: 0,
+            calls: 0,
+        };
+        // BufReader 会把 inner 的短读和 Interrupted 原样传上来
+        let mut reader = BitReader::new(trickle);
+        assert_eq!(reader.read_bits(4).unwrap(), 0x0);
+        assert_eq!(reader.read_bits(32).unwrap(), 0x10203040);
+        assert_eq!(reader.read_bits(12).unwrap(), 0x506);
+        let mut rest = Vec::new();
+        while let Some(byte) = reader.read_bits_opt(8).unwrap() {
+            rest.push(byte as u8);
+        }
+        let expected: Vec<u8> = data[6..].to_vec();
+        assert_eq!(rest, expected);
+    }
+
+    #[test]
+    fn test_wide_read_after_leftover_bits() {
+        let data = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0, 0x11];
+        let mut reader = BitReader::new(Cursor::new(data));
+        assert_eq!(reader.read_bits(3).unwrap(), 0b000);
+        assert_eq!(reader.read_bits(64).unwrap(), 0x91A2B3C4D5E6F780);
+        assert_eq!(reader.read_bits(5).unwrap(), 0b10001);
+
+        let mut reader = BitReader::with_byte_order(ByteOrder::LittleEndian, Cursor::new(data));
+        assert_eq!(reader.read_bits(4).unwrap(), 0x2);
+        assert_eq!(reader.read_bits(64).unwrap(), 0x1F0DEBC9A7856341);
+        assert!(reader.read_bits(5).is_err());
+    }
+
+    #[test]
+    fn test_with_capacity_and_unbuffered() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut expected = BitReader::new(Cursor::new(data.clone()));
+        let mut small =
+            BitReader::with_capacity(ByteOrder::BigEndian, 3, Cursor::new(data.clone()));
+        let mut direct = BitReader::unbuffered(ByteOrder::BigEndian, &data[..]);
+        for n in (1..=64).cycle().take(60) {
+            let value = expected.read_bits(n).unwrap();
+            assert_eq!(small.read_bits(n).unwrap(), value);
+            assert_eq!(direct.read_bits(n).unwrap(), value);
+        }
+
+        let mut direct = BitReader::unbuffered(ByteOrder::LittleEndian, &data[..4]);
+        assert_eq!(direct.read_bits(8).unwrap(), 0);
+        let mut buf = [0u8; 3];
+        direct.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [1, 2, 3]);
+        assert!(direct.read_bits(1).is_err());
+    }
+
+    #[test]
+    fn test_read_bytes_unaligned() {
+        let data: Vec<u8> = (1..=20u8).map(|i| i.wrapping_mul(37)).collect();
+        let mut reader = BitReader::new(Cursor::new(data.clone()));
+        assert_eq!(reader.read_bits(3).unwrap(), (data[0] >> 5) as u64);
+        let expected: Vec<u8> = (0..11).map(|i| data[i] << 3 | data[i + 1] >> 5).collect();
+        assert_eq!(reader.read_bytes(11).unwrap(), expected);
+        assert_eq!(reader.read_bits(5).unwrap(), (data[11] & 0x1F) as u64);
+        assert_eq!(reader.read_bytes(2).unwrap(), &data[12..14]);
+
+        let mut reader =
+            BitReader::with_byte_order(ByteOrder::LittleEndian, Cursor::new(data.clone()));
+        assert_eq!(reader.read_bits(3).unwrap(), (data[0] & 0x07) as u64);
+        let mut buf = [0u8; 9];
+        reader.read_bytes_into(&mut buf).unwrap();
+        let expected: Vec<u8> = (0..9).map(|i| data[i] >> 3 | data[i + 1] << 5).collect();
+        assert_eq!(buf, expected[..]);
+
+        assert!(reader.read_bytes(20).is_err());
+    }
+
+    #[test]
+    fn test_bytes_read() {
+        let data: Vec<u8> = (0..32).collect();
+        let mut reader = BitReader::new(Cursor::new(data));
+        assert_eq!(reader.bytes_read(), 0);
+        reader.read_bits(3).unwrap();
+        assert_eq!(reader.bytes_read(), 1);
+        reader.read_bits(13).unwrap();
+        assert_eq!(reader.bytes_read(), 2);
+        // 取得的字节中尚未消费的比特也计入
+        reader.read_bits(1).unwrap();
+        reader.read_bits(7).unwrap();
+        assert_eq!(reader.bytes_read(), 3);
+
+        let mut buf = [0u8; 5];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(reader.bytes_read(), 8);
+        reader.read_bytes(24).unwrap();
+        assert_eq!(reader.bytes_read(), 32);
+        assert!(reader.read_bits(1).is_err());
+        assert_eq!(reader.bytes_read(), 32);
+    }
+
+    #[test]
+    fn test_find_sync() {
+        // ADTS 同步字 0xFFF 位于第 13 比特处
+        let data = [0x12, 0x37, 0xFF, 0xE5, 0x00];
+        let mut reader = BitReader::new(&data[..]);
+        reader.read_bits(3).unwrap();
+        assert_eq!(reader.find_sync(0xFFF, 12).unwrap(), 10);
+        assert_eq!(reader.read_bits(12).unwrap(), 0xFFF);
+        assert_eq!(reader.read_bits(3).unwrap(), 0b110);
+
+        // 已位于同步字上时不跳过
+        let mut reader = BitReader::new(&[0x47, 0x00][..]);
+        assert_eq!(reader.find_sync(0x47, 8).unwrap(), 0);
+        assert_eq!(reader.read_bits(8).unwrap(), 0x47);
+
+        // 跨越多次比特缓冲区填充
+        let mut data = vec![0u8; 40];
+        data.extend_from_slice(&[0x01, 0x23, 0x45, 0x67]);
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut reader = BitReader::with_byte_order(byte_order, Cursor::new(data.clone()));
+            let sync = match byte_order {
+                ByteOrder::BigEndian => 0x0123_4567,
+                ByteOrder::LittleEndian => 0x6745_2301,
+            };
+            assert_eq!(reader.find_sync(sync, 32).unwrap(), 320);
+            assert_eq!(reader.read_bits(32).unwrap(), sync);
+        }
+    }
+
+    #[test]
+    fn test_find_sync_errors() {
+        let mut reader = BitReader::new(&[0x00, 0x00, 0x0F][..]);
+        assert!(reader.find_sync(0xFF, 8).is_err());
+        assert_eq!(reader.read_bits(7).unwrap(), 0x0F);
+        assert!(reader.find_sync(1, 0).is_err());
+        assert!(reader.find_sync(1, 57).is_err());
+
+        let mut reader = BitReader::new(&[0x00, 0x00, 0xFF][..]).max_bits(20);
+        assert!(reader.find_sync(0xFF, 8).is_err());
+    }
+
+    #[test]
+    fn test_peek_bytes() {
+        let data: Vec<u8> = b"RIFF\x24\x00\x00\x00WAVEfmt ".to_vec();
+        let mut reader = PeekableBitReader::new(Cursor::new(data.clone()));
+        let mut magic = [0u8; 12];
+        reader.peek_bytes(&mut magic).unwrap();
+        assert_eq!(&magic, &data[..12]);
+        // 预读不消费
+        assert_eq!(reader.read_bits(32).unwrap(), 0x5249_4646);
+
+        // 比特缓冲区中的字节与预读字节拼接
+        reader.peek_bits(16).unwrap();
+        let mut next = [0u8; 10];
+        reader.peek_bytes(&mut next).unwrap();
+        assert_eq!(&next, &data[4..14]);
+        let mut rest = Vec::new();
+        reader.read_bits(8).unwrap();
+        for _ in 0..11 {
+            rest.push(reader.read_bits(8).unwrap() as u8);
+        }
+        assert_eq!(rest, &data[5..]);
+
+        let mut reader = PeekableBitReader::with_byte_order(Cursor::new(data.clone()));
+        reader.read_bits(3).unwrap();
+        assert!(reader.peek_bytes(&mut [0u8; 1]).is_err());
+        reader.read_bits(5).unwrap();
+        assert!(reader.peek_bytes(&mut [0u8; 16]).is_err());
+        let mut tail = [0u8; 15];
+        reader.peek_bytes(&mut tail).unwrap();
+        assert_eq!(&tail, &data[1..]);
+    }
+
+    #[test]
+    fn test_peek_respects_max_bits() {
+        let mut reader = PeekableBitReader::new(Cursor::new(vec![0xABu8; 8])).max_bits(12);
+        assert_eq!(reader.peek_bits(12).unwrap(), 0xABA);
+        let err = bitio_rs::Error::from(reader.peek_bits(13).unwrap_err());
+        assert!(matches!(
+            err.root(),
+            bitio_rs::Error::LimitExceeded { limit: 12 }
+        ));
+        assert!(reader.peek_bytes(&mut [0u8; 2]).is_err());
+        reader.peek_bytes(&mut [0u8; 1]).unwrap();
+
+        // 限额按已消费的比特计算
+        reader.read_bits(8).unwrap();
+        assert_eq!(reader.peek_bits(4).unwrap(), 0xA);
+        assert!(reader.peek_bits(5).is_err());
+        assert_eq!(reader.read_bits(4).unwrap(), 0xA);
+        assert!(reader.peek_bits(1).is_err());
+    }
+
+    #[test]
+    fn test_read_to_end_bits() {
+        let data: Vec<u8> = (1..=20).collect();
+        let mut reader = BitReader::new(Cursor::new(data.clone()));
+        reader.read_bits(3).unwrap();
+        let mut tail = vec![0xEE];
+        assert_eq!(reader.read_to_end_bits(&mut tail).unwrap(), 157);
+        assert_eq!(tail.len(), 1 + 20);
+        let expected: Vec<u8> = (0..20)
+            .map(|i| data[i] << 3 | data.get(i + 1).map_or(0, |b| b >> 5))
+            .collect();
+        assert_eq!(&tail[1..], &expected[..]);
+        assert_eq!(reader.read_to_end_bits(&mut tail).unwrap(), 0);
+
+        let mut reader =
+            BitReader::with_byte_order(ByteOrder::LittleEndian, Cursor::new(data.clone()));
+        reader.read_bits(8).unwrap();
+        let mut tail = Vec::new();
+        assert_eq!(reader.read_to_end_bits(&mut tail).unwrap(), 152);
+        assert_eq!(tail, &data[1..]);
+
+        let mut reader = BitReader::with_byte_order(ByteOrder::LittleEndian, &data[..]);
+        reader.read_bits(5).unwrap();
+        let mut tail = Vec::new();
+        assert_eq!(reader.read_to_end_bits(&mut tail).unwrap(), 155);
+        assert_eq!(tail[19], data[19] >> 5);
+
+        // 限额恰好等于流长度时正常结束，否则报错
+        let mut reader = BitReader::new(&data[..]).max_bits(160);
+        assert_eq!(reader.read_to_end_bits(&mut Vec::new()).unwrap(), 160);
+        let mut reader = BitReader::new(&data[..]).max_bits(100);
+        let mut tail = Vec::new();
+        assert!(reader.read_to_end_bits(&mut tail).is_err());
+        assert_eq!(tail.len(), 13);
+    }
+
+    #[test]
+    fn test_from_iterators() {
+        let data: Vec<u8> = (0..40u8).map(|i| i.wrapping_mul(29)).collect();
+
+        // 逐字节的迭代器
+        let mut reader = BitReader::from_bytes_iter(ByteOrder::BigEndian, data.iter().copied());
+        reader.read_bits(4).unwrap();
+        assert_eq!(
+            reader.read_bits(12).unwrap(),
+            u16::from_be_bytes([data[0], data[1]]) as u64 & 0xFFF
+        );
+        assert_eq!(reader.read_bytes(38).unwrap(), &data[2..]);
+        assert_eq!(
+            reader.read_bits(1).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+
+        // 长短不一的块（含空块），字段跨越块边界
+        let chunks: Vec<&[u8]> = vec![&data[..3], &[], &data[3..4], &data[4..17], &data[17..]];
+        let mut reader = BitReader::from_chunks(ByteOrder::BigEndian, chunks.iter().copied());
+        let mut expected = BitReader::new(&data[..]);
+        for n in [5, 17, 64, 3, 33, 64, 64, 64] {
+            assert_eq!(reader.read_bits(n).unwrap(), expected.read_bits(n).unwrap());
+        }
+        assert_eq!(
+            reader.read_bits(7).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+
+        // 拥有所有权的块，例如来自 channel
+        let (tx, rx) = std::sync::mpsc::channel();
+        for chunk in data.chunks(7) {
+            tx.send(chunk.to_vec()).unwrap();
+        }
+        drop(tx);
+        let mut reader = BitReader::from_chunks(ByteOrder::LittleEndian, rx);
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn test_clone_forks_position() {
+        let data: Vec<u8> = (0..300u16).map(|i| (i * 7) as u8).collect();
+        for mut reader in [
+            BitReader::new(&data[..]),
+            BitReader::unbuffered(ByteOrder::BigEndian, &data[..]),
+            BitReader::with_capacity(ByteOrder::BigEndian, 16, &data[..]),
+        ] {
+            reader.read_bits(13).unwrap();
+
+            // 试探性地按另一种方式解析，失败后丢弃副本
+            let mut attempt = reader.clone();
+            attempt.read_bits(64).unwrap();
+            assert!(attempt.read_bytes(400).is_err());
+
+            let mut fork = reader.clone();
+            let mut expected = BitReader::new(&data[..]);
+            expected.read_bits(13).unwrap();
+            for n in [3, 64, 7, 40] {
+                let value = expected.read_bits(n).unwrap();
+                assert_eq!(fork.read_bits(n).unwrap(), value);
+                assert_eq!(reader.read_bits(n).unwrap(), value);
+            }
+            assert_eq!(fork.bytes_read(), reader.bytes_read());
+            let mut rest = Vec::new();
+            assert_eq!(fork.read_to_end_bits(&mut rest).unwrap(), 300 * 8 - 127);
+        }
+
+        // 克隆时 peek_bytes 预读的字节也一并保留
+        let mut reader = PeekableBitReader::new(Cursor::new(data.clone()));
+        let mut head = [0u8; 40];
+        reader.peek_bytes(&mut head).unwrap();
+        let mut fork = reader.clone();
+        assert_eq!(
+            fork.read_bits(64).unwrap(),
+            u64::from_be_bytes(data[..8].try_into().unwrap())
+        );
+        assert_eq!(reader.read_bits(8).unwrap(), data[0] as u64);
+    }
+
+    #[test]
+    fn test_export_import_state() {
+        let data: Vec<u8> = (0..200u16).map(|i| (i * 13) as u8).collect();
+        for order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut reader = BitReader::with_capacity(order, 32, Cursor::new(data.clone()));
+            reader.read_bits(64).unwrap();
+            reader.read_bits(13).unwrap();
+            reader.read_bits(3).unwrap();
+            let state = reader.export_state().unwrap();
+            assert_eq!(state.byte_offset * 8 - state.bits_in_buffer as u64, 80);
+            let value = reader.read_bits(37).unwrap();
+
+            // 在新的 reader 上恢复，从同一位置继续
+            let mut resumed = BitReader::with_byte_order(order, Cursor::new(data.clone()));
+            resumed.import_state(&state).unwrap();
+            assert_eq!(resumed.read_bits(37).unwrap(), value);
+            assert_eq!(resumed.bytes_read(), reader.bytes_read());
+            let mut rest = Vec::new();
+            resumed.read_to_end_bits(&mut rest).unwrap();
+            let mut original = Vec::new();
+            reader.read_to_end_bits(&mut original).unwrap();
+            assert_eq!(rest, original);
+        }
+
+        let mut reader = BitReader::new(Cursor::new(data.clone()));
+        reader.read_bits(16).unwrap();
+        let state = reader.export_state().unwrap();
+        let mut other = BitReader::with_byte_order(ByteOrder::LittleEndian, Cursor::new(data));
+        assert_eq!(
+            other.import_state(&state).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn test_read_fields() {
+        let data: Vec<u8> = (0..64u16).map(|i| (i * 37 + 11) as u8).collect();
+        let widths = [3, 1, 12, 7, 64, 5, 33, 31, 1, 64, 9, 2];
+        for order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut reader = BitReader::with_byte_order(order, &data[..]);
+            reader.read_bits(5).unwrap();
+            let fields = reader.read_fields(&widths).unwrap();
+
+            let mut expected = BitReader::with_byte_order(order, &data[..]);
+            expected.read_bits(5).unwrap();
+            for (&n, &field) in widths.iter().zip(&fields) {
+                assert_eq!(field, expected.read_bits(n).unwrap());
+            }
+            assert_eq!(reader.read_bits(8).unwrap(), expected.read_bits(8).unwrap());
+
+            // 默认实现给出同样的结果
+            let mut cursor = BitCursor::new(&data[..]);
+            cursor.set_position(5);
+            if order == ByteOrder::BigEndian {
+                assert_eq!(cursor.read_fields(&widths).unwrap(), fields);
+            }
+        }
+
+        let mut reader = BitReader::new(&data[..]);
+        let mut out = [0u64; 2];
+        let err = reader.read_fields_into(&[1, 2, 3], &mut out).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = reader.read_fields_into(&[1, 0], &mut out).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(reader.read_bits(8).unwrap(), data[0] as u64);
+        assert!(reader.read_fields(&[64; 9]).is_err());
+    }
+
+    #[test]
+    fn test_read_bits_const() {
+        let data = [0xAB, 0xCD, 0xEF, 0x12, 0x34];
+        let mut reader = BitReader::new(&data[..]);
+        assert_eq!(reader.read_bits_const::<12>().unwrap(), 0xABC);
+        assert_eq!(reader.read_bits_const::<12>().unwrap(), 0xDEF);
+        assert_eq!(reader.read_bits_const::<4>().unwrap(), 0x1);
+        assert!(reader.read_bits_const::<16>().is_err());
+    }
+
+    #[test]
+    fn test_expect_bits_and_bytes() {
+        let data = *b"\x89PNG\x0d\x0a\x1a\x0a";
+        let mut reader = BitReader::new(&data[..]);
+        reader.expect_bits(0x8, 4).unwrap();
+        reader.expect_bits(0x9, 4).unwrap();
+        reader.expect_bytes(b"PNG").unwrap();
+        let err = reader.expect_bytes(&[0x0D, 0x0A, 0x1A, 0x0B]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "Expected 0xb (8 bits) at bit offset 56, found 0xa"
+        );
+
+        let mut reader = BitReader::new(&data[..]);
+        let err = reader.expect_bits(0x100, 8).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Other);
+        // 期望值放不下时不读取
+        reader.expect_bits(0x89, 8).unwrap();
+        assert!(reader.expect_bits(0, 65).is_err());
+    }
+
+    #[test]
+    fn test_expect_aligned_and_eof() {
+        let data = [0xAB, 0xCD];
+        let mut reader = BitReader::new(&data[..]);
+        reader.expect_byte_aligned().unwrap();
+        reader.read_bits(3).unwrap();
+        let err = reader.expect_byte_aligned().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "Expected a byte boundary at bit offset 3");
+        reader.read_bits(5).unwrap();
+        let err = reader.expect_eof().unwrap_err();
+        assert_eq!(err.to_string(), "Expected end of stream at bit offset 8");
+        // 检查不消费任何比特
+        assert_eq!(reader.read_bits(4).unwrap(), 0xC);
+        assert!(reader.expect_eof().is_err());
+        assert_eq!(reader.read_bits(4).unwrap(), 0xD);
+        reader.expect_eof().unwrap();
+        reader.expect_byte_aligned().unwrap();
+
+        // max_bits 限额的末尾也算 EOF
+        let mut reader = BitReader::new(&data[..]).max_bits(12);
+        reader.read_bits(12).unwrap();
+        reader.expect_eof().unwrap();
+    }
+
+    /// 每次只返回一个字节的数据源
+    struct OneByte<'a>(&'a [u8]);
+
+    impl Read for OneByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.0.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_compile_time_byte_order() {
+        // Big / Little 标记的读取器与运行时字节序的读取器结果一致
+        let data: Vec<u8> = (0..48u8).map(|i| i.wrapping_mul(73) ^ 0xC3).collect();
+        let mut runtime = BitReader::with_byte_order(ByteOrder::BigEndian, Cursor::new(&data));
+        let mut big = BitReaderBig::with_endianness(Big, Cursor::new(&data));
+        assert_eq!(big.read_u24().unwrap(), runtime.read_u24().unwrap());
+        for n in [3, 11, 64, 7, 13, 1, 21] {
+            assert_eq!(big.read_bits(n).unwrap(), runtime.read_bits(n).unwrap());
+        }
+        assert_eq!(big.bytes_read(), runtime.bytes_read());
+
+        let mut runtime = BitReader::with_byte_order(ByteOrder::LittleEndian, Cursor::new(&data));
+        let mut little = BitReaderLittle::with_endianness(Little, Cursor::new(&data));
+        assert_eq!(little.read_u24().unwrap(), runtime.read_u24().unwrap());
+        for n in [3, 11, 64, 7, 13, 1, 21] {
+            assert_eq!(little.read_bits(n).unwrap(), runtime.read_bits(n).unwrap());
+        }
+        let mut rest = Vec::new();
+        let mut expected = Vec::new();
+        little.read_to_end(&mut rest).unwrap();
+        runtime.read_to_end(&mut expected).unwrap();
+        assert_eq!(rest, expected);
+    }
+
+    #[test]
+    fn test_compact_reader_matches_standard() {
+        let data: Vec<u8> = (0..64u8).map(|i| i.wrapping_mul(29) ^ 0x96).collect();
+        let widths = [3, 29, 32, 64, 7, 33, 1, 17, 5, 23, 64, 2, 30, 8];
+        for order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut expected = BitReader::with_byte_order(order, Cursor::new(&data));
+            let mut compact = CompactBitReader::with_byte_order(order, OneByte(&data));
+            for &n in &widths {
+                assert_eq!(
+                    compact.read_bits(n).unwrap(),
+                    expected.read_bits(n).unwrap()
+                );
+            }
+            assert_eq!(compact.read_ue().unwrap(), expected.read_ue().unwrap());
+            assert_eq!(compact.read_u16().unwrap(), expected.read_u16().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_compact_reader_bytes_and_errors() {
+        let data = [0xA5, 0x12, 0x34, 0x56, 0x78];
+        let mut reader = CompactBitReader::new(&data[..]);
+        assert!(reader.read_bits(0).is_err());
+        assert!(reader.read_bits(65).is_err());
+        assert_eq!(reader.read_bits(4).unwrap(), 0xA);
+        let mut buf = [0u8; 2];
+        let err = reader.read(&mut buf).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Attempted to consume bytes while bits are buffered at bit offset 4"
+        );
+        assert_eq!(reader.read_bits(12).unwrap(), 0x512);
+        assert!(reader.is_byte_aligned());
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [0x34, 0x56]);
+        assert_eq!(reader.read_bits(4).unwrap(), 0x7);
+        // 数据不足时报错
+        assert!(reader.read_bits(5).is_err());
+    }
+
+    #[test]
+    fn test_fill_buf_and_consume() {
+        let data = b"\xA5line one\nline two\n";
+        let mut reader = BitReader::new(&data[..]);
+        assert_eq!(reader.read_bits(4).unwrap(), 0xA);
+        let err = reader.fill_buf().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Attempted to consume bytes while bits are buffered at bit offset 4"
+        );
+        assert_eq!(reader.read_bits(4).unwrap(), 0x5);
+        assert_eq!(&reader.fill_buf().unwrap()[..4], b"line");
+        reader.consume(1);
+        assert_eq!(reader.bytes_read(), 2);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "ine one\n");
+        assert_eq!(reader.read_bits(8).unwrap(), b'l' as u64);
+        assert_eq!(reader.bytes_read(), 11);
+
+        // 无 BufReader 的读取器与 max_bits 限额
+        let mut reader = BitReader::unbuffered(ByteOrder::BigEndian, &data[..]).max_bits(8 * 5);
+        reader.read_bits(8).unwrap();
+        assert_eq!(reader.fill_buf().unwrap(), b"line");
+        reader.consume(4);
+        assert!(reader.fill_buf().unwrap().is_empty());
+        assert!(reader.read_bits(1).is_err());
+    }
+
+    #[test]
+    fn test_consume_more_than_filled() {
+        let data: Vec<u8> = (1..=20).collect();
+        // 无 BufReader：fill_buf 只预读 lookahead，超出部分被截断而不是记入 bytes_read
+        let mut reader = BitReader::unbuffered(ByteOrder::BigEndian, Cursor::new(data.clone()));
+        reader.read_bits(8).unwrap();
+        let filled = reader.fill_buf().unwrap().len();
+        assert_eq!(filled, 19);
+        reader.consume(filled + 10);
+        assert_eq!(reader.bytes_read(), 20);
+        assert!(reader.read_bits(1).is_err());
+
+        let mut reader = BitReader::unbuffered(ByteOrder::BigEndian, &data[..]).max_bits(8 * 4);
+        assert_eq!(reader.fill_buf().unwrap(), [1, 2, 3, 4]);
+        reader.consume(100);
+        assert_eq!(reader.bytes_read(), 4);
+        assert!(reader.read_bits(1).is_err());
+
+        // 带 BufReader 的读取器同样截断
+        let mut reader = BitReader::with_capacity(ByteOrder::BigEndian, 4, &data[..]);
+        assert_eq!(reader.fill_buf().unwrap(), [1, 2, 3, 4]);
+        reader.consume(6);
+        assert_eq!(reader.bytes_read(), 4);
+        assert_eq!(reader.read_bits(8).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_fill_buf_after_state_import() {
+        // 导入的状态把整字节留在比特缓冲区中，fill_buf 应先借出它们
+        let data: Vec<u8> = (1..=16).collect();
+        let mut reader = BitReader::new(Cursor::new(&data));
+        reader.read_bits(8).unwrap();
+        let mut state = reader.export_state().unwrap();
+        state.bits_buffer = 0x0203_0000_0000_0000;
+        state.bits_in_buffer = 16;
+        state.byte_offset = 3;
+        state.bytes_read = 3;
+        let mut reader = BitReader::new(Cursor::new(&data));
+        reader.import_state(&state).unwrap();
+        assert_eq!(reader.fill_buf().unwrap(), [2, 3]);
+        reader.consume(2);
+        assert_eq!(reader.fill_buf().unwrap()[0], 4);
+        reader.consume(1);
+        assert_eq!(reader.bytes_read(), 4);
+        assert_eq!(reader.read_bits(8).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_refill_from_short_reads() {
+        // 一次只给一个字节的数据源走逐字节补充，结果应与整块载入一致
+        let data: Vec<u8> = (0..64u8).map(|i| i.wrapping_mul(37) ^ 0x5A).collect();
+        let widths = [3, 11, 64, 7, 13, 1, 17, 5, 23, 64, 2, 60];
+        for order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut whole = BitReader::with_byte_order(order, Cursor::new(&data));
+            let mut short = BitReader::with_byte_order(order, OneByte(&data));
+            for &n in &widths {
+                assert_eq!(whole.read_bits(n).unwrap(), short.read_bits(n).unwrap());
+                assert_eq!(whole.bytes_read(), short.bytes_read());
+            }
+        }
+    }
+
+    #[test]
+    fn test_word_swap_reader() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut swapped = Vec::new();
+        WordSwapReader::new(&data[..], WordSize::Bits16)
+            .read_to_end(&mut swapped)
+            .unwrap();
+        assert_eq!(swapped, [2, 1, 4, 3, 6, 5, 8, 7, 9]);
+
+        // 短读和小缓冲区也按字翻转
+        let mut reader = WordSwapReader::new(OneByte(&data), WordSize::Bits32);
+        let mut out = Vec::new();
+        let mut buf = [0u8; 3];
+        loop {
+            let n = reader.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(out, [4, 3, 2, 1, 8, 7, 6, 5, 9]);
+
+        let mut swapped = Vec::new();
+        WordSwapReader::new(OneByte(&data), WordSize::Bits64)
+            .read_to_end(&mut swapped)
+            .unwrap();
+        assert_eq!(swapped, [8, 7, 6, 5, 4, 3, 2, 1, 9]);
+    }
+
+    #[test]
+    fn test_bit_reader_with_word_swap() {
+        // DSP 按小端 16 位字存储的字节流 0x12 0x34 0x56 0x78
+        let capture = [0x34, 0x12, 0x78, 0x56];
+        let mut reader =
+            BitReader::with_word_swap(ByteOrder::BigEndian, WordSize::Bits16, &capture[..]);
+        assert_eq!(reader.read_bits(4).unwrap(), 0x1);
+        assert_eq!(reader.read_bits(16).unwrap(), 0x2345);
+        assert_eq!(reader.read_bits(12).unwrap(), 0x678);
+        assert!(reader.read_bits(1).is_err());
+    }
+
+    #[test]
+    fn test_with_initial_offset() {
+        let data = [0b1011_0110, 0xFF];
+        let mut reader = BitReader::new(&data[..]).with_initial_offset(3).unwrap();
+        assert_eq!(reader.read_bits(5).unwrap(), 0b1_0110);
+        assert!(reader.is_byte_aligned());
+        assert_eq!(reader.read_bits(8).unwrap(), 0xFF);
+
+        let mut reader = BitReader::with_byte_order(ByteOrder::LittleEndian, &data[..])
+            .with_initial_offset(2)
+            .unwrap();
+        assert_eq!(reader.read_bits(6).unwrap(), 0b10_1101);
+
+        // 被丢弃的比特计入限额
+        let mut reader = BitReader::new(&data[..])
+            .max_bits(8)
+            .with_initial_offset(7)
+            .unwrap();
+        assert_eq!(reader.read_bits(1).unwrap(), 0);
+        assert!(reader.read_bits(1).is_err());
+
+        let reader = BitReader::new(&data[..]).with_initial_offset(0).unwrap();
+        assert_eq!(reader.bytes_read(), 0);
+        assert_eq!(
+            BitReader::new(&data[..])
+                .with_initial_offset(8)
+                .err()
+                .unwrap()
+                .kind(),
+            ErrorKind::InvalidInput
+        );
+        assert!(BitReader::new(&[][..]).with_initial_offset(1).is_err());
+    }
 }
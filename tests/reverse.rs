@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::reverse::reverse_bits;
+    use bitio_rs::traits::{BitRead, BitReadExt, BitWrite, BitWriteExt};
+
+    #[test]
+    fn test_reverse_bits() {
+        assert_eq!(reverse_bits(0b1101, 4), 0b1011);
+        assert_eq!(reverse_bits(0b1, 8), 0b1000_0000);
+        // 高于 n 位的部分被丢弃
+        assert_eq!(reverse_bits(0xF0, 4), 0);
+        assert_eq!(reverse_bits(1, 64), 1 << 63);
+        assert_eq!(reverse_bits(u64::MAX, 0), 0);
+        for v in 0..256 {
+            assert_eq!(reverse_bits(reverse_bits(v, 9), 9), v);
+        }
+    }
+
+    #[test]
+    fn test_reversed_fields() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_bits(0b1, 1).unwrap();
+        cursor.write_bits_reversed(0b110, 3).unwrap();
+        cursor.write_bits_reversed(0x0F, 12).unwrap();
+        assert!(cursor.write_bits_reversed(0, 65).is_err());
+        assert_eq!(cursor.get_ref(), &vec![0b1011_1111, 0b0000_0000]);
+
+        cursor.set_position(0);
+        cursor.read_bits(1).unwrap();
+        assert_eq!(cursor.read_bits_reversed(3).unwrap(), 0b110);
+        assert_eq!(cursor.read_bits_reversed(12).unwrap(), 0x0F);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reverse_too_wide() {
+        reverse_bits(0, 65);
+    }
+}
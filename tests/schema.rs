@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::schema::{BitLayout, Condition, Field, Value};
+    use bitio_rs::traits::BitWrite;
+    use std::io::ErrorKind;
+
+    fn layout() -> BitLayout {
+        BitLayout::new()
+            .field(Field::unsigned("version", 3))
+            .field(Field::unsigned("has_ext", 1))
+            .field(Field::signed("offset", 12))
+            .field(Field::unsigned("count", 4))
+            .field(Field::unsigned("samples", 6).repeat_by("count"))
+            .field(Field::unsigned("ext", 16).when(Condition::NonZero("has_ext".to_string())))
+            .field(Field::unsigned("crc", 8).repeat(2))
+    }
+
+    fn record(has_ext: bool) -> Vec<u8> {
+        let mut writer = BitCursor::new(Vec::new());
+        writer.write_bits(5, 3).unwrap();
+        writer.write_bits(has_ext as u64, 1).unwrap();
+        writer.write_bits(-3i64 as u64 & 0xFFF, 12).unwrap();
+        writer.write_bits(3, 4).unwrap();
+        for sample in [1, 42, 63] {
+            writer.write_bits(sample, 6).unwrap();
+        }
+        if has_ext {
+            writer.write_bits(0xBEEF, 16).unwrap();
+        }
+        writer.write_bits(0xA5, 8).unwrap();
+        writer.write_bits(0x5A, 8).unwrap();
+        writer.into_inner()
+    }
+
+    #[test]
+    fn test_read_vec() {
+        let data = record(true);
+        let values = layout().read_vec(&mut BitReader::new(&data[..])).unwrap();
+        assert_eq!(
+            values,
+            vec![
+                Some(Value::Unsigned(5)),
+                Some(Value::Unsigned(1)),
+                Some(Value::Signed(-3)),
+                Some(Value::Unsigned(3)),
+                Some(Value::Array(vec![
+                    Value::Unsigned(1),
+                    Value::Unsigned(42),
+                    Value::Unsigned(63)
+                ])),
+                Some(Value::Unsigned(0xBEEF)),
+                Some(Value::Array(vec![
+                    Value::Unsigned(0xA5),
+                    Value::Unsigned(0x5A)
+                ])),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_map_skips_absent_fields() {
+        let data = record(false);
+        let map = layout().read_map(&mut BitReader::new(&data[..])).unwrap();
+        assert_eq!(map.len(), 6);
+        assert!(!map.contains_key("ext"));
+        assert_eq!(map["offset"].as_i64(), Some(-3));
+        assert_eq!(map["samples"].as_array().unwrap().len(), 3);
+        assert_eq!(map["crc"].as_array().unwrap()[1], Value::Unsigned(0x5A));
+    }
+
+    #[test]
+    fn test_field_byte_order() {
+        let data = [0x34, 0x12, 0xAB];
+        let layout = BitLayout::new()
+            .field(Field::unsigned("le", 16).byte_order(ByteOrder::LittleEndian))
+            .field(Field::unsigned("be", 8));
+        let map = layout.read_map(&mut BitReader::new(&data[..])).unwrap();
+        assert_eq!(map["le"], Value::Unsigned(0x1234));
+        assert_eq!(map["be"], Value::Unsigned(0xAB));
+    }
+
+    #[test]
+    fn test_errors() {
+        let data = [0xFF; 4];
+        let layout = BitLayout::new().field(Field::unsigned("x", 8).repeat_by("missing"));
+        let err = layout.read_vec(&mut BitReader::new(&data[..])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        let layout = BitLayout::new().field(Field::unsigned("x", 65));
+        let err = layout.read_vec(&mut BitReader::new(&data[..])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        let layout = BitLayout::new()
+            .field(Field::unsigned("count", 8))
+            .field(Field::unsigned("items", 8).repeat_by("count"));
+        let err = layout.read_vec(&mut BitReader::new(&data[..])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_parse_text_description() {
+        let layout: BitLayout = "version:u4 flags:u3 pad:1 length:u16le payload:bytes[length] \
+                                 delta:i4[2] ext:u8?flags=5 more:8?flags!=5"
+            .parse()
+            .unwrap();
+        assert_eq!(layout.fields().len(), 8);
+        assert_eq!(
+            layout.fields()[3],
+            Field::unsigned("length", 16).byte_order(ByteOrder::LittleEndian)
+        );
+        assert_eq!(
+            layout.fields()[6],
+            Field::unsigned("ext", 8).when(Condition::Equals("flags".to_string(), 5))
+        );
+
+        let data = [0x3A, 0x02, 0x00, 0xDE, 0xAD, 0xF1, 0x77];
+        let map = layout.read_map(&mut BitReader::new(&data[..])).unwrap();
+        assert_eq!(map["version"], Value::Unsigned(3));
+        assert_eq!(map["flags"], Value::Unsigned(5));
+        assert_eq!(map["length"], Value::Unsigned(2));
+        assert_eq!(
+            map["payload"],
+            Value::Array(vec![Value::Unsigned(0xDE), Value::Unsigned(0xAD)])
+        );
+        assert_eq!(
+            map["delta"],
+            Value::Array(vec![Value::Signed(-1), Value::Signed(1)])
+        );
+        assert_eq!(map["ext"], Value::Unsigned(0x77));
+        assert!(!map.contains_key("more"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for text in [
+            "version", ":u4", "x:u0", "x:u65", "x:float", "x:bytes", "x:8le", "x:u8[", "x:u8[]",
+            "x:u8?", "x:u8?f=y",
+        ] {
+            let err = BitLayout::parse(text).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{}", text);
+        }
+    }
+}
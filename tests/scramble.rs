@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::scramble::{Descrambler, Lfsr, Scrambler, Scrambling};
+    use bitio_rs::traits::{BitRead, BitWrite};
+    use std::io::ErrorKind;
+
+    /// DVB 能量扩散：1 + x^14 + x^15，初值 100101010000000（第 1 级在前）
+    fn dvb() -> Lfsr {
+        Lfsr::new(0x6000, 15, 0b1010_1001).unwrap()
+    }
+
+    #[test]
+    fn test_lfsr_sequence() {
+        assert_eq!(dvb().next_bits(32).unwrap(), 0x03F6_0834);
+
+        // 本原多项式 x^7 + x^6 + 1 的周期为 127
+        let mut lfsr = Lfsr::new(0x60, 7, 1).unwrap();
+        let seed = lfsr.state();
+        let period = (1..=200).find(|_| {
+            lfsr.next_bit();
+            lfsr.state() == seed
+        });
+        assert_eq!(period, Some(127));
+
+        assert_eq!(
+            Lfsr::new(0, 7, 1).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            Lfsr::new(0x80, 7, 1).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+        assert!(Lfsr::new(1, 65, 1).is_err());
+    }
+
+    #[test]
+    fn test_additive_round_trip() {
+        let mut writer = Scrambler::new(BitCursor::new(Vec::new()), dvb(), Scrambling::Additive);
+        writer.write_bits(0, 32).unwrap();
+        writer.write_bits(0x1234, 16).unwrap();
+        let mut cursor = writer.into_inner();
+        // 全零数据得到的就是 PRBS 本身
+        assert_eq!(cursor.get_ref()[..4], [0x03, 0xF6, 0x08, 0x34]);
+
+        cursor.set_position(0);
+        let mut reader = Descrambler::new(cursor, dvb(), Scrambling::Additive);
+        assert_eq!(reader.read_bits(32).unwrap(), 0);
+        assert_eq!(reader.read_bits(16).unwrap(), 0x1234);
+
+        // 重新装载初值后序列从头开始
+        let mut writer = Scrambler::new(BitCursor::new(Vec::new()), dvb(), Scrambling::Additive);
+        writer.write_bits(0, 8).unwrap();
+        writer.lfsr_mut().set_state(dvb().state());
+        writer.write_bits(0, 8).unwrap();
+        assert_eq!(writer.get_ref().get_ref()[..2], [0x03, 0x03]);
+    }
+
+    #[test]
+    fn test_multiplicative_self_synchronizes() {
+        let lfsr = Lfsr::new(0x48, 7, 0x5A).unwrap();
+        let mut writer =
+            Scrambler::new(BitCursor::new(Vec::new()), lfsr, Scrambling::Multiplicative);
+        writer.write_bits(0xFFFF_FFFF, 32).unwrap();
+        writer.write_bits(0xDEAD_BEEF, 32).unwrap();
+        let mut cursor = writer.into_inner();
+
+        // 接收端用错误的初值，只有前 7 个比特受影响
+        cursor.set_position(0);
+        let lfsr = Lfsr::new(0x48, 7, 0).unwrap();
+        let mut reader = Descrambler::new(cursor, lfsr, Scrambling::Multiplicative);
+        reader.read_bits(7).unwrap();
+        assert_eq!(reader.read_bits(25).unwrap(), 0x1FF_FFFF);
+        assert_eq!(reader.read_bits(32).unwrap(), 0xDEAD_BEEF);
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::string::{
+        SixBitCharset, armor_ais, read_cstring, read_padded_string, read_sixbit_string,
+        read_string, read_string_bytes, unarmor_ais, write_cstring, write_padded_string,
+        write_sixbit_string, write_string_bytes,
+    };
+    use bitio_rs::traits::{BitRead, BitWrite};
+    use std::io::{Cursor, ErrorKind};
+
+    #[test]
+    fn test_aligned_strings() {
+        let data = b"RIFF\x04\x00\x00\x00name\0WAVE    ".to_vec();
+        let mut reader = BitReader::new(Cursor::new(data));
+        assert_eq!(read_string_bytes(&mut reader, 4).unwrap(), b"RIFF");
+        reader.read_bits(32).unwrap();
+        assert_eq!(read_cstring(&mut reader, 16).unwrap(), b"name");
+        assert_eq!(read_padded_string(&mut reader, 8, b' ').unwrap(), b"WAVE");
+    }
+
+    #[test]
+    fn test_unaligned_round_trip() {
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut cursor = BitCursor::with_byte_order(byte_order, Vec::new());
+            cursor.write_bits(0b101, 3).unwrap();
+            write_string_bytes(&mut cursor, "héllo".as_bytes()).unwrap();
+            write_cstring(&mut cursor, b"abc").unwrap();
+            write_padded_string(&mut cursor, b"xy", 5, 0).unwrap();
+            cursor.write_bits(1, 1).unwrap();
+            assert_eq!(cursor.position(), 3 + 6 * 8 + 4 * 8 + 5 * 8 + 1);
+
+            cursor.set_position(3);
+            assert_eq!(read_string(&mut cursor, 6).unwrap(), "héllo");
+            assert_eq!(read_cstring(&mut cursor, 3).unwrap(), b"abc");
+            assert_eq!(read_padded_string(&mut cursor, 5, 0).unwrap(), b"xy");
+            assert_eq!(cursor.read_bits(1).unwrap(), 1);
+        }
+    }
+
+    #[test]
+    fn test_string_errors() {
+        let mut cursor = BitCursor::new(Vec::new());
+        assert!(write_cstring(&mut cursor, b"a\0b").is_err());
+        assert!(write_padded_string(&mut cursor, b"toolong", 4, b' ').is_err());
+        assert!(cursor.get_ref().is_empty());
+
+        let data = [0xFFu8, 0xFE, b'a', b'b', b'c', 0];
+        let mut cursor = BitCursor::new(&data[..]);
+        let err = read_string(&mut cursor, 2).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = read_cstring(&mut cursor, 2).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(read_string_bytes(&mut cursor, 10).is_err());
+
+        // 长度来自不可信数据时不会按 len 预分配
+        let mut cursor = BitCursor::new(&data[..]);
+        let err = read_string_bytes(&mut cursor, usize::MAX).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        let mut cursor = BitCursor::new(&data[..]);
+        let err = read_sixbit_string(&mut cursor, usize::MAX, SixBitCharset::Ais).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_sixbit_charsets() {
+        assert_eq!(SixBitCharset::Ais.decode(0), '@');
+        assert_eq!(SixBitCharset::Ais.decode(1), 'A');
+        assert_eq!(SixBitCharset::Ais.decode(32), ' ');
+        assert_eq!(SixBitCharset::Ais.decode(48), '0');
+        assert_eq!(SixBitCharset::Dec.decode(0), ' ');
+        assert_eq!(SixBitCharset::Dec.decode(33), 'A');
+        for code in 0..64 {
+            for charset in [SixBitCharset::Ais, SixBitCharset::Dec] {
+                assert_eq!(charset.encode(charset.decode(code)), Some(code));
+            }
+        }
+        assert_eq!(SixBitCharset::Ais.encode('a'), None);
+        assert_eq!(SixBitCharset::Dec.encode('é'), None);
+    }
+
+    #[test]
+    fn test_sixbit_fields() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_bits(0b10, 2).unwrap();
+        write_sixbit_string(&mut cursor, "SEA BIRD", 10, SixBitCharset::Ais).unwrap();
+        write_sixbit_string(&mut cursor, "AB", 2, SixBitCharset::Dec).unwrap();
+        assert_eq!(cursor.position(), 2 + 60 + 12);
+
+        cursor.set_position(2);
+        let name = read_sixbit_string(&mut cursor, 10, SixBitCharset::Ais).unwrap();
+        assert_eq!(name, "SEA BIRD@@");
+        assert_eq!(name.trim_end_matches(['@', ' ']), "SEA BIRD");
+        assert_eq!(cursor.read_bits(12).unwrap(), 33 << 6 | 34);
+
+        let mut cursor = BitCursor::new(Vec::new());
+        let err = write_sixbit_string(&mut cursor, "lower", 8, SixBitCharset::Ais).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(write_sixbit_string(&mut cursor, "TOO LONG", 4, SixBitCharset::Dec).is_err());
+        assert_eq!(cursor.position(), 0);
+    }
+
+    #[test]
+    fn test_ais_armoring() {
+        // 1 类位置报告，MMSI 477553000
+        let payload = "177KQJ5000G?tO`K>RA1wUbN0TKH";
+        let data = unarmor_ais(payload).unwrap();
+        assert_eq!(data.len(), 21);
+        let mut cursor = BitCursor::new(&data);
+        assert_eq!(cursor.read_bits(6).unwrap(), 1);
+        assert_eq!(cursor.read_bits(2).unwrap(), 0);
+        assert_eq!(cursor.read_bits(30).unwrap(), 477_553_000);
+        assert_eq!(armor_ais(&data, 168).unwrap(), (payload.to_string(), 0));
+
+        // 不是 6 的倍数时补零位
+        assert_eq!(armor_ais(&[0xFF, 0xFF], 10).unwrap(), ("wt".to_string(), 2));
+        assert_eq!(unarmor_ais("wt").unwrap(), [0xFF, 0xC0]);
+
+        assert!(unarmor_ais("1X").is_err());
+        assert!(armor_ais(&[0], 9).is_err());
+    }
+}
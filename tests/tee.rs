@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use bitio_rs::byte_order::ByteOrder;
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::reader::BitReader;
+    use bitio_rs::tee::TeeBitReader;
+    use bitio_rs::traits::{BitPeek, BitRead};
+    use bitio_rs::writer::BitWriter;
+
+    #[test]
+    fn test_tee_records_consumed_bits() {
+        let data = [0b1011_0011, 0x5A, 0xC3, 0xFF];
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let reader = BitReader::with_byte_order(byte_order, &data[..]);
+            let writer = BitWriter::with_byte_order(byte_order, Vec::new());
+            let mut tee = TeeBitReader::new(reader, writer);
+            for n in [3, 1, 12, 8] {
+                tee.read_bits(n).unwrap();
+            }
+            let (mut reader, writer) = tee.into_inner();
+            assert_eq!(writer.into_inner().unwrap(), &data[..3]);
+            assert_eq!(reader.read_bits(8).unwrap(), 0xFF);
+        }
+    }
+
+    #[test]
+    fn test_tee_peek_and_error() {
+        let data = [0xAB];
+        let mut tee = TeeBitReader::new(BitCursor::new(&data[..]), BitCursor::new(Vec::new()));
+        assert_eq!(tee.peek_bits(4).unwrap(), 0xA);
+        assert!(tee.writer().get_ref().is_empty());
+
+        assert_eq!(tee.read_bits(4).unwrap(), 0xA);
+        assert!(tee.read_bits(8).is_err());
+        assert_eq!(tee.writer().position(), 4);
+        assert_eq!(tee.reader().position(), 4);
+        assert_eq!(tee.writer_mut().get_ref(), &vec![0xA0]);
+    }
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "trace")]
+
+#[cfg(test)]
+mod tests {
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::trace::{Trace, TraceEntry, TraceOp};
+    use bitio_rs::traits::{BitPeek, BitRead, BitWrite};
+
+    #[test]
+    fn test_trace_write_then_read() {
+        let mut writer = Trace::new(BitCursor::new(Vec::new()));
+        writer.write_bits_labeled("version", 2, 3).unwrap();
+        writer.write_bits(0x1FF, 5).unwrap();
+        writer.write_bits_labeled("length", 0xABC, 12).unwrap();
+        assert_eq!(writer.offset(), 20);
+        assert_eq!(
+            writer.entries()[1],
+            TraceEntry {
+                op: TraceOp::Write,
+                offset: 3,
+                width: 5,
+                value: 0x1F,
+                label: None,
+            }
+        );
+
+        let data = writer.into_inner().into_inner();
+        let mut reader = Trace::new(BitCursor::new(data));
+        assert_eq!(reader.read_bits_labeled("version", 3).unwrap(), 2);
+        assert_eq!(reader.peek_bits(5).unwrap(), 0x1F);
+        reader.read_bits(5).unwrap();
+        reader.read_bits_labeled("length", 12).unwrap();
+        assert!(reader.read_bits(8).is_err());
+        assert_eq!(reader.entries().len(), 3);
+        assert_eq!(
+            reader.dump(),
+            "R @       0  3b 0x2 version\nR @       3  5b 0x1f\nR @       8 12b 0xabc length\n"
+        );
+        assert_eq!(reader.take_entries().len(), 3);
+        assert!(reader.entries().is_empty());
+    }
+}
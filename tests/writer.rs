@@ -1,9 +1,13 @@
+#![allow(clippy::unused_io_amount)]
+
 #[cfg(test)]
 mod tests {
     use bitio_rs::byte_order::ByteOrder;
-    use bitio_rs::traits::BitWrite;
-    use bitio_rs::writer::BitWriter;
-    use std::io::{Cursor, Write};
+    use bitio_rs::cursor::BitCursor;
+    use bitio_rs::error::BitReadWriteError;
+    use bitio_rs::traits::{BitWrite, BitWriteExt};
+    use bitio_rs::writer::{BitWriter, BulkBitWriter, OverflowPolicy};
+    use std::io::{Cursor, ErrorKind, IoSlice, Seek, SeekFrom, Write};
 
     #[test]
     fn test_write_bits_big_endian() {
@@ -11,7 +15,7 @@ mod tests {
         let mut writer = BitWriter::with_byte_order(ByteOrder::BigEndian, &mut buf);
         writer.write_bits(0b1010, 4).unwrap();
         writer.write_bits(0b1100, 4).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buf.into_inner(), vec![0xAC]);
     }
@@ -22,7 +26,7 @@ mod tests {
         let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, &mut buf);
         writer.write_bits(0b1010, 4).unwrap();
         writer.write_bits(0b1100, 4).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buf.into_inner(), vec![0xCA]);
     }
@@ -35,7 +39,7 @@ mod tests {
         let data = [0x11, 0x22, 0x33];
         writer.write(&data).unwrap();
         writer.write_bits(0b101, 3).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buf.into_inner(), vec![0xFF, 0x11, 0x22, 0x33, 0xA0]);
     }
@@ -46,7 +50,7 @@ mod tests {
         let mut writer = BitWriter::new(&mut buf);
         let data = [1, 2, 3, 4];
         let n = writer.write(&data).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         assert_eq!(n, 4);
         drop(writer);
         assert_eq!(buf.into_inner(), vec![1, 2, 3, 4]);
@@ -57,7 +61,7 @@ mod tests {
         let mut buf = Cursor::new(Vec::new());
         let mut writer = BitWriter::new(&mut buf);
         writer.write_bits(0b111, 3).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buf.into_inner(), vec![0xE0]);
     }
@@ -71,7 +75,7 @@ mod tests {
         writer.write_bits(0x22, 8).unwrap();
         writer.write_bits(0x33, 8).unwrap();
         writer.write_bits(0x14, 5).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buffer, vec![0xFF, 0x11, 0x22, 0x33, 0xA0]);
     }
@@ -85,7 +89,7 @@ mod tests {
         writer.write_bits(0x22, 8).unwrap();
         writer.write_bits(0x33, 8).unwrap();
         writer.write_bits(0x14, 5).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buffer, vec![0xFF, 0x11, 0x22, 0x33, 0x14]);
     }
@@ -102,7 +106,7 @@ mod tests {
         let mut buffer = Vec::new();
         let mut writer = BitWriter::with_byte_order(ByteOrder::BigEndian, &mut buffer);
         writer.write_bits(0x0123456789ABCDEF, 64).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buffer, vec![0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF]);
     }
@@ -112,7 +116,7 @@ mod tests {
         let mut buffer = Vec::new();
         let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, &mut buffer);
         writer.write_bits(0x0123456789ABCDEF, 64).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buffer, vec![0xEF, 0xCD, 0xAB, 0x89, 0x67, 0x45, 0x23, 0x01]);
     }
@@ -130,7 +134,7 @@ mod tests {
         let mut writer = BitWriter::with_byte_order(ByteOrder::BigEndian, &mut buffer);
         writer.write_bits(0x7F, 7).unwrap();
         writer.write_bits(0x1, 1).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buffer, vec![0xFF]);
     }
@@ -141,7 +145,7 @@ mod tests {
         let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, &mut buffer);
         writer.write_bits(0x7F, 7).unwrap();
         writer.write_bits(0x1, 1).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buffer, vec![0xFF]);
     }
@@ -153,7 +157,7 @@ mod tests {
         writer.write(&[0xAA]).unwrap();
         writer.write_bits(0x7F, 7).unwrap();
         writer.write_bits(0x1, 1).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buffer, vec![0xAA, 0xFF]);
     }
@@ -165,7 +169,7 @@ mod tests {
         writer.write(&[0xAA]).unwrap();
         writer.write_bits(0x7F, 7).unwrap();
         writer.write_bits(0x1, 1).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buffer, vec![0xAA, 0xFF]);
     }
@@ -175,10 +179,10 @@ mod tests {
         let mut buffer = Vec::new();
         let mut writer = BitWriter::with_byte_order(ByteOrder::BigEndian, &mut buffer);
         writer.write_bits(0x0F, 4).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         writer.write(&[0xAA]).unwrap();
         writer.write_bits(0x0F, 4).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buffer, vec![0xF0, 0xAA, 0xF0]);
     }
@@ -188,10 +192,10 @@ mod tests {
         let mut buffer = Vec::new();
         let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, &mut buffer);
         writer.write_bits(0x0F, 4).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         writer.write(&[0xAA]).unwrap();
         writer.write_bits(0x0F, 4).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buffer, vec![0x0F, 0xAA, 0x0F]);
     }
@@ -202,7 +206,7 @@ mod tests {
         let mut writer = BitWriter::with_byte_order(ByteOrder::BigEndian, &mut buffer);
         writer.write_bits(0x0123456789ABCDEF, 64).unwrap();
         writer.write_bits(0x1, 1).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(
             buffer,
@@ -216,7 +220,7 @@ mod tests {
         let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, &mut buffer);
         writer.write_bits(0x0123456789ABCDEF, 64).unwrap();
         writer.write_bits(0x1, 1).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(
             buffer,
@@ -229,7 +233,7 @@ mod tests {
         let mut buffer = Vec::new();
         let mut writer = BitWriter::with_byte_order(ByteOrder::BigEndian, &mut buffer);
         writer.write_bits(0x0F, 4).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buffer, vec![0xF0]);
     }
@@ -239,8 +243,656 @@ mod tests {
         let mut buffer = Vec::new();
         let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, &mut buffer);
         writer.write_bits(0x0F, 4).unwrap();
-        writer.flush().unwrap();
+        writer.finish().unwrap();
         drop(writer);
         assert_eq!(buffer, vec![0x0F]);
     }
+
+    #[test]
+    fn test_drop_flushes_partial_byte() {
+        let mut buffer = Vec::new();
+        {
+            let mut writer = BitWriter::new(&mut buffer);
+            writer.write_bits(0xAB, 8).unwrap();
+            writer.write_bits(0b101, 3).unwrap();
+            // 没有显式调用 flush，依赖 Drop 输出残余比特
+        }
+        assert_eq!(buffer, vec![0xAB, 0xA0]);
+    }
+
+    #[test]
+    fn test_into_inner_flushes_and_returns_writer() {
+        let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, Vec::new());
+        writer.write_bits(0x0F, 4).unwrap();
+        let buffer = writer.into_inner().unwrap();
+        assert_eq!(buffer, vec![0x0F]);
+    }
+
+    #[test]
+    fn test_into_inner_surfaces_error() {
+        struct FailingWriter;
+
+        impl Write for FailingWriter {
+            fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("sink closed"))
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut writer = BitWriter::new(FailingWriter);
+        writer.write_bits(0b1, 1).unwrap();
+        assert!(writer.into_inner().is_err());
+    }
+
+    #[test]
+    fn test_write_bits_from_slice_big_endian() {
+        let mut buffer = Vec::new();
+        let mut writer = BitWriter::with_byte_order(ByteOrder::BigEndian, &mut buffer);
+        writer.write_bits(0b1, 1).unwrap();
+        writer
+            .write_bits_from_slice(&[0xAB, 0xCD, 0b1110_0000], 19)
+            .unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+        // 1 + 1010_1011 + 1100_1101 + 111 => 1101_0101 1110_0110 1111_0000
+        assert_eq!(buffer, vec![0xD5, 0xE6, 0xF0]);
+    }
+
+    #[test]
+    fn test_write_bits_from_slice_little_endian() {
+        let mut buffer = Vec::new();
+        let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, &mut buffer);
+        writer.write_bits_from_slice(&[0x12, 0x0F], 12).unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+        assert_eq!(buffer, vec![0x12, 0x0F]);
+    }
+
+    #[test]
+    fn test_write_bits_from_slice_too_short() {
+        let mut buffer = Vec::new();
+        let mut writer = BitWriter::new(&mut buffer);
+        assert!(writer.write_bits_from_slice(&[0xFF], 9).is_err());
+    }
+
+    // ------------------------------- BulkBitWriter tests ------------------------------- //
+
+    #[test]
+    fn bulk_write_bits_zero_error() {
+        let mut buffer = Vec::new();
+        let mut writer = BulkBitWriter::new(&mut buffer);
+        assert!(writer.write_bits(&[0], 0).is_err());
+    }
+
+    #[test]
+    fn bulk_write_bits_not_enough_chunks() {
+        let mut buffer = Vec::new();
+        let mut writer = BulkBitWriter::new(&mut buffer);
+        assert!(writer.write_bits(&[u64::MAX], 65).is_err());
+    }
+
+    #[test]
+    fn bulk_write_bits_across_chunks() {
+        let mut buffer = Vec::new();
+        let mut writer = BulkBitWriter::new(&mut buffer);
+        writer
+            .write_bits(&[0x0123456789ABCDEF, 0b1010_1010_1010], 76)
+            .unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+        assert_eq!(
+            buffer,
+            vec![0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0xAA, 0xA0]
+        );
+    }
+
+    #[test]
+    fn bulk_round_trip_with_bulk_reader() {
+        use bitio_rs::reader::BulkBitReader;
+        use bitio_rs::traits::BitRead;
+
+        let data = vec![0x5Au8; 13];
+        let mut reader =
+            BulkBitReader::with_endianness(ByteOrder::LittleEndian, Cursor::new(data.clone()));
+        let chunks = reader.read_bits(100).unwrap();
+
+        let mut writer = BulkBitWriter::with_endianness(ByteOrder::LittleEndian, Vec::new());
+        writer.write_bits(&chunks, 100).unwrap();
+        writer.write_bits(&[0x5], 4).unwrap();
+        assert_eq!(writer.into_inner().unwrap(), data);
+    }
+
+    #[test]
+    fn test_write_bits_with_round_trip() {
+        use bitio_rs::reader::BitReader;
+
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut writer = BitWriter::with_byte_order(byte_order, Vec::new());
+            writer.write_bits(0b101, 3).unwrap();
+            writer
+                .write_bits_with(ByteOrder::LittleEndian, 0xABCDE, 20)
+                .unwrap();
+            writer
+                .write_bits_with(ByteOrder::BigEndian, 0x1_2345_6789, 33)
+                .unwrap();
+            let data = writer.into_inner().unwrap();
+
+            let mut reader = BitReader::with_byte_order(byte_order, Cursor::new(data));
+            assert_eq!(
+                bitio_rs::traits::BitRead::read_bits(&mut reader, 3).unwrap(),
+                0b101
+            );
+            assert_eq!(
+                reader.read_bits_with(ByteOrder::LittleEndian, 20).unwrap(),
+                0xABCDE
+            );
+            assert_eq!(
+                reader.read_bits_with(ByteOrder::BigEndian, 33).unwrap(),
+                0x1_2345_6789
+            );
+        }
+    }
+
+    #[test]
+    fn test_little_endian_wide_write_after_leftover_bits() {
+        let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, Vec::new());
+        writer.write_bits(0b1011, 4).unwrap();
+        writer.write_bits(0x0123_4567_89AB_CDEF, 64).unwrap();
+        assert_eq!(
+            writer.into_inner().unwrap(),
+            vec![0xFB, 0xDE, 0xBC, 0x9A, 0x78, 0x56, 0x34, 0x12, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_write_bytes_unaligned_round_trip() {
+        use bitio_rs::reader::BitReader;
+        use bitio_rs::traits::BitRead;
+
+        let payload: Vec<u8> = (0..19u8).map(|i| i.wrapping_mul(29) ^ 0x5A).collect();
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut writer = BitWriter::with_byte_order(byte_order, Vec::new());
+            writer.write_bits(0b101, 3).unwrap();
+            writer.write_bytes_unaligned(&payload).unwrap();
+            writer.write_bits(0b11, 2).unwrap();
+            writer.write_bytes_unaligned(&payload[..2]).unwrap();
+            let out = writer.into_inner().unwrap();
+            assert_eq!(out.len(), (3 + 19 * 8 + 2 + 16usize).div_ceil(8));
+
+            let mut reader = BitReader::with_byte_order(byte_order, &out[..]);
+            assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+            assert_eq!(reader.read_bytes(19).unwrap(), payload);
+            assert_eq!(reader.read_bits(2).unwrap(), 0b11);
+            assert_eq!(reader.read_bytes(2).unwrap(), &payload[..2]);
+        }
+
+        // 对齐时与普通字节写入一致
+        let mut writer = BitWriter::new(Vec::new());
+        writer.write_bytes_unaligned(&payload).unwrap();
+        assert_eq!(writer.into_inner().unwrap(), payload);
+    }
+
+    #[test]
+    fn test_write_run() {
+        let mut writer = BitWriter::new(Vec::new());
+        writer.write_bits(0b1, 1).unwrap();
+        writer.write_run(false, 3).unwrap();
+        writer.write_run(true, 4 + 8 * 1000 + 5).unwrap();
+        writer.write_run(false, 0).unwrap();
+        writer.write_bits(0b011, 3).unwrap();
+        let out = writer.into_inner().unwrap();
+        assert_eq!(out.len(), 1002);
+        assert_eq!(out[0], 0b1000_1111);
+        assert!(out[1..1001].iter().all(|&b| b == 0xFF));
+        assert_eq!(out[1001], 0b1111_1011);
+
+        let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, Vec::new());
+        writer.write_bits(0b1, 1).unwrap();
+        writer.write_run(false, 9).unwrap();
+        writer.write_run(true, 6).unwrap();
+        assert_eq!(writer.into_inner().unwrap(), vec![0b0000_0001, 0b1111_1100]);
+    }
+
+    #[test]
+    fn test_typed_round_trip() {
+        use bitio_rs::reader::BitReader;
+        use bitio_rs::traits::BitRead;
+
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            for lead in [0, 3] {
+                let mut writer = BitWriter::with_byte_order(byte_order, Vec::new());
+                if lead > 0 {
+                    writer.write_bits(0b101, lead).unwrap();
+                }
+                writer.write_u16(0x1234).unwrap();
+                writer.write_u24(0x56_789A).unwrap();
+                writer.write_u32(0xBCDE_F012).unwrap();
+                writer.write_u48(0x3456_789A_BCDE).unwrap();
+                writer.write_u64(0xF0E1_D2C3_B4A5_9687).unwrap();
+                assert!(writer.write_u24(1 << 24).is_err());
+                assert!(writer.write_u48(1 << 48).is_err());
+                let out = writer.into_inner().unwrap();
+                assert_eq!(out.len(), (lead + 8 * 23usize).div_ceil(8));
+
+                let mut reader = BitReader::with_byte_order(byte_order, &out[..]);
+                if lead > 0 {
+                    reader.read_bits(lead).unwrap();
+                }
+                assert_eq!(reader.read_u16().unwrap(), 0x1234);
+                assert_eq!(reader.read_u24().unwrap(), 0x56_789A);
+                assert_eq!(reader.read_u32().unwrap(), 0xBCDE_F012);
+                assert_eq!(reader.read_u48().unwrap(), 0x3456_789A_BCDE);
+                assert_eq!(reader.read_u64().unwrap(), 0xF0E1_D2C3_B4A5_9687);
+                assert!(reader.read_u16().is_err());
+            }
+        }
+
+        let mut writer = BitWriter::with_byte_order(ByteOrder::LittleEndian, Vec::new());
+        writer.write_u24(0x56_789A).unwrap();
+        assert_eq!(writer.into_inner().unwrap(), vec![0x9A, 0x78, 0x56]);
+    }
+
+    #[test]
+    fn test_typed_reads_on_truncated_input() {
+        use bitio_rs::reader::BitReader;
+        use bitio_rs::traits::BitRead;
+
+        // 对齐与非对齐时都报带类型的 UnexpectedEof
+        let data = [0x12, 0x34, 0x56];
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut reader = BitReader::with_byte_order(byte_order, &data[..]);
+            reader.read_bits(8).unwrap();
+            let err = reader.read_u32().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+            assert!(matches!(
+                bitio_rs::Error::from(err),
+                bitio_rs::Error::Positioned {
+                    offset: 8,
+                    requested: Some(32),
+                    available: Some(16),
+                    error,
+                } if matches!(*error, bitio_rs::Error::UnexpectedEof)
+            ));
+
+            let mut reader = BitReader::with_byte_order(byte_order, &data[..]);
+            reader.read_bits(4).unwrap();
+            let err = reader.read_u32().unwrap_err();
+            assert!(matches!(
+                bitio_rs::Error::from(err),
+                bitio_rs::Error::Positioned {
+                    offset: 4,
+                    requested: Some(32),
+                    available: Some(20),
+                    error,
+                } if matches!(*error, bitio_rs::Error::UnexpectedEof)
+            ));
+
+            let mut reader = BitReader::with_byte_order(byte_order, &data[..2]);
+            let mut samples = [0; 1];
+            let err = reader.read_i24_samples(&mut samples).unwrap_err();
+            assert!(matches!(
+                bitio_rs::Error::from(err),
+                bitio_rs::Error::Positioned {
+                    offset: 0,
+                    requested: Some(24),
+                    available: Some(16),
+                    error,
+                } if matches!(*error, bitio_rs::Error::UnexpectedEof)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_reserve_and_patch() {
+        for order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut writer = BitWriter::with_byte_order(order, Cursor::new(Vec::new()));
+            writer.write_bits(0b101, 3).unwrap();
+            let length = writer.reserve_bits(13).unwrap();
+            assert_eq!((length.offset(), length.bits()), (3, 13));
+            for i in 0..300u32 {
+                writer.write_bits(i as u64, 11).unwrap();
+            }
+            writer.write_bits(0b11, 2).unwrap();
+            let crc = writer.reserve_bits(7).unwrap();
+            writer.patch(length, 3300).unwrap();
+            // 最后一个字段的末尾还在比特缓冲区中
+            writer.patch(crc, 0x55).unwrap();
+            writer.write_bits(0b1, 1).unwrap();
+            let patched = writer.into_inner().unwrap().into_inner();
+
+            let mut writer = BitWriter::with_byte_order(order, Vec::new());
+            writer.write_bits(0b101, 3).unwrap();
+            writer.write_bits(3300, 13).unwrap();
+            for i in 0..300u32 {
+                writer.write_bits(i as u64, 11).unwrap();
+            }
+            writer.write_bits(0b11, 2).unwrap();
+            writer.write_bits(0x55, 7).unwrap();
+            writer.write_bits(0b1, 1).unwrap();
+            assert_eq!(patched, writer.into_inner().unwrap());
+        }
+
+        let mut writer = BitWriter::new(Cursor::new(Vec::new()));
+        let field = writer.reserve_bits(4).unwrap();
+        let err = writer.patch(field, 16).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Other);
+        assert!(writer.reserve_bits(65).is_err());
+    }
+
+    #[test]
+    fn test_sections() {
+        let mut writer = BitWriter::new(Cursor::new(Vec::new()));
+        writer.write_bits(0x3, 2).unwrap();
+        writer.begin_byte_section(16).unwrap();
+        writer.write_bits(0xAB, 8).unwrap();
+        // 嵌套的比特长度段
+        writer.begin_section(6).unwrap();
+        writer.write_bits(0x1F, 5).unwrap();
+        writer.write_bits(0x2, 6).unwrap();
+        assert_eq!(writer.end_section().unwrap(), 11);
+        writer.write_bits(0, 7).unwrap();
+        assert_eq!(writer.end_section().unwrap(), 4);
+        writer.write_bits(0x1, 1).unwrap();
+        let sectioned = writer.into_inner().unwrap().into_inner();
+
+        let mut writer = BitWriter::new(Vec::new());
+        writer.write_bits(0x3, 2).unwrap();
+        writer.write_bits(4, 16).unwrap();
+        writer.write_bits(0xAB, 8).unwrap();
+        writer.write_bits(11, 6).unwrap();
+        writer.write_bits(0x1F, 5).unwrap();
+        writer.write_bits(0x2, 6).unwrap();
+        writer.write_bits(0, 7).unwrap();
+        writer.write_bits(0x1, 1).unwrap();
+        assert_eq!(sectioned, writer.into_inner().unwrap());
+
+        let mut writer = BitWriter::new(Cursor::new(Vec::new()));
+        assert_eq!(
+            writer.end_section().unwrap_err().kind(),
+            std::io::ErrorKind::InvalidInput
+        );
+        writer.begin_byte_section(8).unwrap();
+        writer.write_bits(0, 3).unwrap();
+        assert!(writer.end_section().is_err());
+        // 出错后段仍然打开，补齐后可以结束
+        writer.write_bits(0, 5).unwrap();
+        assert_eq!(writer.end_section().unwrap(), 1);
+
+        writer.begin_section(3).unwrap();
+        writer.write_bits(0, 8).unwrap();
+        assert!(writer.end_section().is_err());
+    }
+
+    #[test]
+    fn test_overflow_policy() {
+        let mut cursor = BitCursor::new(Vec::new());
+        cursor.write_bits_checked(0x7, 3).unwrap();
+        let err = cursor.write_bits_checked(0x8, 3).unwrap_err();
+        assert!(matches!(
+            err.get_ref()
+                .and_then(|e| e.downcast_ref::<BitReadWriteError>()),
+            Some(BitReadWriteError::ValueOutOfRange { value: 8, bits: 3 })
+        ));
+        cursor.write_bits_checked(u64::MAX, 64).unwrap();
+        assert_eq!(cursor.position(), 67);
+        // 先检查位数：n = 0 和 n > 64 报 InvalidBitCount，而不是 ValueOutOfRange
+        for n in [0, 65] {
+            let err = cursor.write_bits_checked(1, n).unwrap_err();
+            assert!(matches!(
+                err.get_ref()
+                    .and_then(|e| e.downcast_ref::<BitReadWriteError>()),
+                Some(BitReadWriteError::InvalidBitCount(bits)) if *bits == n
+            ));
+        }
+        assert_eq!(cursor.position(), 67);
+
+        let write = |policy| {
+            let mut writer = BitWriter::new(Vec::new()).overflow_policy(policy);
+            let result = writer.write_bits(0x1FF, 4);
+            writer.write_bits(0, 4).unwrap();
+            result.map(|_| writer.into_inner().unwrap())
+        };
+        assert_eq!(write(OverflowPolicy::Mask).unwrap(), [0xF0]);
+        assert_eq!(write(OverflowPolicy::Saturate).unwrap(), [0xF0]);
+        assert!(write(OverflowPolicy::Error).is_err());
+
+        let mut writer = BitWriter::new(Vec::new()).overflow_policy(OverflowPolicy::Saturate);
+        writer.write_bits(300, 8).unwrap();
+        writer.write_bits(2, 8).unwrap();
+        assert_eq!(writer.into_inner().unwrap(), [0xFF, 0x02]);
+
+        // 库内部的写入不受 Error 策略影响
+        for order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut writer = BitWriter::with_byte_order(order, Vec::new())
+                .overflow_policy(OverflowPolicy::Error);
+            writer.write_run(true, 13).unwrap();
+            writer.write_bits_from_slice(&[0xFF, 0xFF], 11).unwrap();
+            writer
+                .write_bits_with(ByteOrder::LittleEndian, 0xABC, 12)
+                .unwrap();
+            assert_eq!(writer.into_inner().unwrap().len(), 5);
+        }
+    }
+
+    #[test]
+    fn test_write_bits_at() {
+        for order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut writer = BitWriter::with_byte_order(order, Cursor::new(Vec::new()));
+            writer.write_bits(0xAA, 8).unwrap();
+            writer.write_bits(0, 32).unwrap();
+            writer.write_bits(0x5, 3).unwrap();
+            writer.write_bits_at(1, 0x1234_5678, 32).unwrap();
+            writer.write_bits(0x1F, 5).unwrap();
+            let patched = writer.into_inner().unwrap().into_inner();
+
+            let mut writer = BitWriter::with_byte_order(order, Vec::new());
+            writer.write_bits(0xAA, 8).unwrap();
+            writer.write_bits(0x1234_5678, 32).unwrap();
+            writer.write_bits(0x5, 3).unwrap();
+            writer.write_bits(0x1F, 5).unwrap();
+            assert_eq!(patched, writer.into_inner().unwrap());
+        }
+
+        let mut writer = BitWriter::new(Cursor::new(Vec::new()));
+        writer.write_bits(0, 20).unwrap();
+        let err = writer.write_bits_at(0, 0, 12).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = writer.write_bits_at(0, 0x100, 8).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Other);
+        // 第 3 个字节还在比特缓冲区中
+        let err = writer.write_bits_at(1, 0, 16).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        writer.write_bits_at(1, 0xFF, 8).unwrap();
+        assert_eq!(
+            writer.into_inner().unwrap().into_inner(),
+            vec![0x00, 0xFF, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut writer = BitWriter::new(Cursor::new(Vec::new()));
+        writer.write_bits(0xABCD, 16).unwrap();
+        writer.write_bits(0x1, 4).unwrap();
+        assert_eq!(
+            writer.stream_position().unwrap_err().kind(),
+            ErrorKind::Other
+        );
+        writer.write_bits(0x2, 4).unwrap();
+        assert_eq!(writer.stream_position().unwrap(), 3);
+
+        // 回到开头覆盖一个字节，再回到末尾追加
+        assert_eq!(writer.seek(SeekFrom::Start(1)).unwrap(), 1);
+        writer.write_bits(0xEE, 8).unwrap();
+        assert_eq!(writer.stream_position().unwrap(), 2);
+        writer.seek(SeekFrom::End(0)).unwrap();
+        writer.write_bits(0x7, 3).unwrap();
+        // seek 补齐残余的比特
+        assert_eq!(writer.seek(SeekFrom::Current(-1)).unwrap(), 3);
+        assert_eq!(
+            writer.into_inner().unwrap().into_inner(),
+            vec![0xAB, 0xEE, 0x12, 0xE0]
+        );
+    }
+
+    #[test]
+    fn test_flush_keeps_partial_byte() {
+        let mut buffer = Vec::new();
+        let mut writer = BitWriter::new(&mut buffer);
+        writer.write_bits(0xAB, 8).unwrap();
+        writer.write_bits(0x5, 4).unwrap();
+        writer.flush().unwrap();
+        // 不足 1 字节的比特留在缓冲区，之后的比特紧接着写
+        writer.write_bits(0xC, 4).unwrap();
+        writer.flush().unwrap();
+        writer.write_bits(0x1, 1).unwrap();
+        writer.finish().unwrap();
+        writer.write_bits(0xFF, 8).unwrap();
+        drop(writer);
+        assert_eq!(buffer, vec![0xAB, 0x5C, 0x80, 0xFF]);
+
+        let mut buffer = Vec::new();
+        let mut writer = BitWriter::new(&mut buffer).pad_on_flush(true);
+        writer.write_bits(0x5, 4).unwrap();
+        writer.flush().unwrap();
+        writer.write_bits(0xC, 4).unwrap();
+        drop(writer);
+        assert_eq!(buffer, vec![0x50, 0xC0]);
+    }
+
+    #[test]
+    fn test_alignment_introspection() {
+        let mut writer = BitWriter::new(Vec::new());
+        assert!(writer.is_byte_aligned());
+        assert_eq!(writer.pending_bits(), 0);
+        writer.write_bits(0x3, 3).unwrap();
+        assert!(!writer.is_byte_aligned());
+        assert_eq!(writer.pending_bits(), 3);
+        writer.write_bits(0xFFF, 13).unwrap();
+        assert!(writer.is_byte_aligned());
+        writer.write_bits(0x1, 7).unwrap();
+        assert_eq!(writer.pending_bits(), 7);
+        writer.finish().unwrap();
+        assert_eq!(writer.pending_bits(), 0);
+        assert!(writer.is_byte_aligned());
+    }
+
+    #[test]
+    fn test_at_bit_offset_patches_in_place() {
+        let mut file = Cursor::new(vec![0xFF; 4]);
+        let mut writer = BitWriter::at_bit_offset(ByteOrder::BigEndian, &mut file, 5).unwrap();
+        writer.write_bits(0, 6).unwrap();
+        writer.finish_in_place().unwrap();
+        drop(writer);
+        assert_eq!(file.get_ref(), &[0xF8, 0x1F, 0xFF, 0xFF]);
+
+        let mut file = Cursor::new(vec![0xFF; 4]);
+        let mut writer = BitWriter::at_bit_offset(ByteOrder::LittleEndian, &mut file, 13).unwrap();
+        writer.write_bits(0, 6).unwrap();
+        writer.finish_in_place().unwrap();
+        drop(writer);
+        assert_eq!(file.get_ref(), &[0xFF, 0x1F, 0xF8, 0xFF]);
+    }
+
+    #[test]
+    fn test_at_bit_offset_past_end() {
+        let mut file = Cursor::new(vec![0xAA]);
+        let mut writer = BitWriter::at_bit_offset(ByteOrder::BigEndian, &mut file, 4).unwrap();
+        writer.write_bits(0xFF, 8).unwrap();
+        writer.finish_in_place().unwrap();
+        drop(writer);
+        assert_eq!(file.get_ref(), &[0xAF, 0xF0]);
+
+        // finish 用零补齐最后一个字节
+        let mut file = Cursor::new(vec![0xFF; 2]);
+        let mut writer = BitWriter::at_bit_offset(ByteOrder::BigEndian, &mut file, 2).unwrap();
+        writer.write_bits(0, 2).unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+        assert_eq!(file.get_ref(), &[0xC0, 0xFF]);
+    }
+
+    /// 每次 write 最多接受 limit 字节的写入器
+    struct Limited {
+        data: Vec<u8>,
+        limit: usize,
+    }
+
+    impl Write for Limited {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(self.limit);
+            self.data.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_vectored_aligned() {
+        let parts: Vec<Vec<u8>> = (0..3u8).map(|i| vec![i + 1; 5000]).collect();
+        let slices: Vec<IoSlice> = parts.iter().map(|p| IoSlice::new(p)).collect();
+        // Vec 支持原生向量写入，大块数据一次交给它，不经过 BufWriter 拼接
+        let mut writer = BitWriter::new(Vec::new());
+        writer.write_bits(0xAB, 8).unwrap();
+        assert_eq!(writer.write_vectored(&slices).unwrap(), 15000);
+        let out = writer.into_inner().unwrap();
+        assert_eq!(out[0], 0xAB);
+        assert_eq!(out[1..], parts.concat()[..]);
+
+        // 不支持向量写入的底层写入器可能只写出一部分，返回值如实反映
+        let mut writer = BitWriter::new(Limited {
+            data: Vec::new(),
+            limit: usize::MAX,
+        });
+        let mut written = writer.write_vectored(&slices).unwrap();
+        assert!(written > 0);
+        while written < 15000 {
+            let rest = parts.concat();
+            written += writer.write(&rest[written..]).unwrap();
+        }
+        assert_eq!(writer.into_inner().unwrap().data, parts.concat());
+    }
+
+    #[test]
+    fn test_write_vectored_unaligned() {
+        let parts = [[0xFFu8, 0x00], [0x0F, 0xF0]];
+        let slices = [IoSlice::new(&parts[0]), IoSlice::new(&parts[1])];
+        for order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut expected = Vec::new();
+            let mut writer = BitWriter::with_byte_order(order, &mut expected);
+            writer.write_bits(0b101, 3).unwrap();
+            for part in &parts {
+                writer.write_all(part).unwrap();
+            }
+            writer.finish().unwrap();
+            drop(writer);
+
+            let mut out = Vec::new();
+            let mut writer = BitWriter::with_byte_order(order, &mut out);
+            writer.write_bits(0b101, 3).unwrap();
+            assert_eq!(writer.write_vectored(&slices).unwrap(), 4);
+            writer.finish().unwrap();
+            drop(writer);
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn test_write_reports_partial_writes() {
+        // 超过 BufWriter 容量的数据直接交给底层写入器，只写出一部分时返回实际字节数
+        let data = vec![0x5Au8; 10000];
+        let mut writer = BitWriter::new(Limited {
+            data: Vec::new(),
+            limit: 100,
+        });
+        assert_eq!(writer.write(&data).unwrap(), 100);
+        writer.write_all(&data[100..]).unwrap();
+        assert_eq!(writer.into_inner().unwrap().data, data);
+    }
 }